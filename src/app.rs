@@ -1,36 +1,83 @@
 use crate::{
     log_on_error,
-    ui::popup::{info_popup::InfoPopUp, PopUp},
+    ui::popup::{
+        checkpatch::CheckpatchPopUp,
+        confirm::{ConfirmPopUp, Confirmation},
+        git_am_conflict::GitAmConflictPopUp,
+        info_popup::InfoPopUp,
+        log_viewer::LogViewerPopUp,
+        PopUp,
+    },
 };
 use ansi_to_tui::IntoText;
+use apply_history::{ApplyRecord, ApplyResult};
+use build_test::BuildStatus;
 use color_eyre::eyre::bail;
 use config::Config;
 use cover_renderer::render_cover;
-use logging::Logger;
+use export::ExportFormat;
+use git_am::{GitAmConflict, GitAmResolution, GitAmState};
+use keymap::Keymap;
+use list_activity::ListActivity;
+use logging::{LogLevel, Logger};
+use navigation::{NavigationHistory, Snapshot};
 use patch_hub::lore::{
+    attestation::AttestationStatus,
+    fixes,
     lore_api_client::BlockingLoreAPIClient,
     lore_session,
-    patch::{Author, Patch},
+    maintainers::{self, MaintainersSection},
+    patch::Patch,
+    trailers,
 };
 use patch_renderer::{render_patch_preview, PatchRenderer};
-use ratatui::text::Text;
+use ratatui::{crossterm::event::KeyEvent, layout::Size, text::Text};
 use screens::{
+    apply_history::ApplyHistory,
     bookmarked::BookmarkedPatchsets,
-    details_actions::{DetailsActions, PatchsetAction},
+    details_actions::{validate_kernel_tree, DetailsActions, PatchsetAction, ReplyTrailer},
     edit_config::EditConfig,
+    kernel_trees::{KernelTreeFormKind, KernelTrees},
     latest::LatestPatchsets,
     mail_list::MailingListSelection,
+    recent::RecentPatchsets,
+    worktrees::Worktrees,
     CurrentScreen,
 };
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    fs, mem,
+    sync::Mutex,
+    thread,
+};
+use tabs::{tab_title, Tab, Tabs};
+use theme::{Theme, ThemePreset};
 
 use crate::utils;
 
+pub mod apply_history;
+pub mod base_tree;
+pub mod build_test;
+pub mod command_line;
 pub mod config;
 pub mod cover_renderer;
+pub mod export;
+pub mod git_am;
+pub mod keymap;
+pub mod list_activity;
 pub mod logging;
+pub mod navigation;
 pub mod patch_renderer;
+pub mod patchset_filter;
+pub mod preview_search;
 pub mod screens;
+pub mod tabs;
+pub mod theme;
+pub mod upstream;
+pub mod worktree;
+
+/// Patchsets downloaded at once to classify them by the "my subsystems" filter
+const CLASSIFY_DOWNLOAD_THREADS: usize = 8;
 
 /// Type that represents the overall state of the application. It can be viewed
 /// as the **Model** component of `patch-hub`.
@@ -41,34 +88,65 @@ pub struct App {
     pub mailing_list_selection: MailingListSelection,
     /// Screen with listing patchsets that were previously bookmarked
     pub bookmarked_patchsets: BookmarkedPatchsets,
+    /// Screen with the patchsets recently viewed, the jump list
+    pub recent_patchsets: RecentPatchsets,
     /// Screen with paginated listing of latest patchsets from a target list
     pub latest_patchsets: Option<LatestPatchsets>,
     /// Screen with details (metadata and previewing) and runnable actions of individual patchset
     pub details_actions: Option<DetailsActions>,
     /// Screen to edit configurations of the app
     pub edit_config: Option<EditConfig>,
+    /// Screen to manage the worktrees in which patchsets were applied
+    pub worktrees: Option<Worktrees>,
+    /// Screen to manage the kernel trees in which actions are run
+    pub kernel_trees: Option<KernelTrees>,
+    /// Screen with the history of applied patchsets
+    pub apply_history: Option<ApplyHistory>,
     /// Database to track patchsets `Reviewed-by` state
     pub reviewed_patchsets: HashMap<String, HashSet<usize>>,
+    /// Database to track the attestation status of patchsets
+    pub attestation_statuses: HashMap<String, AttestationStatus>,
+    /// Database to track the build-test status of patchsets
+    pub build_statuses: HashMap<String, BuildStatus>,
+    /// Database of every apply of a patchset, oldest first
+    pub apply_records: Vec<ApplyRecord>,
+    /// `git am` stopped by conflicts, if any, kept until it's resolved
+    pub git_am_conflict: Option<GitAmConflict>,
+    /// Commands run in the command line, oldest first
+    pub command_history: Vec<String>,
     /// Configurations of the app
     pub config: Config,
+    /// Keys bound to the actions of each screen
+    pub keymap: Keymap,
+    /// Styles with which the UI is drawn
+    pub theme: Theme,
     /// Client to handle Lore API requests and responses
     pub lore_api_client: BlockingLoreAPIClient,
     pub popup: Option<Box<dyn PopUp>>,
+    /// Key pressed while waiting for the rest of a key sequence that it
+    /// didn't continue, to be handled as if pressed again (see
+    /// [crate::handler::read_action])
+    pub replayed_key: Option<KeyEvent>,
+    /// Tabs of the workspace. The state of the active one is in
+    /// [App::current_screen], [App::latest_patchsets],
+    /// [App::details_actions] and [App::navigation_history]
+    pub tabs: Tabs,
+    /// Screens to go back and forward to in the active tab
+    pub navigation_history: NavigationHistory,
 }
 
 impl App {
     /// Creates a new instance of `App`. It dynamically loads configurations
     /// based on precedence (see [crate::app::Config::build]), app data
-    /// (available mailing lists, bookmarked patchsets, reviewed patchsets), and
+    /// (available mailing lists, bookmarked and recent patchsets, reviewed
+    /// patchsets, attestation and build-test statuses, apply and command
+    /// histories, `git am` stopped by conflicts), and
     /// initializes the Logger (see [crate::app::logging::Logger])
     ///
     /// # Returns
     ///
     /// `App` instance with loading configurations and app data.
     pub fn new(config: Config) -> color_eyre::Result<Self> {
-        let mailing_lists =
-            lore_session::load_available_lists(config.mailing_lists_path()).unwrap_or_default();
-
         let bookmarked_patchsets =
             lore_session::load_bookmarked_patchsets(config.bookmarked_patchsets_path())
                 .unwrap_or_default();
@@ -77,6 +155,25 @@ impl App {
             lore_session::load_reviewed_patchsets(config.reviewed_patchsets_path())
                 .unwrap_or_default();
 
+        let attestation_statuses =
+            lore_session::load_attestation_statuses(config.attestation_statuses_path())
+                .unwrap_or_default();
+
+        let build_statuses =
+            build_test::load_build_statuses(config.build_statuses_path()).unwrap_or_default();
+
+        let apply_records =
+            apply_history::load_apply_history(config.apply_history_path()).unwrap_or_default();
+
+        let git_am_conflict =
+            git_am::load_git_am_conflict(config.git_am_conflict_path()).unwrap_or_default();
+
+        let command_history =
+            command_line::load_command_history(config.command_history_path()).unwrap_or_default();
+
+        let recent_patchsets =
+            navigation::load_jump_list(config.recent_patchsets_path()).unwrap_or_default();
+
         let lore_api_client = BlockingLoreAPIClient::default();
 
         // Initialize the logger before the app starts
@@ -84,27 +181,51 @@ impl App {
         Logger::info("patch-hub started");
         logging::garbage_collector::collect_garbage(&config);
 
+        let (keymap, keymap_errors) = Keymap::new(config.keybindings());
+        for error in keymap_errors {
+            Logger::error(format!("Ignoring keybinding: {error}"));
+        }
+
+        let (theme, theme_errors) = Theme::new(
+            *config.theme(),
+            config.theme_colors(),
+            theme::no_color_requested(),
+        );
+        for error in theme_errors {
+            Logger::error(format!("Ignoring theme color: {error}"));
+        }
+
         Ok(App {
             current_screen: CurrentScreen::MailingListSelection,
-            mailing_list_selection: MailingListSelection {
-                mailing_lists: mailing_lists.clone(),
-                target_list: String::new(),
-                possible_mailing_lists: mailing_lists,
-                highlighted_list_index: 0,
-                mailing_lists_path: config.mailing_lists_path().to_string(),
-                lore_api_client: lore_api_client.clone(),
-            },
+            mailing_list_selection: MailingListSelection::new(&config, lore_api_client.clone()),
             latest_patchsets: None,
             details_actions: None,
             edit_config: None,
+            worktrees: None,
+            kernel_trees: None,
+            apply_history: None,
             bookmarked_patchsets: BookmarkedPatchsets {
                 bookmarked_patchsets,
                 patchset_index: 0,
             },
+            recent_patchsets: RecentPatchsets {
+                recent_patchsets,
+                patchset_index: 0,
+            },
             reviewed_patchsets,
+            attestation_statuses,
+            build_statuses,
+            apply_records,
+            git_am_conflict,
+            command_history,
             config,
+            keymap,
+            theme,
             lore_api_client,
             popup: None,
+            replayed_key: None,
+            tabs: Tabs::default(),
+            navigation_history: NavigationHistory::default(),
         })
     }
 
@@ -117,38 +238,224 @@ impl App {
         let target_list = self.mailing_list_selection.possible_mailing_lists[list_index]
             .name()
             .to_string();
+        self.init_latest_patchsets_of(target_list, String::new());
+    }
+
+    /// Initializes field [App::latest_patchsets] with the patchsets of
+    /// `target_list` matching `search_query` (all of them, if empty).
+    pub fn init_latest_patchsets_of(&mut self, target_list: String, search_query: String) {
         self.latest_patchsets = Some(LatestPatchsets::new(
             target_list,
+            search_query,
             self.config.page_size(),
             self.lore_api_client.clone(),
         ));
     }
 
-    /// Sets field [App::latest_patchsets] to `None`.
-    pub fn reset_latest_patchsets(&mut self) {
-        self.latest_patchsets = None;
+    /// Runs `f` with [App::tabs] and the state of the active tab, which is
+    /// taken from the fields of the app and put back after `f` runs.
+    fn with_active_tab<T>(&mut self, f: impl FnOnce(&mut Tabs, &mut Tab) -> T) -> T {
+        let mut active_tab = Tab {
+            current_screen: self.current_screen.clone(),
+            latest_patchsets: self.latest_patchsets.take(),
+            details_actions: self.details_actions.take(),
+            navigation_history: mem::take(&mut self.navigation_history),
+        };
+        let result = f(&mut self.tabs, &mut active_tab);
+        self.current_screen = active_tab.current_screen;
+        self.latest_patchsets = active_tab.latest_patchsets;
+        self.details_actions = active_tab.details_actions;
+        self.navigation_history = active_tab.navigation_history;
+        result
+    }
+
+    /// Opens a new tab, in the mailing list selection, and switches to it
+    pub fn open_tab(&mut self) {
+        self.with_active_tab(|tabs, active_tab| tabs.open(active_tab));
+        self.mailing_list_selection.clear_target_list();
+    }
+
+    /// Closes the active tab, unless it's the only one, and switches to the
+    /// next one. Returns if the tab was closed.
+    pub fn close_tab(&mut self) -> bool {
+        self.with_active_tab(|tabs, active_tab| tabs.close(active_tab))
+    }
+
+    /// Switches to tab `index`, if there is such a tab
+    pub fn switch_tab(&mut self, index: usize) -> bool {
+        self.with_active_tab(|tabs, active_tab| tabs.switch(index, active_tab))
+    }
+
+    pub fn switch_to_next_tab(&mut self) {
+        self.with_active_tab(|tabs, active_tab| tabs.switch_to_next(active_tab));
+    }
+
+    pub fn switch_to_previous_tab(&mut self) {
+        self.with_active_tab(|tabs, active_tab| tabs.switch_to_previous(active_tab));
+    }
+
+    /// Returns the titles of the tabs (see [tabs::Tab::title])
+    pub fn tab_titles(&self) -> Vec<String> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(i, tab)| {
+                if i == self.tabs.active() {
+                    tab_title(
+                        &self.current_screen,
+                        self.latest_patchsets.as_ref(),
+                        self.details_actions.as_ref(),
+                    )
+                } else {
+                    tab.title()
+                }
+            })
+            .collect()
+    }
+
+    /// Records the activity of the list of [App::latest_patchsets] (see
+    /// [ListActivity]), counted from the patchsets fetched so far, unless they
+    /// are the results of a search.
+    pub fn record_list_activity(&mut self) {
+        let Some(latest_patchsets) = self.latest_patchsets.as_ref() else {
+            return;
+        };
+        if !latest_patchsets.lore_session().search_query().is_empty() {
+            return;
+        }
+
+        let updates: Vec<&str> = latest_patchsets
+            .processed_patchsets()
+            .into_iter()
+            .map(|patch| patch.updated().as_str())
+            .collect();
+        let activity = ListActivity::new(&updates, chrono::Utc::now());
+        self.mailing_list_selection
+            .record_list_activity(latest_patchsets.target_list(), activity);
+    }
+
+    /// Takes the current screen and the state owned by it (see
+    /// [navigation::Snapshot])
+    fn take_snapshot(&mut self) -> Snapshot {
+        let screen = self.current_screen.clone();
+        Snapshot {
+            latest_patchsets: match screen {
+                CurrentScreen::LatestPatchsets => self.latest_patchsets.take(),
+                _ => None,
+            },
+            details_actions: match screen {
+                CurrentScreen::PatchsetDetails => self.details_actions.take(),
+                _ => None,
+            },
+            screen,
+        }
+    }
+
+    fn restore_snapshot(&mut self, snapshot: Snapshot) {
+        // The apply history is shared by every tab, so it may have been closed
+        // from another one
+        if snapshot.screen == CurrentScreen::ApplyHistory && self.apply_history.is_none() {
+            self.init_apply_history();
+        }
+        self.current_screen = snapshot.screen;
+        self.latest_patchsets = snapshot.latest_patchsets;
+        self.details_actions = snapshot.details_actions;
+    }
+
+    /// Changes the current screen to `screen`, recording the previous one,
+    /// with its state, in the navigation history of the tab. The state of
+    /// `screen` must be initialized, except if it's the same as the current
+    /// screen, whose state is recorded first.
+    pub fn navigate_to(&mut self, screen: CurrentScreen) {
+        let snapshot = self.take_snapshot();
+        self.navigation_history.push(snapshot);
+        self.current_screen = screen;
+    }
+
+    /// Goes back to the previous screen of the tab, exactly as it was left.
+    /// Returns if there was a screen to go back to.
+    pub fn go_back(&mut self) -> bool {
+        let mut snapshot = self.take_snapshot();
+        let went_back = self.navigation_history.go_back(&mut snapshot);
+        self.restore_snapshot(snapshot);
+        went_back
+    }
+
+    /// Goes back to the previous screen of the tab (see [App::go_back]) or, if
+    /// there is none, to the mailing list selection, discarding the state of
+    /// the current screen
+    pub fn exit_screen(&mut self) {
+        let mut snapshot = self.take_snapshot();
+        if !self.navigation_history.go_back(&mut snapshot) {
+            snapshot = Snapshot {
+                screen: CurrentScreen::MailingListSelection,
+                latest_patchsets: None,
+                details_actions: None,
+            };
+        }
+        self.restore_snapshot(snapshot);
+    }
+
+    /// Goes forward to the screen of the tab gone back from. Returns if there
+    /// was a screen to go forward to.
+    pub fn go_forward(&mut self) -> bool {
+        let mut snapshot = self.take_snapshot();
+        let went_forward = self.navigation_history.go_forward(&mut snapshot);
+        self.restore_snapshot(snapshot);
+        went_forward
     }
 
     /// Initializes field [App::details_actions], from currently selected
-    /// patchset in [App::bookmarked_patchsets] or [App::latest_patchsets],
-    /// depending on the value of [App::current_screen].
+    /// patchset in [App::bookmarked_patchsets], [App::recent_patchsets],
+    /// [App::latest_patchsets] or [App::apply_history], depending on the value
+    /// of [App::current_screen]. The patchset is added to the jump list and
+    /// a `git am` stopped by conflicts is offered to be proceeded with (see
+    /// [App::check_git_am_conflict]).
     pub fn init_details_actions(&mut self) -> color_eyre::Result<()> {
         let representative_patch: Patch;
         let mut is_patchset_bookmarked = true;
-        let mut reviewed_by = Vec::new();
-        let mut tested_by = Vec::new();
-        let mut acked_by = Vec::new();
 
         match &self.current_screen {
             CurrentScreen::BookmarkedPatchsets => {
                 representative_patch = self.bookmarked_patchsets.get_selected_patchset();
             }
+            CurrentScreen::RecentPatchsets => {
+                representative_patch = self.recent_patchsets.get_selected_patchset();
+                if !self
+                    .bookmarked_patchsets
+                    .bookmarked_patchsets
+                    .contains(&representative_patch)
+                {
+                    is_patchset_bookmarked = false;
+                }
+            }
             CurrentScreen::LatestPatchsets => {
-                representative_patch = self
+                let Some(patch) = self
                     .latest_patchsets
                     .as_ref()
                     .unwrap()
-                    .get_selected_patchset();
+                    .get_selected_patchset()
+                else {
+                    bail!("No patchset is selected");
+                };
+                representative_patch = patch;
+                if !self
+                    .bookmarked_patchsets
+                    .bookmarked_patchsets
+                    .contains(&representative_patch)
+                {
+                    is_patchset_bookmarked = false;
+                }
+            }
+            CurrentScreen::ApplyHistory => {
+                let Some(entry) = self
+                    .apply_history
+                    .as_ref()
+                    .and_then(|apply_history| apply_history.get_highlighted_entry())
+                else {
+                    bail!("No apply record highlighted");
+                };
+                representative_patch = entry.record.patchset().clone();
                 if !self
                     .bookmarked_patchsets
                     .bookmarked_patchsets
@@ -168,43 +475,93 @@ impl App {
             Err(io_error) => bail!("{io_error}"),
         };
 
+        // Replies are only needed to collect trailers, so failing to download
+        // the thread shouldn't prevent the patchset from being displayed
+        let raw_thread = match log_on_error!(
+            LogLevel::Warning,
+            lore_session::download_patchset_thread(
+                self.config.patchsets_cache_dir(),
+                &representative_patch,
+            )
+        ) {
+            Ok(thread_path) => fs::read_to_string(thread_path).unwrap_or_default(),
+            Err(_) => String::new(),
+        };
+
         match log_on_error!(lore_session::split_patchset(&patchset_path)) {
             Ok(raw_patches) => {
+                let has_cover_letter = representative_patch.number_in_series() == 0;
+                let trailer_names: Vec<&str> = trailers::DEFAULT_TRAILER_NAMES
+                    .into_iter()
+                    .chain(self.config.extra_trailers().iter().map(String::as_str))
+                    .collect();
+                let trailers = trailers::collect_thread_trailers(
+                    &raw_patches,
+                    has_cover_letter,
+                    &raw_thread,
+                    &trailer_names,
+                );
+                if let Some(latest_patchsets) = self.latest_patchsets.as_mut() {
+                    let review_count = trailers
+                        .iter()
+                        .flat_map(|patch_trailers| {
+                            trailers::CODE_REVIEW_TRAILERS
+                                .map(|name| patch_trailers.get(name).len())
+                        })
+                        .sum();
+                    latest_patchsets
+                        .set_review_count(&representative_patch.message_id().href, review_count);
+                }
+
+                // `Fixes:` trailers are only resolved once their patch is
+                // previewed (see `resolve_previewed_fixes`)
+                let fixes = trailers
+                    .iter()
+                    .map(|patch_trailers| {
+                        patch_trailers
+                            .get("Fixes")
+                            .iter()
+                            .map(|trailer| fixes::check_fixes(&trailer.value))
+                            .collect()
+                    })
+                    .collect();
+
+                let maintainers =
+                    self.load_maintainers_and_user_email()
+                        .ok()
+                        .map(|(sections, user_email)| {
+                            maintainers::touched_sections(&sections, &raw_patches, &user_email)
+                        });
+
+                let tree_entries: Vec<String> = maintainers
+                    .iter()
+                    .flatten()
+                    .flat_map(|section| section.trees().iter().cloned())
+                    .collect();
+                // This runs git in the kernel trees, but only the cheap
+                // `cat-file -e` and `rev-parse` unless a `base-commit` is
+                // only found outside the candidates, and opening the details
+                // is always done behind a loading screen
+                let apply_base = base_tree::detect_base_tree(
+                    &self.config,
+                    base_tree::list_of_message_id(&representative_patch.message_id().href)
+                        .as_deref(),
+                    &tree_entries,
+                    base_tree::extract_base_commit(&raw_patches).as_deref(),
+                    &base_tree::GitKernelTrees,
+                );
+
                 let mut patches_preview: Vec<Text> = Vec::new();
                 for raw_patch in &raw_patches {
                     let raw_patch = raw_patch.replace('\t', "        ");
 
                     let (raw_cover, raw_patch) = lore_session::split_cover(&raw_patch);
 
-                    let mut authors_reviewed_by = HashSet::new();
-                    let mut authors_tested_by = HashSet::new();
-                    let mut authors_acked_by = HashSet::new();
-
-                    let mut map = [
-                        ("Reviewed-by:", &mut authors_reviewed_by),
-                        ("Tested-by:", &mut authors_tested_by),
-                        ("Acked-by:", &mut authors_acked_by),
-                    ];
-
-                    for line in raw_cover.lines() {
-                        for (prefix, authors) in map.iter_mut() {
-                            if let Some(stripped) = line.trim_start().strip_prefix(*prefix) {
-                                let parts: Vec<&str> = stripped.trim().split('<').collect();
-                                if parts.len() == 2 {
-                                    let name = parts[0].trim().to_string();
-                                    let email = parts[1].trim_end_matches('>').trim().to_string();
-                                    authors.insert(Author { name, email });
-                                }
-                                break; // Avoid unnecessary checks once a match is found
-                            }
-                        }
-                    }
-                    reviewed_by.push(authors_reviewed_by);
-                    tested_by.push(authors_tested_by);
-                    acked_by.push(authors_acked_by);
-
-                    let rendered_cover = match render_cover(raw_cover, self.config.cover_renderer())
-                    {
+                    let rendered_cover = match render_cover(
+                        raw_cover,
+                        self.config.cover_renderer(),
+                        self.theme.preset,
+                    ) {
                         Ok(render) => render,
                         Err(_) => {
                             Logger::error("Failed to render cover preview with external program");
@@ -212,53 +569,198 @@ impl App {
                         }
                     };
 
-                    let rendered_patch =
-                        match render_patch_preview(raw_patch, self.config.patch_renderer()) {
-                            Ok(render) => render,
-                            Err(_) => {
-                                Logger::error(
-                                    "Failed to render patch preview with external program",
-                                );
-                                raw_patch.to_string()
-                            }
-                        };
-
-                    patches_preview
-                        .push(format!("{}---\n{}", rendered_cover, rendered_patch).into_text()?);
+                    let rendered_patch = match render_patch_preview(
+                        raw_patch,
+                        self.config.patch_renderer(),
+                        self.theme.preset,
+                    ) {
+                        Ok(render) => render,
+                        Err(_) => {
+                            Logger::error("Failed to render patch preview with external program");
+                            raw_patch.to_string()
+                        }
+                    };
+
+                    let mut patch_preview =
+                        format!("{}---\n{}", rendered_cover, rendered_patch).into_text()?;
+                    // The renderers color their output regardless of the theme
+                    if self.theme.preset == ThemePreset::NoColor {
+                        theme::strip_styles(&mut patch_preview);
+                    }
+                    patches_preview.push(patch_preview);
                 }
-                let has_cover_letter = representative_patch.number_in_series() == 0;
                 let patches_to_reply = vec![false; raw_patches.len()];
+
+                let attestations = lore_session::load_patchset_attestation(&patchset_path)
+                    .per_patch(raw_patches.len(), has_cover_letter);
+                self.attestation_statuses.insert(
+                    representative_patch.message_id().href.clone(),
+                    AttestationStatus::from_patches(&attestations),
+                );
+                let _ = log_on_error!(lore_session::save_attestation_statuses(
+                    &self.attestation_statuses,
+                    self.config.attestation_statuses_path(),
+                ));
+
                 self.details_actions = Some(DetailsActions {
                     representative_patch,
                     raw_patches,
                     patches_preview,
                     patches_to_reply,
+                    reply_trailer: ReplyTrailer::default(),
                     has_cover_letter,
                     preview_index: 0,
                     preview_scroll_offset: 0,
                     preview_pan: 0,
                     preview_fullscreen: false,
+                    preview_viewport: Size::default(),
+                    preview_search: None,
+                    search_prompt: None,
                     patchset_actions: HashMap::from([
                         (PatchsetAction::Bookmark, is_patchset_bookmarked),
                         (PatchsetAction::ReplyWithReviewedBy, false),
                         (PatchsetAction::Apply, false),
+                        (PatchsetAction::Checkpatch, false),
+                        (PatchsetAction::BuildTest, false),
+                        (PatchsetAction::Export, false),
                     ]),
-                    reviewed_by,
-                    tested_by,
-                    acked_by,
-                    last_screen: self.current_screen.clone(),
+                    apply_per_patch: false,
+                    apply_base,
+                    applied_kernel_tree: None,
+                    applied_branch: None,
+                    applied_worktree: None,
+                    export_format: ExportFormat::default(),
+                    git_am_conflict: None,
+                    apply_upstream: None,
+                    last_apply_record: None,
+                    trailers,
+                    fixes,
+                    checkpatch_reports: None,
+                    maintainers,
+                    attestations,
                     lore_api_client: self.lore_api_client.clone(),
                     patchset_path,
                 });
+                self.push_to_jump_list();
+                self.check_git_am_conflict();
                 Ok(())
             }
             Err(message) => bail!(message),
         }
     }
 
-    /// Sets field [App::details_actions] to `None`.
-    pub fn reset_details_actions(&mut self) {
-        self.details_actions = None;
+    /// Resolves the `Fixes:` trailers of the patch previewed in
+    /// [App::details_actions] against the target kernel tree. They are left
+    /// unresolved if the tree isn't valid.
+    pub fn resolve_previewed_fixes(&mut self) -> color_eyre::Result<()> {
+        let kernel_tree = validate_kernel_tree(&self.config).ok();
+        let kernel_tree_path = kernel_tree.as_ref().map(|tree| tree.path().as_str());
+
+        if let Some(details_actions) = self.details_actions.as_mut() {
+            for fixes_check in &mut details_actions.fixes[details_actions.preview_index] {
+                fixes_check.resolve(kernel_tree_path);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Loads the `MAINTAINERS` sections of the target kernel tree and the email
+    /// of the user in it (see [lore_session::get_git_signature]).
+    ///
+    /// Returns a `String` with the error message on failure.
+    fn load_maintainers_and_user_email(&self) -> Result<(Vec<MaintainersSection>, String), String> {
+        let kernel_tree = validate_kernel_tree(&self.config)?;
+        let sections = maintainers::load_maintainers(kernel_tree.path())
+            .map_err(|e| format!("failed to load MAINTAINERS of {}: {e}", kernel_tree.path()))?;
+        let (_, user_email) = lore_session::get_git_signature(kernel_tree.path());
+        if user_email.is_empty() {
+            return Err("`git config user.email` not set".to_string());
+        }
+
+        Ok((sections, user_email))
+    }
+
+    /// Classifies the patchsets fetched so far in [App::latest_patchsets] that
+    /// weren't classified yet as touching `MAINTAINERS` sections in which the
+    /// user is a maintainer or reviewer or not, which requires downloading
+    /// them. On failure, the "my subsystems" filter is disabled and a pop-up
+    /// with the reason is displayed.
+    pub fn classify_latest_patchsets_by_my_subsystems(&mut self) {
+        if let Err(msg) = self.try_classify_latest_patchsets_by_my_subsystems() {
+            let latest_patchsets = self.latest_patchsets.as_mut().unwrap();
+            if latest_patchsets.my_subsystems_filter() {
+                latest_patchsets.toggle_my_subsystems_filter();
+            }
+            self.popup = Some(InfoPopUp::generate_info_popup(
+                "My Subsystems Filter Fail",
+                &msg,
+            ));
+        }
+        self.latest_patchsets
+            .as_mut()
+            .unwrap()
+            .select_first_listed_patchset();
+    }
+
+    fn try_classify_latest_patchsets_by_my_subsystems(&mut self) -> Result<(), String> {
+        let (sections, user_email) = self.load_maintainers_and_user_email()?;
+        let latest_patchsets = self.latest_patchsets.as_mut().unwrap();
+
+        let patchsets_cache_dir = self.config.patchsets_cache_dir();
+        let download = |patch: &Patch| {
+            let patchset_path = lore_session::download_patchset(patchsets_cache_dir, patch)
+                .map_err(|e| format!("failed to download '{}': {e}", patch.title()))?;
+            lore_session::split_patchset(&patchset_path)
+        };
+
+        // Patchsets are downloaded a few at a time, as each download waits on
+        // the network
+        for patchsets in latest_patchsets
+            .unclassified_patchsets()
+            .chunks(CLASSIFY_DOWNLOAD_THREADS)
+        {
+            let downloads: Vec<Result<Vec<String>, String>> = thread::scope(|s| {
+                let handles: Vec<_> = patchsets
+                    .iter()
+                    .map(|patch| s.spawn(move || download(patch)))
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().expect("download thread panicked"))
+                    .collect()
+            });
+
+            for (patch, raw_patches) in patchsets.iter().zip(downloads) {
+                let touches_my_subsystems =
+                    maintainers::touched_sections(&sections, &raw_patches?, &user_email)
+                        .iter()
+                        .any(|section| section.role().is_some());
+                latest_patchsets
+                    .set_touches_my_subsystems(&patch.message_id().href, touches_my_subsystems);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds the patchset of [App::details_actions] to the jump list (see
+    /// [navigation::push_to_jump_list]) and saves it
+    fn push_to_jump_list(&mut self) {
+        let representative_patch = &self.details_actions.as_ref().unwrap().representative_patch;
+        navigation::push_to_jump_list(
+            &mut self.recent_patchsets.recent_patchsets,
+            representative_patch,
+        );
+        // The patchset moved to the top, so it stays selected
+        self.recent_patchsets.patchset_index = 0;
+        let _ = log_on_error!(
+            LogLevel::Warning,
+            navigation::save_jump_list(
+                &self.recent_patchsets.recent_patchsets,
+                self.config.recent_patchsets_path(),
+            )
+        );
     }
 
     /// Determines and consolidates all actions (if any) to take for the current
@@ -317,11 +819,46 @@ impl App {
             .as_ref()
             .unwrap()
             .patchset_actions
-            .get(&PatchsetAction::Apply)
+            .get(&PatchsetAction::Checkpatch)
         {
+            let details_actions = self.details_actions.as_mut().unwrap();
+            let popup = match details_actions.run_checkpatch(&self.config) {
+                Ok(checkpatch_reports) => {
+                    details_actions.checkpatch_reports = Some(checkpatch_reports);
+                    CheckpatchPopUp::generate_checkpatch_popup(details_actions)
+                }
+                Err(msg) => InfoPopUp::generate_info_popup("Checkpatch Fail", &msg),
+            };
+
+            self.popup = Some(popup);
+
+            details_actions.toggle_checkpatch_action();
+        }
+
+        let details_actions = self.details_actions.as_mut().unwrap();
+        if let Some(true) = details_actions
+            .patchset_actions
+            .get(&PatchsetAction::Export)
+        {
+            let popup = match details_actions.export_patchset(&self.config) {
+                Ok(msg) => InfoPopUp::generate_info_popup("Patchset Export Success", &msg),
+                Err(msg) => InfoPopUp::generate_info_popup("Patchset Export Fail", &msg),
+            };
+            self.popup = Some(popup);
+
+            details_actions.toggle_export_action();
+        }
+
+        let details_actions = self.details_actions.as_ref().unwrap();
+        // Applying one patch at a time streams its log, so it is done apart
+        // (see [App::apply_patchset_per_patch])
+        if let (Some(true), false) = (
+            details_actions.patchset_actions.get(&PatchsetAction::Apply),
+            details_actions.apply_per_patch,
+        ) {
             let popup = match self
                 .details_actions
-                .as_ref()
+                .as_mut()
                 .unwrap()
                 .apply_patchset(&self.config)
             {
@@ -331,12 +868,205 @@ impl App {
 
             self.popup = Some(popup);
 
-            self.details_actions.as_mut().unwrap().toggle_apply_action();
+            let details_actions = self.details_actions.as_mut().unwrap();
+            details_actions.toggle_apply_action();
+            // The conflicts outlive the patchset details until resolved
+            if let Some(conflict) = details_actions.git_am_conflict.take() {
+                self.popup = Some(GitAmConflictPopUp::generate_git_am_conflict_popup(
+                    &conflict,
+                ));
+                self.git_am_conflict = Some(conflict);
+                self.save_git_am_conflict();
+            }
+            self.save_last_apply_record();
         }
 
         Ok(())
     }
 
+    /// Proceeds with [App::git_am_conflict] as chosen in `resolution` (see
+    /// [git_am::resolve_git_am_conflict]) and displays the outcome in a
+    /// pop-up, which lets the user choose again if there are still conflicts.
+    /// Once `git am` is over, its outcome is recorded in the apply history.
+    pub fn resolve_git_am_conflict(&mut self, resolution: GitAmResolution) {
+        let Some(conflict) = self.git_am_conflict.as_mut() else {
+            self.popup = Some(InfoPopUp::generate_info_popup(
+                "Patchset Apply",
+                "There is no apply stopped by conflicts",
+            ));
+            return;
+        };
+
+        let state = match git_am::resolve_git_am_conflict(conflict, resolution) {
+            Ok(state) => state,
+            Err(msg) => {
+                self.popup = Some(InfoPopUp::generate_info_popup("Patchset Apply Fail", &msg));
+                return;
+            }
+        };
+        if state == GitAmState::InProgress {
+            self.popup = Some(GitAmConflictPopUp::generate_git_am_conflict_popup(conflict));
+            self.save_git_am_conflict();
+            return;
+        }
+
+        let conflict = self.git_am_conflict.take().unwrap();
+        self.save_git_am_conflict();
+
+        let applied = state == GitAmState::Applied;
+        let result = if applied {
+            ApplyResult::Applied
+        } else {
+            ApplyResult::Failed
+        };
+        self.save_apply_record(conflict.apply_record(result));
+
+        let title = conflict.patchset().title();
+        let msg = if applied {
+            if let Some(details_actions) = self
+                .details_actions
+                .as_mut()
+                .filter(|details_actions| conflict.is_of(&details_actions.representative_patch))
+            {
+                details_actions.finish_git_am_conflict(&conflict);
+            }
+            format!(
+                " Patchset '{title}' applied successfully!\n\n - Kernel Tree: '{}'\n\n - Applied branch: '{}'",
+                conflict.kernel_tree().path(),
+                conflict.branch()
+            )
+        } else {
+            format!(" Applying patchset '{title}' was aborted")
+        };
+        self.popup = Some(InfoPopUp::generate_info_popup("Patchset Apply", &msg));
+    }
+
+    /// Offers to proceed with [App::git_am_conflict] in a pop-up, if there
+    /// is one. Once patchset details are open, it's only offered in those of
+    /// the patchset whose apply stopped. The conflict is dropped if `git am`
+    /// was finished outside of patch-hub.
+    pub fn check_git_am_conflict(&mut self) {
+        let Some(conflict) = &self.git_am_conflict else {
+            return;
+        };
+        if !git_am::is_git_am_in_progress(conflict.repo_path()) {
+            self.git_am_conflict = None;
+            self.save_git_am_conflict();
+            return;
+        }
+
+        if self
+            .details_actions
+            .as_ref()
+            .is_none_or(|details_actions| conflict.is_of(&details_actions.representative_patch))
+        {
+            self.popup = Some(GitAmConflictPopUp::generate_git_am_conflict_popup(conflict));
+        }
+    }
+
+    /// Saves [App::git_am_conflict].
+    fn save_git_am_conflict(&self) {
+        let _ = log_on_error!(git_am::save_git_am_conflict(
+            self.git_am_conflict.as_ref(),
+            self.config.git_am_conflict_path(),
+        ));
+    }
+
+    /// Applies the patchset in `details_actions` one patch at a time (see
+    /// [DetailsActions::apply_patchset_per_patch]) if the apply action is
+    /// marked in this mode, and displays what was done in a pop-up.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `details_actions` is `None`.
+    pub fn apply_patchset_per_patch(&mut self, log: &Mutex<Vec<String>>) {
+        let details_actions = self.details_actions.as_mut().unwrap();
+        if !details_actions.apply_per_patch {
+            return;
+        }
+        if let Some(false) | None = details_actions.patchset_actions.get(&PatchsetAction::Apply) {
+            return;
+        }
+        details_actions.toggle_apply_action();
+
+        let (title, msg) = match details_actions.apply_patchset_per_patch(&self.config, log) {
+            Ok(msg) => ("Per-patch Apply Success", msg),
+            Err(msg) => ("Per-patch Apply Fail", msg),
+        };
+        let mut log = log.lock().map(|log| log.clone()).unwrap_or_default();
+        log.push(String::new());
+        log.extend(msg.lines().map(String::from));
+        self.popup = Some(LogViewerPopUp::generate_log_viewer_popup(title, log));
+        self.save_last_apply_record();
+    }
+
+    /// Adds the record of the last apply of the patchset in `details_actions`
+    /// (if it wasn't added yet) to [App::apply_records] and saves it.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `details_actions` is `None`.
+    fn save_last_apply_record(&mut self) {
+        let details_actions = self.details_actions.as_mut().unwrap();
+        if let Some(record) = details_actions.last_apply_record.take() {
+            self.save_apply_record(record);
+        }
+    }
+
+    /// Adds `record` to [App::apply_records] (see
+    /// [apply_history::record_apply]) and saves it.
+    fn save_apply_record(&mut self, record: ApplyRecord) {
+        apply_history::record_apply(&mut self.apply_records, record);
+        let _ = log_on_error!(apply_history::save_apply_history(
+            &self.apply_records,
+            self.config.apply_history_path(),
+        ));
+    }
+
+    /// Build-tests the patchset in `details_actions` (see
+    /// [DetailsActions::build_test_patchset]) if the action is marked,
+    /// records its [BuildStatus] and displays the build log in a pop-up.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `details_actions` is `None`.
+    pub fn build_test_patchset(&mut self, log: &Mutex<Vec<String>>) {
+        let details_actions = self.details_actions.as_mut().unwrap();
+        if let Some(false) | None = details_actions
+            .patchset_actions
+            .get(&PatchsetAction::BuildTest)
+        {
+            return;
+        }
+        details_actions.toggle_build_test_action();
+
+        let message_id = details_actions
+            .representative_patch
+            .message_id()
+            .href
+            .clone();
+        match details_actions.build_test_patchset(&self.config, log) {
+            Ok(build_status) => {
+                self.build_statuses.insert(message_id, build_status);
+                let _ = log_on_error!(build_test::save_build_statuses(
+                    &self.build_statuses,
+                    self.config.build_statuses_path(),
+                ));
+
+                let log = log.lock().map(|log| log.clone()).unwrap_or_default();
+                self.popup = Some(LogViewerPopUp::generate_log_viewer_popup(
+                    &format!("Build-test {build_status}"),
+                    log,
+                ));
+            }
+            // Don't hide why applying the patchset (if also marked) failed
+            Err(_) if details_actions.applied_branch.is_none() && self.popup.is_some() => {}
+            Err(msg) => {
+                self.popup = Some(InfoPopUp::generate_info_popup("Build-test Fail", &msg));
+            }
+        }
+    }
+
     /// Initializes field [App::edit_config], using values from [App::config].
     pub fn init_edit_config(&mut self) {
         self.edit_config = Some(EditConfig::new(&self.config));
@@ -379,6 +1109,165 @@ impl App {
         }
     }
 
+    /// Initializes field [App::worktrees] with the worktrees of every kernel
+    /// tree under [Config::worktrees_dir] (see [Worktrees::new]).
+    pub fn init_worktrees(&mut self) {
+        self.worktrees = Some(Worktrees::new(&self.config));
+    }
+
+    /// Sets field [App::worktrees] to `None`.
+    pub fn reset_worktrees(&mut self) {
+        self.worktrees = None;
+    }
+
+    /// Removes the worktree selected in [App::worktrees] (see
+    /// [Worktrees::remove_selected_worktree]). If git refused to remove it
+    /// without `force`, a pop-up offers to force removing it.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `worktrees` is `None`.
+    pub fn remove_selected_worktree(&mut self, force: bool) {
+        let worktrees = self.worktrees.as_mut().unwrap();
+
+        self.popup = match worktrees.remove_selected_worktree(force) {
+            Ok(()) => None,
+            Err(msg) if force => Some(InfoPopUp::generate_info_popup("Prune Worktree Fail", &msg)),
+            Err(msg) => Some(ConfirmPopUp::generate_confirm_popup(
+                "Force Prune Worktree",
+                &format!("{msg}\n\nForce removing the worktree, discarding its changes?"),
+                Confirmation::RemoveWorktree { force: true },
+            )),
+        };
+    }
+
+    /// Initializes field [App::apply_history] from [App::apply_records].
+    pub fn init_apply_history(&mut self) {
+        self.apply_history = Some(ApplyHistory::new(&self.apply_records));
+    }
+
+    /// Sets field [App::apply_history] to `None`.
+    pub fn reset_apply_history(&mut self) {
+        self.apply_history = None;
+    }
+
+    /// Deletes the branches chosen in [App::apply_history] (see
+    /// [ApplyHistory::delete_branches]), prunes their records from
+    /// [App::apply_records] and saves it. If some branches couldn't be deleted
+    /// without `force`, a pop-up offers to force deleting them.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `apply_history` is `None`.
+    pub fn delete_apply_history_branches(&mut self, force: bool) {
+        let apply_history = self.apply_history.as_mut().unwrap();
+        let (deleted_records, errors) = apply_history.delete_branches(force);
+
+        if !deleted_records.is_empty() {
+            self.apply_records
+                .retain(|record| !deleted_records.contains(record));
+            let _ = log_on_error!(apply_history::save_apply_history(
+                &self.apply_records,
+                self.config.apply_history_path(),
+            ));
+        }
+
+        let mut msg = format!("Deleted {} branch(es)", deleted_records.len());
+        for error in &errors {
+            msg.push_str(&format!("\n\n{error}"));
+        }
+        self.popup = Some(if errors.is_empty() || force {
+            InfoPopUp::generate_info_popup("Delete Branches", &msg)
+        } else {
+            msg.push_str("\n\nForce deleting the branches left (and their worktrees)?");
+            ConfirmPopUp::generate_confirm_popup(
+                "Force Delete Branches",
+                &msg,
+                Confirmation::DeleteBranches { force: true },
+            )
+        });
+    }
+
+    /// Initializes field [App::kernel_trees], using values from [App::config].
+    pub fn init_kernel_trees(&mut self) {
+        self.kernel_trees = Some(KernelTrees::new(&self.config));
+    }
+
+    /// Sets field [App::kernel_trees] to `None`.
+    pub fn reset_kernel_trees(&mut self) {
+        self.kernel_trees = None;
+    }
+
+    /// Adds or renames a kernel tree in [App::config] as filled in the form
+    /// of [App::kernel_trees] and saves the config. A kernel tree is only
+    /// added if its path is a git repository with the branch, which is
+    /// prefilled with the checked out one if empty. If the values are
+    /// invalid, the form is kept open with the error.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `kernel_trees` is `None`.
+    pub fn consolidate_kernel_tree_form(&mut self) -> color_eyre::Result<()> {
+        let kernel_trees = self.kernel_trees.as_mut().unwrap();
+        let Some(form) = kernel_trees.form.as_mut() else {
+            return Ok(());
+        };
+
+        let kernel_tree_id = form.value("Name").to_string();
+        let result = match &form.kind {
+            KernelTreeFormKind::Add => {
+                form.prefill_branch();
+                form.validated_kernel_tree().and_then(|kernel_tree| {
+                    self.config.add_kernel_tree(&kernel_tree_id, kernel_tree)
+                })
+            }
+            KernelTreeFormKind::Rename(old_id) => {
+                self.config.rename_kernel_tree(old_id, &kernel_tree_id)
+            }
+        };
+
+        match result {
+            Ok(_) => {
+                self.config.save_patch_hub_config()?;
+                kernel_trees.refresh(&self.config, Some(&kernel_tree_id));
+            }
+            Err(msg) => {
+                form.error = Some(msg);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the kernel tree named `kernel_tree_id` from [App::config],
+    /// saves the config and refreshes [App::kernel_trees], if initialized.
+    pub fn remove_kernel_tree(&mut self, kernel_tree_id: &str) -> color_eyre::Result<()> {
+        self.config.remove_kernel_tree(kernel_tree_id);
+        self.config.save_patch_hub_config()?;
+        if let Some(kernel_trees) = self.kernel_trees.as_mut() {
+            kernel_trees.refresh(&self.config, None);
+        }
+        Ok(())
+    }
+
+    /// Sets the highlighted kernel tree of [App::kernel_trees] as the target
+    /// kernel tree and saves the config.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `kernel_trees` is `None`.
+    pub fn target_highlighted_kernel_tree(&mut self) -> color_eyre::Result<()> {
+        let kernel_trees = self.kernel_trees.as_mut().unwrap();
+        if let Some(entry) = kernel_trees.get_highlighted() {
+            if let Err(msg) = self.config.set_target_kernel_tree(&entry.id) {
+                self.popup = Some(InfoPopUp::generate_info_popup("Kernel Tree Fail", &msg));
+                return Ok(());
+            }
+            self.config.save_patch_hub_config()?;
+            kernel_trees.refresh(&self.config, None);
+        }
+        Ok(())
+    }
+
     /// Change the current active screen in [App::current_screen].
     pub fn set_current_screen(&mut self, new_current_screen: CurrentScreen) {
         self.current_screen = new_current_screen;
@@ -401,24 +1290,16 @@ impl App {
         }
 
         match self.config.patch_renderer() {
-            PatchRenderer::Bat => {
-                if !utils::binary_exists("bat") {
-                    Logger::warn("bat is not installed, patch rendering will fallback to default");
-                }
+            PatchRenderer::Bat if !utils::binary_exists("bat") => {
+                Logger::warn("bat is not installed, patch rendering will fallback to default");
             }
-            PatchRenderer::Delta => {
-                if !utils::binary_exists("delta") {
-                    Logger::warn(
-                        "delta is not installed, patch rendering will fallback to default",
-                    );
-                }
+            PatchRenderer::Delta if !utils::binary_exists("delta") => {
+                Logger::warn("delta is not installed, patch rendering will fallback to default");
             }
-            PatchRenderer::DiffSoFancy => {
-                if !utils::binary_exists("diff-so-fancy") {
-                    Logger::warn(
-                        "diff-so-fancy is not installed, patch rendering will fallback to default",
-                    );
-                }
+            PatchRenderer::DiffSoFancy if !utils::binary_exists("diff-so-fancy") => {
+                Logger::warn(
+                    "diff-so-fancy is not installed, patch rendering will fallback to default",
+                );
             }
             _ => {}
         }
@@ -0,0 +1,150 @@
+use std::{fmt::Display, io, process::Command};
+
+use derive_getters::Getters;
+use patch_hub::lore::{lore_session, patch::Patch};
+use serde::{Deserialize, Serialize};
+
+use super::worktree;
+
+#[cfg(test)]
+mod tests;
+
+/// Outcome of applying a patchset
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+pub enum ApplyResult {
+    #[serde(rename = "applied")]
+    Applied,
+    #[serde(rename = "failed")]
+    Failed,
+    /// `git am` stopped by conflicts that weren't resolved yet
+    #[serde(rename = "conflict")]
+    Conflict,
+}
+
+impl Display for ApplyResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApplyResult::Applied => write!(f, "applied"),
+            ApplyResult::Failed => write!(f, "failed"),
+            ApplyResult::Conflict => write!(f, "conflict"),
+        }
+    }
+}
+
+/// Record of a patchset applied to a kernel tree
+#[derive(Getters, Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ApplyRecord {
+    /// Representative patch of the patchset, used to open it again
+    patchset: Patch,
+    kernel_tree_path: String,
+    /// Ref from which `branch` was created
+    base: String,
+    /// Branch created to apply the patchset
+    branch: String,
+    /// Worktree in which `branch` is checked out, if the patchset was
+    /// applied in a worktree
+    worktree: Option<String>,
+    result: ApplyResult,
+    /// When the patchset was applied, like `2024-10-18 12:00`
+    timestamp: String,
+}
+
+impl ApplyRecord {
+    pub fn new(
+        patchset: Patch,
+        kernel_tree_path: String,
+        base: String,
+        branch: String,
+        worktree: Option<String>,
+        result: ApplyResult,
+    ) -> Self {
+        ApplyRecord {
+            patchset,
+            kernel_tree_path,
+            base,
+            branch,
+            worktree,
+            result,
+            timestamp: chrono::Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        }
+    }
+}
+
+/// Adds `record` to `apply_history`, replacing the record of the same branch
+/// of the same kernel tree, if any (like when the conflicts of an apply are
+/// resolved).
+///
+/// # Tests
+///
+/// [tests::should_record_apply]
+pub fn record_apply(apply_history: &mut Vec<ApplyRecord>, record: ApplyRecord) {
+    match apply_history
+        .iter_mut()
+        .find(|r| r.kernel_tree_path == record.kernel_tree_path && r.branch == record.branch)
+    {
+        Some(existing_record) => *existing_record = record,
+        None => apply_history.push(record),
+    }
+}
+
+/// Returns if the branch of `record` still exists in its kernel tree.
+pub fn branch_exists(record: &ApplyRecord) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(&record.kernel_tree_path)
+        .arg("rev-parse")
+        .arg("--verify")
+        .arg("--quiet")
+        .arg(format!("refs/heads/{}", record.branch))
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Deletes the branch of `record` from its kernel tree, removing the worktree
+/// in which it is checked out first, if any. Unless `force` is set, like
+/// `git branch -d`, a branch that isn't merged or a worktree with changes
+/// isn't deleted.
+///
+/// Returns `()` on success and a `String` with the error message on failure.
+pub fn delete_branch(record: &ApplyRecord, force: bool) -> Result<(), String> {
+    if let Some(worktree_path) = &record.worktree {
+        worktree::remove_worktree(&record.kernel_tree_path, worktree_path, force)?;
+    }
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(&record.kernel_tree_path)
+        .arg("branch")
+        .arg(if force { "-D" } else { "-d" })
+        .arg(&record.branch)
+        .output()
+        .map_err(|e| format!("failed to execute git-branch: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "failed to delete branch '{}': {}",
+            record.branch,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Saves `apply_history` to the file in `filepath`.
+///
+/// # Tests
+///
+/// [tests::should_save_and_load_apply_history]
+pub fn save_apply_history(apply_history: &[ApplyRecord], filepath: &str) -> io::Result<()> {
+    lore_session::save_json(apply_history, filepath)
+}
+
+/// Loads the apply history saved in the file in `filepath`.
+///
+/// # Tests
+///
+/// [tests::should_save_and_load_apply_history]
+pub fn load_apply_history(filepath: &str) -> io::Result<Vec<ApplyRecord>> {
+    lore_session::load_json(filepath)
+}
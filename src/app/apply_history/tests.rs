@@ -0,0 +1,74 @@
+use std::fs;
+
+use super::*;
+
+use patch_hub::lore::patch::{Author, MessageID};
+
+fn dummy_record(branch: &str, result: ApplyResult) -> ApplyRecord {
+    let patchset = Patch::new(
+        "[PATCH] foo: bar".to_string(),
+        Author {
+            name: "Foo Bar".to_string(),
+            email: "foo@bar.foo".to_string(),
+        },
+        MessageID {
+            href: "https://lore.kernel.org/all/1234.5678-1-foo@bar.foo/".to_string(),
+        },
+        None,
+        "2024-10-18T12:00:00Z".to_string(),
+    );
+    ApplyRecord::new(
+        patchset,
+        "/home/user/linux".to_string(),
+        "master".to_string(),
+        branch.to_string(),
+        None,
+        result,
+    )
+}
+
+#[test]
+fn should_record_apply() {
+    let mut apply_history = Vec::new();
+
+    record_apply(
+        &mut apply_history,
+        dummy_record("patchset-1", ApplyResult::Conflict),
+    );
+    record_apply(
+        &mut apply_history,
+        dummy_record("patchset-2", ApplyResult::Failed),
+    );
+    assert_eq!(2, apply_history.len());
+
+    record_apply(
+        &mut apply_history,
+        dummy_record("patchset-1", ApplyResult::Applied),
+    );
+    assert_eq!(
+        2,
+        apply_history.len(),
+        "Records of the same branch should be replaced"
+    );
+    assert_eq!("patchset-1", apply_history[0].branch());
+    assert_eq!(&ApplyResult::Applied, apply_history[0].result());
+}
+
+#[test]
+fn should_save_and_load_apply_history() {
+    let filepath = std::env::temp_dir().join(format!(
+        "patch-hub-apply-history-{}.json",
+        std::process::id()
+    ));
+    let filepath = filepath.to_str().unwrap();
+    let apply_history = vec![
+        dummy_record("patchset-1", ApplyResult::Applied),
+        dummy_record("patchset-2", ApplyResult::Conflict),
+    ];
+
+    save_apply_history(&apply_history, filepath).unwrap();
+    let loaded_apply_history = load_apply_history(filepath).unwrap();
+    fs::remove_file(filepath).unwrap();
+
+    assert_eq!(apply_history, loaded_apply_history);
+}
@@ -0,0 +1,280 @@
+use std::{process::Command, sync::LazyLock};
+
+use derive_getters::Getters;
+use regex::Regex;
+
+use super::config::{Config, KernelTree};
+
+#[cfg(test)]
+mod tests;
+
+/// Kernel tree and branch chosen to apply a patchset
+#[derive(Getters, Debug, Clone, Eq, PartialEq)]
+pub struct BaseTreeChoice {
+    /// Name of a kernel tree in `Config::kernel_trees`
+    kernel_tree_id: String,
+    branch: String,
+    /// Why the kernel tree and branch were chosen, to be displayed
+    reason: String,
+}
+
+impl BaseTreeChoice {
+    pub fn new(kernel_tree_id: String, branch: String, reason: String) -> Self {
+        BaseTreeChoice {
+            kernel_tree_id,
+            branch,
+            reason,
+        }
+    }
+}
+
+/// Returns the commit in the `base-commit:` line that `git format-patch
+/// --base` (and `b4`) appends to the cover letter or to the first patch of a
+/// series, if any.
+///
+/// # Tests
+///
+/// [tests::should_extract_base_commit]
+pub fn extract_base_commit(raw_patches: &[String]) -> Option<String> {
+    static RE_BASE_COMMIT: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(?m)^base-commit: ([0-9a-f]{7,40})\s*$").unwrap());
+
+    raw_patches.iter().find_map(|raw_patch| {
+        RE_BASE_COMMIT
+            .captures(raw_patch)
+            .map(|capture| capture[1].to_string())
+    })
+}
+
+/// Returns the name of the mailing list in the lore URL of a message, like
+/// `netdev` in `https://lore.kernel.org/netdev/<message-id>/`.
+///
+/// # Tests
+///
+/// [tests::should_extract_list_of_message_id]
+pub fn list_of_message_id(message_id_href: &str) -> Option<String> {
+    let path = message_id_href
+        .split_once("://")
+        .map_or(message_id_href, |(_, rest)| rest);
+    let mut segments = path.split('/');
+    segments.next()?;
+    segments
+        .next()
+        .filter(|list| !list.is_empty())
+        .map(String::from)
+}
+
+/// Queries about the git repositories of kernel trees needed to detect the base
+/// tree of a patchset, so they can be replaced in tests
+pub trait KernelTreeQueries {
+    /// Returns if `branch` is a local branch of the repository of
+    /// `kernel_tree`
+    fn has_branch(&self, kernel_tree: &KernelTree, branch: &str) -> bool;
+    /// Returns if `commit` is in the repository of `kernel_tree`
+    fn has_commit(&self, kernel_tree: &KernelTree, commit: &str) -> bool;
+    /// Returns the local branches of the repository of `kernel_tree` that
+    /// contain `commit`
+    fn branches_containing(&self, kernel_tree: &KernelTree, commit: &str) -> Vec<String>;
+}
+
+/// [KernelTreeQueries] answered by running git in the kernel trees
+pub struct GitKernelTrees;
+
+impl KernelTreeQueries for GitKernelTrees {
+    fn has_branch(&self, kernel_tree: &KernelTree, branch: &str) -> bool {
+        has_branch(kernel_tree.path(), branch)
+    }
+
+    fn has_commit(&self, kernel_tree: &KernelTree, commit: &str) -> bool {
+        run_git(
+            kernel_tree.path(),
+            &["cat-file", "-e", &format!("{commit}^{{commit}}")],
+        )
+        .is_some()
+    }
+
+    fn branches_containing(&self, kernel_tree: &KernelTree, commit: &str) -> Vec<String> {
+        run_git(
+            kernel_tree.path(),
+            &["branch", "--format=%(refname:short)", "--contains", commit],
+        )
+        .map(|output| output.lines().map(String::from).collect())
+        .unwrap_or_default()
+    }
+}
+
+/// Chooses the kernel tree and branch to apply a patchset sent to `list` that
+/// touches `MAINTAINERS` sections with `tree_entries` (`T:` entries), using
+/// the mapping in `Config::base_trees`, whose keys are either mailing list
+/// names or trailing segments of `T:` URLs. Mappings matching `T:` entries
+/// take precedence over the one of the mailing list. Mapped branches that
+/// don't exist in their kernel tree are replaced by the branch of the kernel
+/// tree.
+///
+/// If the patchset has a `base_commit`, the first candidate containing it is
+/// chosen, or any registered kernel tree containing it if no candidate does,
+/// on its branch if it contains the commit or on a branch that does
+/// otherwise. The repositories are inspected with `queries`, which only looks
+/// for branches containing the commit in the kernel tree chosen this way.
+///
+/// Returns `None` if no kernel tree was found.
+///
+/// # Tests
+///
+/// [tests::should_detect_base_tree]
+/// [tests::should_check_branches_of_base_tree]
+pub fn detect_base_tree(
+    config: &Config,
+    list: Option<&str>,
+    tree_entries: &[String],
+    base_commit: Option<&str>,
+    queries: &impl KernelTreeQueries,
+) -> Option<BaseTreeChoice> {
+    let mut mappings: Vec<_> = config.base_trees().iter().collect();
+    mappings.sort_by_key(|(key, _)| key.as_str());
+
+    let mut candidates: Vec<BaseTreeChoice> = Vec::new();
+    for (key, base_tree) in &mappings {
+        if let Some(entry) = tree_entries
+            .iter()
+            .find(|entry| tree_entry_matches(entry, key))
+        {
+            candidates.push(BaseTreeChoice {
+                kernel_tree_id: base_tree.kernel_tree().clone(),
+                branch: base_tree.branch().clone().unwrap_or_default(),
+                reason: format!("MAINTAINERS tree '{entry}'"),
+            });
+        }
+    }
+    if let Some((list, base_tree)) =
+        list.and_then(|list| mappings.iter().find(|(key, _)| key.as_str() == list))
+    {
+        candidates.push(BaseTreeChoice {
+            kernel_tree_id: base_tree.kernel_tree().clone(),
+            branch: base_tree.branch().clone().unwrap_or_default(),
+            reason: format!("mailing list '{list}'"),
+        });
+    }
+
+    // Unregistered kernel trees can't be used and branches default to the
+    // one of the kernel tree
+    let mut candidates: Vec<(BaseTreeChoice, &KernelTree)> = candidates
+        .into_iter()
+        .filter_map(|mut candidate| {
+            let kernel_tree = config.get_kernel_tree(&candidate.kernel_tree_id)?;
+            if candidate.branch.is_empty() {
+                candidate.branch = kernel_tree.branch().clone();
+            } else if !queries.has_branch(kernel_tree, &candidate.branch) {
+                candidate
+                    .reason
+                    .push_str(&format!(", mapped branch '{}' not found", candidate.branch));
+                candidate.branch = kernel_tree.branch().clone();
+            }
+            Some((candidate, kernel_tree))
+        })
+        .collect();
+
+    let Some(base_commit) = base_commit else {
+        return candidates
+            .into_iter()
+            .next()
+            .map(|(candidate, _)| candidate);
+    };
+
+    if let Some(position) = candidates
+        .iter()
+        .position(|(_, kernel_tree)| queries.has_commit(kernel_tree, base_commit))
+    {
+        let (mut candidate, _) = candidates.swap_remove(position);
+        candidate.reason.push_str(", contains base-commit");
+        return Some(candidate);
+    }
+
+    let mut kernel_tree_ids: Vec<&String> = config.kernel_trees().into_iter().collect();
+    kernel_tree_ids.sort();
+    let with_base_commit = kernel_tree_ids.into_iter().find_map(|id| {
+        let kernel_tree = config.get_kernel_tree(id)?;
+        if !queries.has_commit(kernel_tree, base_commit) {
+            return None;
+        }
+
+        let branches = queries.branches_containing(kernel_tree, base_commit);
+        let branch = match branches.first() {
+            Some(branch) if !branches.contains(kernel_tree.branch()) => branch.clone(),
+            _ => kernel_tree.branch().clone(),
+        };
+        Some(BaseTreeChoice {
+            kernel_tree_id: id.clone(),
+            branch,
+            reason: format!(
+                "contains base-commit {}",
+                base_commit.get(..12).unwrap_or(base_commit)
+            ),
+        })
+    });
+
+    with_base_commit.or_else(|| {
+        candidates
+            .into_iter()
+            .next()
+            .map(|(candidate, _)| candidate)
+    })
+}
+
+/// Returns if the URL of a `T:` entry, like `git
+/// git://git.kernel.org/pub/scm/linux/kernel/git/netdev/net-next.git main`,
+/// ends with `key`, matching whole path segments (`net-next.git` and
+/// `netdev/net-next.git` match, `next.git` and `netdev` don't).
+fn tree_entry_matches(tree_entry: &str, key: &str) -> bool {
+    tree_entry.split_whitespace().any(|token| {
+        let token = token.trim_end_matches('/');
+        token == key
+            || token
+                .strip_suffix(key)
+                .is_some_and(|prefix| prefix.ends_with('/'))
+    })
+}
+
+/// Returns if `branch` is a local branch of the repository in `repo_path`.
+pub fn has_branch(repo_path: &str, branch: &str) -> bool {
+    run_git(
+        repo_path,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{branch}"),
+        ],
+    )
+    .is_some()
+}
+
+/// Returns the branch checked out in the repository in `repo_path`, unless
+/// it's in detached HEAD or isn't a repository at all.
+pub fn current_branch(repo_path: &str) -> Option<String> {
+    run_git(repo_path, &["branch", "--show-current"])
+        .map(|output| output.trim().to_string())
+        .filter(|branch| !branch.is_empty())
+}
+
+/// Returns the local branches of the repository in `repo_path`, sorted.
+pub fn local_branches(repo_path: &str) -> Vec<String> {
+    run_git(
+        repo_path,
+        &["for-each-ref", "--format=%(refname:short)", "refs/heads/"],
+    )
+    .map(|output| output.lines().map(String::from).collect())
+    .unwrap_or_default()
+}
+
+/// Runs `git -C <repo_path> <args>` and returns its stdout if it succeeds.
+fn run_git(repo_path: &str, args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,164 @@
+use super::*;
+
+use serde_json::json;
+use std::fs;
+
+#[test]
+fn should_extract_base_commit() {
+    let raw_patches =
+        vec![fs::read_to_string("test_samples/app/base_tree/cover_letter.cover").unwrap()];
+
+    assert_eq!(
+        Some("8f3b1a2c4d5e6f708192a3b4c5d6e7f8091a2b3c".to_string()),
+        extract_base_commit(&raw_patches)
+    );
+    assert_eq!(None, extract_base_commit(&["Subject: foo\n".to_string()]));
+}
+
+#[test]
+fn should_extract_list_of_message_id() {
+    assert_eq!(
+        Some("netdev".to_string()),
+        list_of_message_id("https://lore.kernel.org/netdev/20241018.12345-1-foo@bar.foo/")
+    );
+    assert_eq!(None, list_of_message_id("https://lore.kernel.org/"));
+}
+
+/// [KernelTreeQueries] of kernel trees (by path) with the given branches, each
+/// with the commits it contains
+#[derive(Default)]
+struct FakeKernelTrees {
+    branches: Vec<(&'static str, &'static str, Vec<&'static str>)>,
+}
+
+impl KernelTreeQueries for FakeKernelTrees {
+    fn has_branch(&self, kernel_tree: &KernelTree, branch: &str) -> bool {
+        self.branches
+            .iter()
+            .any(|(path, b, _)| path == kernel_tree.path() && *b == branch)
+    }
+
+    fn has_commit(&self, kernel_tree: &KernelTree, commit: &str) -> bool {
+        !self.branches_containing(kernel_tree, commit).is_empty()
+    }
+
+    fn branches_containing(&self, kernel_tree: &KernelTree, commit: &str) -> Vec<String> {
+        self.branches
+            .iter()
+            .filter(|(path, _, commits)| path == kernel_tree.path() && commits.contains(&commit))
+            .map(|(_, branch, _)| branch.to_string())
+            .collect()
+    }
+}
+
+#[test]
+fn should_detect_base_tree() {
+    let config: Config = serde_json::from_value(json!({
+        "kernel_trees": {
+            "linux": { "path": "/home/user/linux", "branch": "master" },
+            "net": { "path": "/home/user/net", "branch": "main" },
+            "next": { "path": "/home/user/linux-next", "branch": "master" }
+        },
+        "base_trees": {
+            "netdev": { "kernel_tree": "net" },
+            "netdev/net-next.git": { "kernel_tree": "linux", "branch": "net-next" },
+            "unregistered": { "kernel_tree": "invalid-id" }
+        }
+    }))
+    .unwrap();
+    let tree_entries =
+        vec!["git git://git.kernel.org/pub/scm/linux/kernel/git/netdev/net-next.git".to_string()];
+    let has_no_commit = FakeKernelTrees {
+        branches: vec![("/home/user/linux", "net-next", vec![])],
+    };
+
+    let choice = detect_base_tree(&config, Some("netdev"), &tree_entries, None, &has_no_commit);
+    assert_eq!(
+        Some(("linux", "net-next")),
+        choice
+            .as_ref()
+            .map(|c| (c.kernel_tree_id().as_str(), c.branch().as_str())),
+        "MAINTAINERS trees should take precedence over mailing lists"
+    );
+
+    let choice = detect_base_tree(&config, Some("netdev"), &[], None, &has_no_commit).unwrap();
+    assert_eq!(
+        ("net", "main"),
+        (choice.kernel_tree_id().as_str(), choice.branch().as_str()),
+        "Branch should default to the one of the kernel tree"
+    );
+
+    let net_has_commit = FakeKernelTrees {
+        branches: vec![
+            ("/home/user/linux", "net-next", vec![]),
+            ("/home/user/net", "main", vec!["8f3b1a2c4d5e"]),
+        ],
+    };
+    let choice = detect_base_tree(
+        &config,
+        Some("netdev"),
+        &tree_entries,
+        Some("8f3b1a2c4d5e"),
+        &net_has_commit,
+    )
+    .unwrap();
+    assert_eq!("net", choice.kernel_tree_id());
+
+    let next_has_commit = FakeKernelTrees {
+        branches: vec![("/home/user/linux-next", "master", vec!["8f3b1a2c4d5e"])],
+    };
+    let choice = detect_base_tree(
+        &config,
+        Some("dri-devel"),
+        &[],
+        Some("8f3b1a2c4d5e"),
+        &next_has_commit,
+    )
+    .unwrap();
+    assert_eq!("next", choice.kernel_tree_id());
+
+    assert!(detect_base_tree(&config, Some("unregistered"), &[], None, &has_no_commit).is_none());
+}
+
+#[test]
+fn should_check_branches_of_base_tree() {
+    let config: Config = serde_json::from_value(json!({
+        "kernel_trees": {
+            "linux": { "path": "/home/user/linux", "branch": "master" },
+            "next": { "path": "/home/user/linux-next", "branch": "master" }
+        },
+        "base_trees": {
+            "netdev": { "kernel_tree": "linux", "branch": "net-next" }
+        }
+    }))
+    .unwrap();
+    let kernel_trees = FakeKernelTrees {
+        branches: vec![
+            ("/home/user/linux", "master", vec![]),
+            ("/home/user/linux-next", "master", vec![]),
+            ("/home/user/linux-next", "fixes", vec!["8f3b1a2c4d5e"]),
+        ],
+    };
+
+    let choice = detect_base_tree(&config, Some("netdev"), &[], None, &kernel_trees).unwrap();
+    assert_eq!(
+        ("linux", "master"),
+        (choice.kernel_tree_id().as_str(), choice.branch().as_str()),
+        "Mapped branches that don't exist should be replaced"
+    );
+    assert!(choice.reason().contains("'net-next' not found"));
+
+    let choice = detect_base_tree(
+        &config,
+        Some("netdev"),
+        &[],
+        Some("8f3b1a2c4d5e"),
+        &kernel_trees,
+    )
+    .unwrap();
+    assert_eq!(
+        ("next", "fixes"),
+        (choice.kernel_tree_id().as_str(), choice.branch().as_str()),
+        "The branch containing the base-commit should be chosen"
+    );
+}
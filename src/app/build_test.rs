@@ -0,0 +1,133 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::{self, BufRead, BufReader},
+    process::{Command, Stdio},
+    sync::Mutex,
+};
+
+use patch_hub::lore::{lore_session, maintainers::touched_files};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Placeholder of [crate::app::config::Config::build_command] replaced by the
+/// directories touched by the patchset
+pub const TOUCHED_DIRS_PLACEHOLDER: &str = "{touched_dirs}";
+
+/// Top-level directories of a kernel tree that aren't Kbuild targets, so
+/// `make <dir>/` fails for them. `include/` directories aren't targets at any
+/// depth (like `arch/x86/include/`).
+const NON_BUILD_DIRS: [&str; 4] = ["Documentation", "LICENSES", "include", "tools"];
+
+/// Outcome of building a kernel tree with a patchset applied
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum BuildStatus {
+    #[serde(rename = "passed")]
+    Passed,
+    #[serde(rename = "failed")]
+    Failed,
+}
+
+impl Display for BuildStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildStatus::Passed => write!(f, "passed"),
+            BuildStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// Returns the directories (with a trailing slash, like `drivers/foo/`) that
+/// contain files changed by any of `raw_patches`, in order of appearance.
+/// Directories that can't be built on their own (see [NON_BUILD_DIRS]) are
+/// left out, so a patchset touching only them expands `{touched_dirs}` to
+/// nothing and a plain `make` is run.
+///
+/// # Tests
+///
+/// [tests::should_expand_build_command]
+pub fn touched_dirs(raw_patches: &[String]) -> Vec<String> {
+    let mut dirs: Vec<String> = Vec::new();
+
+    for file in raw_patches
+        .iter()
+        .flat_map(|raw_patch| touched_files(raw_patch))
+    {
+        let Some((dir, _)) = file.rsplit_once('/') else {
+            continue;
+        };
+        let mut components = dir.split('/');
+        if components
+            .next()
+            .is_some_and(|top_level| NON_BUILD_DIRS.contains(&top_level))
+            || components.any(|component| component == "include")
+        {
+            continue;
+        }
+        let dir = format!("{dir}/");
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+
+    dirs
+}
+
+/// Replaces [TOUCHED_DIRS_PLACEHOLDER] in `build_command` by `touched_dirs`.
+///
+/// # Tests
+///
+/// [tests::should_expand_build_command]
+pub fn expand_build_command(build_command: &str, touched_dirs: &[String]) -> String {
+    build_command
+        .replace(TOUCHED_DIRS_PLACEHOLDER, &touched_dirs.join(" "))
+        .trim()
+        .to_string()
+}
+
+/// Runs `build_command` with `sh` in `kernel_tree_path`, appending each line
+/// of its output (both stdout and stderr) to `log` as soon as it is printed.
+///
+/// Returns if the build succeeded.
+pub fn run_build_command(
+    kernel_tree_path: &str,
+    build_command: &str,
+    log: &Mutex<Vec<String>>,
+) -> io::Result<BuildStatus> {
+    let mut build = Command::new("sh")
+        .arg("-c")
+        .arg(format!("({build_command}) 2>&1"))
+        .current_dir(kernel_tree_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(stdout) = build.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            let line = line.unwrap_or_default();
+            if let Ok(mut log) = log.lock() {
+                log.push(line);
+            }
+        }
+    }
+
+    if build.wait()?.success() {
+        Ok(BuildStatus::Passed)
+    } else {
+        Ok(BuildStatus::Failed)
+    }
+}
+
+pub fn save_build_statuses(
+    build_statuses: &HashMap<String, BuildStatus>,
+    filepath: &str,
+) -> io::Result<()> {
+    lore_session::save_json(build_statuses, filepath)
+}
+
+pub fn load_build_statuses(filepath: &str) -> io::Result<HashMap<String, BuildStatus>> {
+    lore_session::load_json(filepath)
+}
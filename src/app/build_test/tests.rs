@@ -0,0 +1,49 @@
+use super::*;
+
+use std::fs;
+
+#[test]
+fn should_expand_build_command() {
+    let raw_patches = vec![fs::read_to_string("test_samples/app/build_test/patch.mbx").unwrap()];
+
+    let touched_dirs = touched_dirs(&raw_patches);
+
+    assert_eq!(vec!["drivers/foo/", "lib/"], touched_dirs);
+    assert_eq!(
+        "make -j$(nproc) W=1 drivers/foo/ lib/",
+        expand_build_command("make -j$(nproc) W=1 {touched_dirs}", &touched_dirs)
+    );
+    assert_eq!(
+        "kw build",
+        expand_build_command("kw build", &touched_dirs),
+        "Commands without placeholder should be kept"
+    );
+
+    let raw_patches = vec![[
+        "diff --git a/include/linux/foo.h b/include/linux/foo.h",
+        "diff --git a/arch/x86/include/asm/foo.h b/arch/x86/include/asm/foo.h",
+        "diff --git a/Documentation/foo.rst b/Documentation/foo.rst",
+        "diff --git a/tools/foo/foo.c b/tools/foo/foo.c",
+    ]
+    .join("\n")];
+    assert_eq!(
+        "make -j$(nproc) W=1",
+        expand_build_command(
+            "make -j$(nproc) W=1 {touched_dirs}",
+            &super::touched_dirs(&raw_patches)
+        ),
+        "Directories that aren't build targets should be left out"
+    );
+}
+
+#[test]
+fn should_stream_build_output() {
+    let log = Mutex::new(Vec::new());
+
+    let status = run_build_command(".", "echo foo; echo bar >&2", &log).unwrap();
+    assert_eq!(BuildStatus::Passed, status);
+    assert_eq!(vec!["foo", "bar"], *log.lock().unwrap());
+
+    let status = run_build_command(".", "exit 2", &log).unwrap();
+    assert_eq!(BuildStatus::Failed, status);
+}
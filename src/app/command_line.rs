@@ -0,0 +1,407 @@
+use std::{io, str::FromStr};
+
+use patch_hub::lore::{lore_session, mailing_list::MailingList};
+
+use super::{
+    export::ExportFormat,
+    git_am::GitAmResolution,
+    patchset_filter::PatchsetSort,
+    screens::details_actions::ReplyTrailer,
+    screens::CurrentScreen,
+    tabs::{TabCommand, TAB_SCREENS},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of commands kept in the command history
+const MAX_HISTORY_LEN: usize = 100;
+
+/// Screens listing patchsets or mailing lists
+const LIST_SCREENS: &[CurrentScreen] = &[
+    CurrentScreen::MailingListSelection,
+    CurrentScreen::BookmarkedPatchsets,
+    CurrentScreen::RecentPatchsets,
+    CurrentScreen::LatestPatchsets,
+];
+
+const DETAILS_SCREENS: &[CurrentScreen] = &[CurrentScreen::PatchsetDetails];
+
+/// A command of the command line
+#[derive(Debug)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub help: &'static str,
+    /// Screens in which the command is available. Empty if available in every
+    /// screen
+    pub screens: &'static [CurrentScreen],
+}
+
+impl CommandSpec {
+    pub fn is_available_in(&self, screen: &CurrentScreen) -> bool {
+        self.screens.is_empty() || self.screens.contains(screen)
+    }
+}
+
+/// Registry of the commands of the command line
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "help",
+        usage: "help",
+        help: "List the available commands",
+        screens: &[],
+    },
+    CommandSpec {
+        name: "quit",
+        usage: "quit | q",
+        help: "Quit patch-hub",
+        screens: &[],
+    },
+    CommandSpec {
+        name: "tab",
+        usage: "tab new | close | next | previous | <number>",
+        help: "Open, close or switch tabs",
+        screens: TAB_SCREENS,
+    },
+    CommandSpec {
+        name: "list",
+        usage: "list <mailing list>",
+        help: "Show the latest patchsets of a mailing list",
+        screens: LIST_SCREENS,
+    },
+    CommandSpec {
+        name: "search",
+        usage: "search <query>",
+        help: "Search patchsets with a Lore query (like f:foo) in the current list or in all lists",
+        screens: LIST_SCREENS,
+    },
+    CommandSpec {
+        name: "page",
+        usage: "page <number>",
+        help: "Go to a page of the latest patchsets",
+        screens: &[CurrentScreen::LatestPatchsets],
+    },
+    CommandSpec {
+        name: "filter",
+        usage: "filter [<terms>]",
+        help: "Filter the latest patchsets (like a:foo v:2- type:rfc), or clear the filter",
+        screens: &[CurrentScreen::LatestPatchsets],
+    },
+    CommandSpec {
+        name: "sort",
+        usage: "sort newest | oldest | largest | most-reviewed",
+        help: "Sort the latest patchsets",
+        screens: &[CurrentScreen::LatestPatchsets],
+    },
+    CommandSpec {
+        name: "apply",
+        usage: "apply",
+        help: "Toggle apply action",
+        screens: DETAILS_SCREENS,
+    },
+    CommandSpec {
+        name: "bookmark",
+        usage: "bookmark",
+        help: "Toggle bookmark action",
+        screens: DETAILS_SCREENS,
+    },
+    CommandSpec {
+        name: "checkpatch",
+        usage: "checkpatch",
+        help: "Toggle checkpatch action",
+        screens: DETAILS_SCREENS,
+    },
+    CommandSpec {
+        name: "build-test",
+        usage: "build-test",
+        help: "Toggle build-test action",
+        screens: DETAILS_SCREENS,
+    },
+    CommandSpec {
+        name: "export",
+        usage: "export [patches | mbox | quilt]",
+        help: "Toggle export action, or mark it with the given format",
+        screens: DETAILS_SCREENS,
+    },
+    CommandSpec {
+        name: "reply",
+        usage: "reply [reviewed | acked | tested] [all]",
+        help: "Toggle reply action for the previewed patch (or all patches) with the given trailer",
+        screens: DETAILS_SCREENS,
+    },
+    CommandSpec {
+        name: "run",
+        usage: "run",
+        help: "Consolidate marked actions",
+        screens: DETAILS_SCREENS,
+    },
+    CommandSpec {
+        name: "jump",
+        usage: "jump <patch> <line>",
+        help: "Scroll the preview to a line of the raw contents of a patch (0 is the cover letter)",
+        screens: DETAILS_SCREENS,
+    },
+    CommandSpec {
+        name: "base",
+        usage: "base <kernel tree> [<branch>]",
+        help: "Choose the kernel tree and branch to apply the patchset on",
+        screens: DETAILS_SCREENS,
+    },
+    CommandSpec {
+        name: "am",
+        usage: "am resolve | continue | skip | abort",
+        help: "Proceed with an apply stopped by conflicts",
+        screens: &[],
+    },
+];
+
+/// Values of the arguments of commands that can be completed, besides
+/// mailing list names
+const EXPORT_FORMATS: &[&str] = &["patches", "mbox", "quilt"];
+const REPLY_TRAILERS: &[&str] = &["reviewed", "acked", "tested"];
+const SORTS: &[&str] = &["newest", "oldest", "largest", "most-reviewed"];
+const TAB_COMMANDS: &[&str] = &["new", "close", "next", "previous"];
+const AM_RESOLUTIONS: &[&str] = &["resolve", "continue", "skip", "abort"];
+
+/// A parsed command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Help,
+    Quit,
+    Tab(TabCommand),
+    List(String),
+    Search(String),
+    Page(usize),
+    Filter(String),
+    Sort(PatchsetSort),
+    Apply,
+    Bookmark,
+    Checkpatch,
+    BuildTest,
+    Export(Option<ExportFormat>),
+    Reply {
+        trailer: ReplyTrailer,
+        all: bool,
+    },
+    Run,
+    Jump {
+        patch: usize,
+        line: usize,
+    },
+    Base {
+        kernel_tree: String,
+        branch: Option<String>,
+    },
+    Am(GitAmResolution),
+}
+
+impl Command {
+    /// Returns the name of the command in [COMMANDS]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Help => "help",
+            Command::Quit => "quit",
+            Command::Tab(_) => "tab",
+            Command::List(_) => "list",
+            Command::Search(_) => "search",
+            Command::Page(_) => "page",
+            Command::Filter(_) => "filter",
+            Command::Sort(_) => "sort",
+            Command::Apply => "apply",
+            Command::Bookmark => "bookmark",
+            Command::Checkpatch => "checkpatch",
+            Command::BuildTest => "build-test",
+            Command::Export(_) => "export",
+            Command::Reply { .. } => "reply",
+            Command::Run => "run",
+            Command::Jump { .. } => "jump",
+            Command::Base { .. } => "base",
+            Command::Am(_) => "am",
+        }
+    }
+
+    /// Returns the spec of the command in [COMMANDS]
+    pub fn spec(&self) -> &'static CommandSpec {
+        COMMANDS
+            .iter()
+            .find(|spec| spec.name == self.name())
+            .expect("Every command must be in the registry")
+    }
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    /// Parses a command line like `list netdev` or `reply acked all`.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_parse_commands]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (name, args) = s.split_once(' ').unwrap_or((s, ""));
+        let args = args.trim();
+        let usage_error = || {
+            let usage = COMMANDS
+                .iter()
+                .find(|spec| spec.name == name)
+                .map_or("", |spec| spec.usage);
+            format!("Usage: {usage}")
+        };
+
+        let command = match name {
+            "help" => Command::Help,
+            "quit" | "q" => Command::Quit,
+            "tab" => Command::Tab(args.parse().map_err(|_| usage_error())?),
+            "list" if !args.is_empty() && !args.contains(' ') => Command::List(args.to_string()),
+            "search" if !args.is_empty() => Command::Search(args.to_string()),
+            "page" => Command::Page(args.parse().map_err(|_| usage_error())?),
+            "filter" => Command::Filter(args.to_string()),
+            "sort" => Command::Sort(args.parse().map_err(|_| usage_error())?),
+            "apply" => Command::Apply,
+            "bookmark" => Command::Bookmark,
+            "checkpatch" => Command::Checkpatch,
+            "build-test" => Command::BuildTest,
+            "export" if args.is_empty() => Command::Export(None),
+            "export" => Command::Export(Some(args.parse()?)),
+            "reply" => {
+                let mut trailer = ReplyTrailer::default();
+                let mut all = false;
+                for arg in args.split_whitespace() {
+                    match arg {
+                        "all" if !all => all = true,
+                        arg => trailer = arg.parse().map_err(|_| usage_error())?,
+                    }
+                }
+                Command::Reply { trailer, all }
+            }
+            "run" => Command::Run,
+            "jump" => {
+                let (patch, line) = args.split_once(' ').ok_or_else(usage_error)?;
+                Command::Jump {
+                    patch: patch.parse().map_err(|_| usage_error())?,
+                    line: line.trim().parse().map_err(|_| usage_error())?,
+                }
+            }
+            "base" => {
+                let mut args = args.split_whitespace().map(String::from);
+                let (Some(kernel_tree), branch, None) = (args.next(), args.next(), args.next())
+                else {
+                    return Err(usage_error());
+                };
+                Command::Base {
+                    kernel_tree,
+                    branch,
+                }
+            }
+            "am" => Command::Am(args.parse().map_err(|_| usage_error())?),
+            "" => return Err("Empty command".to_string()),
+            _ if COMMANDS.iter().any(|spec| spec.name == name) => return Err(usage_error()),
+            _ => return Err(format!("Unknown command '{name}'")),
+        };
+
+        if !args.is_empty()
+            && matches!(
+                command,
+                Command::Help
+                    | Command::Quit
+                    | Command::Apply
+                    | Command::Bookmark
+                    | Command::Checkpatch
+                    | Command::BuildTest
+                    | Command::Run
+            )
+        {
+            return Err(usage_error());
+        }
+
+        Ok(command)
+    }
+}
+
+/// Parses the command line `input` (see [Command::from_str]), failing if the
+/// command isn't available in `screen`.
+///
+/// # Tests
+///
+/// [tests::should_parse_commands_available_in_screen]
+pub fn parse_command(input: &str, screen: &CurrentScreen) -> Result<Command, String> {
+    let command: Command = input.parse()?;
+    if !command.spec().is_available_in(screen) {
+        return Err(format!(
+            "'{}' isn't available in this screen",
+            command.name()
+        ));
+    }
+    Ok(command)
+}
+
+/// Returns the completions of the command line `input` in `screen`: the names
+/// of the commands available in `screen` or, for the arguments, the names of
+/// `mailing_lists` or the values the command accepts. Each completion is the
+/// whole completed command line.
+///
+/// # Tests
+///
+/// [tests::should_complete_command_names]
+/// [tests::should_complete_command_arguments]
+pub fn complete(input: &str, screen: &CurrentScreen, mailing_lists: &[MailingList]) -> Vec<String> {
+    let Some((name, args)) = input.split_once(' ') else {
+        return COMMANDS
+            .iter()
+            .filter(|spec| spec.is_available_in(screen) && spec.name.starts_with(input))
+            .map(|spec| spec.name.to_string())
+            .collect();
+    };
+
+    let (completed_args, arg) = match args.rsplit_once(' ') {
+        Some((completed_args, arg)) => (format!("{completed_args} "), arg),
+        None => (String::new(), args),
+    };
+    let values: Vec<&str> = match (name, completed_args.is_empty()) {
+        ("list", true) => mailing_lists
+            .iter()
+            .map(|list| list.name().as_str())
+            .collect(),
+        ("export", true) => EXPORT_FORMATS.to_vec(),
+        ("sort", true) => SORTS.to_vec(),
+        ("tab", true) => TAB_COMMANDS.to_vec(),
+        ("am", true) => AM_RESOLUTIONS.to_vec(),
+        ("reply", true) => [REPLY_TRAILERS, &["all"]].concat(),
+        ("reply", false) if !completed_args.contains("all") => vec!["all"],
+        _ => Vec::new(),
+    };
+
+    values
+        .into_iter()
+        .filter(|value| value.starts_with(arg))
+        .map(|value| format!("{name} {completed_args}{value}"))
+        .collect()
+}
+
+/// Adds `command` to the end of `history`, removing its previous occurrence
+/// and the oldest commands beyond the maximum history length.
+///
+/// # Tests
+///
+/// [tests::should_push_commands_to_history]
+pub fn push_to_history(history: &mut Vec<String>, command: &str) {
+    let command = command.trim();
+    if command.is_empty() {
+        return;
+    }
+    history.retain(|c| c != command);
+    history.push(command.to_string());
+    if history.len() > MAX_HISTORY_LEN {
+        history.drain(..history.len() - MAX_HISTORY_LEN);
+    }
+}
+
+pub fn save_command_history(history: &[String], filepath: &str) -> io::Result<()> {
+    lore_session::save_json(history, filepath)
+}
+
+pub fn load_command_history(filepath: &str) -> io::Result<Vec<String>> {
+    lore_session::load_json(filepath)
+}
@@ -0,0 +1,246 @@
+use std::fs;
+
+use super::*;
+
+#[test]
+fn should_parse_commands() {
+    assert_eq!(Ok(Command::Quit), "q".parse());
+    assert_eq!(Ok(Command::Quit), " quit ".parse());
+    assert_eq!(
+        Ok(Command::List("netdev".to_string())),
+        "list netdev".parse()
+    );
+    assert_eq!(
+        Ok(Command::Search("f:foo AND s:bar".to_string())),
+        "search f:foo AND s:bar".parse()
+    );
+    assert_eq!(Ok(Command::Page(5)), "page 5".parse());
+    assert_eq!(Ok(Command::Tab(TabCommand::New)), "tab new".parse());
+    assert_eq!(Ok(Command::Tab(TabCommand::Go(3))), "tab 3".parse());
+    assert_eq!(
+        Ok(Command::Filter("a:foo type:rfc".to_string())),
+        "filter a:foo type:rfc".parse()
+    );
+    assert_eq!(Ok(Command::Filter(String::new())), "filter".parse());
+    assert_eq!(
+        Ok(Command::Sort(PatchsetSort::MostReviewed)),
+        "sort most-reviewed".parse()
+    );
+    assert_eq!(Ok(Command::Export(None)), "export".parse());
+    assert_eq!(
+        Ok(Command::Export(Some(ExportFormat::Mbox))),
+        "export mbox".parse()
+    );
+    assert_eq!(
+        Ok(Command::Reply {
+            trailer: ReplyTrailer::Reviewed,
+            all: false
+        }),
+        "reply".parse()
+    );
+    assert_eq!(
+        Ok(Command::Reply {
+            trailer: ReplyTrailer::Acked,
+            all: true
+        }),
+        "reply acked all".parse()
+    );
+    assert_eq!(
+        Ok(Command::Reply {
+            trailer: ReplyTrailer::Tested,
+            all: true
+        }),
+        "reply all Tested-by".parse()
+    );
+    assert_eq!(
+        Ok(Command::Jump { patch: 2, line: 15 }),
+        "jump 2  15".parse()
+    );
+    assert_eq!(
+        Ok(Command::Base {
+            kernel_tree: "net".to_string(),
+            branch: Some("net-next".to_string())
+        }),
+        "base net net-next".parse()
+    );
+    assert_eq!(
+        Ok(Command::Base {
+            kernel_tree: "net".to_string(),
+            branch: None
+        }),
+        "base net".parse()
+    );
+    assert_eq!(
+        Ok(Command::Am(GitAmResolution::Continue)),
+        "am continue".parse()
+    );
+
+    assert_eq!(
+        Err("Usage: list <mailing list>".to_string()),
+        "list".parse::<Command>()
+    );
+    assert_eq!(
+        Err("Usage: page <number>".to_string()),
+        "page five".parse::<Command>()
+    );
+    assert_eq!(
+        Err("Usage: tab new | close | next | previous | <number>".to_string()),
+        "tab".parse::<Command>()
+    );
+    assert_eq!(
+        Err("Usage: reply [reviewed | acked | tested] [all]".to_string()),
+        "reply nacked".parse::<Command>()
+    );
+    assert_eq!(
+        Err("Usage: apply".to_string()),
+        "apply now".parse::<Command>()
+    );
+    assert_eq!(
+        Err("Unknown command 'foo'".to_string()),
+        "foo".parse::<Command>()
+    );
+    assert_eq!(
+        Err("Usage: jump <patch> <line>".to_string()),
+        "jump 2".parse::<Command>()
+    );
+    assert!("base".parse::<Command>().is_err());
+    assert!("base net net-next main".parse::<Command>().is_err());
+    assert!("am".parse::<Command>().is_err());
+    assert!("".parse::<Command>().is_err());
+    assert!("export tarball".parse::<Command>().is_err());
+}
+
+#[test]
+fn should_parse_commands_available_in_screen() {
+    let details = CurrentScreen::PatchsetDetails;
+    assert_eq!(Ok(Command::Apply), parse_command("apply", &details));
+    assert_eq!(Ok(Command::Help), parse_command("help", &details));
+    assert_eq!(
+        Err("'list' isn't available in this screen".to_string()),
+        parse_command("list netdev", &details)
+    );
+    assert!(parse_command("apply", &CurrentScreen::LatestPatchsets).is_err());
+}
+
+#[test]
+fn every_command_should_be_in_the_registry() {
+    let commands = [
+        Command::Help,
+        Command::Quit,
+        Command::Tab(TabCommand::Next),
+        Command::List(String::new()),
+        Command::Search(String::new()),
+        Command::Page(1),
+        Command::Filter(String::new()),
+        Command::Sort(PatchsetSort::Newest),
+        Command::Apply,
+        Command::Bookmark,
+        Command::Checkpatch,
+        Command::BuildTest,
+        Command::Export(None),
+        Command::Reply {
+            trailer: ReplyTrailer::Reviewed,
+            all: false,
+        },
+        Command::Run,
+        Command::Jump { patch: 0, line: 1 },
+        Command::Base {
+            kernel_tree: String::new(),
+            branch: None,
+        },
+        Command::Am(GitAmResolution::Abort),
+    ];
+    for command in commands {
+        assert_eq!(command.name(), command.spec().name);
+    }
+}
+
+#[test]
+fn should_complete_command_names() {
+    let details = CurrentScreen::PatchsetDetails;
+    assert_eq!(
+        vec!["bookmark", "build-test", "base"],
+        complete("b", &details, &[])
+    );
+    assert_eq!(vec!["reply", "run"], complete("r", &details, &[]));
+    assert!(
+        complete("l", &details, &[]).is_empty(),
+        "Commands not available in the screen shouldn't be completed"
+    );
+    assert_eq!(
+        vec!["list"],
+        complete("l", &CurrentScreen::MailingListSelection, &[])
+    );
+    assert_eq!(COMMANDS.len(), complete("", &details, &[]).len() + 5);
+}
+
+#[test]
+fn should_complete_command_arguments() {
+    let mailing_lists = [
+        MailingList::new("netdev", ""),
+        MailingList::new("netfilter-devel", ""),
+        MailingList::new("amd-gfx", ""),
+    ];
+    let latest = CurrentScreen::LatestPatchsets;
+    let details = CurrentScreen::PatchsetDetails;
+
+    assert_eq!(
+        vec!["list netdev", "list netfilter-devel"],
+        complete("list net", &latest, &mailing_lists)
+    );
+    assert_eq!(
+        vec!["list amd-gfx"],
+        complete("list amd", &latest, &mailing_lists)
+    );
+    assert!(complete("list netdev foo", &latest, &mailing_lists).is_empty());
+    assert_eq!(vec!["export mbox"], complete("export m", &details, &[]));
+    assert_eq!(vec!["sort most-reviewed"], complete("sort m", &latest, &[]));
+    assert_eq!(
+        vec!["tab new", "tab next"],
+        complete("tab ne", &details, &[])
+    );
+    assert_eq!(
+        vec!["reply acked", "reply all"],
+        complete("reply a", &details, &[])
+    );
+    assert_eq!(
+        vec!["reply acked all"],
+        complete("reply acked ", &details, &[])
+    );
+    assert!(complete("reply all ", &details, &[]).is_empty());
+    assert!(complete("page ", &latest, &[]).is_empty());
+}
+
+#[test]
+fn should_push_commands_to_history() {
+    let mut history = Vec::new();
+    push_to_history(&mut history, "list netdev");
+    push_to_history(&mut history, "page 2");
+    push_to_history(&mut history, "  ");
+    push_to_history(&mut history, " list netdev ");
+    assert_eq!(vec!["page 2", "list netdev"], history);
+
+    for i in 0..MAX_HISTORY_LEN {
+        push_to_history(&mut history, &format!("page {i}"));
+    }
+    assert_eq!(MAX_HISTORY_LEN, history.len());
+    assert_eq!("page 0", history[0]);
+    assert_eq!(
+        format!("page {}", MAX_HISTORY_LEN - 1),
+        history[MAX_HISTORY_LEN - 1]
+    );
+}
+
+#[test]
+fn should_save_and_load_command_history() {
+    let dir =
+        std::env::temp_dir().join(format!("patch-hub-command-history-{}", std::process::id()));
+    let path = dir.join("command_history.json");
+    let path = path.to_str().unwrap();
+    let history = vec!["list netdev".to_string(), "page 2".to_string()];
+
+    save_command_history(&history, path).unwrap();
+    assert_eq!(history, load_command_history(path).unwrap());
+
+    fs::remove_dir_all(dir).unwrap();
+}
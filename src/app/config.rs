@@ -11,7 +11,7 @@ use std::{
 
 pub const DEFAULT_CONFIG_PATH_SUFFIX: &str = ".config/patch-hub/config.json";
 
-use super::{cover_renderer::CoverRenderer, patch_renderer::PatchRenderer};
+use super::{cover_renderer::CoverRenderer, patch_renderer::PatchRenderer, theme::ThemePreset};
 
 #[cfg(test)]
 mod tests;
@@ -25,8 +25,29 @@ pub struct Config {
     bookmarked_patchsets_path: String,
     mailing_lists_path: String,
     reviewed_patchsets_path: String,
+    /// Path to the database of patchsets attestation (DKIM and patatt) status
+    attestation_statuses_path: String,
+    /// Path to the database of patchsets build-test status
+    build_statuses_path: String,
+    /// Path to the database of applied patchsets
+    apply_history_path: String,
+    /// Path to the `git am` stopped by conflicts, if any
+    git_am_conflict_path: String,
+    /// Path to the history of commands run in the command line
+    command_history_path: String,
+    /// Path to the jump list, the recently viewed patchsets
+    recent_patchsets_path: String,
+    /// Path to the names of the mailing lists pinned to the top
+    favorite_lists_path: String,
+    /// Path to the activity of the mailing lists whose patchsets were fetched
+    list_activity_path: String,
     /// Logs directory
     logs_path: String,
+    /// Directory in which a git worktree is created for each applied
+    /// patchset, if `apply_to_worktree` is set
+    worktrees_dir: String,
+    /// Directory to which patchsets are exported
+    exports_dir: String,
     git_send_email_options: String,
     /// Base directory for all patch-hub cache
     cache_dir: String,
@@ -46,6 +67,50 @@ pub struct Config {
     /// Flags to be use with git am command when applying patches
     git_am_options: String,
     git_am_branch_prefix: String,
+    /// Map from mailing list names (like `netdev`) or trailing path segments
+    /// of `MAINTAINERS` `T:` entries (like `netdev/net-next.git`) to the kernel
+    /// tree and branch on which patchsets are applied by default
+    base_trees: HashMap<String, BaseTree>,
+    /// If true, patchsets are applied in a dedicated git worktree (under
+    /// `worktrees_dir`) instead of switching branches of the kernel tree
+    apply_to_worktree: bool,
+    /// If true, the upstream of the base branch is fetched before applying a
+    /// patchset and how far behind the local branch was is reported
+    fetch_before_apply: bool,
+    /// If true (and `fetch_before_apply` is set), patchsets are applied on top
+    /// of the freshly fetched upstream ref instead of the local base branch
+    apply_on_upstream: bool,
+    /// Names of trailers to collect besides the default ones (e.g.
+    /// `Signed-off-by`)
+    extra_trailers: Vec<String>,
+    /// Flags to be used with `checkpatch.pl` (e.g. `--strict` to also report
+    /// CHECKs)
+    checkpatch_options: String,
+    /// Shell command to build-test applied patchsets in the target kernel
+    /// tree. `{touched_dirs}` is replaced by the buildable directories touched
+    /// by the patchset (none, building the whole tree, if only headers or
+    /// docs are touched)
+    build_command: String,
+    /// Shell command run in the target kernel tree after applying each patch
+    /// when applying patchsets one patch at a time. `{touched_dirs}` is
+    /// replaced by the buildable directories touched by the patch. If empty, only
+    /// whether each patch applies is checked
+    bisect_check_command: String,
+    /// Map from screen names (like `details`) to action names (like
+    /// `go_to_first_line`) to the keys bound to the action, replacing the
+    /// default ones. Keys pressed in sequence are separated by spaces (like
+    /// `g g`)
+    keybindings: HashMap<String, HashMap<String, Vec<String>>>,
+    /// If true, the mouse can be used to select and open items of lists,
+    /// scroll the patch preview and toggle patchset actions
+    mouse_support: bool,
+    /// Color scheme of the UI (`dark`, `light`, `high-contrast` or
+    /// `no-color`). The `NO_COLOR` environment variable forces `no-color`
+    theme: ThemePreset,
+    /// Map from roles of the theme (like `title` or `selected`) to the styles
+    /// replacing the ones of the preset (like `bold light-green` or
+    /// `black on yellow`)
+    theme_colors: HashMap<String, String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Getters, Eq, PartialEq, Clone)]
@@ -56,6 +121,21 @@ pub struct KernelTree {
     branch: String,
 }
 
+impl KernelTree {
+    pub fn new(path: String, branch: String) -> Self {
+        KernelTree { path, branch }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Getters, Eq, PartialEq, Clone)]
+pub struct BaseTree {
+    /// Name of a kernel tree in `Config::kernel_trees`
+    kernel_tree: String,
+    /// Branch to use instead of the one of the kernel tree
+    #[serde(default)]
+    branch: Option<String>,
+}
+
 impl Default for Config {
     fn default() -> Self {
         let home = env::var("HOME").unwrap_or_else(|_| {
@@ -71,7 +151,17 @@ impl Default for Config {
             bookmarked_patchsets_path: format!("{data_dir}/bookmarked_patchsets.json"),
             mailing_lists_path: format!("{data_dir}/mailing_lists.json"),
             reviewed_patchsets_path: format!("{data_dir}/reviewed_patchsets.json"),
+            attestation_statuses_path: format!("{data_dir}/attestation_statuses.json"),
+            build_statuses_path: format!("{data_dir}/build_statuses.json"),
+            apply_history_path: format!("{data_dir}/apply_history.json"),
+            git_am_conflict_path: format!("{data_dir}/git_am_conflict.json"),
+            command_history_path: format!("{data_dir}/command_history.json"),
+            recent_patchsets_path: format!("{data_dir}/recent_patchsets.json"),
+            favorite_lists_path: format!("{data_dir}/favorite_lists.json"),
+            list_activity_path: format!("{data_dir}/list_activity.json"),
             logs_path: format!("{data_dir}/logs"),
+            worktrees_dir: format!("{data_dir}/worktrees"),
+            exports_dir: format!("{data_dir}/exports"),
             git_send_email_options: "--dry-run --suppress-cc=all".to_string(),
             patch_renderer: Default::default(),
             cover_renderer: Default::default(),
@@ -82,6 +172,18 @@ impl Default for Config {
             target_kernel_tree: None,
             git_am_options: String::new(),
             git_am_branch_prefix: String::from("patchset-"),
+            base_trees: HashMap::new(),
+            apply_to_worktree: false,
+            fetch_before_apply: false,
+            apply_on_upstream: false,
+            extra_trailers: Vec::new(),
+            checkpatch_options: String::new(),
+            build_command: String::from("make -j$(nproc) W=1 {touched_dirs}"),
+            bisect_check_command: String::from("make -j$(nproc) W=1 {touched_dirs}"),
+            keybindings: HashMap::new(),
+            mouse_support: false,
+            theme: ThemePreset::default(),
+            theme_colors: HashMap::new(),
         }
     }
 }
@@ -163,7 +265,17 @@ impl Config {
         self.bookmarked_patchsets_path = format!("{data_dir}/bookmarked_patchsets.json");
         self.mailing_lists_path = format!("{data_dir}/mailing_lists.json");
         self.reviewed_patchsets_path = format!("{data_dir}/reviewed_patchsets.json");
+        self.attestation_statuses_path = format!("{data_dir}/attestation_statuses.json");
+        self.build_statuses_path = format!("{data_dir}/build_statuses.json");
+        self.apply_history_path = format!("{data_dir}/apply_history.json");
+        self.git_am_conflict_path = format!("{data_dir}/git_am_conflict.json");
+        self.command_history_path = format!("{data_dir}/command_history.json");
+        self.recent_patchsets_path = format!("{data_dir}/recent_patchsets.json");
+        self.favorite_lists_path = format!("{data_dir}/favorite_lists.json");
+        self.list_activity_path = format!("{data_dir}/list_activity.json");
         self.logs_path = format!("{data_dir}/logs");
+        self.worktrees_dir = format!("{data_dir}/worktrees");
+        self.exports_dir = format!("{data_dir}/exports");
         self.data_dir = data_dir;
     }
 
@@ -175,7 +287,6 @@ impl Config {
         self.git_am_options = git_am_options;
     }
 
-    #[allow(dead_code)]
     /// Returns the list of names of the registered kernel trees
     pub fn kernel_trees(&self) -> HashSet<&String> {
         self.kernel_trees.keys().collect::<HashSet<&String>>()
@@ -183,7 +294,6 @@ impl Config {
 
     /// Returns a reference to the `KernelTree` mapped by `@kernel_tree_id`, if
     /// it exists.
-    #[allow(dead_code)]
     pub fn get_kernel_tree(&self, kernel_tree_id: &str) -> Option<&KernelTree> {
         if let Some(kernel_tree) = self.kernel_trees.get(kernel_tree_id) {
             Some(kernel_tree)
@@ -192,6 +302,89 @@ impl Config {
         }
     }
 
+    /// Registers `kernel_tree` mapped by `kernel_tree_id`.
+    ///
+    /// Returns a `String` with the error message if the id is empty or already
+    /// in use.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_manage_kernel_trees]
+    pub fn add_kernel_tree(
+        &mut self,
+        kernel_tree_id: &str,
+        kernel_tree: KernelTree,
+    ) -> Result<(), String> {
+        let kernel_tree_id = kernel_tree_id.trim();
+        if kernel_tree_id.is_empty() {
+            return Err("kernel tree name can't be empty".to_string());
+        } else if self.kernel_trees.contains_key(kernel_tree_id) {
+            return Err(format!("kernel tree '{kernel_tree_id}' already exists"));
+        }
+
+        self.kernel_trees
+            .insert(kernel_tree_id.to_string(), kernel_tree);
+        Ok(())
+    }
+
+    /// Unregisters the kernel tree mapped by `kernel_tree_id`, unsetting the
+    /// target kernel tree if it was the one removed.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_manage_kernel_trees]
+    pub fn remove_kernel_tree(&mut self, kernel_tree_id: &str) -> Option<KernelTree> {
+        if self.target_kernel_tree.as_deref() == Some(kernel_tree_id) {
+            self.target_kernel_tree = None;
+        }
+        self.kernel_trees.remove(kernel_tree_id)
+    }
+
+    /// Maps the kernel tree mapped by `kernel_tree_id` by `new_kernel_tree_id`
+    /// instead, keeping it as the target kernel tree if it was.
+    ///
+    /// Returns a `String` with the error message if there is no kernel tree
+    /// mapped by `kernel_tree_id` or `new_kernel_tree_id` can't be used.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_manage_kernel_trees]
+    pub fn rename_kernel_tree(
+        &mut self,
+        kernel_tree_id: &str,
+        new_kernel_tree_id: &str,
+    ) -> Result<(), String> {
+        let Some(kernel_tree) = self.kernel_trees.get(kernel_tree_id).cloned() else {
+            return Err(format!("invalid kernel tree '{kernel_tree_id}'"));
+        };
+        if kernel_tree_id == new_kernel_tree_id.trim() {
+            return Ok(());
+        }
+
+        self.add_kernel_tree(new_kernel_tree_id, kernel_tree)?;
+        let was_target = self.target_kernel_tree.as_deref() == Some(kernel_tree_id);
+        self.remove_kernel_tree(kernel_tree_id);
+        if was_target {
+            self.target_kernel_tree = Some(new_kernel_tree_id.trim().to_string());
+        }
+        Ok(())
+    }
+
+    /// Sets the target kernel tree to the one mapped by `kernel_tree_id`.
+    ///
+    /// Returns a `String` with the error message if there is no such kernel tree.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_manage_kernel_trees]
+    pub fn set_target_kernel_tree(&mut self, kernel_tree_id: &str) -> Result<(), String> {
+        if !self.kernel_trees.contains_key(kernel_tree_id) {
+            return Err(format!("invalid kernel tree '{kernel_tree_id}'"));
+        }
+        self.target_kernel_tree = Some(kernel_tree_id.to_string());
+        Ok(())
+    }
+
     pub fn set_patch_renderer(&mut self, patch_renderer: PatchRenderer) {
         self.patch_renderer = patch_renderer;
     }
@@ -70,10 +70,50 @@ fn can_build_with_default_values() {
         "/fake/home/path/.local/share/patch_hub/reviewed_patchsets.json",
         config.reviewed_patchsets_path()
     );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/attestation_statuses.json",
+        config.attestation_statuses_path()
+    );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/build_statuses.json",
+        config.build_statuses_path()
+    );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/apply_history.json",
+        config.apply_history_path()
+    );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/git_am_conflict.json",
+        config.git_am_conflict_path()
+    );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/command_history.json",
+        config.command_history_path()
+    );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/recent_patchsets.json",
+        config.recent_patchsets_path()
+    );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/favorite_lists.json",
+        config.favorite_lists_path()
+    );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/list_activity.json",
+        config.list_activity_path()
+    );
     assert_eq!(
         "/fake/home/path/.local/share/patch_hub/logs",
         config.logs_path()
     );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/worktrees",
+        config.worktrees_dir()
+    );
+    assert_eq!(
+        "/fake/home/path/.local/share/patch_hub/exports",
+        config.exports_dir()
+    );
     assert_eq!(
         "--dry-run --suppress-cc=all",
         config.git_send_email_options()
@@ -83,6 +123,21 @@ fn can_build_with_default_values() {
     assert!(config.target_kernel_tree().is_none());
     assert_eq!("", config.git_am_options());
     assert_eq!("patchset-", config.git_am_branch_prefix());
+    assert!(config.base_trees().is_empty());
+    assert!(!config.apply_to_worktree());
+    assert!(!config.fetch_before_apply());
+    assert!(!config.apply_on_upstream());
+    assert!(config.extra_trailers().is_empty());
+    assert_eq!("", config.checkpatch_options());
+    assert_eq!("make -j$(nproc) W=1 {touched_dirs}", config.build_command());
+    assert_eq!(
+        "make -j$(nproc) W=1 {touched_dirs}",
+        config.bisect_check_command()
+    );
+    assert!(config.keybindings().is_empty());
+    assert!(!config.mouse_support());
+    assert_eq!(&ThemePreset::Dark, config.theme());
+    assert!(config.theme_colors().is_empty());
 }
 
 #[test]
@@ -102,7 +157,20 @@ fn can_build_with_config_file() {
     );
     assert_eq!("/mailing/lists/path", config.mailing_lists_path());
     assert_eq!("/reviewed/patchsets/path", config.reviewed_patchsets_path());
+    assert_eq!(
+        "/attestation/statuses/path",
+        config.attestation_statuses_path()
+    );
+    assert_eq!("/build/statuses/path", config.build_statuses_path());
+    assert_eq!("/apply/history/path", config.apply_history_path());
+    assert_eq!("/git/am/conflict/path", config.git_am_conflict_path());
+    assert_eq!("/command/history/path", config.command_history_path());
+    assert_eq!("/recent/patchsets/path", config.recent_patchsets_path());
+    assert_eq!("/favorite/lists/path", config.favorite_lists_path());
+    assert_eq!("/list/activity/path", config.list_activity_path());
     assert_eq!("/logs/path", config.logs_path());
+    assert_eq!("/worktrees/path", config.worktrees_dir());
+    assert_eq!("/exports/path", config.exports_dir());
     assert_eq!(
         "--long-option value -s -h -o -r -t",
         config.git_send_email_options()
@@ -126,6 +194,51 @@ fn can_build_with_config_file() {
         config.git_am_options()
     );
     assert_eq!("really-creative-prefix-", config.git_am_branch_prefix());
+    assert_eq!(
+        &HashMap::from([
+            (
+                "amd-gfx".to_string(),
+                BaseTree {
+                    kernel_tree: "amd-gfx".to_string(),
+                    branch: None
+                }
+            ),
+            (
+                "netdev/net-next.git".to_string(),
+                BaseTree {
+                    kernel_tree: "linux".to_string(),
+                    branch: Some("net-next".to_string())
+                }
+            ),
+        ]),
+        config.base_trees()
+    );
+    assert!(config.apply_to_worktree());
+    assert!(config.fetch_before_apply());
+    assert!(config.apply_on_upstream());
+    assert_eq!(
+        &vec!["Signed-off-by".to_string(), "Cc".to_string()],
+        config.extra_trailers()
+    );
+    assert_eq!("--strict --show-types", config.checkpatch_options());
+    assert_eq!("kw build", config.build_command());
+    assert_eq!("make olddefconfig", config.bisect_check_command());
+    assert_eq!(
+        &HashMap::from([(
+            "details".to_string(),
+            HashMap::from([(
+                "go_to_first_line".to_string(),
+                vec!["g g".to_string(), "Home".to_string()]
+            )])
+        )]),
+        config.keybindings()
+    );
+    assert!(config.mouse_support());
+    assert_eq!(&ThemePreset::Light, config.theme());
+    assert_eq!(
+        &HashMap::from([("selected".to_string(), "black on light-green".to_string())]),
+        config.theme_colors()
+    );
 }
 
 #[test]
@@ -157,7 +270,41 @@ fn can_build_with_env_vars() {
         "/fake/data/path/reviewed_patchsets.json",
         config.reviewed_patchsets_path()
     );
+    assert_eq!(
+        "/fake/data/path/attestation_statuses.json",
+        config.attestation_statuses_path()
+    );
+    assert_eq!(
+        "/fake/data/path/build_statuses.json",
+        config.build_statuses_path()
+    );
+    assert_eq!(
+        "/fake/data/path/apply_history.json",
+        config.apply_history_path()
+    );
+    assert_eq!(
+        "/fake/data/path/git_am_conflict.json",
+        config.git_am_conflict_path()
+    );
+    assert_eq!(
+        "/fake/data/path/command_history.json",
+        config.command_history_path()
+    );
+    assert_eq!(
+        "/fake/data/path/recent_patchsets.json",
+        config.recent_patchsets_path()
+    );
+    assert_eq!(
+        "/fake/data/path/favorite_lists.json",
+        config.favorite_lists_path()
+    );
+    assert_eq!(
+        "/fake/data/path/list_activity.json",
+        config.list_activity_path()
+    );
     assert_eq!("/fake/data/path/logs", config.logs_path());
+    assert_eq!("/fake/data/path/worktrees", config.worktrees_dir());
+    assert_eq!("/fake/data/path/exports", config.exports_dir());
     assert_eq!("--option1 --option2", config.git_send_email_options());
 
     env::remove_var("PATCH_HUB_CACHE_DIR");
@@ -188,6 +335,37 @@ fn test_config_precedence() {
     env::remove_var("PATCH_HUB_PAGE_SIZE");
 }
 
+#[test]
+fn should_manage_kernel_trees() {
+    let mut config = Config::default();
+    let linux = KernelTree::new("/home/user/linux".to_string(), "master".to_string());
+
+    config.add_kernel_tree("linux", linux.clone()).unwrap();
+    assert!(
+        config.add_kernel_tree("linux", linux.clone()).is_err(),
+        "Kernel tree names should be unique"
+    );
+    assert!(config.add_kernel_tree("  ", linux.clone()).is_err());
+    config.set_target_kernel_tree("linux").unwrap();
+    assert!(config.set_target_kernel_tree("invalid-id").is_err());
+
+    config.rename_kernel_tree("linux", "mainline").unwrap();
+    assert!(config.get_kernel_tree("linux").is_none());
+    assert_eq!(&linux, config.get_kernel_tree("mainline").unwrap());
+    assert_eq!(
+        Some("mainline"),
+        config.target_kernel_tree().as_deref(),
+        "Renaming the target kernel tree should keep it as target"
+    );
+
+    assert_eq!(Some(linux), config.remove_kernel_tree("mainline"));
+    assert!(config.kernel_trees().is_empty());
+    assert!(
+        config.target_kernel_tree().is_none(),
+        "Removing the target kernel tree should unset it"
+    );
+}
+
 #[test]
 fn test_deserialize_config_with_missing_field() {
     // Example JSON string that doesn't contain `page_size` but has `max_log_age` set to 500.
@@ -6,7 +6,7 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 
-use super::logging::Logger;
+use super::{logging::Logger, theme::ThemePreset};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
 pub enum CoverRenderer {
@@ -44,24 +44,33 @@ impl Display for CoverRenderer {
     }
 }
 
-pub fn render_cover(raw: &str, renderer: &CoverRenderer) -> color_eyre::Result<String> {
+/// Renders the cover `raw` with `renderer`, using the colors that match the
+/// theme `preset` if the renderer supports it.
+pub fn render_cover(
+    raw: &str,
+    renderer: &CoverRenderer,
+    preset: ThemePreset,
+) -> color_eyre::Result<String> {
     let text = match renderer {
         CoverRenderer::Default => Ok(raw.to_string()),
-        CoverRenderer::Bat => bat_cover_renderer(raw),
+        CoverRenderer::Bat => bat_cover_renderer(raw, preset),
     }?;
 
     Ok(text)
 }
 
-/// Renders a .mbx cover using the `bat` command line tool.
+/// Renders a .mbx cover using the `bat` command line tool, with the syntax
+/// theme of `preset`.
 ///
 /// # Errors
 ///
 /// If bat isn't installed or if the command fails, an error will be returned.
-fn bat_cover_renderer(patch: &str) -> color_eyre::Result<String> {
+fn bat_cover_renderer(patch: &str, preset: ThemePreset) -> color_eyre::Result<String> {
     let mut bat = Command::new("bat")
         .arg("-pp")
         .arg("-f")
+        .arg("--theme")
+        .arg(preset.syntax_theme())
         .arg("-l")
         .arg("mbx")
         .stdin(Stdio::piped())
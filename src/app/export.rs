@@ -0,0 +1,233 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use patch_hub::lore::{lore_session::split_cover, trailers::Trailers};
+
+#[cfg(test)]
+mod tests;
+
+/// `From` line that starts each message of the exported patches, like the
+/// ones of the mboxes downloaded by `b4`
+const MBOX_FROM_LINE: &str = "From git@z Thu Jan  1 00:00:00 1970\n";
+
+/// Maximum length of the subject part of the name of patch files, as in
+/// `git format-patch`
+const MAX_PATCH_NAME_LEN: usize = 52;
+
+/// Formats in which a patchset can be exported
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ExportFormat {
+    /// A directory of numbered `.patch` files, like `git format-patch`
+    /// outputs
+    #[default]
+    PatchFiles,
+    /// A single mbox, ready for `git am`, with the trailers collected from
+    /// the thread added to each patch
+    Mbox,
+    /// A directory with the `.patch` files and a `series` file listing them
+    /// in order, to be used with `quilt`
+    Quilt,
+}
+
+impl ExportFormat {
+    /// Returns the format after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            ExportFormat::PatchFiles => ExportFormat::Mbox,
+            ExportFormat::Mbox => ExportFormat::Quilt,
+            ExportFormat::Quilt => ExportFormat::PatchFiles,
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::PatchFiles => write!(f, "patches"),
+            ExportFormat::Mbox => write!(f, "mbox"),
+            ExportFormat::Quilt => write!(f, "quilt"),
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "patches" => Ok(ExportFormat::PatchFiles),
+            "mbox" => Ok(ExportFormat::Mbox),
+            "quilt" => Ok(ExportFormat::Quilt),
+            _ => Err(format!("invalid export format '{s}'")),
+        }
+    }
+}
+
+/// Returns the file name of the patch `raw_patch`, numbered `number`, like
+/// `0001-file-Do-foo.patch` for `Subject: [PATCH 1/3] file: Do foo`. Cover
+/// letters (numbered 0) are named `0000-cover-letter.patch`.
+///
+/// # Tests
+///
+/// [tests::should_name_patch_files]
+pub fn patch_file_name(number: usize, raw_patch: &str) -> String {
+    if number == 0 {
+        return "0000-cover-letter.patch".to_string();
+    }
+
+    let subject = patch_subject(raw_patch);
+    // Tags like `[PATCH v2 1/3]` aren't part of the name
+    let subject = subject
+        .strip_prefix('[')
+        .and_then(|subject| subject.split_once(']'))
+        .map_or(subject.as_str(), |(_, subject)| subject);
+
+    let mut name = String::new();
+    for word in subject.split(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '.') {
+        let word = word.trim_matches('.');
+        if word.is_empty() {
+            continue;
+        }
+        if !name.is_empty() {
+            name.push('-');
+        }
+        name.push_str(word);
+    }
+    name.truncate(MAX_PATCH_NAME_LEN);
+    let name = name.trim_end_matches(['-', '.']);
+
+    format!("{number:04}-{name}.patch")
+}
+
+/// Returns the subject of `raw_patch`, unfolding it if it spans more than one
+/// line.
+fn patch_subject(raw_patch: &str) -> String {
+    let mut lines = raw_patch
+        .lines()
+        .skip_while(|line| !line.starts_with("Subject:"));
+    let Some(first_line) = lines.next() else {
+        return String::new();
+    };
+
+    let mut subject = first_line.trim_start_matches("Subject:").trim().to_string();
+    for line in lines.take_while(|line| line.starts_with([' ', '\t'])) {
+        subject.push(' ');
+        subject.push_str(line.trim());
+    }
+    subject
+}
+
+/// Adds to the commit message of `raw_patch` the `trailers` it doesn't have
+/// yet, after its last line, like `b4 am` does.
+///
+/// # Tests
+///
+/// [tests::should_add_trailers_to_patch]
+pub fn add_trailers_to_patch(raw_patch: &str, trailers: &Trailers) -> String {
+    let (message, diff) = split_cover(raw_patch);
+
+    let new_trailers: Vec<String> = trailers
+        .iter()
+        .flat_map(|(name, trailers)| {
+            trailers
+                .iter()
+                .map(move |trailer| format!("{name}: {}", trailer.value))
+        })
+        .filter(|line| !message.lines().any(|l| l.trim() == line))
+        .collect();
+    if new_trailers.is_empty() {
+        return raw_patch.to_string();
+    }
+
+    let mut patch = message.trim_end().to_string();
+    patch.push('\n');
+    for trailer in new_trailers {
+        patch.push_str(&trailer);
+        patch.push('\n');
+    }
+    // There is no diff to keep if there wasn't a `---` line
+    if message.len() < raw_patch.len() {
+        patch.push_str("---\n");
+        patch.push_str(diff);
+    }
+    patch
+}
+
+/// Exports `raw_patches` (starting with the cover letter if
+/// `has_cover_letter`) in `format` to `output_path`, which is a directory
+/// for [ExportFormat::PatchFiles] and [ExportFormat::Quilt] and a file for
+/// [ExportFormat::Mbox]. Only the mbox gets the collected `trailers` of each
+/// patch added (see [add_trailers_to_patch]).
+///
+/// Returns the path written or a `String` with the error message on failure.
+///
+/// # Tests
+///
+/// [tests::should_export_patch_files]
+/// [tests::should_export_mbox]
+/// [tests::should_export_quilt_series]
+pub fn export_patchset(
+    raw_patches: &[String],
+    has_cover_letter: bool,
+    trailers: &[Trailers],
+    format: ExportFormat,
+    output_path: &Path,
+) -> Result<PathBuf, String> {
+    let numbered_patches = raw_patches
+        .iter()
+        .enumerate()
+        .map(|(i, raw_patch)| (if has_cover_letter { i } else { i + 1 }, raw_patch));
+
+    match format {
+        ExportFormat::PatchFiles | ExportFormat::Quilt => {
+            create_dir(output_path)?;
+            let mut series = String::new();
+            for (number, raw_patch) in numbered_patches {
+                // Quilt series don't have cover letters
+                if number == 0 && format == ExportFormat::Quilt {
+                    continue;
+                }
+                let file_name = patch_file_name(number, raw_patch);
+                write_file(
+                    &output_path.join(&file_name),
+                    &format!("{MBOX_FROM_LINE}{raw_patch}"),
+                )?;
+                series.push_str(&file_name);
+                series.push('\n');
+            }
+            if format == ExportFormat::Quilt {
+                write_file(&output_path.join("series"), &series)?;
+            }
+        }
+        ExportFormat::Mbox => {
+            if let Some(parent) = output_path.parent() {
+                create_dir(parent)?;
+            }
+            let mut mbox = String::new();
+            for (i, raw_patch) in raw_patches.iter().enumerate() {
+                mbox.push_str(MBOX_FROM_LINE);
+                match trailers.get(i) {
+                    Some(trailers) if !(has_cover_letter && i == 0) => {
+                        mbox.push_str(&add_trailers_to_patch(raw_patch, trailers))
+                    }
+                    _ => mbox.push_str(raw_patch),
+                }
+            }
+            write_file(output_path, &mbox)?;
+        }
+    }
+
+    Ok(output_path.to_path_buf())
+}
+
+fn create_dir(path: &Path) -> Result<(), String> {
+    fs::create_dir_all(path).map_err(|e| format!("failed to create {}: {e}", path.display()))
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("failed to write {}: {e}", path.display()))
+}
@@ -0,0 +1,151 @@
+use super::*;
+
+use patch_hub::lore::trailers::{collect_thread_trailers, DEFAULT_TRAILER_NAMES};
+
+const SAMPLES_DIR: &str = "test_samples/trailers/collect_thread_trailers";
+
+fn read_raw_patches() -> Vec<String> {
+    ["cover_letter.cover", "patch_1.mbx", "patch_2.mbx"]
+        .iter()
+        .map(|file| fs::read_to_string(format!("{SAMPLES_DIR}/{file}")).unwrap())
+        .collect()
+}
+
+fn read_trailers(raw_patches: &[String]) -> Vec<Trailers> {
+    let raw_thread = fs::read_to_string(format!("{SAMPLES_DIR}/thread.mbox")).unwrap();
+    collect_thread_trailers(raw_patches, true, &raw_thread, &DEFAULT_TRAILER_NAMES)
+}
+
+fn tmp_output_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("patch-hub-export-{}-{name}", std::process::id()))
+}
+
+#[test]
+fn should_name_patch_files() {
+    let raw_patches = read_raw_patches();
+
+    assert_eq!(
+        "0000-cover-letter.patch",
+        patch_file_name(0, &raw_patches[0])
+    );
+    assert_eq!(
+        "0001-file-Do-foo.patch",
+        patch_file_name(1, &raw_patches[1])
+    );
+    assert_eq!(
+        "0012-drm-amdgpu-fix-the-foo.bar-check-for-v2.patch",
+        patch_file_name(
+            12,
+            "Subject: [PATCH v2 12/14] drm/amdgpu: fix the foo.bar check\n for v2\n"
+        )
+    );
+    assert_eq!(
+        52,
+        patch_file_name(1, &format!("Subject: {}\n", "a".repeat(80)))
+            .trim_start_matches("0001-")
+            .trim_end_matches(".patch")
+            .len(),
+        "Names should be truncated like `git format-patch` does"
+    );
+}
+
+#[test]
+fn should_add_trailers_to_patch() {
+    let raw_patches = read_raw_patches();
+    let trailers = read_trailers(&raw_patches);
+
+    let patch = add_trailers_to_patch(&raw_patches[1], &trailers[1]);
+
+    assert!(patch.contains(
+        "Link: https://lore.kernel.org/r/1234-report@johnson.com\n\
+         Signed-off-by: Foo Bar <foo@bar.foo.bar>\n\
+         Reviewed-by: Roberto Silva <roberto@silva.br>\n\
+         Tested-by: Roberto Silva <roberto@silva.br>\n\
+         Acked-by: John Johnson <john@johnson.com>\n\
+         ---\n file.rs | 2 +-\n"
+    ));
+    assert_eq!(
+        1,
+        patch.matches("Link: ").count(),
+        "Trailers already in the patch shouldn't be added again"
+    );
+    assert!(patch.ends_with("-- \n2.34.1\n"));
+
+    let empty_trailers = Trailers::new(&DEFAULT_TRAILER_NAMES);
+    assert_eq!(
+        raw_patches[2],
+        add_trailers_to_patch(&raw_patches[2], &empty_trailers)
+    );
+}
+
+#[test]
+fn should_export_patch_files() {
+    let raw_patches = read_raw_patches();
+    let output_path = tmp_output_path("patches");
+
+    export_patchset(
+        &raw_patches,
+        true,
+        &[],
+        ExportFormat::PatchFiles,
+        &output_path,
+    )
+    .unwrap();
+    let mut files: Vec<String> = fs::read_dir(&output_path)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    files.sort();
+    let first_patch = fs::read_to_string(output_path.join("0001-file-Do-foo.patch")).unwrap();
+    fs::remove_dir_all(&output_path).unwrap();
+
+    assert_eq!(
+        vec![
+            "0000-cover-letter.patch",
+            "0001-file-Do-foo.patch",
+            "0002-file-Do-bar.patch"
+        ],
+        files
+    );
+    assert_eq!(format!("{MBOX_FROM_LINE}{}", raw_patches[1]), first_patch);
+}
+
+#[test]
+fn should_export_mbox() {
+    let raw_patches = read_raw_patches();
+    let trailers = read_trailers(&raw_patches);
+    let output_path = tmp_output_path("mbox").join("patchset.mbx");
+
+    export_patchset(
+        &raw_patches,
+        true,
+        &trailers,
+        ExportFormat::Mbox,
+        &output_path,
+    )
+    .unwrap();
+    let mbox = fs::read_to_string(&output_path).unwrap();
+    fs::remove_dir_all(output_path.parent().unwrap()).unwrap();
+
+    assert_eq!(3, mbox.matches(MBOX_FROM_LINE).count());
+    assert!(mbox.starts_with(&format!("{MBOX_FROM_LINE}{}", raw_patches[0])));
+    assert!(mbox.contains("Reviewed-by: Roberto Silva <roberto@silva.br>\n"));
+    assert!(mbox.contains("Tested-by: Lima Luma <lima@luma.rs>\n"));
+}
+
+#[test]
+fn should_export_quilt_series() {
+    let raw_patches = read_raw_patches();
+    let output_path = tmp_output_path("quilt");
+
+    export_patchset(&raw_patches, true, &[], ExportFormat::Quilt, &output_path).unwrap();
+    let series = fs::read_to_string(output_path.join("series")).unwrap();
+    let has_cover_letter = output_path.join("0000-cover-letter.patch").exists();
+    fs::remove_dir_all(&output_path).unwrap();
+
+    assert_eq!("0001-file-Do-foo.patch\n0002-file-Do-bar.patch\n", series);
+    assert!(
+        !has_cover_letter,
+        "Quilt series shouldn't have cover letters"
+    );
+}
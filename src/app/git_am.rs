@@ -0,0 +1,273 @@
+use std::{io, path::Path, process::Command, str::FromStr};
+
+use derive_getters::Getters;
+use patch_hub::lore::{lore_session, patch::Patch};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    apply_history::{ApplyRecord, ApplyResult},
+    config::KernelTree,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A `git am` stopped by conflicts, waiting for the user to resolve them. It
+/// is saved until resolved, so it survives closing the patchset details or
+/// quitting patch-hub.
+#[derive(Getters, Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GitAmConflict {
+    /// Representative patch of the patchset being applied
+    patchset: Patch,
+    /// Kernel tree in which the patchset is applied
+    kernel_tree: KernelTree,
+    /// Ref from which `branch` was created
+    base: String,
+    /// Branch in which the patchset is applied
+    branch: String,
+    /// Path of the repository (the kernel tree or one of its worktrees) in
+    /// the middle of `git am`
+    repo_path: String,
+    /// Branch of the kernel tree to switch back to once `git am` is over,
+    /// unless the patchset is applied in a worktree
+    original_branch: Option<String>,
+    /// Output of the last `git am` run
+    output: String,
+}
+
+impl GitAmConflict {
+    pub fn new(
+        patchset: Patch,
+        kernel_tree: KernelTree,
+        base: String,
+        branch: String,
+        repo_path: String,
+        original_branch: Option<String>,
+        output: String,
+    ) -> Self {
+        GitAmConflict {
+            patchset,
+            kernel_tree,
+            base,
+            branch,
+            repo_path,
+            original_branch,
+            output,
+        }
+    }
+
+    /// Returns if the apply of the patchset of `representative_patch` stopped
+    /// by this conflict.
+    pub fn is_of(&self, representative_patch: &Patch) -> bool {
+        &self.patchset == representative_patch
+    }
+
+    /// Worktree in which the patchset is applied, if not the kernel tree
+    pub fn worktree(&self) -> Option<&str> {
+        (&self.repo_path != self.kernel_tree.path()).then_some(self.repo_path.as_str())
+    }
+
+    /// Returns a record of the apply with `result`.
+    pub fn apply_record(&self, result: ApplyResult) -> ApplyRecord {
+        ApplyRecord::new(
+            self.patchset.clone(),
+            self.kernel_tree.path().clone(),
+            self.base.clone(),
+            self.branch.clone(),
+            self.worktree().map(String::from),
+            result,
+        )
+    }
+}
+
+/// How to proceed with a [GitAmConflict]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GitAmResolution {
+    /// Suspend patch-hub to resolve the conflicts in a shell
+    Resolve,
+    /// `git am --continue`
+    Continue,
+    /// `git am --skip`
+    Skip,
+    /// `git am --abort`
+    Abort,
+}
+
+impl FromStr for GitAmResolution {
+    type Err = String;
+
+    /// Parses `resolve`, `continue`, `skip` or `abort`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "resolve" => Ok(GitAmResolution::Resolve),
+            "continue" => Ok(GitAmResolution::Continue),
+            "skip" => Ok(GitAmResolution::Skip),
+            "abort" => Ok(GitAmResolution::Abort),
+            _ => Err(format!("invalid `git am` resolution '{s}'")),
+        }
+    }
+}
+
+/// State of a [GitAmConflict] after proceeding with it
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GitAmState {
+    /// `git am` stopped by conflicts again
+    InProgress,
+    /// `git am` is over and the patchset was applied
+    Applied,
+    /// `git am` was aborted
+    Aborted,
+}
+
+/// Runs `git am` with `resolution` (`--continue`, `--skip` or `--abort`) in
+/// the repository of `conflict`. If `git am` stops again, the output of
+/// `conflict` is updated. Once `git am` is over, the kernel tree is switched
+/// back to its original branch.
+///
+/// Returns the [GitAmState] after running `git am` or a `String` with the
+/// error message on failure.
+///
+/// # Tests
+///
+/// [tests::should_abort_git_am_conflict]
+/// [tests::should_keep_git_am_conflict_until_resolved]
+/// [tests::should_skip_git_am_conflict]
+/// [tests::should_not_resolve_git_am_conflict_without_git_am]
+pub fn resolve_git_am_conflict(
+    conflict: &mut GitAmConflict,
+    resolution: GitAmResolution,
+) -> Result<GitAmState, String> {
+    let flag = match resolution {
+        GitAmResolution::Continue => "--continue",
+        GitAmResolution::Skip => "--skip",
+        GitAmResolution::Abort => "--abort",
+        GitAmResolution::Resolve => {
+            return Err("conflicts must be resolved in a shell".to_string());
+        }
+    };
+    if !is_git_am_in_progress(&conflict.repo_path) {
+        return Err(format!(
+            "there is no `git am` in progress in '{}'",
+            conflict.repo_path
+        ));
+    }
+
+    let git_am_out = Command::new("git")
+        .arg("-C")
+        .arg(&conflict.repo_path)
+        .arg("am")
+        .arg(flag)
+        .output()
+        .map_err(|e| format!("failed to execute git-am: {}", e))?;
+
+    if is_git_am_in_progress(&conflict.repo_path) {
+        conflict.output = format!(
+            "{}{}",
+            String::from_utf8_lossy(&git_am_out.stdout),
+            String::from_utf8_lossy(&git_am_out.stderr)
+        );
+        return Ok(GitAmState::InProgress);
+    }
+
+    if let Some(original_branch) = &conflict.original_branch {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(conflict.kernel_tree.path())
+            .arg("switch")
+            .arg(original_branch)
+            .output()
+            .map_err(|e| format!("failed to switch branch: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "failed to switch to branch '{}': {}",
+                original_branch,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+    }
+
+    Ok(match resolution {
+        GitAmResolution::Abort => GitAmState::Aborted,
+        _ => GitAmState::Applied,
+    })
+}
+
+/// Keeps the failed `git am --3way` in the repository in `repo_path` in
+/// progress if it stopped by conflicts, which leave unmerged paths to
+/// resolve. Otherwise, like when a patch doesn't apply at all or the blobs
+/// for the 3-way merge are missing, there is nothing to resolve, so it's
+/// aborted.
+///
+/// Returns if `git am` stopped by conflicts.
+///
+/// # Tests
+///
+/// [tests::should_abort_git_am_without_conflicts]
+pub fn keep_git_am_if_conflicted(repo_path: &str) -> bool {
+    if !is_git_am_in_progress(repo_path) {
+        return false;
+    }
+
+    let has_unmerged_paths = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("diff")
+        .arg("--name-only")
+        .arg("--diff-filter=U")
+        .output()
+        .is_ok_and(|output| output.status.success() && !output.stdout.trim_ascii().is_empty());
+    if !has_unmerged_paths {
+        abort_git_am(repo_path);
+    }
+    has_unmerged_paths
+}
+
+/// Aborts the `git am` in progress in the repository in `repo_path`, if any.
+pub fn abort_git_am(repo_path: &str) {
+    let _ = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("am")
+        .arg("--abort")
+        .output();
+}
+
+/// Returns if the repository in `repo_path` is in the middle of `git am`.
+/// Worktrees have their own state, so `git rev-parse --git-path` is used to
+/// find it.
+pub fn is_git_am_in_progress(repo_path: &str) -> bool {
+    let Ok(output) = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .arg("rev-parse")
+        .arg("--git-path")
+        .arg("rebase-apply")
+        .output()
+    else {
+        return false;
+    };
+
+    let rebase_apply = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    output.status.success() && Path::new(repo_path).join(rebase_apply).is_dir()
+}
+
+/// Saves `git_am_conflict` to the file in `filepath`.
+///
+/// # Tests
+///
+/// [tests::should_save_and_load_git_am_conflict]
+pub fn save_git_am_conflict(
+    git_am_conflict: Option<&GitAmConflict>,
+    filepath: &str,
+) -> io::Result<()> {
+    lore_session::save_json(&git_am_conflict, filepath)
+}
+
+/// Loads the `git am` stopped by conflicts saved in the file in `filepath`.
+///
+/// # Tests
+///
+/// [tests::should_save_and_load_git_am_conflict]
+pub fn load_git_am_conflict(filepath: &str) -> io::Result<Option<GitAmConflict>> {
+    lore_session::load_json(filepath)
+}
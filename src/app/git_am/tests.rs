@@ -0,0 +1,243 @@
+use std::{fs, path::PathBuf};
+
+use super::*;
+
+use patch_hub::lore::patch::{Author, MessageID};
+
+fn dummy_patch() -> Patch {
+    Patch::new(
+        "[PATCH] foo: bar".to_string(),
+        Author {
+            name: "Foo Bar".to_string(),
+            email: "foo@bar.foo".to_string(),
+        },
+        MessageID {
+            href: "https://lore.kernel.org/all/1234.5678-1-foo@bar.foo/".to_string(),
+        },
+        None,
+        "2024-10-18T12:00:00Z".to_string(),
+    )
+}
+
+fn git(repo_path: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Creates a scratch repository named after `name` in which a patch changing
+/// file `foo` of `master` is applied with `git am --3way` to branch
+/// `patchset`, which changed `foo` too, so `git am` stops by conflicts.
+fn scratch_repo_with_conflict(name: &str) -> (PathBuf, GitAmConflict) {
+    let repo_path =
+        std::env::temp_dir().join(format!("patch-hub-git-am-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&repo_path);
+    fs::create_dir_all(&repo_path).unwrap();
+    let mbox_path = repo_path.with_extension("mbx");
+
+    git(&repo_path, &["init", "--quiet", "--initial-branch=master"]);
+    git(&repo_path, &["config", "user.name", "Foo Bar"]);
+    git(&repo_path, &["config", "user.email", "foo@bar.foo"]);
+    git(&repo_path, &["config", "commit.gpgsign", "false"]);
+    fs::write(repo_path.join("foo"), "foo\n").unwrap();
+    git(&repo_path, &["add", "foo"]);
+    git(&repo_path, &["commit", "--quiet", "--message", "foo: add"]);
+
+    git(&repo_path, &["switch", "--quiet", "--create", "upstream"]);
+    fs::write(repo_path.join("foo"), "bar\n").unwrap();
+    git(
+        &repo_path,
+        &["commit", "--quiet", "--all", "--message", "foo: bar"],
+    );
+    let mbox = git(&repo_path, &["format-patch", "-1", "--stdout"]);
+    fs::write(&mbox_path, format!("{mbox}\n")).unwrap();
+
+    git(&repo_path, &["switch", "--quiet", "master"]);
+    git(&repo_path, &["switch", "--quiet", "--create", "patchset"]);
+    fs::write(repo_path.join("foo"), "baz\n").unwrap();
+    git(
+        &repo_path,
+        &["commit", "--quiet", "--all", "--message", "foo: baz"],
+    );
+    git(&repo_path, &["am", "--3way", mbox_path.to_str().unwrap()]);
+    fs::remove_file(&mbox_path).unwrap();
+
+    let repo = repo_path.to_str().unwrap().to_string();
+    let conflict = GitAmConflict::new(
+        dummy_patch(),
+        KernelTree::new(repo.clone(), "master".to_string()),
+        "master".to_string(),
+        "patchset".to_string(),
+        repo,
+        Some("master".to_string()),
+        String::new(),
+    );
+    assert!(
+        is_git_am_in_progress(conflict.repo_path()),
+        "`git am` should have stopped by conflicts"
+    );
+
+    (repo_path, conflict)
+}
+
+#[test]
+fn should_abort_git_am_conflict() {
+    let (repo_path, mut conflict) = scratch_repo_with_conflict("abort");
+
+    let state = resolve_git_am_conflict(&mut conflict, GitAmResolution::Abort);
+
+    assert_eq!(Ok(GitAmState::Aborted), state);
+    assert!(!is_git_am_in_progress(conflict.repo_path()));
+    assert_eq!(
+        "master",
+        git(&repo_path, &["branch", "--show-current"]),
+        "The kernel tree should be switched back to its original branch"
+    );
+    assert_eq!(
+        "foo: baz",
+        git(&repo_path, &["log", "-1", "--format=%s", "patchset"])
+    );
+    fs::remove_dir_all(repo_path).unwrap();
+}
+
+#[test]
+fn should_keep_git_am_conflict_until_resolved() {
+    let (repo_path, mut conflict) = scratch_repo_with_conflict("continue");
+
+    let state = resolve_git_am_conflict(&mut conflict, GitAmResolution::Continue);
+
+    assert_eq!(Ok(GitAmState::InProgress), state);
+    assert!(is_git_am_in_progress(conflict.repo_path()));
+    assert!(
+        !conflict.output().is_empty(),
+        "The output of the last `git am` should be kept"
+    );
+    assert_eq!("patchset", git(&repo_path, &["branch", "--show-current"]));
+
+    fs::write(repo_path.join("foo"), "bar\nbaz\n").unwrap();
+    git(&repo_path, &["add", "foo"]);
+    let state = resolve_git_am_conflict(&mut conflict, GitAmResolution::Continue);
+
+    assert_eq!(Ok(GitAmState::Applied), state);
+    assert!(!is_git_am_in_progress(conflict.repo_path()));
+    assert_eq!("master", git(&repo_path, &["branch", "--show-current"]));
+    assert_eq!(
+        "foo: bar",
+        git(&repo_path, &["log", "-1", "--format=%s", "patchset"])
+    );
+    fs::remove_dir_all(repo_path).unwrap();
+}
+
+#[test]
+fn should_skip_git_am_conflict() {
+    let (repo_path, mut conflict) = scratch_repo_with_conflict("skip");
+
+    let state = resolve_git_am_conflict(&mut conflict, GitAmResolution::Skip);
+
+    assert_eq!(Ok(GitAmState::Applied), state);
+    assert!(!is_git_am_in_progress(conflict.repo_path()));
+    assert_eq!("master", git(&repo_path, &["branch", "--show-current"]));
+    assert_eq!(
+        "foo: baz",
+        git(&repo_path, &["log", "-1", "--format=%s", "patchset"]),
+        "The conflicting patch should be skipped"
+    );
+    fs::remove_dir_all(repo_path).unwrap();
+}
+
+#[test]
+fn should_not_resolve_git_am_conflict_without_git_am() {
+    let (repo_path, mut conflict) = scratch_repo_with_conflict("none");
+
+    assert!(
+        resolve_git_am_conflict(&mut conflict, GitAmResolution::Resolve).is_err(),
+        "Conflicts should only be resolved in a shell"
+    );
+    assert!(is_git_am_in_progress(conflict.repo_path()));
+
+    resolve_git_am_conflict(&mut conflict, GitAmResolution::Abort).unwrap();
+    assert!(
+        resolve_git_am_conflict(&mut conflict, GitAmResolution::Continue).is_err(),
+        "There is no `git am` in progress anymore"
+    );
+    fs::remove_dir_all(repo_path).unwrap();
+}
+
+#[test]
+fn should_abort_git_am_without_conflicts() {
+    let (repo_path, conflict) = scratch_repo_with_conflict("conflicted");
+
+    assert!(keep_git_am_if_conflicted(conflict.repo_path()));
+    assert!(
+        is_git_am_in_progress(conflict.repo_path()),
+        "`git am` stopped by conflicts should be kept"
+    );
+
+    // A patch to a file that doesn't exist, whose blobs are missing too
+    git(&repo_path, &["am", "--abort"]);
+    let mbox_path = repo_path.with_extension("mbx");
+    fs::write(
+        &mbox_path,
+        "From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001
+From: Foo Bar <foo@bar.foo>
+Date: Fri, 18 Oct 2024 12:00:00 +0000
+Subject: [PATCH] qux: change
+
+---
+ qux | 2 +-
+ 1 file changed, 1 insertion(+), 1 deletion(-)
+
+diff --git a/qux b/qux
+index 1234567..89abcde 100644
+--- a/qux
++++ b/qux
+@@ -1 +1 @@
+-qux
++quux
+-- 
+2.47.0
+",
+    )
+    .unwrap();
+    git(&repo_path, &["am", "--3way", mbox_path.to_str().unwrap()]);
+    fs::remove_file(&mbox_path).unwrap();
+    assert!(is_git_am_in_progress(conflict.repo_path()));
+
+    assert!(!keep_git_am_if_conflicted(conflict.repo_path()));
+    assert!(
+        !is_git_am_in_progress(conflict.repo_path()),
+        "`git am` without conflicts to resolve should be aborted"
+    );
+    fs::remove_dir_all(repo_path).unwrap();
+}
+
+#[test]
+fn should_save_and_load_git_am_conflict() {
+    let filepath = std::env::temp_dir().join(format!(
+        "patch-hub-git-am-conflict-{}.json",
+        std::process::id()
+    ));
+    let filepath = filepath.to_str().unwrap();
+    let conflict = GitAmConflict::new(
+        dummy_patch(),
+        KernelTree::new("/home/user/linux".to_string(), "master".to_string()),
+        "master".to_string(),
+        "patchset-1".to_string(),
+        "/home/user/worktrees/patchset-1".to_string(),
+        None,
+        "CONFLICT (content): Merge conflict in foo".to_string(),
+    );
+
+    save_git_am_conflict(Some(&conflict), filepath).unwrap();
+    let loaded_conflict = load_git_am_conflict(filepath).unwrap();
+    assert_eq!(Some(conflict), loaded_conflict);
+
+    save_git_am_conflict(None, filepath).unwrap();
+    let loaded_conflict = load_git_am_conflict(filepath).unwrap();
+    fs::remove_file(filepath).unwrap();
+    assert_eq!(None, loaded_conflict);
+}
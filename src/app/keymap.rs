@@ -0,0 +1,806 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::{screens::CurrentScreen, tabs::TAB_SCREENS};
+
+#[cfg(test)]
+mod tests;
+
+/// A key pressed with modifiers, like `j`, `G`, `Ctrl+t` or `ENTER`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Key {
+    code: KeyCode,
+    /// Only `Ctrl` and `Alt`, as `Shift` is part of the character (or of the
+    /// code, like for `BackTab`)
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Key {
+            code,
+            modifiers: modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(key: KeyEvent) -> Self {
+        Key::new(key.code, key.modifiers)
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    /// Parses keys like `j`, `G`, `Shift+g`, `Ctrl+t`, `Alt+x`, `Enter`,
+    /// `Space` or `F5`. Names are case-insensitive.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_parse_and_display_keys]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut shift = false;
+        let mut rest = s;
+        while let Some((modifier, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+            match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => shift = true,
+                _ => return Err(format!("invalid modifier '{modifier}' in key '{s}'")),
+            }
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(ch), None) if shift => KeyCode::Char(ch.to_ascii_uppercase()),
+            (Some(ch), None) => KeyCode::Char(ch),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "esc" => KeyCode::Esc,
+                "enter" => KeyCode::Enter,
+                "space" => KeyCode::Char(' '),
+                "tab" if shift => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("invalid key '{s}'")),
+                },
+            },
+        };
+
+        Ok(Key::new(code, modifiers))
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "SPACE"),
+            KeyCode::Char(ch) if ch.is_ascii_uppercase() => {
+                write!(f, "Shift+{}", ch.to_ascii_lowercase())
+            }
+            KeyCode::Char(ch) => write!(f, "{ch}"),
+            KeyCode::Esc => write!(f, "ESC"),
+            KeyCode::Enter => write!(f, "ENTER"),
+            KeyCode::Tab => write!(f, "TAB"),
+            KeyCode::BackTab => write!(f, "Shift+TAB"),
+            KeyCode::Backspace => write!(f, "BACKSPACE"),
+            KeyCode::Delete => write!(f, "DEL"),
+            KeyCode::Up => write!(f, "🡅"),
+            KeyCode::Down => write!(f, "🡇"),
+            KeyCode::Left => write!(f, "🡄"),
+            KeyCode::Right => write!(f, "🡆"),
+            KeyCode::Home => write!(f, "HOME"),
+            KeyCode::End => write!(f, "END"),
+            KeyCode::PageUp => write!(f, "PAGEUP"),
+            KeyCode::PageDown => write!(f, "PAGEDOWN"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// Keys that must be pressed one after the other, like `g g`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct KeySequence(Vec<Key>);
+
+impl FromStr for KeySequence {
+    type Err = String;
+
+    /// Parses whitespace-separated keys (see [Key::from_str]).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(Key::from_str)
+            .collect::<Result<Vec<Key>, String>>()?;
+        if keys.is_empty() {
+            return Err("empty key sequence".to_string());
+        }
+        Ok(KeySequence(keys))
+    }
+}
+
+impl Display for KeySequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Actions that can be bound to keys. Each screen has its own subset of
+/// actions (see [default_bindings]).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Action {
+    Help,
+    CommandLine,
+    Exit,
+    Down,
+    Up,
+    Open,
+    // Tabs, in the screens of tabs
+    NewTab,
+    CloseTab,
+    NextTab,
+    PreviousTab,
+    // Navigation history, in the screens of tabs
+    Back,
+    Forward,
+    // Mailing list selection
+    ShowBookmarked,
+    ShowRecent,
+    EditConfig,
+    ManageWorktrees,
+    ManageKernelTrees,
+    RefreshLists,
+    ShowApplyHistory,
+    ToggleFavorite,
+    // Latest patchsets
+    NextPage,
+    PreviousPage,
+    ToggleMySubsystems,
+    Filter,
+    CycleSort,
+    // Patchset details
+    RunActions,
+    PanLeft,
+    PanRight,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    GoToLineStart,
+    GoToFirstLine,
+    GoToLastLine,
+    ToggleFullscreen,
+    NextPatch,
+    PreviousPatch,
+    SearchForward,
+    SearchBackward,
+    NextMatch,
+    PreviousMatch,
+    ToggleBookmark,
+    ToggleApply,
+    ToggleApplyPerPatch,
+    ChooseApplyBase,
+    ToggleReviewedBy,
+    ToggleReviewedByAll,
+    ToggleCheckpatch,
+    ShowCheckpatch,
+    ShowConflict,
+    ToggleBuildTest,
+    ShowTrailers,
+    ToggleExport,
+    CycleExportFormat,
+    // Edit config
+    Edit,
+    // Kernel trees
+    Add,
+    Rename,
+    Remove,
+    SetTarget,
+    // Worktrees
+    Prune,
+    // Apply history
+    Mark,
+    MarkAll,
+    DeleteBranches,
+}
+
+impl Action {
+    /// Name of the action in `Config::keybindings`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Help => "help",
+            Action::CommandLine => "command_line",
+            Action::Exit => "exit",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::Open => "open",
+            Action::NewTab => "new_tab",
+            Action::CloseTab => "close_tab",
+            Action::NextTab => "next_tab",
+            Action::PreviousTab => "previous_tab",
+            Action::Back => "back",
+            Action::Forward => "forward",
+            Action::ShowBookmarked => "show_bookmarked",
+            Action::ShowRecent => "show_recent",
+            Action::ToggleFavorite => "toggle_favorite",
+            Action::EditConfig => "edit_config",
+            Action::ManageWorktrees => "manage_worktrees",
+            Action::ManageKernelTrees => "manage_kernel_trees",
+            Action::RefreshLists => "refresh_lists",
+            Action::ShowApplyHistory => "show_apply_history",
+            Action::NextPage => "next_page",
+            Action::PreviousPage => "previous_page",
+            Action::ToggleMySubsystems => "toggle_my_subsystems",
+            Action::Filter => "filter",
+            Action::CycleSort => "cycle_sort",
+            Action::RunActions => "run_actions",
+            Action::PanLeft => "pan_left",
+            Action::PanRight => "pan_right",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::HalfPageUp => "half_page_up",
+            Action::HalfPageDown => "half_page_down",
+            Action::GoToLineStart => "go_to_line_start",
+            Action::GoToFirstLine => "go_to_first_line",
+            Action::GoToLastLine => "go_to_last_line",
+            Action::ToggleFullscreen => "toggle_fullscreen",
+            Action::NextPatch => "next_patch",
+            Action::PreviousPatch => "previous_patch",
+            Action::SearchForward => "search_forward",
+            Action::SearchBackward => "search_backward",
+            Action::NextMatch => "next_match",
+            Action::PreviousMatch => "previous_match",
+            Action::ToggleBookmark => "toggle_bookmark",
+            Action::ToggleApply => "toggle_apply",
+            Action::ToggleApplyPerPatch => "toggle_apply_per_patch",
+            Action::ChooseApplyBase => "choose_apply_base",
+            Action::ToggleReviewedBy => "toggle_reviewed_by",
+            Action::ToggleReviewedByAll => "toggle_reviewed_by_all",
+            Action::ToggleCheckpatch => "toggle_checkpatch",
+            Action::ShowCheckpatch => "show_checkpatch",
+            Action::ShowConflict => "show_conflict",
+            Action::ToggleBuildTest => "toggle_build_test",
+            Action::ShowTrailers => "show_trailers",
+            Action::ToggleExport => "toggle_export",
+            Action::CycleExportFormat => "cycle_export_format",
+            Action::Edit => "edit",
+            Action::Add => "add",
+            Action::Rename => "rename",
+            Action::Remove => "remove",
+            Action::SetTarget => "set_target",
+            Action::Prune => "prune",
+            Action::Mark => "mark",
+            Action::MarkAll => "mark_all",
+            Action::DeleteBranches => "delete_branches",
+        }
+    }
+}
+
+/// Screens with a keymap, in the order of [screen_name]
+const KEYMAP_SCREENS: [CurrentScreen; 9] = [
+    CurrentScreen::MailingListSelection,
+    CurrentScreen::BookmarkedPatchsets,
+    CurrentScreen::RecentPatchsets,
+    CurrentScreen::LatestPatchsets,
+    CurrentScreen::PatchsetDetails,
+    CurrentScreen::EditConfig,
+    CurrentScreen::Worktrees,
+    CurrentScreen::KernelTrees,
+    CurrentScreen::ApplyHistory,
+];
+
+/// Name of `screen` in `Config::keybindings`
+pub fn screen_name(screen: &CurrentScreen) -> &'static str {
+    match screen {
+        CurrentScreen::MailingListSelection => "mail_list",
+        CurrentScreen::BookmarkedPatchsets => "bookmarked",
+        CurrentScreen::RecentPatchsets => "recent",
+        CurrentScreen::LatestPatchsets => "latest",
+        CurrentScreen::PatchsetDetails => "details",
+        CurrentScreen::EditConfig => "edit_config",
+        CurrentScreen::Worktrees => "worktrees",
+        CurrentScreen::KernelTrees => "kernel_trees",
+        CurrentScreen::ApplyHistory => "apply_history",
+    }
+}
+
+/// Actions shared by the screens of tabs (see [TAB_SCREENS]), with their
+/// default keys and their help messages. They are listed in the help of each of
+/// these screens after the actions of [default_bindings].
+const TAB_BINDINGS: &[(Action, &[&str], &str)] = &[
+    (Action::NewTab, &["Ctrl+n"], "Open a new tab"),
+    (Action::CloseTab, &["Ctrl+w"], "Close the tab"),
+    (Action::NextTab, &["Tab"], "Switch to the next tab"),
+    (
+        Action::PreviousTab,
+        &["Shift+Tab"],
+        "Switch to the previous tab",
+    ),
+    (
+        Action::Back,
+        &["Backspace", "Alt+Left"],
+        "Go back to the previous screen",
+    ),
+    (
+        Action::Forward,
+        &["Alt+Right"],
+        "Go forward to the screen gone back from",
+    ),
+];
+
+/// Returns whether `action` is one of the actions shared by the screens of tabs
+/// (see [TAB_BINDINGS])
+pub fn is_tab_action(action: Action) -> bool {
+    TAB_BINDINGS
+        .iter()
+        .any(|(tab_action, _, _)| *tab_action == action)
+}
+
+/// Returns whether `key`, when not bound to an action, types text in `screen`,
+/// like the name of the target list in the mailing list selection
+pub fn types_text(screen: &CurrentScreen, key: &Key) -> bool {
+    *screen == CurrentScreen::MailingListSelection
+        && key.modifiers.is_empty()
+        && matches!(key.code, KeyCode::Char(_) | KeyCode::Backspace)
+}
+
+/// Returns the actions of `screen`, in the order they are listed in its help,
+/// with their default keys and their help messages. The screens of tabs also
+/// have the actions of [TAB_BINDINGS].
+pub fn default_bindings(
+    screen: &CurrentScreen,
+) -> &'static [(Action, &'static [&'static str], &'static str)] {
+    match screen {
+        CurrentScreen::MailingListSelection => &[
+            (Action::Exit, &["Esc"], "Exit"),
+            (Action::Open, &["Enter"], "Open the selected mailing list"),
+            (Action::Help, &["?"], "Show this help screen"),
+            (Action::CommandLine, &[":"], "Open the command line"),
+            (Action::Down, &["Down"], "Down"),
+            (Action::Up, &["Up"], "Up"),
+            (
+                Action::ToggleFavorite,
+                &["Ctrl+f"],
+                "Pin the highlighted list to the top, or unpin it",
+            ),
+            (Action::ShowBookmarked, &["F1"], "Show bookmarked patchsets"),
+            (Action::EditConfig, &["F2"], "Edit config options"),
+            (
+                Action::ManageWorktrees,
+                &["F3"],
+                "Manage worktrees of applied patchsets",
+            ),
+            (Action::ManageKernelTrees, &["F4"], "Manage kernel trees"),
+            (Action::RefreshLists, &["F5"], "Refresh lists"),
+            (
+                Action::ShowApplyHistory,
+                &["F6"],
+                "Show the history of applied patchsets",
+            ),
+            (
+                Action::ShowRecent,
+                &["F7"],
+                "Show the recently viewed patchsets",
+            ),
+        ],
+        CurrentScreen::BookmarkedPatchsets => &[
+            (Action::Exit, &["Esc", "q"], "Exit"),
+            (
+                Action::Open,
+                &["Enter"],
+                "See details of the selected patchset",
+            ),
+            (Action::Help, &["?"], "Show this help screen"),
+            (Action::CommandLine, &[":"], "Open the command line"),
+            (Action::Down, &["j", "Down"], "Down"),
+            (Action::Up, &["k", "Up"], "Up"),
+        ],
+        CurrentScreen::RecentPatchsets => &[
+            (Action::Exit, &["Esc", "q"], "Exit"),
+            (
+                Action::Open,
+                &["Enter"],
+                "See details of the selected patchset",
+            ),
+            (Action::Help, &["?"], "Show this help screen"),
+            (Action::CommandLine, &[":"], "Open the command line"),
+            (Action::Down, &["j", "Down"], "Down"),
+            (Action::Up, &["k", "Up"], "Up"),
+        ],
+        CurrentScreen::LatestPatchsets => &[
+            (
+                Action::Exit,
+                &["Esc", "q"],
+                "Clear the filter, if any, or exit",
+            ),
+            (
+                Action::Open,
+                &["Enter"],
+                "See details of the selected patchset",
+            ),
+            (Action::Help, &["?"], "Show this help screen"),
+            (Action::CommandLine, &[":"], "Open the command line"),
+            (Action::Down, &["j", "Down"], "Down"),
+            (Action::Up, &["k", "Up"], "Up"),
+            (Action::NextPage, &["l", "Right"], "Next page"),
+            (Action::PreviousPage, &["h", "Left"], "Previous page"),
+            (
+                Action::ToggleMySubsystems,
+                &["m"],
+                "Toggle \"my subsystems\" filter",
+            ),
+            (Action::Filter, &["/"], "Edit the filter of the list"),
+            (
+                Action::CycleSort,
+                &["s"],
+                "Sort by newest, oldest, largest or most-reviewed",
+            ),
+        ],
+        CurrentScreen::PatchsetDetails => &[
+            (
+                Action::Exit,
+                &["Esc", "q"],
+                "Clear the search highlights, if any, or exit",
+            ),
+            (Action::RunActions, &["Enter"], "Consolidate marked actions"),
+            (Action::Help, &["?"], "Show this help screen"),
+            (Action::CommandLine, &[":"], "Open the command line"),
+            (Action::Down, &["j", "Down"], "Scroll down"),
+            (Action::Up, &["k", "Up"], "Scroll up"),
+            (Action::PanLeft, &["h", "Left"], "Pan left"),
+            (Action::PanRight, &["l", "Right"], "Pan right"),
+            (Action::PageDown, &["Ctrl+f"], "Scroll down a page"),
+            (Action::PageUp, &["Ctrl+b"], "Scroll up a page"),
+            (Action::HalfPageDown, &["Ctrl+d"], "Scroll down half a page"),
+            (Action::HalfPageUp, &["Ctrl+u"], "Scroll up half a page"),
+            (Action::GoToLineStart, &["0"], "Go to start of line"),
+            (Action::GoToFirstLine, &["g g"], "Go to first line"),
+            (Action::GoToLastLine, &["G"], "Go to last line"),
+            (Action::ToggleFullscreen, &["f"], "Toggle fullscreen"),
+            (Action::NextPatch, &["n"], "Preview next patch"),
+            (Action::PreviousPatch, &["p"], "Preview previous patch"),
+            (Action::SearchForward, &["/"], "Search a regex forward"),
+            (
+                Action::SearchBackward,
+                &["Alt+/"],
+                "Search a regex backward",
+            ),
+            (
+                Action::NextMatch,
+                &["Alt+n"],
+                "Jump to the next search match",
+            ),
+            (
+                Action::PreviousMatch,
+                &["Alt+p"],
+                "Jump to the previous search match",
+            ),
+            (Action::ToggleBookmark, &["b"], "Toggle bookmark action"),
+            (Action::ToggleApply, &["a"], "Toggle apply action"),
+            (
+                Action::ToggleApplyPerPatch,
+                &["A"],
+                "Toggle applying one patch at a time with a check after each",
+            ),
+            (
+                Action::ChooseApplyBase,
+                &["K"],
+                "Choose the kernel tree and branch to apply the patchset on",
+            ),
+            (
+                Action::ToggleReviewedBy,
+                &["r"],
+                "Toggle reply with Reviewed-by action",
+            ),
+            (
+                Action::ToggleReviewedByAll,
+                &["R"],
+                "Toggle reply with Reviewed-by action for all patches",
+            ),
+            (Action::ToggleCheckpatch, &["c"], "Toggle checkpatch action"),
+            (Action::ShowCheckpatch, &["C"], "Show checkpatch issues"),
+            (
+                Action::ShowConflict,
+                &["M"],
+                "Show conflicts of the patchset apply in progress",
+            ),
+            (Action::ToggleBuildTest, &["t"], "Toggle build-test action"),
+            (Action::ShowTrailers, &["Ctrl+t"], "Show trailers details"),
+            (Action::ToggleExport, &["e"], "Toggle export action"),
+            (
+                Action::CycleExportFormat,
+                &["E"],
+                "Choose the export format",
+            ),
+        ],
+        CurrentScreen::EditConfig => &[
+            (Action::Exit, &["Esc", "q"], "Save changes and exit"),
+            (
+                Action::Edit,
+                &["Enter", "e"],
+                "Toggle editing for a configuration option",
+            ),
+            (Action::Help, &["?"], "Show this help screen"),
+            (Action::CommandLine, &[":"], "Open the command line"),
+            (Action::Down, &["j", "Down"], "Down"),
+            (Action::Up, &["k", "Up"], "Up"),
+        ],
+        CurrentScreen::Worktrees => &[
+            (Action::Exit, &["Esc", "q"], "Exit"),
+            (
+                Action::Open,
+                &["Enter"],
+                "Open a shell in the selected worktree",
+            ),
+            (Action::Help, &["?"], "Show this help screen"),
+            (Action::CommandLine, &[":"], "Open the command line"),
+            (Action::Down, &["j", "Down"], "Down"),
+            (Action::Up, &["k", "Up"], "Up"),
+            (Action::Prune, &["d"], "Prune the selected worktree"),
+        ],
+        CurrentScreen::KernelTrees => &[
+            (Action::Exit, &["Esc", "q"], "Exit"),
+            (
+                Action::SetTarget,
+                &["Enter"],
+                "Set the selected kernel tree as target",
+            ),
+            (Action::Help, &["?"], "Show this help screen"),
+            (Action::CommandLine, &[":"], "Open the command line"),
+            (Action::Down, &["j", "Down"], "Down"),
+            (Action::Up, &["k", "Up"], "Up"),
+            (Action::Add, &["a"], "Add a kernel tree"),
+            (Action::Rename, &["r"], "Rename the selected kernel tree"),
+            (Action::Remove, &["d"], "Remove the selected kernel tree"),
+        ],
+        CurrentScreen::ApplyHistory => &[
+            (Action::Exit, &["Esc", "q"], "Exit"),
+            (
+                Action::Open,
+                &["Enter"],
+                "See details of the patchset of the selected apply",
+            ),
+            (Action::Help, &["?"], "Show this help screen"),
+            (Action::CommandLine, &[":"], "Open the command line"),
+            (Action::Down, &["j", "Down"], "Down"),
+            (Action::Up, &["k", "Up"], "Up"),
+            (
+                Action::Mark,
+                &["Space"],
+                "Mark/unmark the branch of the selected apply",
+            ),
+            (Action::MarkAll, &["a"], "Mark/unmark every branch"),
+            (
+                Action::DeleteBranches,
+                &["d"],
+                "Delete the marked branches (or the selected one)",
+            ),
+        ],
+    }
+}
+
+/// Keys bound to an action of a screen
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Binding {
+    pub action: Action,
+    pub keys: Vec<KeySequence>,
+    pub help: &'static str,
+}
+
+/// Result of matching pressed keys against the keymap of a screen
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum KeyMatch {
+    /// The keys are bound to the action
+    Action(Action),
+    /// The keys are the start of a longer sequence, so more keys are
+    /// expected. If they are also bound to an action on their own, it is the
+    /// one to run if no more keys come.
+    Pending(Option<Action>),
+    /// The keys aren't bound to anything
+    None,
+}
+
+/// Keys bound to the actions of each screen: the defaults (see
+/// [default_bindings]) with the overrides from `Config::keybindings`
+#[derive(Debug, Clone, Default)]
+pub struct Keymap {
+    bindings: HashMap<CurrentScreen, Vec<Binding>>,
+}
+
+impl Keymap {
+    /// Builds the keymap from the defaults and `overrides`, which map screen
+    /// names (see [screen_name]) to action names (see [Action::name]) to the
+    /// keys that replace the default ones. A key bound by an override is
+    /// unbound from the other actions of the screen.
+    ///
+    /// Overrides binding keys that type text in a screen (see [types_text]),
+    /// other than the ones it binds by default, are invalid.
+    ///
+    /// Returns the keymap and the error messages of the invalid overrides,
+    /// which are ignored.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_override_default_bindings]
+    /// [tests::should_report_invalid_overrides]
+    pub fn new(overrides: &HashMap<String, HashMap<String, Vec<String>>>) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut bindings = HashMap::new();
+
+        for screen in KEYMAP_SCREENS {
+            let tab_bindings = if TAB_SCREENS.contains(&screen) {
+                TAB_BINDINGS
+            } else {
+                &[]
+            };
+            let mut screen_bindings: Vec<Binding> = default_bindings(&screen)
+                .iter()
+                .chain(tab_bindings)
+                .map(|(action, keys, help)| Binding {
+                    action: *action,
+                    keys: keys
+                        .iter()
+                        .map(|keys| keys.parse().unwrap())
+                        // Shared keys don't take over the text typed in a screen
+                        .filter(|keys: &KeySequence| {
+                            !is_tab_action(*action) || !types_text(&screen, &keys.0[0])
+                        })
+                        .collect(),
+                    help,
+                })
+                .collect();
+
+            let mut screen_overrides: Vec<_> = overrides
+                .get(screen_name(&screen))
+                .into_iter()
+                .flatten()
+                .collect();
+            screen_overrides.sort();
+            for (action_name, keys) in screen_overrides {
+                let Some(index) = screen_bindings
+                    .iter()
+                    .position(|binding| binding.action.name() == action_name)
+                else {
+                    errors.push(format!(
+                        "invalid action '{action_name}' for screen '{}'",
+                        screen_name(&screen)
+                    ));
+                    continue;
+                };
+                let keys: Vec<KeySequence> = match keys.iter().map(|keys| keys.parse()).collect() {
+                    Ok(keys) => keys,
+                    Err(e) => {
+                        errors.push(format!("{e} for action '{action_name}'"));
+                        continue;
+                    }
+                };
+                // Keys typing text in the screen would stop typing it, unless
+                // they are already bound by default
+                if let Some(typing_keys) = keys.iter().find(|keys| {
+                    types_text(&screen, &keys.0[0])
+                        && !screen_bindings
+                            .iter()
+                            .any(|binding| binding.keys.contains(keys))
+                }) {
+                    errors.push(format!(
+                        "'{typing_keys}' types text in screen '{}' and can't be bound to action '{action_name}'",
+                        screen_name(&screen)
+                    ));
+                    continue;
+                }
+
+                for binding in screen_bindings.iter_mut() {
+                    binding.keys.retain(|k| !keys.contains(k));
+                }
+                screen_bindings[index].keys = keys;
+            }
+
+            bindings.insert(screen, screen_bindings);
+        }
+
+        for screen_name in overrides.keys() {
+            if !KEYMAP_SCREENS
+                .iter()
+                .any(|s| self::screen_name(s) == screen_name)
+            {
+                errors.push(format!("invalid screen '{screen_name}'"));
+            }
+        }
+
+        (Keymap { bindings }, errors)
+    }
+
+    /// Returns the bindings of `screen`, in the order they are listed in its
+    /// help.
+    pub fn bindings(&self, screen: &CurrentScreen) -> &[Binding] {
+        self.bindings.get(screen).map_or(&[], Vec::as_slice)
+    }
+
+    /// Matches `keys`, pressed in this order, against the bindings of
+    /// `screen`.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_match_key_sequences]
+    pub fn match_keys(&self, screen: &CurrentScreen, keys: &[Key]) -> KeyMatch {
+        let mut exact = None;
+        let mut is_prefix = false;
+        for binding in self.bindings(screen) {
+            for sequence in &binding.keys {
+                if sequence.0 == keys {
+                    exact = Some(binding.action);
+                } else if sequence.0.starts_with(keys) {
+                    is_prefix = true;
+                }
+            }
+        }
+
+        match (exact, is_prefix) {
+            (exact, true) => KeyMatch::Pending(exact),
+            (Some(action), false) => KeyMatch::Action(action),
+            (None, false) => KeyMatch::None,
+        }
+    }
+
+    /// Returns the keys bound to `action` in `screen` joined by `separator`,
+    /// like `j/🡇`.
+    pub fn keys_of(&self, screen: &CurrentScreen, action: Action, separator: &str) -> String {
+        self.bindings(screen)
+            .iter()
+            .find(|binding| binding.action == action)
+            .map(|binding| {
+                binding
+                    .keys
+                    .iter()
+                    .map(KeySequence::to_string)
+                    .collect::<Vec<String>>()
+                    .join(separator)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns a hint like `(ESC / q) to return | (?) help` of the keys bound
+    /// to each of `actions` of `screen` followed by its description. Actions
+    /// without keys are left out.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_generate_keys_hint]
+    pub fn keys_hint(&self, screen: &CurrentScreen, actions: &[(Action, &str)]) -> String {
+        actions
+            .iter()
+            .filter_map(|(action, description)| {
+                let keys = self.keys_of(screen, *action, " / ");
+                (!keys.is_empty()).then(|| format!("({keys}) {description}"))
+            })
+            .collect::<Vec<String>>()
+            .join(" | ")
+    }
+}
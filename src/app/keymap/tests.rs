@@ -0,0 +1,222 @@
+use super::*;
+
+fn key(s: &str) -> Key {
+    s.parse().unwrap()
+}
+
+fn overrides(
+    screen: &str,
+    action: &str,
+    keys: &[&str],
+) -> HashMap<String, HashMap<String, Vec<String>>> {
+    HashMap::from([(
+        screen.to_string(),
+        HashMap::from([(
+            action.to_string(),
+            keys.iter().map(|k| k.to_string()).collect(),
+        )]),
+    )])
+}
+
+#[test]
+fn should_parse_and_display_keys() {
+    assert_eq!(Key::new(KeyCode::Char('j'), KeyModifiers::NONE), key("j"));
+    assert_eq!(Key::new(KeyCode::Char('G'), KeyModifiers::NONE), key("G"));
+    assert_eq!(key("G"), key("Shift+g"));
+    assert_eq!(
+        key("G"),
+        Key::from(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT))
+    );
+    assert_eq!(
+        Key::new(KeyCode::Char('t'), KeyModifiers::CONTROL),
+        key("Ctrl+t")
+    );
+    assert_eq!(
+        Key::new(KeyCode::Char('+'), KeyModifiers::ALT),
+        key("alt++")
+    );
+    assert_eq!(
+        Key::new(KeyCode::Char(' '), KeyModifiers::NONE),
+        key("Space")
+    );
+    assert_eq!(
+        Key::new(KeyCode::BackTab, KeyModifiers::NONE),
+        key("Shift+Tab")
+    );
+    assert_eq!(Key::new(KeyCode::F(5), KeyModifiers::NONE), key("F5"));
+    assert_eq!(Key::new(KeyCode::Enter, KeyModifiers::NONE), key("ENTER"));
+    assert!("Meta+x".parse::<Key>().is_err());
+    assert!("F13".parse::<Key>().is_err());
+    assert!("foo".parse::<Key>().is_err());
+    assert!("".parse::<Key>().is_err());
+
+    assert_eq!("j", key("j").to_string());
+    assert_eq!("Shift+g", key("G").to_string());
+    assert_eq!("Ctrl+t", key("ctrl+t").to_string());
+    assert_eq!("ESC", key("esc").to_string());
+    assert_eq!("🡇", key("Down").to_string());
+    assert_eq!("F6", key("f6").to_string());
+    assert_eq!("g g", "g  g".parse::<KeySequence>().unwrap().to_string());
+    assert!(" ".parse::<KeySequence>().is_err());
+}
+
+#[test]
+fn default_bindings_should_not_conflict() {
+    for screen in KEYMAP_SCREENS {
+        let tab_bindings = if TAB_SCREENS.contains(&screen) {
+            TAB_BINDINGS
+        } else {
+            &[]
+        };
+        let mut keys = Vec::new();
+        for (action, action_keys, _) in default_bindings(&screen).iter().chain(tab_bindings) {
+            for action_keys in *action_keys {
+                let sequence: KeySequence = action_keys.parse().unwrap_or_else(|e| {
+                    panic!("{e} for {} of {}", action.name(), screen_name(&screen))
+                });
+                // Shared keys typing text in the screen aren't bound
+                if is_tab_action(*action) && types_text(&screen, &sequence.0[0]) {
+                    continue;
+                }
+                assert!(
+                    !keys.contains(&sequence),
+                    "'{action_keys}' is bound twice in {}",
+                    screen_name(&screen)
+                );
+                keys.push(sequence);
+            }
+        }
+    }
+}
+
+#[test]
+fn tab_bindings_should_be_shared_by_the_screens_of_tabs() {
+    let (keymap, _) = Keymap::new(&HashMap::new());
+
+    for screen in TAB_SCREENS {
+        assert_eq!("Ctrl+n", keymap.keys_of(screen, Action::NewTab, "/"));
+    }
+    assert_eq!(
+        "BACKSPACE/Alt+🡄",
+        keymap.keys_of(&CurrentScreen::LatestPatchsets, Action::Back, "/")
+    );
+    // Backspace types in the name of the target list
+    assert_eq!(
+        "Alt+🡄",
+        keymap.keys_of(&CurrentScreen::MailingListSelection, Action::Back, "/")
+    );
+    assert_eq!(
+        "?",
+        keymap.keys_of(&CurrentScreen::MailingListSelection, Action::Help, "/")
+    );
+    assert_eq!(
+        KeyMatch::None,
+        keymap.match_keys(&CurrentScreen::EditConfig, &[key("Ctrl+n")])
+    );
+}
+
+#[test]
+fn should_override_default_bindings() {
+    let (keymap, errors) = Keymap::new(&overrides("details", "go_to_first_line", &["g g", "a"]));
+    assert!(errors.is_empty());
+
+    let details = CurrentScreen::PatchsetDetails;
+    assert_eq!(
+        "g g/a",
+        keymap.keys_of(&details, Action::GoToFirstLine, "/")
+    );
+    assert_eq!(
+        "",
+        keymap.keys_of(&details, Action::ToggleApply, "/"),
+        "Keys bound by an override should be unbound from other actions"
+    );
+    assert_eq!(
+        KeyMatch::Action(Action::GoToFirstLine),
+        keymap.match_keys(&details, &[key("a")])
+    );
+    assert_eq!(
+        KeyMatch::Action(Action::Add),
+        keymap.match_keys(&CurrentScreen::KernelTrees, &[key("a")]),
+        "Overrides should only affect their screen"
+    );
+}
+
+#[test]
+fn should_report_invalid_overrides() {
+    let mut invalid = overrides("details", "invalid_action", &["x"]);
+    invalid.extend(overrides("latest", "next_page", &["Meta+x"]));
+    invalid.extend(overrides("invalid_screen", "exit", &["x"]));
+    invalid.extend(overrides("mail_list", "show_bookmarked", &["b"]));
+
+    let (keymap, errors) = Keymap::new(&invalid);
+    assert_eq!(4, errors.len());
+    assert_eq!(
+        "l / 🡆",
+        keymap.keys_of(&CurrentScreen::LatestPatchsets, Action::NextPage, " / "),
+        "Invalid overrides should be ignored"
+    );
+    assert_eq!(
+        "F1",
+        keymap.keys_of(
+            &CurrentScreen::MailingListSelection,
+            Action::ShowBookmarked,
+            "/"
+        ),
+        "Printable keys should keep typing the target list"
+    );
+
+    let (keymap, errors) = Keymap::new(&overrides("mail_list", "help", &["?", "F12"]));
+    assert!(errors.is_empty(), "Keys bound by default can be rebound");
+    assert_eq!(
+        "?/F12",
+        keymap.keys_of(&CurrentScreen::MailingListSelection, Action::Help, "/")
+    );
+}
+
+#[test]
+fn should_match_key_sequences() {
+    let (keymap, _) = Keymap::new(&overrides("details", "toggle_fullscreen", &["g f"]));
+    let details = CurrentScreen::PatchsetDetails;
+
+    assert_eq!(
+        KeyMatch::Pending(None),
+        keymap.match_keys(&details, &[key("g")])
+    );
+    assert_eq!(
+        KeyMatch::Action(Action::GoToFirstLine),
+        keymap.match_keys(&details, &[key("g"), key("g")])
+    );
+    assert_eq!(
+        KeyMatch::Action(Action::ToggleFullscreen),
+        keymap.match_keys(&details, &[key("g"), key("f")])
+    );
+    assert_eq!(
+        KeyMatch::None,
+        keymap.match_keys(&details, &[key("g"), key("x")])
+    );
+    assert_eq!(KeyMatch::None, keymap.match_keys(&details, &[key("f")]));
+
+    let (keymap, _) = Keymap::new(&overrides("details", "go_to_last_line", &["g"]));
+    assert_eq!(
+        KeyMatch::Pending(Some(Action::GoToLastLine)),
+        keymap.match_keys(&details, &[key("g")])
+    );
+}
+
+#[test]
+fn should_generate_keys_hint() {
+    let (keymap, _) = Keymap::new(&overrides("latest", "toggle_my_subsystems", &[]));
+
+    assert_eq!(
+        "(ESC / q) to return | (h / 🡄) previous page | (?) help",
+        keymap.keys_hint(
+            &CurrentScreen::LatestPatchsets,
+            &[
+                (Action::Exit, "to return"),
+                (Action::PreviousPage, "previous page"),
+                (Action::ToggleMySubsystems, "my subsystems"),
+                (Action::Help, "help"),
+            ]
+        )
+    );
+}
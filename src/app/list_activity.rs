@@ -0,0 +1,103 @@
+use std::{collections::HashMap, io};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use patch_hub::lore::lore_session;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Activity of a mailing list, counted from the latest patchsets fetched from
+/// it, as of the last time they were fetched
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ListActivity {
+    /// When the patchsets were last fetched, in RFC 3339
+    last_fetched: String,
+    /// Patchsets updated in the day before the last fetch
+    last_day: usize,
+    /// Patchsets updated in the week before the last fetch
+    last_week: usize,
+    /// Hours between the oldest patchset fetched and the last fetch. The
+    /// counts of periods longer than it are only lower bounds, as older
+    /// patchsets weren't fetched.
+    fetched_hours: i64,
+}
+
+impl ListActivity {
+    /// Counts the activity from `updates`, the RFC 3339 update times of the
+    /// patchsets fetched at `now`. Invalid times are ignored.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_count_list_activity]
+    pub fn new(updates: &[&str], now: DateTime<Utc>) -> Self {
+        let updates: Vec<DateTime<Utc>> = updates
+            .iter()
+            .filter_map(|updated| DateTime::parse_from_rfc3339(updated).ok())
+            .map(|updated| updated.with_timezone(&Utc))
+            .collect();
+        let count_since =
+            |period: TimeDelta| updates.iter().filter(|&&u| u >= now - period).count();
+
+        ListActivity {
+            last_fetched: now.to_rfc3339(),
+            last_day: count_since(TimeDelta::days(1)),
+            last_week: count_since(TimeDelta::weeks(1)),
+            fetched_hours: updates
+                .iter()
+                .min()
+                .map_or(0, |oldest| (now - *oldest).num_hours()),
+        }
+    }
+
+    /// Returns a summary like `3/24h 17+/7d, 2h ago` of the activity, as of
+    /// `now`. Counts that are lower bounds (see [ListActivity::fetched_hours])
+    /// have a `+`.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_summarize_list_activity]
+    pub fn summary(&self, now: DateTime<Utc>) -> String {
+        let count = |count: usize, hours: i64| {
+            if self.fetched_hours < hours {
+                format!("{count}+")
+            } else {
+                count.to_string()
+            }
+        };
+        let age = DateTime::parse_from_rfc3339(&self.last_fetched).map_or_else(
+            |_| "?".to_string(),
+            |last_fetched| format_age(now - last_fetched.with_timezone(&Utc)),
+        );
+
+        format!(
+            "{}/24h {}/7d, {age}",
+            count(self.last_day, 24),
+            count(self.last_week, 24 * 7)
+        )
+    }
+}
+
+/// Formats `age` like `just now`, `5m ago`, `3h ago` or `2d ago`
+fn format_age(age: TimeDelta) -> String {
+    if age.num_days() > 0 {
+        format!("{}d ago", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h ago", age.num_hours())
+    } else if age.num_minutes() > 0 {
+        format!("{}m ago", age.num_minutes())
+    } else {
+        "just now".to_string()
+    }
+}
+
+pub fn save_list_activity(
+    list_activity: &HashMap<String, ListActivity>,
+    filepath: &str,
+) -> io::Result<()> {
+    lore_session::save_json(list_activity, filepath)
+}
+
+pub fn load_list_activity(filepath: &str) -> io::Result<HashMap<String, ListActivity>> {
+    lore_session::load_json(filepath)
+}
@@ -0,0 +1,54 @@
+use super::*;
+
+fn now() -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("2024-06-10T12:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+#[test]
+fn should_count_list_activity() {
+    let activity = ListActivity::new(
+        &[
+            "2024-06-10T11:00:00Z",
+            "2024-06-09T13:00:00+00:00",
+            "2024-06-08T12:00:00Z",
+            "2024-06-02T12:00:00Z",
+            "not a date",
+        ],
+        now(),
+    );
+
+    assert_eq!(2, activity.last_day);
+    assert_eq!(3, activity.last_week);
+    assert_eq!(8 * 24, activity.fetched_hours);
+    assert_eq!(now().to_rfc3339(), activity.last_fetched);
+
+    let empty = ListActivity::new(&[], now());
+    assert_eq!(
+        (0, 0, 0),
+        (empty.last_day, empty.last_week, empty.fetched_hours)
+    );
+}
+
+#[test]
+fn should_summarize_list_activity() {
+    let activity = ListActivity::new(&["2024-06-10T11:00:00Z", "2024-06-05T12:00:00Z"], now());
+
+    assert_eq!("1/24h 2+/7d, just now", activity.summary(now()));
+    assert_eq!(
+        "1/24h 2+/7d, 3h ago",
+        activity.summary(now() + TimeDelta::hours(3))
+    );
+    assert_eq!(
+        "1/24h 2+/7d, 2d ago",
+        activity.summary(now() + TimeDelta::days(2))
+    );
+
+    let activity = ListActivity::new(&["2024-06-10T11:59:00Z"], now());
+    assert_eq!(
+        "1+/24h 1+/7d, 5m ago",
+        activity.summary(now() + TimeDelta::minutes(5)),
+        "Counts of periods older than the patchsets fetched should be lower bounds"
+    );
+}
@@ -0,0 +1,103 @@
+use std::{io, mem};
+
+use patch_hub::lore::{lore_session, patch::Patch};
+
+use super::screens::{details_actions::DetailsActions, latest::LatestPatchsets, CurrentScreen};
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum number of screens kept to go back to
+const MAX_HISTORY_LEN: usize = 50;
+/// Maximum number of patchsets kept in the jump list
+const MAX_JUMP_LIST_LEN: usize = 50;
+
+/// A screen of a tab and the state owned by it, as kept in the navigation
+/// history. Only the state of the screen itself is kept, like the latest
+/// patchsets (with their page, selection and filter) of
+/// [CurrentScreen::LatestPatchsets].
+pub struct Snapshot {
+    pub screen: CurrentScreen,
+    pub latest_patchsets: Option<LatestPatchsets>,
+    pub details_actions: Option<DetailsActions>,
+}
+
+/// History of the screens visited in a tab, to go back and forward through
+/// them like in a web browser
+#[derive(Default)]
+pub struct NavigationHistory {
+    back: Vec<Snapshot>,
+    forward: Vec<Snapshot>,
+}
+
+impl NavigationHistory {
+    /// Records `snapshot` as the screen to go back to, discarding the screens
+    /// to go forward to and the oldest screens beyond the maximum length.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_go_back_and_forward]
+    /// [tests::should_discard_forward_screens_when_navigating]
+    pub fn push(&mut self, snapshot: Snapshot) {
+        self.forward.clear();
+        self.back.push(snapshot);
+        if self.back.len() > MAX_HISTORY_LEN {
+            self.back.drain(..self.back.len() - MAX_HISTORY_LEN);
+        }
+    }
+
+    /// Goes back to the previous screen, swapping it with `current`, the
+    /// snapshot of the current screen. Returns if there was a screen to go
+    /// back to.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_go_back_and_forward]
+    pub fn go_back(&mut self, current: &mut Snapshot) -> bool {
+        match self.back.pop() {
+            Some(previous) => {
+                self.forward.push(mem::replace(current, previous));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Goes forward to the screen gone back from, swapping it with `current`,
+    /// the snapshot of the current screen. Returns if there was a screen to go
+    /// forward to.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_go_back_and_forward]
+    pub fn go_forward(&mut self, current: &mut Snapshot) -> bool {
+        match self.forward.pop() {
+            Some(next) => {
+                self.back.push(mem::replace(current, next));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Adds `patch` to the start of `jump_list`, the recently viewed patchsets
+/// (most recent first), removing its previous occurrence and the oldest
+/// patchsets beyond the maximum length.
+///
+/// # Tests
+///
+/// [tests::should_push_patchsets_to_jump_list]
+pub fn push_to_jump_list(jump_list: &mut Vec<Patch>, patch: &Patch) {
+    jump_list.retain(|p| p.message_id() != patch.message_id());
+    jump_list.insert(0, patch.clone());
+    jump_list.truncate(MAX_JUMP_LIST_LEN);
+}
+
+pub fn save_jump_list(jump_list: &[Patch], filepath: &str) -> io::Result<()> {
+    lore_session::save_json(jump_list, filepath)
+}
+
+pub fn load_jump_list(filepath: &str) -> io::Result<Vec<Patch>> {
+    lore_session::load_json(filepath)
+}
@@ -0,0 +1,108 @@
+use std::fs;
+
+use patch_hub::lore::patch::{Author, MessageID};
+
+use super::*;
+
+fn snapshot(screen: CurrentScreen) -> Snapshot {
+    Snapshot {
+        screen,
+        latest_patchsets: None,
+        details_actions: None,
+    }
+}
+
+fn patch(title: &str) -> Patch {
+    Patch::new(
+        title.to_string(),
+        Author {
+            name: "Foo Bar".to_string(),
+            email: "foo@bar.baz".to_string(),
+        },
+        MessageID {
+            href: format!("https://lore.kernel.org/foo/{title}"),
+        },
+        None,
+        "2024-01-01T00:00:00Z".to_string(),
+    )
+}
+
+#[test]
+fn should_go_back_and_forward() {
+    let mut history = NavigationHistory::default();
+    let mut current = snapshot(CurrentScreen::PatchsetDetails);
+    assert!(!history.go_back(&mut current));
+    assert!(!history.go_forward(&mut current));
+    assert_eq!(CurrentScreen::PatchsetDetails, current.screen);
+
+    history.push(snapshot(CurrentScreen::MailingListSelection));
+    history.push(snapshot(CurrentScreen::LatestPatchsets));
+
+    assert!(history.go_back(&mut current));
+    assert_eq!(CurrentScreen::LatestPatchsets, current.screen);
+    assert!(history.go_back(&mut current));
+    assert_eq!(CurrentScreen::MailingListSelection, current.screen);
+    assert!(!history.go_back(&mut current));
+
+    assert!(history.go_forward(&mut current));
+    assert_eq!(CurrentScreen::LatestPatchsets, current.screen);
+    assert!(history.go_forward(&mut current));
+    assert_eq!(CurrentScreen::PatchsetDetails, current.screen);
+    assert!(!history.go_forward(&mut current));
+
+    assert!(history.go_back(&mut current));
+    assert_eq!(CurrentScreen::LatestPatchsets, current.screen);
+}
+
+#[test]
+fn should_discard_forward_screens_when_navigating() {
+    let mut history = NavigationHistory::default();
+    let mut current = snapshot(CurrentScreen::LatestPatchsets);
+    history.push(snapshot(CurrentScreen::MailingListSelection));
+    assert!(history.go_back(&mut current));
+
+    history.push(snapshot(CurrentScreen::MailingListSelection));
+    current = snapshot(CurrentScreen::BookmarkedPatchsets);
+
+    assert!(!history.go_forward(&mut current));
+    assert!(history.go_back(&mut current));
+    assert_eq!(CurrentScreen::MailingListSelection, current.screen);
+
+    for _ in 0..MAX_HISTORY_LEN + 10 {
+        history.push(snapshot(CurrentScreen::LatestPatchsets));
+    }
+    assert_eq!(MAX_HISTORY_LEN, history.back.len());
+}
+
+#[test]
+fn should_push_patchsets_to_jump_list() {
+    let mut jump_list = Vec::new();
+
+    push_to_jump_list(&mut jump_list, &patch("foo"));
+    push_to_jump_list(&mut jump_list, &patch("bar"));
+    push_to_jump_list(&mut jump_list, &patch("foo"));
+    assert_eq!(vec![patch("foo"), patch("bar")], jump_list);
+
+    for i in 0..MAX_JUMP_LIST_LEN {
+        push_to_jump_list(&mut jump_list, &patch(&i.to_string()));
+    }
+    assert_eq!(MAX_JUMP_LIST_LEN, jump_list.len());
+    assert_eq!(
+        patch(&(MAX_JUMP_LIST_LEN - 1).to_string()),
+        jump_list[0],
+        "The most recent patchset should be first"
+    );
+}
+
+#[test]
+fn should_save_and_load_jump_list() {
+    let dir = std::env::temp_dir().join(format!("patch-hub-jump-list-{}", std::process::id()));
+    let path = dir.join("recent_patchsets.json");
+    let path = path.to_str().unwrap();
+    let jump_list = vec![patch("foo"), patch("bar")];
+
+    save_jump_list(&jump_list, path).unwrap();
+    assert_eq!(jump_list, load_jump_list(path).unwrap());
+
+    fs::remove_dir_all(dir).unwrap();
+}
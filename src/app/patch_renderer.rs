@@ -7,7 +7,7 @@ use std::{
 use color_eyre::eyre::eyre;
 use serde::{Deserialize, Serialize};
 
-use super::logging::Logger;
+use super::{logging::Logger, theme::ThemePreset};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
 pub enum PatchRenderer {
@@ -55,11 +55,17 @@ impl Display for PatchRenderer {
     }
 }
 
-pub fn render_patch_preview(raw: &str, renderer: &PatchRenderer) -> color_eyre::Result<String> {
+/// Renders the patch `raw` with `renderer`, using the colors that match the
+/// theme `preset` if the renderer supports it.
+pub fn render_patch_preview(
+    raw: &str,
+    renderer: &PatchRenderer,
+    preset: ThemePreset,
+) -> color_eyre::Result<String> {
     let text = match renderer {
         PatchRenderer::Default => Ok(raw.to_string()),
-        PatchRenderer::Bat => bat_patch_renderer(raw),
-        PatchRenderer::Delta => delta_patch_renderer(raw),
+        PatchRenderer::Bat => bat_patch_renderer(raw, preset),
+        PatchRenderer::Delta => delta_patch_renderer(raw, preset),
         PatchRenderer::DiffSoFancy => diff_so_fancy_renderer(raw),
     }?;
 
@@ -79,7 +85,8 @@ fn clean_patch_for_preview(patch: &str) -> String {
     }
 }
 
-/// Renders a patch using the `bat` command line tool.
+/// Renders a patch using the `bat` command line tool, with the syntax theme of
+/// `preset`.
 ///
 /// # Errors
 ///
@@ -88,12 +95,14 @@ fn clean_patch_for_preview(patch: &str) -> String {
 /// # Tests
 ///
 /// [tests::test_bat_patch_renderer]
-fn bat_patch_renderer(patch: &str) -> color_eyre::Result<String> {
+fn bat_patch_renderer(patch: &str, preset: ThemePreset) -> color_eyre::Result<String> {
     let cleaned_patch = clean_patch_for_preview(patch);
 
     let mut bat = Command::new("bat")
         .arg("-pp")
         .arg("-f")
+        .arg("--theme")
+        .arg(preset.syntax_theme())
         .arg("-l")
         .arg("patch")
         .stdin(Stdio::piped())
@@ -112,7 +121,8 @@ fn bat_patch_renderer(patch: &str) -> color_eyre::Result<String> {
     Ok(String::from_utf8(output.stdout)?)
 }
 
-/// Renders a patch using the `delta` command line tool.
+/// Renders a patch using the `delta` command line tool, with the syntax theme
+/// and the light or dark colors of `preset`.
 ///
 /// # Errors
 ///
@@ -121,13 +131,20 @@ fn bat_patch_renderer(patch: &str) -> color_eyre::Result<String> {
 /// # Tests
 ///
 /// [tests::test_delta_patch_renderer]
-fn delta_patch_renderer(patch: &str) -> color_eyre::Result<String> {
+fn delta_patch_renderer(patch: &str, preset: ThemePreset) -> color_eyre::Result<String> {
     let cleaned_patch = clean_patch_for_preview(patch);
 
     let mut delta = Command::new("delta")
         .arg("--pager")
         .arg("less")
         .arg("--no-gitconfig")
+        .arg(if preset == ThemePreset::Light {
+            "--light"
+        } else {
+            "--dark"
+        })
+        .arg("--syntax-theme")
+        .arg(preset.syntax_theme())
         .arg("--paging")
         .arg("never")
         .arg("-w")
@@ -200,7 +217,7 @@ index 83db48f..e3b0c44 100644
     #[ignore = "optional-dependency"]
     /// Tests [bat_patch_renderer]
     fn test_bat_patch_renderer() {
-        let result = bat_patch_renderer(PATCH_SAMPLE);
+        let result = bat_patch_renderer(PATCH_SAMPLE, ThemePreset::Dark);
         assert!(result.is_ok());
         let rendered_patch = result.unwrap();
         assert_eq!(
@@ -214,7 +231,7 @@ index 83db48f..e3b0c44 100644
     #[ignore = "optional-dependency"]
     /// Tests [delta_patch_renderer]
     fn test_delta_patch_renderer() {
-        let result = delta_patch_renderer(PATCH_SAMPLE);
+        let result = delta_patch_renderer(PATCH_SAMPLE, ThemePreset::Dark);
         assert!(result.is_ok());
         let rendered_patch = result.unwrap();
         assert_eq!(
@@ -0,0 +1,257 @@
+use std::{cmp::Reverse, fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use chrono::{DateTime, NaiveDate};
+use patch_hub::lore::patch::Patch;
+use regex::{Regex, RegexBuilder};
+
+#[cfg(test)]
+mod tests;
+
+/// Kind of a patchset, from its patch tag
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PatchsetKind {
+    Rfc,
+    Patch,
+}
+
+/// Filter of the listed patchsets, parsed from whitespace-separated terms:
+///
+/// - `a:<text>`: author name or email contains `<text>`
+/// - `v:<range>`: version in `<range>`, like `2`, `2-4`, `2-` or `-3`
+/// - `n:<range>`: number of patches in the series in `<range>`
+/// - `type:rfc` or `type:patch`: RFCs or not
+/// - `since:<YYYY-MM-DD>` and `until:<YYYY-MM-DD>`: last updated in the range
+/// - `re:<regex>`: title matches `<regex>`
+/// - any other term: title contains it
+///
+/// Text and regexes are case-insensitive.
+#[derive(Debug, Clone, Default)]
+pub struct PatchsetFilter {
+    title_terms: Vec<String>,
+    title_regex: Option<Regex>,
+    author: Option<String>,
+    version: Option<RangeInclusive<usize>>,
+    size: Option<RangeInclusive<usize>>,
+    kind: Option<PatchsetKind>,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+}
+
+impl FromStr for PatchsetFilter {
+    type Err = String;
+
+    /// # Tests
+    ///
+    /// [tests::should_parse_filter]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = PatchsetFilter::default();
+        for term in s.split_whitespace() {
+            let Some((key, value)) = term.split_once(':').filter(|(_, value)| !value.is_empty())
+            else {
+                filter.title_terms.push(term.to_lowercase());
+                continue;
+            };
+            match key {
+                "a" => filter.author = Some(value.to_lowercase()),
+                "v" => filter.version = Some(parse_range(value)?),
+                "n" => filter.size = Some(parse_range(value)?),
+                "type" => {
+                    filter.kind = match value.to_ascii_lowercase().as_str() {
+                        "rfc" => Some(PatchsetKind::Rfc),
+                        "patch" => Some(PatchsetKind::Patch),
+                        _ => return Err(format!("invalid type '{value}', expected rfc or patch")),
+                    }
+                }
+                "since" => filter.since = Some(parse_date(value)?),
+                "until" => filter.until = Some(parse_date(value)?),
+                "re" => {
+                    filter.title_regex = Some(
+                        RegexBuilder::new(value)
+                            .case_insensitive(true)
+                            .build()
+                            .map_err(|e| e.to_string())?,
+                    )
+                }
+                _ => filter.title_terms.push(term.to_lowercase()),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// Parses ranges like `2`, `2-4`, `2-` or `-3`
+fn parse_range(s: &str) -> Result<RangeInclusive<usize>, String> {
+    let parse = |n: &str, default: usize| {
+        if n.is_empty() {
+            Ok(default)
+        } else {
+            n.parse::<usize>()
+                .map_err(|_| format!("invalid range '{s}'"))
+        }
+    };
+    match s.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (parse(start, 0)?, parse(end, usize::MAX)?);
+            if start > end {
+                return Err(format!("invalid range '{s}', its start is after its end"));
+            }
+            Ok(start..=end)
+        }
+        None => {
+            let n = parse(s, 0)?;
+            Ok(n..=n)
+        }
+    }
+}
+
+fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{s}', expected YYYY-MM-DD"))
+}
+
+impl PatchsetFilter {
+    /// Returns if the filter has no terms, so every patchset passes it
+    pub fn is_empty(&self) -> bool {
+        self.title_terms.is_empty()
+            && self.title_regex.is_none()
+            && self.author.is_none()
+            && self.version.is_none()
+            && self.size.is_none()
+            && self.kind.is_none()
+            && self.since.is_none()
+            && self.until.is_none()
+    }
+
+    /// Returns if `patch`, the representative patch of a patchset, passes the
+    /// filter. Patchsets with unparsable dates don't pass date ranges.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_match_patchsets]
+    pub fn matches(&self, patch: &Patch) -> bool {
+        let title = patch.title().to_lowercase();
+        if !self.title_terms.iter().all(|term| title.contains(term)) {
+            return false;
+        }
+        if self
+            .title_regex
+            .as_ref()
+            .is_some_and(|regex| !regex.is_match(patch.title()))
+        {
+            return false;
+        }
+        if self.author.as_ref().is_some_and(|author| {
+            !patch.author().name.to_lowercase().contains(author)
+                && !patch.author().email.to_lowercase().contains(author)
+        }) {
+            return false;
+        }
+        if self
+            .version
+            .as_ref()
+            .is_some_and(|range| !range.contains(&patch.version()))
+            || self
+                .size
+                .as_ref()
+                .is_some_and(|range| !range.contains(&patch.total_in_series()))
+        {
+            return false;
+        }
+        match self.kind {
+            Some(PatchsetKind::Rfc) if !patch.is_rfc() => return false,
+            Some(PatchsetKind::Patch) if patch.is_rfc() => return false,
+            _ => {}
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let Ok(updated) = DateTime::parse_from_rfc3339(patch.updated()) else {
+                return false;
+            };
+            let updated = updated.date_naive();
+            if self.since.is_some_and(|since| updated < since)
+                || self.until.is_some_and(|until| updated > until)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Order of the listed patchsets
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum PatchsetSort {
+    /// Most recently updated first
+    #[default]
+    Newest,
+    /// Least recently updated first
+    Oldest,
+    /// Most patches in the series first
+    Largest,
+    /// Most code-review trailers first
+    MostReviewed,
+}
+
+impl PatchsetSort {
+    /// Returns the order after this one, wrapping around
+    pub fn next(self) -> PatchsetSort {
+        match self {
+            PatchsetSort::Newest => PatchsetSort::Oldest,
+            PatchsetSort::Oldest => PatchsetSort::Largest,
+            PatchsetSort::Largest => PatchsetSort::MostReviewed,
+            PatchsetSort::MostReviewed => PatchsetSort::Newest,
+        }
+    }
+
+    /// Sorts `patches`, each paired with its number of code-review trailers,
+    /// if known. Ties keep their relative order and patchsets with unparsable
+    /// dates come last when sorting by date.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_sort_patchsets]
+    pub fn sort<T>(&self, patches: &mut [(T, &Patch, Option<usize>)]) {
+        let updated = |patch: &Patch| DateTime::parse_from_rfc3339(patch.updated()).ok();
+        match self {
+            PatchsetSort::Newest => {
+                patches.sort_by_cached_key(|(_, patch, _)| Reverse(updated(patch)))
+            }
+            PatchsetSort::Oldest => patches.sort_by_cached_key(|(_, patch, _)| {
+                let updated = updated(patch);
+                (updated.is_none(), updated)
+            }),
+            PatchsetSort::Largest => {
+                patches.sort_by_key(|(_, patch, _)| Reverse(patch.total_in_series()))
+            }
+            PatchsetSort::MostReviewed => {
+                patches.sort_by_key(|(_, _, review_count)| Reverse(*review_count))
+            }
+        }
+    }
+}
+
+impl Display for PatchsetSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchsetSort::Newest => write!(f, "newest"),
+            PatchsetSort::Oldest => write!(f, "oldest"),
+            PatchsetSort::Largest => write!(f, "largest"),
+            PatchsetSort::MostReviewed => write!(f, "most-reviewed"),
+        }
+    }
+}
+
+impl FromStr for PatchsetSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "newest" => Ok(PatchsetSort::Newest),
+            "oldest" => Ok(PatchsetSort::Oldest),
+            "largest" => Ok(PatchsetSort::Largest),
+            "most-reviewed" | "reviewed" => Ok(PatchsetSort::MostReviewed),
+            _ => Err(format!(
+                "invalid sort '{s}', expected newest, oldest, largest or most-reviewed"
+            )),
+        }
+    }
+}
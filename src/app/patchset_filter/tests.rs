@@ -0,0 +1,114 @@
+use patch_hub::lore::patch::{Author, MessageID, PatchRegex};
+
+use super::*;
+
+fn patch(title: &str, author: &str, updated: &str) -> Patch {
+    let mut patch = Patch::new(
+        title.to_string(),
+        Author {
+            name: author.to_string(),
+            email: format!("{}@foo.bar", author.to_lowercase().replace(' ', ".")),
+        },
+        MessageID {
+            href: format!("https://lore.kernel.org/foo/{title}"),
+        },
+        None,
+        updated.to_string(),
+    );
+    patch.update_patch_metadata(&PatchRegex::new());
+    patch
+}
+
+#[test]
+fn should_parse_filter() {
+    assert!(PatchsetFilter::from_str("").unwrap().is_empty());
+    assert!(PatchsetFilter::from_str("  ").unwrap().is_empty());
+
+    let filter = PatchsetFilter::from_str("drm a:Foo v:2- n:-3 type:RFC since:2024-01-31").unwrap();
+    assert_eq!(vec!["drm".to_string()], filter.title_terms);
+    assert_eq!(Some("foo".to_string()), filter.author);
+    assert_eq!(Some(2..=usize::MAX), filter.version);
+    assert_eq!(Some(0..=3), filter.size);
+    assert_eq!(Some(PatchsetKind::Rfc), filter.kind);
+    assert_eq!(NaiveDate::from_ymd_opt(2024, 1, 31), filter.since);
+
+    let filter = PatchsetFilter::from_str("v:3 x:y a:").unwrap();
+    assert_eq!(Some(3..=3), filter.version);
+    assert_eq!(
+        vec!["x:y".to_string(), "a:".to_string()],
+        filter.title_terms,
+        "Unknown and empty terms should be matched against titles"
+    );
+
+    assert!(PatchsetFilter::from_str("v:two").is_err());
+    assert!(
+        PatchsetFilter::from_str("n:5-2").is_err(),
+        "Inverted ranges should be rejected"
+    );
+    assert!(PatchsetFilter::from_str("type:foo").is_err());
+    assert!(PatchsetFilter::from_str("since:31/01/2024").is_err());
+    assert!(PatchsetFilter::from_str("re:(foo").is_err());
+}
+
+#[test]
+fn should_match_patchsets() {
+    let rfc = patch(
+        "[RFC PATCH v2 0/3] drm/amd: Add foo",
+        "Jane Doe",
+        "2024-02-10T10:00:00Z",
+    );
+    let fix = patch("[PATCH] net: Fix bar", "John Smith", "2024-03-01T12:00:00Z");
+
+    let matches = |filter: &str| {
+        let filter = PatchsetFilter::from_str(filter).unwrap();
+        (filter.matches(&rfc), filter.matches(&fix))
+    };
+    assert_eq!((true, true), matches(""));
+    assert_eq!((true, false), matches("ADD drm"));
+    assert_eq!((false, true), matches("re:^net:.*bar$"));
+    assert_eq!((true, false), matches("a:jane"));
+    assert_eq!((false, true), matches("a:john.smith@"));
+    assert_eq!((true, false), matches("v:2"));
+    assert_eq!((false, true), matches("n:-1"));
+    assert_eq!((true, false), matches("type:rfc"));
+    assert_eq!((false, true), matches("type:patch"));
+    assert_eq!((false, true), matches("since:2024-03-01"));
+    assert_eq!((true, false), matches("until:2024-02-10"));
+    assert_eq!((false, false), matches("type:rfc a:john"));
+}
+
+#[test]
+fn should_sort_patchsets() {
+    let small = patch("[PATCH] small", "Foo", "2024-01-02T00:00:00Z");
+    let large = patch("[PATCH 1/9] large", "Foo", "2024-01-01T00:00:00Z");
+    let new = patch("[PATCH 1/2] new", "Foo", "2024-01-03T00:00:00Z");
+
+    let sorted = |sort: PatchsetSort| {
+        let mut patches = vec![(0, &small, Some(1)), (1, &large, None), (2, &new, Some(4))];
+        sort.sort(&mut patches);
+        patches.into_iter().map(|(i, _, _)| i).collect::<Vec<_>>()
+    };
+    assert_eq!(vec![2, 0, 1], sorted(PatchsetSort::Newest));
+    assert_eq!(vec![1, 0, 2], sorted(PatchsetSort::Oldest));
+    assert_eq!(vec![1, 2, 0], sorted(PatchsetSort::Largest));
+    assert_eq!(vec![2, 0, 1], sorted(PatchsetSort::MostReviewed));
+
+    // 2024-01-01T23:00:00Z, older than `late` despite comparing greater as text
+    let offset = patch("[PATCH] offset", "Foo", "2024-01-02T01:00:00+02:00");
+    let late = patch("[PATCH] late", "Foo", "2024-01-01T23:30:00Z");
+    let unknown = patch("[PATCH] unknown", "Foo", "yesterday");
+    let sorted = |sort: PatchsetSort| {
+        let mut patches = vec![(0, &unknown, None), (1, &offset, None), (2, &late, None)];
+        sort.sort(&mut patches);
+        patches.into_iter().map(|(i, _, _)| i).collect::<Vec<_>>()
+    };
+    assert_eq!(vec![2, 1, 0], sorted(PatchsetSort::Newest));
+    assert_eq!(vec![1, 2, 0], sorted(PatchsetSort::Oldest));
+
+    assert_eq!(PatchsetSort::Newest, PatchsetSort::MostReviewed.next());
+    assert_eq!(
+        Ok(PatchsetSort::MostReviewed),
+        PatchsetSort::from_str("most-reviewed")
+    );
+    assert!(PatchsetSort::from_str("random").is_err());
+}
@@ -0,0 +1,322 @@
+use ratatui::{
+    style::Style,
+    text::{Line, Span, Text},
+};
+use regex::{Regex, RegexBuilder};
+use std::ops::Range;
+
+use super::theme::Theme;
+
+#[cfg(test)]
+mod tests;
+
+/// Direction in which a search looks for matches, like vim's `/` and `?`
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SearchDirection {
+    Forward,
+    Backward,
+}
+
+/// A match of a search in the patches preview. `start` and `end` are byte
+/// offsets in the text of the line.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct PreviewMatch {
+    pub patch_index: usize,
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// State of the prompt in which the pattern of a search is typed
+#[derive(Debug, Clone)]
+pub struct SearchPrompt {
+    pub input: String,
+    pub direction: SearchDirection,
+    pub across_series: bool,
+    /// Error of the pattern typed so far, if it's an invalid regex
+    pub error: Option<String>,
+    /// Preview index, scroll offset and pan when the prompt was opened, to
+    /// search from and to restore if the search is cancelled
+    pub origin: (usize, usize, usize),
+    /// Search active when the prompt was opened, to restore if the search is
+    /// cancelled
+    pub previous_search: Option<PreviewSearch>,
+}
+
+/// A regex search in the patches preview
+#[derive(Debug, Clone)]
+pub struct PreviewSearch {
+    pub pattern: String,
+    pub direction: SearchDirection,
+    /// If true, `n` and `N` jump between the matches of every patch of the
+    /// series instead of only the previewed one
+    pub across_series: bool,
+    /// Every match in every patch, in order
+    pub matches: Vec<PreviewMatch>,
+    /// Index in `matches` of the match jumped to, if any
+    pub current: Option<usize>,
+}
+
+impl PreviewSearch {
+    /// Searches `pattern` in `previews`. The search is case-insensitive
+    /// unless `pattern` has uppercase characters, like vim's `smartcase`.
+    ///
+    /// # Errors
+    ///
+    /// If `pattern` is empty or an invalid regex.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_find_matches]
+    pub fn new(
+        pattern: &str,
+        direction: SearchDirection,
+        across_series: bool,
+        previews: &[Text],
+    ) -> Result<Self, String> {
+        if pattern.is_empty() {
+            return Err("Empty pattern".to_string());
+        }
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(!pattern.chars().any(char::is_uppercase))
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        Ok(PreviewSearch {
+            pattern: pattern.to_string(),
+            direction,
+            across_series,
+            matches: find_matches(&regex, previews),
+            current: None,
+        })
+    }
+
+    /// Returns the index in [Self::matches] of the match after (or before,
+    /// if `forward` is false) the current one, wrapping around. Without a
+    /// current match, it's the first match from line `line` of patch
+    /// `patch_index` in that direction. Unless searching across the series,
+    /// only matches in patch `patch_index` are considered.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_find_next_match]
+    pub fn next_match(&self, patch_index: usize, line: usize, forward: bool) -> Option<usize> {
+        let in_scope: Vec<usize> = (0..self.matches.len())
+            .filter(|&i| self.across_series || self.matches[i].patch_index == patch_index)
+            .collect();
+        let current = self
+            .current
+            .map(|i| self.matches[i])
+            .filter(|m| self.across_series || m.patch_index == patch_index);
+        let key = |i: &usize| {
+            let m = &self.matches[*i];
+            (m.patch_index, m.line, m.start)
+        };
+
+        // Without a current match, search from the start of the line, so
+        // that forward searches include its matches and backward ones don't
+        let (position, include_position) = match current {
+            Some(m) => ((m.patch_index, m.line, m.start), false),
+            None => ((patch_index, line, 0), forward),
+        };
+        if forward {
+            in_scope
+                .iter()
+                .find(|i| key(i) > position || (include_position && key(i) == position))
+                .or_else(|| in_scope.first())
+                .copied()
+        } else {
+            in_scope
+                .iter()
+                .rev()
+                .find(|i| key(i) < position)
+                .or_else(|| in_scope.last())
+                .copied()
+        }
+    }
+
+    /// Returns the position of the current match among the matches in
+    /// scope and how many they are, like `(3, 12)`
+    pub fn current_position(&self, patch_index: usize) -> (usize, usize) {
+        let in_scope = self
+            .matches
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| self.across_series || m.patch_index == patch_index);
+        let mut count = 0;
+        let mut position = 0;
+        for (i, _) in in_scope {
+            count += 1;
+            if Some(i) == self.current {
+                position = count;
+            }
+        }
+        (position, count)
+    }
+}
+
+/// Returns the text of `line` without styles
+pub fn line_text(line: &Line) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// How many lines after the last mapped one are looked at for a raw line in
+/// [preview_line_of_raw_line]
+const RAW_LINE_LOOKAHEAD: usize = 32;
+
+/// Returns the index of the line of `preview` that shows `line` (starting at
+/// 1) of `raw_patch`, the patch it is the preview of.
+///
+/// The preview may be rendered by external programs that decorate the patch
+/// (e.g. `delta` drops the `+`/`-` markers and adds file and hunk headers), so
+/// the raw lines up to `line` are matched in order against the preview lines
+/// that contain them. Lines that can't be matched, like blank ones, are placed
+/// relative to the last matched line.
+///
+/// # Tests
+///
+/// [tests::should_map_raw_lines_to_preview_lines]
+pub fn preview_line_of_raw_line(raw_patch: &str, preview: &Text, line: usize) -> usize {
+    let preview_lines: Vec<String> = preview.lines.iter().map(line_text).collect();
+    let mut in_diff = false;
+    // Last matched pair of raw line and preview line
+    let mut last_match: Option<(usize, usize)> = None;
+
+    for (raw_index, raw_line) in raw_patch.lines().take(line).enumerate() {
+        let raw_line = raw_line.replace('\t', "        ");
+        let mut content = raw_line.as_str();
+        if in_diff && !content.starts_with("+++") && !content.starts_with("---") {
+            content = content.strip_prefix(['+', '-', ' ']).unwrap_or(content);
+        }
+        in_diff |= raw_line == "---";
+
+        let content = content.trim();
+        if content.is_empty() {
+            continue;
+        }
+
+        let search_start = last_match.map_or(0, |(_, preview_index)| preview_index + 1);
+        if let Some(offset) = preview_lines
+            .iter()
+            .skip(search_start)
+            .take(RAW_LINE_LOOKAHEAD)
+            .position(|preview_line| preview_line.contains(content))
+        {
+            last_match = Some((raw_index, search_start + offset));
+        }
+    }
+
+    let target = line.saturating_sub(1);
+    let preview_index = match last_match {
+        Some((raw_index, preview_index)) => preview_index + (target - raw_index),
+        None => target,
+    };
+    preview_index.min(preview_lines.len().saturating_sub(1))
+}
+
+/// Returns the matches of `regex` in each line of each of `previews`, in
+/// order.
+pub fn find_matches(regex: &Regex, previews: &[Text]) -> Vec<PreviewMatch> {
+    let mut matches = Vec::new();
+    for (patch_index, preview) in previews.iter().enumerate() {
+        for (line, preview_line) in preview.lines.iter().enumerate() {
+            let text = line_text(preview_line);
+            matches.extend(regex.find_iter(&text).filter(|m| !m.is_empty()).map(|m| {
+                PreviewMatch {
+                    patch_index,
+                    line,
+                    start: m.start(),
+                    end: m.end(),
+                }
+            }));
+        }
+    }
+    matches
+}
+
+/// Returns the `visible` lines of `text`, the preview of patch `patch_index`,
+/// with the `matches` in them highlighted with the styles of `theme`, `current`
+/// (the match jumped to) in a different one. Only the visible lines are copied,
+/// as the preview is drawn on every frame.
+///
+/// # Tests
+///
+/// [tests::should_highlight_matches]
+pub fn highlight_matches(
+    text: &Text<'static>,
+    patch_index: usize,
+    visible: Range<usize>,
+    matches: &[PreviewMatch],
+    current: Option<&PreviewMatch>,
+    theme: &Theme,
+) -> Text<'static> {
+    let visible = visible.start.min(text.lines.len())..visible.end.min(text.lines.len());
+    let mut highlighted = Text {
+        lines: text.lines[visible.clone()].to_vec(),
+        style: text.style,
+        alignment: text.alignment,
+    };
+    let mut matches = matches
+        .iter()
+        .filter(|m| m.patch_index == patch_index && visible.contains(&m.line))
+        .peekable();
+    for (line_index, line) in (visible.start..).zip(highlighted.lines.iter_mut()) {
+        let mut ranges = Vec::new();
+        while let Some(m) = matches.next_if(|m| m.line == line_index) {
+            let style = if Some(m) == current {
+                theme.search_current
+            } else {
+                theme.search_match
+            };
+            ranges.push((m.start, m.end, style));
+        }
+        if !ranges.is_empty() {
+            line.spans = highlight_ranges(&line.spans, &ranges);
+        }
+    }
+    highlighted
+}
+
+/// Splits `spans` at the boundaries of `ranges` (byte offsets in the text of
+/// the spans, sorted and not overlapping), patching the style of the parts in
+/// each range with its style.
+fn highlight_ranges(
+    spans: &[Span<'static>],
+    ranges: &[(usize, usize, Style)],
+) -> Vec<Span<'static>> {
+    let mut highlighted = Vec::new();
+    let mut span_start = 0;
+    for span in spans {
+        let content = span.content.as_ref();
+        let span_end = span_start + content.len();
+        let mut cursor = span_start;
+        for &(start, end, style) in ranges {
+            let (start, end) = (start.max(cursor), end.min(span_end));
+            if start >= end {
+                continue;
+            }
+            if cursor < start {
+                highlighted.push(Span::styled(
+                    content[cursor - span_start..start - span_start].to_string(),
+                    span.style,
+                ));
+            }
+            highlighted.push(Span::styled(
+                content[start - span_start..end - span_start].to_string(),
+                span.style.patch(style),
+            ));
+            cursor = end;
+        }
+        if cursor < span_end {
+            highlighted.push(Span::styled(
+                content[cursor - span_start..].to_string(),
+                span.style,
+            ));
+        }
+        span_start = span_end;
+    }
+    highlighted
+}
@@ -0,0 +1,206 @@
+use ratatui::style::Color;
+
+use super::*;
+
+fn previews() -> Vec<Text<'static>> {
+    vec![
+        Text::from(vec![
+            Line::from("Subject: [PATCH 1/2] foo: add bar"),
+            Line::from(vec![Span::raw("+int "), Span::raw("bar(void);")]),
+            Line::from("+int Bar;"),
+        ]),
+        Text::from(vec![Line::from("Subject: [PATCH 2/2] foo: use bar")]),
+    ]
+}
+
+#[test]
+fn should_find_matches() {
+    let previews = previews();
+
+    let search = PreviewSearch::new("bar", SearchDirection::Forward, false, &previews).unwrap();
+    assert_eq!(
+        4,
+        search.matches.len(),
+        "Lowercase patterns should ignore case"
+    );
+    assert_eq!(
+        PreviewMatch {
+            patch_index: 0,
+            line: 1,
+            start: 5,
+            end: 8
+        },
+        search.matches[1],
+        "Matches should span the text of all spans of a line"
+    );
+
+    let search = PreviewSearch::new("Bar", SearchDirection::Forward, false, &previews).unwrap();
+    assert_eq!(
+        vec![PreviewMatch {
+            patch_index: 0,
+            line: 2,
+            start: 5,
+            end: 8
+        }],
+        search.matches,
+        "Patterns with uppercase characters should be case-sensitive"
+    );
+
+    let search = PreviewSearch::new(
+        r"\[PATCH \d/2\]",
+        SearchDirection::Forward,
+        false,
+        &previews,
+    )
+    .unwrap();
+    assert_eq!(2, search.matches.len());
+
+    assert!(PreviewSearch::new("", SearchDirection::Forward, false, &previews).is_err());
+    assert!(PreviewSearch::new("(bar", SearchDirection::Forward, false, &previews).is_err());
+}
+
+#[test]
+fn should_find_next_match() {
+    let previews = previews();
+    let mut search = PreviewSearch::new("bar", SearchDirection::Forward, false, &previews).unwrap();
+
+    assert_eq!(Some(1), search.next_match(0, 1, true));
+    assert_eq!(
+        Some(0),
+        search.next_match(0, 1, false),
+        "Searching backwards should skip matches in the starting line"
+    );
+    assert_eq!(
+        Some(3),
+        search.next_match(1, 0, true),
+        "Without a current match, matches in the starting line should be found"
+    );
+
+    search.current = Some(2);
+    assert_eq!(
+        Some(0),
+        search.next_match(0, 0, true),
+        "Searching in a single patch should wrap around to its first match"
+    );
+    assert_eq!(Some(1), search.next_match(0, 0, false));
+    assert_eq!(
+        Some(3),
+        search.next_match(1, 0, true),
+        "A current match in another patch should be ignored"
+    );
+    assert_eq!((3, 3), search.current_position(0));
+
+    search.across_series = true;
+    assert_eq!(Some(3), search.next_match(0, 0, true));
+    search.current = Some(3);
+    assert_eq!(
+        Some(0),
+        search.next_match(1, 0, true),
+        "Searching across the series should wrap around to the first patch"
+    );
+    search.current = Some(0);
+    assert_eq!(Some(3), search.next_match(0, 0, false));
+    assert_eq!((1, 4), search.current_position(0));
+}
+
+#[test]
+fn should_highlight_matches() {
+    let previews = previews();
+    let search = PreviewSearch::new("t bar", SearchDirection::Forward, false, &previews).unwrap();
+
+    let highlighted = highlight_matches(
+        &previews[0],
+        0,
+        0..usize::MAX,
+        &search.matches,
+        search.matches.first(),
+        &Theme::default(),
+    );
+    assert_eq!(previews[0].lines[0], highlighted.lines[0]);
+    assert_eq!(
+        vec!["+in", "t ", "bar", "(void);"],
+        highlighted.lines[1]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect::<Vec<_>>(),
+        "Matches spanning many spans should be split between them"
+    );
+    assert_eq!(
+        Some(Color::LightRed),
+        highlighted.lines[1].spans[1].style.bg
+    );
+    assert_eq!(
+        Some(Color::LightRed),
+        highlighted.lines[1].spans[2].style.bg
+    );
+    assert_eq!(None, highlighted.lines[1].spans[3].style.bg);
+    assert_eq!(Some(Color::Yellow), highlighted.lines[2].spans[1].style.bg);
+    assert_eq!(
+        line_text(&previews[0].lines[2]),
+        line_text(&highlighted.lines[2])
+    );
+
+    let visible = highlight_matches(
+        &previews[0],
+        0,
+        2..usize::MAX,
+        &search.matches,
+        search.matches.first(),
+        &Theme::default(),
+    );
+    assert_eq!(
+        highlighted.lines[2..],
+        visible.lines,
+        "Only the visible lines should be highlighted"
+    );
+}
+
+#[test]
+fn should_map_raw_lines_to_preview_lines() {
+    let raw_patch = "Subject: [PATCH] foo: add bar\n\nDescription\n---\n\
+                     diff --git a/foo.c b/foo.c\n--- a/foo.c\n+++ b/foo.c\n\
+                     @@ -1,3 +1,4 @@\n }\n\n+int\tbar;\n }\n";
+    let raw_preview = Text::from(raw_patch);
+    assert_eq!(10, preview_line_of_raw_line(raw_patch, &raw_preview, 11));
+    assert_eq!(9, preview_line_of_raw_line(raw_patch, &raw_preview, 10));
+
+    // Like the output of `delta`, with no diff markers and decorations
+    let rendered_preview = Text::from(vec![
+        Line::from("Subject: [PATCH] foo: add bar"),
+        Line::from(""),
+        Line::from("Description"),
+        Line::from("---"),
+        Line::from(""),
+        Line::from("foo.c"),
+        Line::from("───────────"),
+        Line::from(""),
+        Line::from("──────┐"),
+        Line::from("1:    │"),
+        Line::from("──────┘"),
+        Line::from("}"),
+        Line::from(""),
+        Line::from(vec![Span::raw("int"), Span::raw("        bar;")]),
+        Line::from("}"),
+    ]);
+    assert_eq!(
+        13,
+        preview_line_of_raw_line(raw_patch, &rendered_preview, 11),
+        "Added lines should be found without their marker"
+    );
+    assert_eq!(
+        12,
+        preview_line_of_raw_line(raw_patch, &rendered_preview, 10),
+        "Blank lines should be placed after the last matched line"
+    );
+    assert_eq!(
+        14,
+        preview_line_of_raw_line(raw_patch, &rendered_preview, 12),
+        "Repeated lines should be matched in order"
+    );
+    assert_eq!(2, preview_line_of_raw_line(raw_patch, &rendered_preview, 3));
+    assert_eq!(
+        14,
+        preview_line_of_raw_line(raw_patch, &rendered_preview, 99)
+    );
+}
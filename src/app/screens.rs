@@ -1,14 +1,22 @@
+pub mod apply_history;
 pub mod bookmarked;
 pub mod details_actions;
 pub mod edit_config;
+pub mod kernel_trees;
 pub mod latest;
 pub mod mail_list;
+pub mod recent;
+pub mod worktrees;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CurrentScreen {
     MailingListSelection,
     BookmarkedPatchsets,
+    RecentPatchsets,
     LatestPatchsets,
     PatchsetDetails,
     EditConfig,
+    Worktrees,
+    KernelTrees,
+    ApplyHistory,
 }
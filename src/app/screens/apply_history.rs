@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use crate::app::apply_history::{self, ApplyRecord};
+
+/// A record of the apply history and if its branch still exists
+pub struct ApplyHistoryEntry {
+    pub record: ApplyRecord,
+    pub branch_exists: bool,
+}
+
+pub struct ApplyHistory {
+    /// Records of applied patchsets, newest first
+    pub entries: Vec<ApplyHistoryEntry>,
+    pub highlighted: usize,
+    /// Indexes of the entries whose branches are marked to be deleted
+    pub marked: HashSet<usize>,
+}
+
+impl ApplyHistory {
+    pub fn new(apply_records: &[ApplyRecord]) -> Self {
+        let entries = apply_records
+            .iter()
+            .rev()
+            .map(|record| ApplyHistoryEntry {
+                record: record.clone(),
+                branch_exists: apply_history::branch_exists(record),
+            })
+            .collect();
+
+        ApplyHistory {
+            entries,
+            highlighted: 0,
+            marked: HashSet::new(),
+        }
+    }
+
+    pub fn highlight_below_entry(&mut self) {
+        if self.highlighted + 1 < self.entries.len() {
+            self.highlighted += 1;
+        }
+    }
+
+    pub fn highlight_above_entry(&mut self) {
+        self.highlighted = self.highlighted.saturating_sub(1);
+    }
+
+    pub fn get_highlighted_entry(&self) -> Option<&ApplyHistoryEntry> {
+        self.entries.get(self.highlighted)
+    }
+
+    /// Marks or unmarks the branch of the highlighted entry to be deleted,
+    /// unless it was already deleted.
+    pub fn toggle_highlighted_mark(&mut self) {
+        let Some(entry) = self.get_highlighted_entry() else {
+            return;
+        };
+        if !entry.branch_exists || self.marked.remove(&self.highlighted) {
+            return;
+        }
+        self.marked.insert(self.highlighted);
+    }
+
+    /// Marks the branches of every entry to be deleted, or unmarks them if
+    /// they are all marked already.
+    pub fn toggle_all_marks(&mut self) {
+        let existing: HashSet<usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.branch_exists)
+            .map(|(i, _)| i)
+            .collect();
+
+        if self.marked == existing {
+            self.marked.clear();
+        } else {
+            self.marked = existing;
+        }
+    }
+
+    /// Returns the indexes of the entries whose branches are marked to be
+    /// deleted, or of the highlighted one if none is marked, in order.
+    pub fn branches_to_delete(&self) -> Vec<usize> {
+        let mut to_delete: Vec<usize> = self.marked.iter().copied().collect();
+        if to_delete.is_empty() {
+            to_delete.extend(
+                self.get_highlighted_entry()
+                    .filter(|entry| entry.branch_exists)
+                    .map(|_| self.highlighted),
+            );
+        }
+        to_delete.sort();
+        to_delete
+    }
+
+    /// Deletes the branches (and worktrees) of [ApplyHistory::branches_to_delete]
+    /// (see [apply_history::delete_branch]) and removes their entries. The
+    /// entries whose branches couldn't be deleted are left marked.
+    ///
+    /// Returns the records of the deleted branches and the error messages of
+    /// the ones that couldn't be deleted.
+    pub fn delete_branches(&mut self, force: bool) -> (Vec<ApplyRecord>, Vec<String>) {
+        let to_delete = self.branches_to_delete();
+        self.marked.clear();
+
+        let mut deleted = HashSet::new();
+        let mut errors = Vec::new();
+        for index in to_delete {
+            match apply_history::delete_branch(&self.entries[index].record, force) {
+                Ok(_) => {
+                    deleted.insert(index);
+                }
+                Err(e) => {
+                    self.marked.insert(index);
+                    errors.push(e);
+                }
+            }
+        }
+
+        let mut deleted_records = Vec::new();
+        let mut kept = Vec::new();
+        for (index, entry) in self.entries.drain(..).enumerate() {
+            if deleted.contains(&index) {
+                deleted_records.push(entry.record);
+            } else {
+                kept.push(entry);
+            }
+        }
+        self.entries = kept;
+
+        // Shift the indexes of the entries that are left
+        let shift = |index: usize| index - deleted.iter().filter(|&&d| d < index).count();
+        self.marked = self.marked.iter().map(|&index| shift(index)).collect();
+        self.highlighted = shift(self.highlighted).min(self.entries.len().saturating_sub(1));
+
+        (deleted_records, errors)
+    }
+}
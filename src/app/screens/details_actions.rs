@@ -1,14 +1,33 @@
-use crate::app::config::{Config, KernelTree};
+use crate::app::{
+    apply_history::{ApplyRecord, ApplyResult},
+    base_tree::{self, BaseTreeChoice},
+    build_test::{self, BuildStatus},
+    config::{Config, KernelTree},
+    export::{self, ExportFormat},
+    git_am::{self, GitAmConflict},
+    preview_search::{self, PreviewSearch, SearchDirection, SearchPrompt},
+    upstream::{self, UpstreamStatus},
+    worktree,
+};
 
-use super::CurrentScreen;
 use ::patch_hub::lore::{lore_api_client::BlockingLoreAPIClient, lore_session, patch::Patch};
 use color_eyre::eyre::{bail, eyre};
-use patch_hub::lore::patch::Author;
-use ratatui::text::Text;
+use patch_hub::lore::{
+    attestation::PatchAttestation,
+    checkpatch::{self, CheckpatchReport},
+    fixes::FixesCheck,
+    maintainers::TouchedSection,
+    trailers::Trailers,
+};
+use ratatui::{layout::Size, text::Text};
 use std::{
     collections::{HashMap, HashSet},
-    path::Path,
-    process::Command,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    str::FromStr,
+    sync::Mutex,
 };
 
 pub struct DetailsActions {
@@ -21,6 +40,8 @@ pub struct DetailsActions {
     pub has_cover_letter: bool,
     /// Which patches to reply
     pub patches_to_reply: Vec<bool>,
+    /// Trailer with which patches are replied
+    pub reply_trailer: ReplyTrailer,
     /// Path to applicable .mbx of patchset
     #[allow(dead_code)]
     pub patchset_path: String,
@@ -30,24 +51,99 @@ pub struct DetailsActions {
     pub preview_pan: usize,
     /// If true, display the preview in full screen
     pub preview_fullscreen: bool,
+    /// Size of the area in which the preview was last displayed
+    pub preview_viewport: Size,
+    /// Search whose matches are highlighted in the preview, if any
+    pub preview_search: Option<PreviewSearch>,
+    /// Prompt in which the pattern of a search is being typed, if any
+    pub search_prompt: Option<SearchPrompt>,
     pub patchset_actions: HashMap<PatchsetAction, bool>,
-    /// For each patch, a set of `Authors` that appear in `Reviewed-by` trailers
-    pub reviewed_by: Vec<HashSet<Author>>,
-    /// For each patch, a set of `Authors` that appear in `Tested-by` trailers
-    pub tested_by: Vec<HashSet<Author>>,
-    /// For each patch, a set of `Authors` that appear in `Acked-by` trailers
-    pub acked_by: Vec<HashSet<Author>>,
-    pub last_screen: CurrentScreen,
+    /// If true, the apply action applies the patchset one patch at a time,
+    /// running the bisect check command from `Config` after each patch
+    pub apply_per_patch: bool,
+    /// Kernel tree and branch to apply the patchset, either detected (see
+    /// [crate::app::base_tree::detect_base_tree]) or chosen by the user, if
+    /// not the target kernel tree and its branch
+    pub apply_base: Option<BaseTreeChoice>,
+    /// Kernel tree in which the patchset was last applied, if any
+    pub applied_kernel_tree: Option<KernelTree>,
+    /// Branch in which the patchset was last applied, if any
+    pub applied_branch: Option<String>,
+    /// Worktree in which the patchset was last applied, if it was applied in
+    /// a worktree
+    pub applied_worktree: Option<String>,
+    /// Format in which the export action exports the patchset
+    pub export_format: ExportFormat,
+    /// `git am` of the patchset stopped by conflicts that wasn't handed over
+    /// to [crate::app::App::git_am_conflict] yet
+    pub git_am_conflict: Option<GitAmConflict>,
+    /// Outcome of fetching the upstream of the branch to apply on, if it was
+    /// fetched (see [Self::fetch_apply_upstream])
+    pub apply_upstream: Option<Result<UpstreamStatus, String>>,
+    /// Record of the last apply of the patchset that wasn't added to the
+    /// apply history yet
+    pub last_apply_record: Option<ApplyRecord>,
+    /// For each patch, the trailers collected from the whole thread
+    pub trailers: Vec<Trailers>,
+    /// For each patch, its `Fixes:` trailers, which are resolved against the
+    /// target kernel tree once the patch is previewed
+    pub fixes: Vec<Vec<FixesCheck>>,
+    /// `MAINTAINERS` sections touched by the patchset, if the target kernel
+    /// tree is valid
+    pub maintainers: Option<Vec<TouchedSection>>,
+    /// For each patch, the issues reported by `checkpatch.pl`, if it was run
+    pub checkpatch_reports: Option<Vec<CheckpatchReport>>,
+    /// For each patch, the result of checking its DKIM and patatt signatures
+    pub attestations: Vec<PatchAttestation>,
     pub lore_api_client: BlockingLoreAPIClient,
 }
 
-const LAST_LINE_PADDING: usize = 10;
+/// Lines above a search match jumped to kept visible in the preview
+const SEARCH_CONTEXT_LINES: usize = 3;
+/// Columns left of a search match jumped to kept visible in the preview
+const SEARCH_CONTEXT_COLUMNS: usize = 10;
 
 #[derive(Hash, Eq, PartialEq)]
 pub enum PatchsetAction {
     Bookmark,
     ReplyWithReviewedBy,
     Apply,
+    Checkpatch,
+    BuildTest,
+    Export,
+}
+
+/// Trailer with which the reply action replies to patches
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ReplyTrailer {
+    #[default]
+    Reviewed,
+    Acked,
+    Tested,
+}
+
+impl Display for ReplyTrailer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplyTrailer::Reviewed => write!(f, "Reviewed-by"),
+            ReplyTrailer::Acked => write!(f, "Acked-by"),
+            ReplyTrailer::Tested => write!(f, "Tested-by"),
+        }
+    }
+}
+
+impl FromStr for ReplyTrailer {
+    type Err = String;
+
+    /// Parses `reviewed`, `acked` or `tested`, optionally followed by `-by`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().trim_end_matches("-by") {
+            "reviewed" => Ok(ReplyTrailer::Reviewed),
+            "acked" => Ok(ReplyTrailer::Acked),
+            "tested" => Ok(ReplyTrailer::Tested),
+            _ => Err(format!("invalid reply trailer '{s}'")),
+        }
+    }
 }
 
 impl DetailsActions {
@@ -85,7 +181,8 @@ impl DetailsActions {
     pub fn go_to_last_line(&mut self) {
         // TODO: Support for renderers (only considers base preview string)
         let number_of_lines = self.patches_preview[self.preview_index].height();
-        self.preview_scroll_offset = number_of_lines - LAST_LINE_PADDING;
+        self.preview_scroll_offset =
+            number_of_lines.saturating_sub(self.preview_viewport.height as usize);
     }
 
     /// Scroll to first line
@@ -147,6 +244,233 @@ impl DetailsActions {
         self.toggle_action(PatchsetAction::Apply);
     }
 
+    pub fn toggle_apply_per_patch(&mut self) {
+        self.apply_per_patch = !self.apply_per_patch;
+    }
+
+    pub fn toggle_build_test_action(&mut self) {
+        self.toggle_action(PatchsetAction::BuildTest);
+    }
+
+    pub fn toggle_checkpatch_action(&mut self) {
+        self.toggle_action(PatchsetAction::Checkpatch);
+    }
+
+    pub fn toggle_export_action(&mut self) {
+        self.toggle_action(PatchsetAction::Export);
+    }
+
+    pub fn cycle_export_format(&mut self) {
+        self.export_format = self.export_format.next();
+    }
+
+    /// Marks the export action to export the patchset in `format`
+    pub fn mark_export_action(&mut self, format: ExportFormat) {
+        self.export_format = format;
+        self.patchset_actions.insert(PatchsetAction::Export, true);
+    }
+
+    /// Exports the patchset in [Self::export_format] under
+    /// `Config::exports_dir`, named after its .mbx (see
+    /// [export::export_patchset]).
+    ///
+    /// Returns a `Result<String, String>` containing either the success or the error message.
+    pub fn export_patchset(&self, config: &Config) -> Result<String, String> {
+        let patchset_name = Path::new(&self.patchset_path).file_stem().map_or_else(
+            || target_branch_name(config),
+            |stem| stem.to_string_lossy().to_string(),
+        );
+        let exports_dir = Path::new(config.exports_dir());
+        let output_path = match self.export_format {
+            ExportFormat::Mbox => exports_dir.join(format!("{patchset_name}.mbx")),
+            format => exports_dir.join(format!("{patchset_name}-{format}")),
+        };
+
+        let output_path = export::export_patchset(
+            &self.raw_patches,
+            self.has_cover_letter,
+            &self.trailers,
+            self.export_format,
+            &output_path,
+        )?;
+        Ok(format!(
+            "Patchset '{}' exported as {} to:\n\n{}",
+            self.representative_patch.title(),
+            self.export_format,
+            output_path.display()
+        ))
+    }
+
+    /// Scrolls the preview down by a page of the size of its viewport, or half
+    /// a page if `half` is true
+    pub fn preview_page_down(&mut self, half: bool) {
+        self.preview_scroll_down(self.preview_page_height(half));
+    }
+
+    /// Scrolls the preview up by a page of the size of its viewport, or half a
+    /// page if `half` is true
+    pub fn preview_page_up(&mut self, half: bool) {
+        self.preview_scroll_up(self.preview_page_height(half));
+    }
+
+    fn preview_page_height(&self, half: bool) -> usize {
+        let height = (self.preview_viewport.height as usize).max(1);
+        if half {
+            height.div_ceil(2)
+        } else {
+            height
+        }
+    }
+
+    /// Previews the patch of index `patch_index` scrolled to the preview line
+    /// that shows `line` (starting at 1) of its raw contents.
+    pub fn jump_to_preview_line(&mut self, patch_index: usize, line: usize) {
+        if patch_index < self.patches_preview.len() {
+            self.preview_index = patch_index;
+            self.preview_scroll_offset = preview_search::preview_line_of_raw_line(
+                &self.raw_patches[patch_index],
+                &self.patches_preview[patch_index],
+                line,
+            );
+            self.preview_pan = 0;
+        }
+    }
+
+    /// Opens the prompt to type the pattern of a search in `direction`
+    pub fn start_preview_search(&mut self, direction: SearchDirection) {
+        let previous_search = self.preview_search.clone();
+        self.search_prompt = Some(SearchPrompt {
+            input: String::new(),
+            direction,
+            across_series: previous_search
+                .as_ref()
+                .is_some_and(|search| search.across_series),
+            error: None,
+            origin: (
+                self.preview_index,
+                self.preview_scroll_offset,
+                self.preview_pan,
+            ),
+            previous_search,
+        });
+    }
+
+    /// Appends `c` to the pattern being typed and searches it
+    pub fn push_search_char(&mut self, c: char) {
+        if let Some(prompt) = self.search_prompt.as_mut() {
+            prompt.input.push(c);
+            self.update_preview_search();
+        }
+    }
+
+    /// Removes the last character of the pattern being typed and searches it
+    pub fn pop_search_char(&mut self) {
+        if let Some(prompt) = self.search_prompt.as_mut() {
+            prompt.input.pop();
+            self.update_preview_search();
+        }
+    }
+
+    /// Toggles if the search being typed looks for matches in all patches of
+    /// the series
+    pub fn toggle_search_across_series(&mut self) {
+        if let Some(prompt) = self.search_prompt.as_mut() {
+            prompt.across_series = !prompt.across_series;
+            self.update_preview_search();
+        }
+    }
+
+    /// Searches the pattern being typed from where the prompt was opened,
+    /// jumping to its first match, if any.
+    fn update_preview_search(&mut self) {
+        let Some(prompt) = self.search_prompt.as_mut() else {
+            return;
+        };
+        (
+            self.preview_index,
+            self.preview_scroll_offset,
+            self.preview_pan,
+        ) = prompt.origin;
+        prompt.error = None;
+        self.preview_search = None;
+        if prompt.input.is_empty() {
+            return;
+        }
+
+        match PreviewSearch::new(
+            &prompt.input,
+            prompt.direction,
+            prompt.across_series,
+            &self.patches_preview,
+        ) {
+            Ok(search) => {
+                self.preview_search = Some(search);
+                self.jump_to_next_search_match(false);
+            }
+            Err(error) => prompt.error = Some(error),
+        }
+    }
+
+    /// Closes the prompt, keeping the search typed in it
+    pub fn confirm_preview_search(&mut self) {
+        if let Some(prompt) = self.search_prompt.take() {
+            if prompt.error.is_some() {
+                self.preview_search = prompt.previous_search;
+            }
+        }
+    }
+
+    /// Closes the prompt, restoring the preview and the search from before
+    /// it was opened
+    pub fn cancel_preview_search(&mut self) {
+        if let Some(prompt) = self.search_prompt.take() {
+            (
+                self.preview_index,
+                self.preview_scroll_offset,
+                self.preview_pan,
+            ) = prompt.origin;
+            self.preview_search = prompt.previous_search;
+        }
+    }
+
+    /// Stops highlighting the matches of the current search
+    pub fn clear_preview_search(&mut self) {
+        self.preview_search = None;
+    }
+
+    /// Jumps to the next match of the current search in its direction, or in
+    /// the opposite one if `reverse` is true, previewing its patch and
+    /// scrolling and panning the preview to it.
+    pub fn jump_to_next_search_match(&mut self, reverse: bool) {
+        let Some(search) = self.preview_search.as_mut() else {
+            return;
+        };
+        let forward = (search.direction == SearchDirection::Forward) != reverse;
+        let Some(index) =
+            search.next_match(self.preview_index, self.preview_scroll_offset, forward)
+        else {
+            return;
+        };
+        search.current = Some(index);
+
+        let found = search.matches[index];
+        let visible_lines = self.preview_scroll_offset
+            ..self.preview_scroll_offset + self.preview_viewport.height as usize;
+        if found.patch_index != self.preview_index || !visible_lines.contains(&found.line) {
+            self.preview_scroll_offset = found.line.saturating_sub(SEARCH_CONTEXT_LINES);
+        }
+        self.preview_index = found.patch_index;
+        let line =
+            preview_search::line_text(&self.patches_preview[found.patch_index].lines[found.line]);
+        let start_column = line[..found.start].chars().count();
+        let end_column = line[..found.end].chars().count();
+        if start_column < self.preview_pan
+            || end_column > self.preview_pan + self.preview_viewport.width as usize
+        {
+            self.preview_pan = start_column.saturating_sub(SEARCH_CONTEXT_COLUMNS);
+        }
+    }
+
     pub fn reset_reply_with_reviewed_by_action(&mut self) {
         self.patches_to_reply = vec![false; self.patches_to_reply.len()];
         self.patchset_actions
@@ -162,6 +486,28 @@ impl DetailsActions {
             .insert(patchset_action, !current_value);
     }
 
+    /// Returns whether there are marked actions, which are lost if the
+    /// patchset details are discarded
+    pub fn has_unfinished_work(&self) -> bool {
+        self.patchset_actions.values().any(|&marked| marked)
+    }
+
+    /// Records that the patchset was applied once the `git am` of `conflict`
+    /// is over, so the following actions (like build-testing) run on it.
+    pub fn finish_git_am_conflict(&mut self, conflict: &GitAmConflict) {
+        self.applied_kernel_tree = Some(conflict.kernel_tree().clone());
+        self.applied_branch = Some(conflict.branch().clone());
+        self.applied_worktree = conflict.worktree().map(String::from);
+    }
+
+    /// Returns whether the previewed patch has `Fixes:` trailers that weren't
+    /// resolved yet.
+    pub fn has_pending_fixes(&self) -> bool {
+        self.fixes[self.preview_index]
+            .iter()
+            .any(FixesCheck::is_pending)
+    }
+
     pub fn actions_require_user_io(&self) -> bool {
         self.patches_to_reply.contains(&true)
     }
@@ -189,12 +535,13 @@ impl DetailsActions {
                 .trim(),
         );
 
-        let git_reply_commands = match lore_session::prepare_reply_patchset_with_reviewed_by(
+        let git_reply_commands = match lore_session::prepare_reply_patchset_with_trailer(
             &self.lore_api_client,
             tmp_dir,
             target_list,
             &self.raw_patches,
             &self.patches_to_reply,
+            &self.reply_trailer.to_string(),
             &format!("{git_user_name} <{git_user_email}>"),
             git_send_email_options,
         ) {
@@ -221,33 +568,6 @@ impl DetailsActions {
         Ok(())
     }
 
-    /// Checks if there is a `target_kernel_tree` and if it is in `Config::kernel_trees` and if
-    /// that kernel tree is a valid git directory.
-    ///
-    /// Returns the a valid `KernelTree` or a `String` with the error message on failure.
-    fn validate_kernel_tree<'a>(&self, config: &'a Config) -> Result<&'a KernelTree, String> {
-        let kernel_tree_id = if let Some(target) = config.target_kernel_tree() {
-            target
-        } else {
-            return Err("target kernel tree unset".to_string());
-        };
-
-        let kernel_tree = if let Some(tree) = config.get_kernel_tree(kernel_tree_id) {
-            tree
-        } else {
-            return Err(format!("invalid target kernel tree '{}'", kernel_tree_id));
-        };
-
-        let kernel_tree_path = Path::new(kernel_tree.path());
-        if !kernel_tree_path.is_dir() {
-            return Err(format!("{} isn't a directory", kernel_tree.path()));
-        } else if !kernel_tree_path.join(".git").is_dir() {
-            return Err(format!("{} isn't a git repository", kernel_tree.path()));
-        }
-
-        Ok(kernel_tree)
-    }
-
     // Ensures the kernel directory is not currently in another git operation,
     // that it does not have unstaged or uncommited changes, and that the base branch
     // is valid.
@@ -291,25 +611,7 @@ impl DetailsActions {
             ));
         }
 
-        let git_show_ref_out = Command::new("git")
-            .arg("-C")
-            .arg(kernel_tree.path())
-            .arg("show-ref")
-            .arg("--verify")
-            .arg("--quiet")
-            .arg(format!("refs/heads/{}", kernel_tree.branch()))
-            .output()
-            .map_err(|e| format!("failed to verify branch: {}", e))?;
-
-        if !git_show_ref_out.status.success() {
-            return Err(format!(
-                "invalid branch '{}' for '{}'",
-                kernel_tree.branch(),
-                kernel_tree.path()
-            ));
-        }
-
-        Ok(())
+        validate_kernel_tree_branch(kernel_tree)
     }
 
     /// Get the current branch of the supplied kernel tree
@@ -353,22 +655,18 @@ impl DetailsActions {
         Ok(())
     }
 
-    /// Create a new branch suffixed with the current timestamp.
+    /// Create a new branch suffixed with the current timestamp, starting at
+    /// `base`.
     ///
     /// Returns a `String` with the branch name on success or the
     /// error message on failure.
     fn create_target_branch(
         &self,
         kernel_tree: &KernelTree,
+        base: &str,
         config: &Config,
     ) -> Result<String, String> {
-        self.switch_to_branch(kernel_tree, kernel_tree.branch())?;
-
-        let target_branch_name = format!(
-            "{}{}",
-            config.git_am_branch_prefix(),
-            chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S")
-        );
+        let target_branch_name = target_branch_name(config);
 
         let output = Command::new("git")
             .arg("-C")
@@ -376,6 +674,7 @@ impl DetailsActions {
             .arg("checkout")
             .arg("-b")
             .arg(&target_branch_name)
+            .arg(base)
             .output()
             .map_err(|e| format!("failed to create target branch: {}", e))?;
 
@@ -390,60 +689,621 @@ impl DetailsActions {
         Ok(target_branch_name)
     }
 
-    /// Apply the selected patchset on the given `kernel_tree` with arguments from `Config`
+    /// Runs the `checkpatch.pl` of the target kernel tree against each patch of
+    /// the patchset with arguments from `Config`. The cover letter isn't
+    /// checked, so it always gets an empty report.
     ///
-    /// Returns `()` on sucess and a `String` containing the error message on failure.
-    fn run_git_am(&self, kernel_tree: &KernelTree, config: &Config) -> Result<(), String> {
-        let mut git_am_out = Command::new("git");
-        git_am_out
-            .arg("-C")
-            .arg(kernel_tree.path())
-            .arg("am")
-            .arg(&self.patchset_path);
-        config.git_am_options().split_whitespace().for_each(|opt| {
-            git_am_out.arg(opt);
-        });
+    /// Returns a report for each patch or a `String` with the error message on failure.
+    pub fn run_checkpatch(&self, config: &Config) -> Result<Vec<CheckpatchReport>, String> {
+        let kernel_tree = validate_kernel_tree(config)?;
+
+        self.raw_patches
+            .iter()
+            .enumerate()
+            .map(|(i, raw_patch)| {
+                if self.has_cover_letter && i == 0 {
+                    return Ok(CheckpatchReport::default());
+                }
+                checkpatch::run_checkpatch(
+                    kernel_tree.path(),
+                    raw_patch,
+                    config.checkpatch_options(),
+                )
+                .map_err(|e| format!("failed to run checkpatch.pl: {e}"))
+            })
+            .collect()
+    }
+
+    /// Builds the target kernel tree on the branch in which the patchset was
+    /// applied with the build command from `Config`, appending its output to
+    /// `log`. The kernel tree is switched back to its original branch
+    /// afterwards.
+    ///
+    /// Returns the `BuildStatus` or a `String` with the error message if the
+    /// build couldn't be run.
+    pub fn build_test_patchset(
+        &self,
+        config: &Config,
+        log: &Mutex<Vec<String>>,
+    ) -> Result<BuildStatus, String> {
+        let Some(applied_branch) = &self.applied_branch else {
+            return Err("the patchset must be applied before being build-tested".to_string());
+        };
+        let Some(kernel_tree) = &self.applied_kernel_tree else {
+            return Err("the patchset must be applied before being build-tested".to_string());
+        };
+
+        let build_command = build_test::expand_build_command(
+            config.build_command(),
+            &build_test::touched_dirs(&self.raw_patches),
+        );
+        if let Ok(mut log) = log.lock() {
+            log.push(format!("$ {build_command}"));
+        }
+
+        if let Some(applied_worktree) = &self.applied_worktree {
+            return build_test::run_build_command(applied_worktree, &build_command, log)
+                .map_err(|e| format!("failed to run build command: {e}"));
+        }
+
+        self.check_git_state(kernel_tree)?;
+        let original_branch = self.get_current_branch(kernel_tree)?;
+        self.switch_to_branch(kernel_tree, applied_branch)?;
+
+        let build_result = build_test::run_build_command(kernel_tree.path(), &build_command, log)
+            .map_err(|e| format!("failed to run build command: {e}"));
+        self.switch_to_branch(kernel_tree, &original_branch)?;
 
-        let git_am_out = git_am_out
+        build_result
+    }
+
+    /// Returns the kernel tree chosen to apply the patchset, which is the
+    /// target kernel tree unless another one was chosen, if it is valid (see
+    /// [validate_kernel_tree_by_id]). Its branch is replaced by the chosen
+    /// base branch, if any.
+    pub fn validate_apply_kernel_tree(&self, config: &Config) -> Result<KernelTree, String> {
+        match &self.apply_base {
+            Some(apply_base) => {
+                let kernel_tree = validate_kernel_tree_by_id(config, apply_base.kernel_tree_id())?;
+                Ok(KernelTree::new(
+                    kernel_tree.path().clone(),
+                    apply_base.branch().clone(),
+                ))
+            }
+            None => validate_kernel_tree(config).cloned(),
+        }
+    }
+
+    /// Chooses the kernel tree `kernel_tree_id` to apply the patchset on
+    /// `branch`, or on the branch of the kernel tree if `None`, overriding the
+    /// detected one.
+    ///
+    /// Returns a `String` with the error message if the kernel tree isn't
+    /// valid or the branch doesn't exist in it.
+    pub fn choose_apply_base(
+        &mut self,
+        config: &Config,
+        kernel_tree_id: &str,
+        branch: Option<&str>,
+    ) -> Result<(), String> {
+        let kernel_tree = validate_kernel_tree_by_id(config, kernel_tree_id)?;
+        let branch = branch.unwrap_or(kernel_tree.branch());
+        if !base_tree::has_branch(kernel_tree.path(), branch) {
+            return Err(format!(
+                "branch '{branch}' doesn't exist in kernel tree '{kernel_tree_id}'"
+            ));
+        }
+
+        self.apply_base = Some(BaseTreeChoice::new(
+            kernel_tree_id.to_string(),
+            branch.to_string(),
+            "chosen manually".to_string(),
+        ));
+        Ok(())
+    }
+
+    /// Returns whether the upstream of the branch to apply on should be fetched
+    /// before applying, which is when applying is marked and
+    /// `Config::fetch_before_apply` is set.
+    pub fn needs_apply_upstream(&self, config: &Config) -> bool {
+        config.fetch_before_apply()
+            && self.patchset_actions.get(&PatchsetAction::Apply) == Some(&true)
+    }
+
+    /// Fetches the upstream of the branch to apply on, if it is needed (see
+    /// [Self::needs_apply_upstream]), for the next apply to use. What is
+    /// fetched is appended to `log`.
+    pub fn fetch_apply_upstream(&mut self, config: &Config, log: &Mutex<Vec<String>>) {
+        self.apply_upstream = None;
+        if !self.needs_apply_upstream(config) {
+            return;
+        }
+
+        // An invalid kernel tree is reported by the apply itself
+        if let Ok(kernel_tree) = self.validate_apply_kernel_tree(config) {
+            if let Ok(mut log) = log.lock() {
+                log.push(format!(
+                    "Fetching the upstream of '{}' in {}",
+                    kernel_tree.branch(),
+                    kernel_tree.path()
+                ));
+            }
+            self.apply_upstream = Some(upstream::fetch_upstream(
+                kernel_tree.path(),
+                kernel_tree.branch(),
+            ));
+        }
+    }
+
+    /// Chooses the ref of `kernel_tree` to apply on from the upstream fetched
+    /// by [Self::fetch_apply_upstream] (see [upstream::resolve_apply_base]).
+    ///
+    /// Returns the ref to apply on and the report of the fetch, if any.
+    fn resolve_apply_base(
+        &self,
+        kernel_tree: &KernelTree,
+        config: &Config,
+    ) -> (String, Option<String>) {
+        let upstream_fetch = self
+            .apply_upstream
+            .as_ref()
+            .filter(|_| config.fetch_before_apply());
+        upstream::resolve_apply_base(
+            kernel_tree.branch(),
+            config.apply_on_upstream(),
+            upstream_fetch,
+        )
+    }
+
+    /// Creates the branch in which the patchset will be applied, either by
+    /// switching the kernel tree to it (which requires a clean kernel tree) or,
+    /// if `Config::apply_to_worktree` is set, by adding a worktree for it
+    /// under `Config::worktrees_dir`, which leaves the kernel tree untouched.
+    ///
+    /// Returns the `ApplyTarget` or a `String` with the error message on failure.
+    fn prepare_apply_target(
+        &self,
+        kernel_tree: &KernelTree,
+        config: &Config,
+    ) -> Result<ApplyTarget, String> {
+        if !config.apply_to_worktree() {
+            self.check_git_state(kernel_tree)?;
+            let original_branch = self.get_current_branch(kernel_tree)?;
+            let (base, upstream_report) = self.resolve_apply_base(kernel_tree, config);
+            let branch = self.create_target_branch(kernel_tree, &base, config)?;
+            return Ok(ApplyTarget {
+                kernel_tree: kernel_tree.clone(),
+                branch,
+                base,
+                upstream_report,
+                repo_path: kernel_tree.path().clone(),
+                original_branch: Some(original_branch),
+            });
+        }
+
+        fs::create_dir_all(config.worktrees_dir())
+            .map_err(|e| format!("failed to create {}: {e}", config.worktrees_dir()))?;
+        let branch = target_branch_name(config);
+        let worktree_path = format!("{}/{branch}", config.worktrees_dir());
+        let (base, upstream_report) = self.resolve_apply_base(kernel_tree, config);
+        worktree::add_worktree(kernel_tree.path(), &worktree_path, &branch, &base)?;
+
+        Ok(ApplyTarget {
+            kernel_tree: kernel_tree.clone(),
+            branch,
+            base,
+            upstream_report,
+            repo_path: worktree_path,
+            original_branch: None,
+        })
+    }
+
+    /// Switches the kernel tree back to the branch it was before applying to
+    /// `apply_target`, if it was switched at all, and records where the
+    /// patchset was applied if `applied` is set.
+    fn finish_apply(&mut self, apply_target: &ApplyTarget, applied: bool) -> Result<(), String> {
+        if let Some(original_branch) = &apply_target.original_branch {
+            self.switch_to_branch(&apply_target.kernel_tree, original_branch)?;
+        }
+
+        let result = if applied {
+            ApplyResult::Applied
+        } else {
+            ApplyResult::Failed
+        };
+        self.record_apply(apply_target, result);
+
+        if applied {
+            self.applied_kernel_tree = Some(apply_target.kernel_tree.clone());
+            self.applied_branch = Some(apply_target.branch.clone());
+            self.applied_worktree = apply_target
+                .original_branch
+                .is_none()
+                .then(|| apply_target.repo_path.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Sets [Self::last_apply_record] to a record of applying the patchset to
+    /// `apply_target` with `result`.
+    fn record_apply(&mut self, apply_target: &ApplyTarget, result: ApplyResult) {
+        self.last_apply_record = Some(ApplyRecord::new(
+            self.representative_patch.clone(),
+            apply_target.kernel_tree.path().clone(),
+            apply_target.base.clone(),
+            apply_target.branch.clone(),
+            apply_target
+                .original_branch
+                .is_none()
+                .then(|| apply_target.repo_path.clone()),
+            result,
+        ));
+    }
+
+    /// Returns the lines describing where the patchset was applied.
+    fn apply_target_summary(&self, apply_target: &ApplyTarget) -> String {
+        let mut summary = format!(
+            " - Kernel Tree: '{}'\n\n - Base Branch: '{}'\n\n - Applied branch: '{}'",
+            apply_target.kernel_tree.path(),
+            apply_target.base,
+            apply_target.branch
+        );
+        if let Some(upstream_report) = &apply_target.upstream_report {
+            summary.push_str(&format!("\n\n - Upstream: {upstream_report}"));
+        }
+        if apply_target.original_branch.is_none() {
+            summary.push_str(&format!("\n\n - Worktree: '{}'", apply_target.repo_path));
+        }
+        summary
+    }
+
+    /// Apply the selected patchset on the repository in `repo_path` (the
+    /// kernel tree or one of its worktrees) with arguments from `Config`. If
+    /// it fails, applying is retried with `--3way`, which leaves the
+    /// repository in the middle of `git am` only if there are conflicts (see
+    /// [git_am::keep_git_am_if_conflicted]).
+    ///
+    /// Returns `()` on sucess and a `GitAmError` on failure.
+    fn run_git_am(&self, repo_path: &str, config: &Config) -> Result<(), GitAmError> {
+        let git_am_out = run_git_am_command(repo_path, &self.patchset_path, config, false)
+            .map_err(GitAmError::Failed)?;
+        if git_am_out.status.success() {
+            return Ok(());
+        }
+
+        git_am::abort_git_am(repo_path);
+
+        let git_am_3way_out = run_git_am_command(repo_path, &self.patchset_path, config, true)
+            .map_err(GitAmError::Failed)?;
+        if git_am_3way_out.status.success() {
+            return Ok(());
+        }
+
+        if git_am::keep_git_am_if_conflicted(repo_path) {
+            Err(GitAmError::Conflict(format!(
+                "{}{}",
+                String::from_utf8_lossy(&git_am_3way_out.stdout),
+                String::from_utf8_lossy(&git_am_3way_out.stderr)
+            )))
+        } else {
+            Err(GitAmError::Failed(
+                String::from_utf8_lossy(&git_am_3way_out.stderr).to_string(),
+            ))
+        }
+    }
+
+    /// Splits the .mbx of the patchset into one file per patch (named `0001`,
+    /// `0002`, ...) inside `output_dir` with `git mailsplit`.
+    ///
+    /// Returns the paths of the files in order or a `String` with the error
+    /// message on failure.
+    fn split_patchset_mbox(&self, output_dir: &Path) -> Result<Vec<PathBuf>, String> {
+        fs::create_dir_all(output_dir)
+            .map_err(|e| format!("failed to create {}: {e}", output_dir.display()))?;
+
+        let output = Command::new("git")
+            .arg("mailsplit")
+            .arg(format!("-o{}", output_dir.display()))
+            .arg(&self.patchset_path)
             .output()
-            .map_err(|e| format!("failed to execute git-am: {}", e))?;
-
-        if !git_am_out.status.success() {
-            let _ = Command::new("git")
-                .arg("-C")
-                .arg(kernel_tree.path())
-                .arg("am")
-                .arg("--abort")
+            .map_err(|e| format!("failed to execute git-mailsplit: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "failed to split patchset: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        let mut patch_files: Vec<PathBuf> = fs::read_dir(output_dir)
+            .map_err(|e| format!("failed to read {}: {e}", output_dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect();
+        patch_files.sort();
+
+        Ok(patch_files)
+    }
+
+    /// Applies the patches in `patch_files` one at a time with `git am` on the
+    /// repository in `repo_path` (the kernel tree or one of its worktrees), running the bisect check command from
+    /// `Config` after each one and appending what is done to `log`.
+    ///
+    /// Returns `()` if every patch applies and passes the check or a `String`
+    /// telling which patch first failed on failure.
+    fn run_git_am_per_patch(
+        &self,
+        repo_path: &str,
+        config: &Config,
+        patch_files: &[PathBuf],
+        log: &Mutex<Vec<String>>,
+    ) -> Result<(), String> {
+        let push_log = |line: String| {
+            if let Ok(mut log) = log.lock() {
+                log.push(line);
+            }
+        };
+        let patches_count = patch_files.len();
+
+        for (i, patch_file) in patch_files.iter().enumerate() {
+            let raw_patch = fs::read_to_string(patch_file).unwrap_or_default();
+            let subject = raw_patch
+                .lines()
+                .find_map(|line| line.strip_prefix("Subject: "))
+                .unwrap_or_default()
+                .to_string();
+            let patch_label = format!("patch {}/{patches_count} '{subject}'", i + 1);
+            push_log(format!("==> Applying {patch_label}"));
+
+            let mut git_am = Command::new("git");
+            git_am.arg("-C").arg(repo_path).arg("am").arg(patch_file);
+            config.git_am_options().split_whitespace().for_each(|opt| {
+                git_am.arg(opt);
+            });
+            let git_am_out = git_am
                 .output()
-                .map_err(|e| format!("failed to abort git-am: {}", e));
+                .map_err(|e| format!("failed to execute git-am: {}", e))?;
+
+            if !git_am_out.status.success() {
+                let _ = Command::new("git")
+                    .arg("-C")
+                    .arg(repo_path)
+                    .arg("am")
+                    .arg("--abort")
+                    .output();
+                String::from_utf8_lossy(&git_am_out.stderr)
+                    .lines()
+                    .for_each(|line| push_log(line.to_string()));
+                return Err(format!("{patch_label} failed to apply"));
+            }
 
-            return Err(String::from_utf8_lossy(&git_am_out.stderr).to_string());
+            if config.bisect_check_command().trim().is_empty() {
+                continue;
+            }
+            let check_command = build_test::expand_build_command(
+                config.bisect_check_command(),
+                &build_test::touched_dirs(&[raw_patch]),
+            );
+            push_log(format!("$ {check_command}"));
+            let check_status = build_test::run_build_command(repo_path, &check_command, log)
+                .map_err(|e| format!("failed to run check command: {e}"))?;
+            if check_status == BuildStatus::Failed {
+                return Err(format!("{patch_label} failed the check"));
+            }
         }
 
         Ok(())
     }
 
+    /// Applies the patchset to the target kernel tree one patch at a time,
+    /// running the bisect check command from `Config` after each patch, so
+    /// the first patch that breaks the bisectability of the series is found.
+    /// What is done is appended to `log`.
+    ///
+    /// Returns a `Result<String, String>` containing either the success
+    /// message or the error message telling which patch first failed to apply
+    /// or to pass the check.
+    pub fn apply_patchset_per_patch(
+        &mut self,
+        config: &Config,
+        log: &Mutex<Vec<String>>,
+    ) -> Result<String, String> {
+        let kernel_tree = self.validate_apply_kernel_tree(config)?;
+        let apply_target = self.prepare_apply_target(&kernel_tree, config)?;
+
+        let split_dir = std::env::temp_dir().join(format!("patch-hub-{}", apply_target.branch));
+        let git_am_result = self
+            .split_patchset_mbox(&split_dir)
+            .and_then(|patch_files| {
+                self.run_git_am_per_patch(&apply_target.repo_path, config, &patch_files, log)
+            });
+        let _ = fs::remove_dir_all(&split_dir);
+        self.finish_apply(&apply_target, git_am_result.is_ok())?;
+
+        match git_am_result {
+            Ok(_) => Ok(format!(
+                "Every patch of '{}' applied and passed the check\n\n{}",
+                self.representative_patch.title(),
+                self.apply_target_summary(&apply_target)
+            )),
+            Err(e) => Err(format!(
+                "{e}\n\nThe patches applied up to it are kept in branch '{}'",
+                apply_target.branch
+            )),
+        }
+    }
+
     /// Try to apply the patchset to a target kernel tree and returns a `String`
     /// informing if the apply succeeded or failed and why.
     ///
     /// Returns a `Result<String, String>` containing either the success or the error message.
     /// # TODO:
     /// - Add unit tests
-    pub fn apply_patchset(&self, config: &Config) -> Result<String, String> {
-        let kernel_tree = self.validate_kernel_tree(config)?;
-        self.check_git_state(kernel_tree)?;
+    pub fn apply_patchset(&mut self, config: &Config) -> Result<String, String> {
+        let kernel_tree = self.validate_apply_kernel_tree(config)?;
+        let apply_target = self.prepare_apply_target(&kernel_tree, config)?;
 
-        let original_branch = self.get_current_branch(kernel_tree)?;
-        let target_branch = self.create_target_branch(kernel_tree, config)?;
-
-        let git_am_result = self.run_git_am(kernel_tree, config);
-        self.switch_to_branch(kernel_tree, &original_branch)?;
+        let git_am_result = self.run_git_am(&apply_target.repo_path, config);
+        if let Err(GitAmError::Conflict(output)) = git_am_result {
+            // The repository is left in the middle of `git am`, so the user
+            // can resolve the conflicts (see [git_am::resolve_git_am_conflict])
+            self.record_apply(&apply_target, ApplyResult::Conflict);
+            self.git_am_conflict = Some(GitAmConflict::new(
+                self.representative_patch.clone(),
+                apply_target.kernel_tree,
+                apply_target.base,
+                apply_target.branch,
+                apply_target.repo_path,
+                apply_target.original_branch,
+                output.clone(),
+            ));
+            return Err(output);
+        }
+        self.finish_apply(&apply_target, git_am_result.is_ok())?;
 
         match git_am_result {
-            Ok(_) => {
-                Ok(format!(" Patchset '{}' applied successfully!\n\n - Kernel Tree: '{}'\n\n - Base Branch: '{}'\n\n - Applied branch: '{}'", self.representative_patch.title(), kernel_tree.path(), kernel_tree.branch(), &target_branch))
-        },
-            Err(e) => Err(format!( "`git am` failed\n{}{}", &original_branch, e))
+            Ok(_) => Ok(format!(
+                " Patchset '{}' applied successfully!\n\n{}",
+                self.representative_patch.title(),
+                self.apply_target_summary(&apply_target)
+            )),
+            Err(GitAmError::Failed(e) | GitAmError::Conflict(e)) => Err(format!(
+                "`git am` failed\n{}{}",
+                apply_target
+                    .original_branch
+                    .as_deref()
+                    .unwrap_or(&apply_target.repo_path),
+                e
+            )),
         }
     }
 }
+
+/// Where a patchset is applied: a new branch checked out either in the kernel
+/// tree itself or in a dedicated worktree of it
+struct ApplyTarget {
+    kernel_tree: KernelTree,
+    branch: String,
+    /// Ref from which `branch` was created: the branch of `kernel_tree` or,
+    /// if `Config::apply_on_upstream` is set, its freshly fetched upstream
+    base: String,
+    /// How the branch of `kernel_tree` compared to its upstream, if it was
+    /// fetched before applying (see `Config::fetch_before_apply`)
+    upstream_report: Option<String>,
+    /// Path of the repository in which `branch` is checked out
+    repo_path: String,
+    /// Branch of the kernel tree to switch back to after applying, unless
+    /// the patchset is applied in a worktree
+    original_branch: Option<String>,
+}
+
+enum GitAmError {
+    /// `git am` failed and was aborted
+    Failed(String),
+    /// `git am --3way` stopped by conflicts and is still in progress
+    Conflict(String),
+}
+
+/// Runs `git am` on `mbox_path` in `repo_path` with arguments from `Config`
+/// and `--3way`, if `three_way` is set.
+fn run_git_am_command(
+    repo_path: &str,
+    mbox_path: &str,
+    config: &Config,
+    three_way: bool,
+) -> Result<Output, String> {
+    let mut git_am = Command::new("git");
+    git_am.arg("-C").arg(repo_path).arg("am");
+    if three_way {
+        git_am.arg("--3way");
+    }
+    git_am.arg(mbox_path);
+    config.git_am_options().split_whitespace().for_each(|opt| {
+        git_am.arg(opt);
+    });
+
+    git_am
+        .output()
+        .map_err(|e| format!("failed to execute git-am: {}", e))
+}
+
+/// Returns the name of a new branch to apply a patchset, suffixed with the
+/// current timestamp.
+fn target_branch_name(config: &Config) -> String {
+    format!(
+        "{}{}",
+        config.git_am_branch_prefix(),
+        chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S")
+    )
+}
+
+/// Checks if there is a `target_kernel_tree` and if it is in `Config::kernel_trees` and if
+/// that kernel tree is a valid git directory.
+///
+/// Returns the a valid `KernelTree` or a `String` with the error message on failure.
+pub fn validate_kernel_tree(config: &Config) -> Result<&KernelTree, String> {
+    let kernel_tree_id = if let Some(target) = config.target_kernel_tree() {
+        target
+    } else {
+        return Err("target kernel tree unset".to_string());
+    };
+
+    validate_kernel_tree_by_id(config, kernel_tree_id)
+}
+
+/// Checks if `kernel_tree_id` is in `Config::kernel_trees` and if that kernel
+/// tree is a valid git directory.
+///
+/// Returns the a valid `KernelTree` or a `String` with the error message on failure.
+pub fn validate_kernel_tree_by_id<'a>(
+    config: &'a Config,
+    kernel_tree_id: &str,
+) -> Result<&'a KernelTree, String> {
+    let kernel_tree = if let Some(tree) = config.get_kernel_tree(kernel_tree_id) {
+        tree
+    } else {
+        return Err(format!("invalid target kernel tree '{}'", kernel_tree_id));
+    };
+
+    validate_kernel_tree_path(kernel_tree)?;
+    Ok(kernel_tree)
+}
+
+/// Checks if the path of `kernel_tree` is a valid git directory.
+///
+/// Returns `()` on success and a `String` with the error message on failure.
+pub fn validate_kernel_tree_path(kernel_tree: &KernelTree) -> Result<(), String> {
+    let kernel_tree_path = Path::new(kernel_tree.path());
+    if !kernel_tree_path.is_dir() {
+        return Err(format!("{} isn't a directory", kernel_tree.path()));
+    } else if !kernel_tree_path.join(".git").is_dir() {
+        return Err(format!("{} isn't a git repository", kernel_tree.path()));
+    }
+
+    Ok(())
+}
+
+/// Checks if the branch of `kernel_tree` exists in it.
+///
+/// Returns `()` on success and a `String` with the error message on failure.
+pub fn validate_kernel_tree_branch(kernel_tree: &KernelTree) -> Result<(), String> {
+    let git_show_ref_out = Command::new("git")
+        .arg("-C")
+        .arg(kernel_tree.path())
+        .arg("show-ref")
+        .arg("--verify")
+        .arg("--quiet")
+        .arg(format!("refs/heads/{}", kernel_tree.branch()))
+        .output()
+        .map_err(|e| format!("failed to verify branch: {}", e))?;
+
+    if !git_show_ref_out.status.success() {
+        return Err(format!(
+            "invalid branch '{}' for '{}'",
+            kernel_tree.branch(),
+            kernel_tree.path()
+        ));
+    }
+
+    Ok(())
+}
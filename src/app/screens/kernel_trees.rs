@@ -0,0 +1,199 @@
+use crate::app::{
+    base_tree,
+    config::{Config, KernelTree},
+    screens::details_actions::{validate_kernel_tree_branch, validate_kernel_tree_path},
+};
+
+pub struct KernelTrees {
+    /// Registered kernel trees, sorted by name
+    pub kernel_trees: Vec<KernelTreeEntry>,
+    pub highlighted: usize,
+    /// Form to add or rename a kernel tree, if one is being filled
+    pub form: Option<KernelTreeForm>,
+}
+
+pub struct KernelTreeEntry {
+    pub id: String,
+    pub kernel_tree: KernelTree,
+    pub is_target: bool,
+    /// Result of validating the path and branch of the kernel tree
+    pub validation: Result<(), String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum KernelTreeFormKind {
+    Add,
+    /// Rename the kernel tree with the given name
+    Rename(String),
+}
+
+pub struct KernelTreeForm {
+    pub kind: KernelTreeFormKind,
+    /// Pairs of field name and value
+    pub fields: Vec<(&'static str, String)>,
+    pub focused: usize,
+    /// Why the values of the form couldn't be saved, if they were confirmed
+    pub error: Option<String>,
+}
+
+impl KernelTrees {
+    pub fn new(config: &Config) -> Self {
+        let mut kernel_tree_ids: Vec<&String> = config.kernel_trees().into_iter().collect();
+        kernel_tree_ids.sort();
+
+        let kernel_trees = kernel_tree_ids
+            .into_iter()
+            .filter_map(|id| {
+                let kernel_tree = config.get_kernel_tree(id)?;
+                Some(KernelTreeEntry {
+                    id: id.to_string(),
+                    kernel_tree: kernel_tree.clone(),
+                    is_target: config.target_kernel_tree().as_ref() == Some(id),
+                    validation: validate_kernel_tree_path(kernel_tree)
+                        .and_then(|_| validate_kernel_tree_branch(kernel_tree)),
+                })
+            })
+            .collect();
+
+        KernelTrees {
+            kernel_trees,
+            highlighted: 0,
+            form: None,
+        }
+    }
+
+    /// Reloads the kernel trees from `config`, keeping the highlighted one
+    /// (or the one named `highlight_id`, if set) highlighted.
+    pub fn refresh(&mut self, config: &Config, highlight_id: Option<&str>) {
+        let highlight_id = highlight_id
+            .map(String::from)
+            .or_else(|| self.get_highlighted().map(|entry| entry.id.clone()));
+        let highlighted = self.highlighted;
+
+        *self = KernelTrees::new(config);
+        self.highlighted = highlight_id
+            .and_then(|id| self.kernel_trees.iter().position(|entry| entry.id == id))
+            .unwrap_or(highlighted.min(self.kernel_trees.len().saturating_sub(1)));
+    }
+
+    pub fn highlight_next(&mut self) {
+        if self.highlighted + 1 < self.kernel_trees.len() {
+            self.highlighted += 1;
+        }
+    }
+
+    pub fn highlight_prev(&mut self) {
+        self.highlighted = self.highlighted.saturating_sub(1);
+    }
+
+    pub fn get_highlighted(&self) -> Option<&KernelTreeEntry> {
+        self.kernel_trees.get(self.highlighted)
+    }
+
+    /// Opens a form to add a new kernel tree
+    pub fn open_add_form(&mut self) {
+        self.form = Some(KernelTreeForm {
+            kind: KernelTreeFormKind::Add,
+            fields: vec![
+                ("Name", String::new()),
+                ("Path", String::new()),
+                ("Branch", String::new()),
+            ],
+            focused: 0,
+            error: None,
+        });
+    }
+
+    /// Opens a form to rename the highlighted kernel tree
+    pub fn open_rename_form(&mut self) {
+        if let Some(entry) = self.get_highlighted() {
+            self.form = Some(KernelTreeForm {
+                kind: KernelTreeFormKind::Rename(entry.id.clone()),
+                fields: vec![("Name", entry.id.clone())],
+                focused: 0,
+                error: None,
+            });
+        }
+    }
+
+    pub fn close_form(&mut self) {
+        self.form = None;
+    }
+}
+
+impl KernelTreeForm {
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % self.fields.len();
+        self.prefill_branch_on_focus();
+    }
+
+    pub fn focus_prev(&mut self) {
+        self.focused = (self.focused + self.fields.len() - 1) % self.fields.len();
+        self.prefill_branch_on_focus();
+    }
+
+    /// Fills the branch field, if focused, like [KernelTreeForm::prefill_branch]
+    fn prefill_branch_on_focus(&mut self) {
+        if self.fields.get(self.focused).map(|(name, _)| *name) == Some("Branch") {
+            self.prefill_branch();
+        }
+    }
+
+    /// Fills the branch field, if empty, with the branch checked out in the
+    /// repository of the path field.
+    pub fn prefill_branch(&mut self) {
+        let Some(current_branch) = base_tree::current_branch(self.value("Path")) else {
+            return;
+        };
+        if let Some((_, branch)) = self
+            .fields
+            .iter_mut()
+            .find(|(name, value)| *name == "Branch" && value.trim().is_empty())
+        {
+            *branch = current_branch;
+        }
+    }
+
+    /// Appends a new char to the focused field
+    pub fn append_edit(&mut self, ch: char) {
+        if let Some((_, value)) = self.fields.get_mut(self.focused) {
+            value.push(ch);
+        }
+    }
+
+    /// Remove the last char from the focused field
+    pub fn backspace_edit(&mut self) {
+        if let Some((_, value)) = self.fields.get_mut(self.focused) {
+            value.pop();
+        }
+    }
+
+    /// Checks if the values of an add form are a valid kernel tree: the path
+    /// must be a git repository with the branch.
+    ///
+    /// Returns the kernel tree on success and a `String` with the error
+    /// message on failure.
+    pub fn validated_kernel_tree(&self) -> Result<KernelTree, String> {
+        let kernel_tree = KernelTree::new(
+            self.value("Path").to_string(),
+            self.value("Branch").to_string(),
+        );
+        if kernel_tree.path().is_empty() {
+            return Err("kernel tree path can't be empty".to_string());
+        } else if kernel_tree.branch().is_empty() {
+            return Err("kernel tree branch can't be empty".to_string());
+        }
+        validate_kernel_tree_path(&kernel_tree)?;
+        validate_kernel_tree_branch(&kernel_tree)?;
+        Ok(kernel_tree)
+    }
+
+    /// Returns the value of the field named `name`
+    pub fn value(&self, name: &str) -> &str {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.trim())
+            .unwrap_or_default()
+    }
+}
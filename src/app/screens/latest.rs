@@ -1,3 +1,5 @@
+use std::{collections::HashMap, str::FromStr};
+
 use color_eyre::eyre::bail;
 use derive_getters::Getters;
 use patch_hub::lore::{
@@ -6,29 +8,70 @@ use patch_hub::lore::{
     patch::Patch,
 };
 
+use crate::app::patchset_filter::{PatchsetFilter, PatchsetSort};
+
+/// State of the filter bar while the filter is being typed
+#[derive(Debug, Clone)]
+pub struct FilterPrompt {
+    pub input: String,
+    /// Error of the filter typed so far, if it's invalid
+    pub error: Option<String>,
+    /// Filter when the bar was opened, to restore if the edit is cancelled
+    pub previous_filter: String,
+}
+
 #[derive(Getters)]
 pub struct LatestPatchsets {
     lore_session: LoreSession,
     lore_api_client: BlockingLoreAPIClient,
     target_list: String,
     page_number: usize,
-    patchset_index: usize,
+    /// Index of the selected patchset, if any is listed in the current page
+    patchset_index: Option<usize>,
     page_size: usize,
+    /// If set, only patchsets touching `MAINTAINERS` sections in which the
+    /// user is a maintainer or reviewer are listed
+    my_subsystems_filter: bool,
+    /// Message-IDs of classified patchsets mapped to whether they touch
+    /// `MAINTAINERS` sections in which the user is a maintainer or reviewer
+    touches_my_subsystems: HashMap<String, bool>,
+    /// Filter of the listed patchsets as typed in the filter bar
+    filter_text: String,
+    #[getter(skip)]
+    filter: PatchsetFilter,
+    /// Filter bar, if the filter is being typed
+    filter_prompt: Option<FilterPrompt>,
+    /// Order of the patchsets listed in each page
+    sort: PatchsetSort,
+    /// Message-IDs of patchsets whose details were loaded mapped to their
+    /// number of code-review trailers
+    review_counts: HashMap<String, usize>,
 }
 
 impl LatestPatchsets {
+    /// Creates the listing of the latest patchsets of `target_list`. If
+    /// `search_query` isn't empty, only patchsets matching it (in the Lore
+    /// search syntax, like `f:foo`) are listed.
     pub fn new(
         target_list: String,
+        search_query: String,
         page_size: usize,
         lore_api_client: BlockingLoreAPIClient,
     ) -> LatestPatchsets {
         LatestPatchsets {
-            lore_session: LoreSession::new(target_list.clone()),
+            lore_session: LoreSession::with_search_query(target_list.clone(), search_query),
             lore_api_client,
             target_list,
             page_number: 1,
-            patchset_index: 0,
+            patchset_index: None,
             page_size,
+            my_subsystems_filter: false,
+            touches_my_subsystems: HashMap::new(),
+            filter_text: String::new(),
+            filter: PatchsetFilter::default(),
+            filter_prompt: None,
+            sort: PatchsetSort::default(),
+            review_counts: HashMap::new(),
         }
     }
 
@@ -46,33 +89,212 @@ impl LatestPatchsets {
                 },
             }
         };
+        self.select_first_listed_patchset();
         Ok(())
     }
 
+    /// Returns the indexes of the patchsets fetched so far that pass the
+    /// enabled filters, in the chosen order.
+    pub fn listed_patchsets(&self) -> Vec<usize> {
+        let representative_patches_ids = self.lore_session.representative_patches_ids();
+        let mut listed: Vec<(usize, &Patch, Option<usize>)> = representative_patches_ids
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.is_patchset_listed(*index))
+            .filter_map(|(index, message_id)| {
+                let patch = self.lore_session.get_processed_patch(message_id)?;
+                let review_count = self.review_counts.get(message_id).copied();
+                Some((index, patch, review_count))
+            })
+            .collect();
+        self.sort.sort(&mut listed);
+        listed.into_iter().map(|(index, _, _)| index).collect()
+    }
+
+    /// Returns the indexes of the listed patchsets (see
+    /// [Self::listed_patchsets]) in the current page. Patchsets are filtered
+    /// and sorted before being split in pages.
+    pub fn listed_patchsets_in_current_page(&self) -> Vec<usize> {
+        self.listed_patchsets()
+            .into_iter()
+            .skip(self.page_size * (self.page_number - 1))
+            .take(self.page_size)
+            .collect()
+    }
+
     pub fn select_below_patchset(&mut self) {
-        if self.patchset_index + 1 < self.lore_session.representative_patches_ids().len()
-            && self.patchset_index + 1 < self.page_size * self.page_number
-        {
-            self.patchset_index += 1;
+        let listed = self.listed_patchsets_in_current_page();
+        if let Some(position) = listed.iter().position(|&i| Some(i) == self.patchset_index) {
+            if let Some(&index) = listed.get(position + 1) {
+                self.patchset_index = Some(index);
+            }
         }
     }
 
     pub fn select_above_patchset(&mut self) {
-        if self.patchset_index == 0 {
+        let listed = self.listed_patchsets_in_current_page();
+        if let Some(position) = listed.iter().position(|&i| Some(i) == self.patchset_index) {
+            if position > 0 {
+                self.patchset_index = Some(listed[position - 1]);
+            }
+        }
+    }
+
+    /// Selects the patchset of index `index`, if it's listed in the current
+    /// page
+    pub fn select_patchset(&mut self, index: usize) {
+        if self.listed_patchsets_in_current_page().contains(&index) {
+            self.patchset_index = Some(index);
+        }
+    }
+
+    /// Selects the first listed patchset of the current page, unless the
+    /// selected one is listed in it.
+    pub fn select_first_listed_patchset(&mut self) {
+        if self
+            .patchset_index
+            .is_some_and(|index| self.listed_patchsets_in_current_page().contains(&index))
+        {
+            return;
+        }
+        self.select_top_patchset();
+    }
+
+    /// Selects the first listed patchset of the current page in the chosen
+    /// order, or none if no patchset is listed in it.
+    pub fn select_top_patchset(&mut self) {
+        self.patchset_index = self.listed_patchsets_in_current_page().first().copied();
+    }
+
+    /// Returns if the patchset of index `index` passes the enabled filters.
+    /// Patchsets not yet classified don't pass the "my subsystems" filter.
+    pub fn is_patchset_listed(&self, index: usize) -> bool {
+        let Some(message_id) = self.lore_session.representative_patches_ids().get(index) else {
+            return false;
+        };
+        if self.my_subsystems_filter && self.touches_my_subsystems.get(message_id) != Some(&true) {
+            return false;
+        }
+
+        self.filter.is_empty()
+            || self
+                .lore_session
+                .get_processed_patch(message_id)
+                .is_some_and(|patch| self.filter.matches(patch))
+    }
+
+    /// Sets the filter of the listed patchsets to `filter_text` (see
+    /// [PatchsetFilter]), an empty one listing every patchset.
+    ///
+    /// Returns a `String` with the error message if `filter_text` is invalid.
+    pub fn set_filter(&mut self, filter_text: &str) -> Result<(), String> {
+        self.filter = PatchsetFilter::from_str(filter_text)?;
+        self.filter_text = filter_text.trim().to_string();
+        self.select_first_listed_patchset();
+        Ok(())
+    }
+
+    /// Opens the filter bar to edit the filter of the listed patchsets
+    pub fn start_filter(&mut self) {
+        self.filter_prompt = Some(FilterPrompt {
+            input: self.filter_text.clone(),
+            error: None,
+            previous_filter: self.filter_text.clone(),
+        });
+    }
+
+    /// Appends `c` to the filter being typed and applies it
+    pub fn push_filter_char(&mut self, c: char) {
+        if let Some(prompt) = self.filter_prompt.as_mut() {
+            prompt.input.push(c);
+            self.update_filter();
+        }
+    }
+
+    /// Removes the last character of the filter being typed and applies it
+    pub fn pop_filter_char(&mut self) {
+        if let Some(prompt) = self.filter_prompt.as_mut() {
+            prompt.input.pop();
+            self.update_filter();
+        }
+    }
+
+    /// Applies the filter being typed, if valid, so the list is filtered as
+    /// it's typed.
+    fn update_filter(&mut self) {
+        let Some(input) = self
+            .filter_prompt
+            .as_ref()
+            .map(|prompt| prompt.input.clone())
+        else {
             return;
+        };
+        let error = self.set_filter(&input).err();
+        if let Some(prompt) = self.filter_prompt.as_mut() {
+            prompt.error = error;
         }
-        if self.patchset_index > self.page_size * (&self.page_number - 1) {
-            self.patchset_index -= 1;
+    }
+
+    /// Closes the filter bar, keeping the last valid filter typed in it
+    pub fn confirm_filter(&mut self) {
+        self.filter_prompt = None;
+    }
+
+    /// Closes the filter bar, restoring the filter from before it was opened
+    pub fn cancel_filter(&mut self) {
+        if let Some(prompt) = self.filter_prompt.take() {
+            let _ = self.set_filter(&prompt.previous_filter);
         }
     }
 
+    /// Lists patchsets in order `sort`, keeping the selected one
+    pub fn set_sort(&mut self, sort: PatchsetSort) {
+        self.sort = sort;
+    }
+
+    /// Lists patchsets in the next order (see [PatchsetSort::next])
+    pub fn cycle_sort(&mut self) {
+        self.set_sort(self.sort.next());
+    }
+
+    /// Records that the patchset with Message-ID `message_id` has
+    /// `review_count` code-review trailers, to sort by it.
+    pub fn set_review_count(&mut self, message_id: &str, review_count: usize) {
+        self.review_counts
+            .insert(message_id.to_string(), review_count);
+    }
+
+    pub fn toggle_my_subsystems_filter(&mut self) {
+        self.my_subsystems_filter = !self.my_subsystems_filter;
+        self.select_first_listed_patchset();
+    }
+
+    /// Returns the patchsets fetched so far that weren't classified as
+    /// touching subsystems of the user or not.
+    pub fn unclassified_patchsets(&self) -> Vec<Patch> {
+        self.processed_patchsets()
+            .into_iter()
+            .filter(|patch| {
+                !self
+                    .touches_my_subsystems
+                    .contains_key(&patch.message_id().href)
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn set_touches_my_subsystems(&mut self, message_id: &str, touches: bool) {
+        self.touches_my_subsystems
+            .insert(message_id.to_string(), touches);
+    }
+
     pub fn increment_page(&mut self) {
         let patchsets_processed: usize = self.lore_session.representative_patches_ids().len();
         if self.page_size * self.page_number > patchsets_processed {
             return;
         }
         self.page_number += 1;
-        self.patchset_index = self.page_size * (&self.page_number - 1);
+        self.patchset_index = None;
     }
 
     pub fn decrement_page(&mut self) {
@@ -80,25 +302,45 @@ impl LatestPatchsets {
             return;
         }
         self.page_number -= 1;
-        self.patchset_index = self.page_size * (&self.page_number - 1);
+        self.select_top_patchset();
+    }
+
+    /// Goes to page `page_number`, fetching the patchsets up to it. The
+    /// current page is kept if there aren't enough patchsets.
+    pub fn go_to_page(&mut self, page_number: usize) -> color_eyre::Result<()> {
+        if page_number == 0 {
+            bail!("Pages start at 1");
+        }
+
+        let current_page_number = self.page_number;
+        self.page_number = page_number;
+        self.fetch_current_page()?;
+        if self.processed_patchsets_count() <= self.page_size * (page_number - 1) {
+            self.page_number = current_page_number;
+            bail!("There are less than {page_number} pages of patchsets");
+        }
+
+        self.select_top_patchset();
+        Ok(())
     }
 
-    pub fn get_selected_patchset(&self) -> Patch {
-        let message_id: &str = self
+    /// Returns the selected patchset, if any is listed in the current page
+    pub fn get_selected_patchset(&self) -> Option<Patch> {
+        let message_id = self
             .lore_session
             .representative_patches_ids()
-            .get(self.patchset_index)
-            .unwrap();
+            .get(self.patchset_index?)?;
 
-        self.lore_session
-            .get_processed_patch(message_id)
-            .unwrap()
-            .clone()
+        self.lore_session.get_processed_patch(message_id).cloned()
     }
 
-    pub fn get_current_patch_feed_page(&self) -> Option<Vec<&Patch>> {
+    /// Returns every patchset fetched so far, regardless of the page
+    pub fn processed_patchsets(&self) -> Vec<&Patch> {
         self.lore_session
-            .get_patch_feed_page(self.page_size, self.page_number)
+            .representative_patches_ids()
+            .iter()
+            .filter_map(|message_id| self.lore_session.get_processed_patch(message_id))
+            .collect()
     }
 
     pub fn processed_patchsets_count(&self) -> usize {
@@ -1,18 +1,61 @@
+use std::{cmp::Reverse, collections::HashMap, io};
+
 use color_eyre::eyre::bail;
 use patch_hub::lore::{
     lore_api_client::BlockingLoreAPIClient, lore_session, mailing_list::MailingList,
 };
 
+use crate::{
+    app::{
+        config::Config,
+        list_activity::{self, ListActivity},
+        logging::{LogLevel, Logger},
+    },
+    log_on_error,
+};
+
+#[cfg(test)]
+mod tests;
+
 pub struct MailingListSelection {
     pub mailing_lists: Vec<MailingList>,
     pub target_list: String,
+    /// Lists matching [MailingListSelection::target_list], in the order they
+    /// are shown (see [MailingListSelection::process_possible_mailing_lists])
     pub possible_mailing_lists: Vec<MailingList>,
     pub highlighted_list_index: usize,
     pub mailing_lists_path: String,
+    /// Names of the lists pinned to the top
+    pub favorite_lists: Vec<String>,
+    pub favorite_lists_path: String,
+    /// Activity of each list whose latest patchsets were fetched
+    pub list_activity: HashMap<String, ListActivity>,
+    pub list_activity_path: String,
     pub lore_api_client: BlockingLoreAPIClient,
 }
 
 impl MailingListSelection {
+    /// Creates the selection with the available lists, favorite lists and
+    /// activity of lists saved in the paths of `config`
+    pub fn new(config: &Config, lore_api_client: BlockingLoreAPIClient) -> Self {
+        let mut mailing_list_selection = MailingListSelection {
+            mailing_lists: lore_session::load_available_lists(config.mailing_lists_path())
+                .unwrap_or_default(),
+            target_list: String::new(),
+            possible_mailing_lists: Vec::new(),
+            highlighted_list_index: 0,
+            mailing_lists_path: config.mailing_lists_path().to_string(),
+            favorite_lists: load_favorite_lists(config.favorite_lists_path()).unwrap_or_default(),
+            favorite_lists_path: config.favorite_lists_path().to_string(),
+            list_activity: list_activity::load_list_activity(config.list_activity_path())
+                .unwrap_or_default(),
+            list_activity_path: config.list_activity_path().to_string(),
+            lore_api_client,
+        };
+        mailing_list_selection.process_possible_mailing_lists();
+        mailing_list_selection
+    }
+
     pub fn refresh_available_mailing_lists(&mut self) -> color_eyre::Result<()> {
         match lore_session::fetch_available_lists(&self.lore_api_client) {
             Ok(available_mailing_lists) => {
@@ -47,16 +90,37 @@ impl MailingListSelection {
         self.process_possible_mailing_lists();
     }
 
+    /// Lists the mailing lists whose name or description fuzzy-matches the
+    /// target list (see [fuzzy_score]). The list named exactly like it comes
+    /// first, then the favorite lists, then the others, from the best match to
+    /// the worst.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_list_favorites_first]
+    /// [tests::should_fuzzy_match_lists]
     fn process_possible_mailing_lists(&mut self) {
-        let mut possible_mailing_lists: Vec<MailingList> = Vec::new();
+        let mut possible_mailing_lists: Vec<(&MailingList, usize)> = self
+            .mailing_lists
+            .iter()
+            .filter_map(|mailing_list| {
+                list_score(&self.target_list, mailing_list).map(|score| (mailing_list, score))
+            })
+            .collect();
 
-        for mailing_list in &self.mailing_lists {
-            if mailing_list.name().starts_with(&self.target_list) {
-                possible_mailing_lists.push(mailing_list.clone());
-            }
-        }
+        possible_mailing_lists.sort_by_key(|(mailing_list, score)| {
+            (
+                mailing_list.name() != &self.target_list,
+                !self.is_favorite(mailing_list.name()),
+                Reverse(*score),
+                mailing_list.name().clone(),
+            )
+        });
 
-        self.possible_mailing_lists = possible_mailing_lists;
+        self.possible_mailing_lists = possible_mailing_lists
+            .into_iter()
+            .map(|(mailing_list, _)| mailing_list.clone())
+            .collect();
         self.highlighted_list_index = 0;
     }
 
@@ -79,4 +143,91 @@ impl MailingListSelection {
         }
         false
     }
+
+    pub fn is_favorite(&self, list_name: &str) -> bool {
+        self.favorite_lists.iter().any(|name| name == list_name)
+    }
+
+    /// Pins the highlighted list to the top, or unpins it if it's a favorite,
+    /// and saves the favorite lists. The list stays highlighted.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_list_favorites_first]
+    pub fn toggle_highlighted_list_favorite(&mut self) -> io::Result<()> {
+        let Some(mailing_list) = self.possible_mailing_lists.get(self.highlighted_list_index)
+        else {
+            return Ok(());
+        };
+        let list_name = mailing_list.name().clone();
+
+        if self.is_favorite(&list_name) {
+            self.favorite_lists.retain(|name| name != &list_name);
+        } else {
+            self.favorite_lists.push(list_name.clone());
+        }
+        self.process_possible_mailing_lists();
+        self.highlighted_list_index = self
+            .possible_mailing_lists
+            .iter()
+            .position(|mailing_list| mailing_list.name() == &list_name)
+            .unwrap_or_default();
+
+        save_favorite_lists(&self.favorite_lists, &self.favorite_lists_path)
+    }
+
+    /// Records `activity` as the activity of `list_name` and saves it
+    pub fn record_list_activity(&mut self, list_name: &str, activity: ListActivity) {
+        self.list_activity.insert(list_name.to_string(), activity);
+        let _ = log_on_error!(
+            LogLevel::Warning,
+            list_activity::save_list_activity(&self.list_activity, &self.list_activity_path)
+        );
+    }
+}
+
+/// Returns how well `mailing_list` matches `query`, if it does. Matches of the
+/// name score higher than matches of the description.
+fn list_score(query: &str, mailing_list: &MailingList) -> Option<usize> {
+    let name_score = fuzzy_score(query, mailing_list.name()).map(|score| 2 * score);
+    let description_score = fuzzy_score(query, mailing_list.description());
+    name_score.max(description_score)
+}
+
+/// Returns how well `query` fuzzy-matches `text`, if all its characters appear
+/// in `text` in the same order, ignoring case. Each matched character scores,
+/// with a bonus for consecutive characters and for characters starting `text`
+/// or a word of it.
+///
+/// # Tests
+///
+/// [tests::should_fuzzy_score_text]
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut text_index = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.to_lowercase().chars() {
+        let match_index = (text_index..text.len()).find(|&i| text[i] == query_char)?;
+        score += 1;
+        if match_index > 0 && previous_match == Some(match_index - 1) {
+            score += 2;
+        }
+        if match_index == 0 || !text[match_index - 1].is_alphanumeric() {
+            score += 2;
+        }
+        previous_match = Some(match_index);
+        text_index = match_index + 1;
+    }
+
+    Some(score)
+}
+
+pub fn save_favorite_lists(favorite_lists: &[String], filepath: &str) -> io::Result<()> {
+    lore_session::save_json(favorite_lists, filepath)
+}
+
+pub fn load_favorite_lists(filepath: &str) -> io::Result<Vec<String>> {
+    lore_session::load_json(filepath)
 }
@@ -0,0 +1,153 @@
+use std::fs;
+
+use super::*;
+
+fn mailing_list_selection(favorite_lists_path: &str) -> MailingListSelection {
+    let mut mailing_list_selection = MailingListSelection {
+        mailing_lists: vec![
+            MailingList::new("amd-gfx", "Discussion list for AMD gfx"),
+            MailingList::new("linux", "Linux kernel"),
+            MailingList::new("linux-media", "Linux Media Subsystem"),
+            MailingList::new("linux-nfs", "Linux NFS mailing list"),
+            MailingList::new("netdev", "Networking development"),
+            MailingList::new("netfilter", "Netfilter discussion"),
+        ],
+        target_list: String::new(),
+        possible_mailing_lists: Vec::new(),
+        highlighted_list_index: 0,
+        mailing_lists_path: String::new(),
+        favorite_lists: Vec::new(),
+        favorite_lists_path: favorite_lists_path.to_string(),
+        list_activity: HashMap::new(),
+        list_activity_path: String::new(),
+        lore_api_client: BlockingLoreAPIClient::default(),
+    };
+    mailing_list_selection.clear_target_list();
+    mailing_list_selection
+}
+
+fn possible_names(mailing_list_selection: &MailingListSelection) -> Vec<&str> {
+    mailing_list_selection
+        .possible_mailing_lists
+        .iter()
+        .map(|mailing_list| mailing_list.name().as_str())
+        .collect()
+}
+
+#[test]
+fn should_fuzzy_score_text() {
+    assert_eq!(Some(0), fuzzy_score("", "netdev"));
+    assert_eq!(None, fuzzy_score("vedten", "netdev"));
+    assert_eq!(None, fuzzy_score("netdevs", "netdev"));
+    assert!(
+        fuzzy_score("NET", "netdev").is_some(),
+        "Case should be ignored"
+    );
+
+    assert!(
+        fuzzy_score("net", "netdev") > fuzzy_score("net", "linux-nfs-test"),
+        "Consecutive matches at the start should score higher"
+    );
+    assert!(
+        fuzzy_score("lm", "linux-media") > fuzzy_score("lm", "alsamixer"),
+        "Matches at the start of words should score higher"
+    );
+}
+
+#[test]
+fn should_fuzzy_match_lists() {
+    let mut mailing_list_selection = mailing_list_selection("");
+
+    for ch in "lnx".chars() {
+        mailing_list_selection.push_char_to_target_list(ch);
+    }
+    assert_eq!(
+        vec!["linux", "linux-media", "linux-nfs"],
+        possible_names(&mailing_list_selection)
+    );
+
+    mailing_list_selection.clear_target_list();
+    for ch in "gfx".chars() {
+        mailing_list_selection.push_char_to_target_list(ch);
+    }
+    assert_eq!(vec!["amd-gfx"], possible_names(&mailing_list_selection));
+
+    mailing_list_selection.clear_target_list();
+    for ch in "network".chars() {
+        mailing_list_selection.push_char_to_target_list(ch);
+    }
+    assert_eq!(
+        vec!["netdev"],
+        possible_names(&mailing_list_selection),
+        "Descriptions should be matched"
+    );
+
+    mailing_list_selection.clear_target_list();
+    for ch in "netf".chars() {
+        mailing_list_selection.push_char_to_target_list(ch);
+    }
+    assert_eq!(
+        "netfilter",
+        possible_names(&mailing_list_selection)[0],
+        "The best match should be first"
+    );
+}
+
+#[test]
+fn should_list_favorites_first() {
+    let dir = std::env::temp_dir().join(format!("patch-hub-favorite-lists-{}", std::process::id()));
+    let path = dir.join("favorite_lists.json");
+    let path = path.to_str().unwrap();
+    let mut mailing_list_selection = mailing_list_selection(path);
+
+    mailing_list_selection.highlighted_list_index = 4;
+    mailing_list_selection
+        .toggle_highlighted_list_favorite()
+        .unwrap();
+    assert_eq!(
+        vec![
+            "netdev",
+            "amd-gfx",
+            "linux",
+            "linux-media",
+            "linux-nfs",
+            "netfilter"
+        ],
+        possible_names(&mailing_list_selection)
+    );
+    assert_eq!(
+        0, mailing_list_selection.highlighted_list_index,
+        "The list toggled should stay highlighted"
+    );
+    assert_eq!(vec!["netdev"], load_favorite_lists(path).unwrap());
+
+    for ch in "net".chars() {
+        mailing_list_selection.push_char_to_target_list(ch);
+    }
+    assert_eq!(
+        vec!["netdev", "netfilter", "linux-media"],
+        possible_names(&mailing_list_selection)
+    );
+
+    // Unpin netdev and pin linux-nfs
+    mailing_list_selection.clear_target_list();
+    mailing_list_selection
+        .toggle_highlighted_list_favorite()
+        .unwrap();
+    mailing_list_selection.highlighted_list_index = 3;
+    mailing_list_selection
+        .toggle_highlighted_list_favorite()
+        .unwrap();
+    assert_eq!(vec!["linux-nfs"], load_favorite_lists(path).unwrap());
+
+    for ch in "linux".chars() {
+        mailing_list_selection.push_char_to_target_list(ch);
+    }
+    assert_eq!(
+        vec!["linux", "linux-nfs", "linux-media"],
+        possible_names(&mailing_list_selection),
+        "The list named exactly like the target should come before favorites"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,27 @@
+use patch_hub::lore::patch::Patch;
+
+/// Screen with the jump list, the patchsets recently viewed (most recent
+/// first), which is kept across runs
+pub struct RecentPatchsets {
+    pub recent_patchsets: Vec<Patch>,
+    pub patchset_index: usize,
+}
+
+impl RecentPatchsets {
+    pub fn select_below_patchset(&mut self) {
+        if self.patchset_index + 1 < self.recent_patchsets.len() {
+            self.patchset_index += 1;
+        }
+    }
+
+    pub fn select_above_patchset(&mut self) {
+        self.patchset_index = self.patchset_index.saturating_sub(1);
+    }
+
+    pub fn get_selected_patchset(&self) -> Patch {
+        self.recent_patchsets
+            .get(self.patchset_index)
+            .unwrap()
+            .clone()
+    }
+}
@@ -0,0 +1,101 @@
+use crate::app::{
+    config::Config,
+    screens::details_actions::validate_kernel_tree_path,
+    worktree::{self, Worktree},
+};
+
+pub struct Worktrees {
+    /// Worktrees created to apply patchsets, grouped by kernel tree
+    pub worktrees: Vec<WorktreeEntry>,
+    pub worktree_index: usize,
+}
+
+pub struct WorktreeEntry {
+    /// Name of the kernel tree of the worktree
+    pub kernel_tree_id: String,
+    /// Path of the kernel tree of the worktree
+    pub kernel_tree_path: String,
+    pub worktree: Worktree,
+}
+
+impl Worktrees {
+    /// Lists the worktrees under [Config::worktrees_dir] of every registered
+    /// kernel tree, sorted by the name of their kernel tree. Kernel trees that
+    /// aren't git repositories are left out, as are kernel trees sharing the
+    /// path of another one, so their worktrees aren't listed twice.
+    pub fn new(config: &Config) -> Self {
+        let mut kernel_tree_ids: Vec<&String> = config.kernel_trees().into_iter().collect();
+        kernel_tree_ids.sort();
+
+        let mut listed_paths: Vec<&String> = Vec::new();
+        let mut worktrees = Vec::new();
+        for id in kernel_tree_ids {
+            let Some(kernel_tree) = config.get_kernel_tree(id) else {
+                continue;
+            };
+            if listed_paths.contains(&kernel_tree.path())
+                || validate_kernel_tree_path(kernel_tree).is_err()
+            {
+                continue;
+            }
+            listed_paths.push(kernel_tree.path());
+
+            let Ok(kernel_tree_worktrees) =
+                worktree::list_worktrees(kernel_tree.path(), config.worktrees_dir())
+            else {
+                continue;
+            };
+            worktrees.extend(
+                kernel_tree_worktrees
+                    .into_iter()
+                    .map(|worktree| WorktreeEntry {
+                        kernel_tree_id: id.to_string(),
+                        kernel_tree_path: kernel_tree.path().to_string(),
+                        worktree,
+                    }),
+            );
+        }
+
+        Worktrees {
+            worktrees,
+            worktree_index: 0,
+        }
+    }
+
+    pub fn select_below_worktree(&mut self) {
+        if self.worktree_index + 1 < self.worktrees.len() {
+            self.worktree_index += 1;
+        }
+    }
+
+    pub fn select_above_worktree(&mut self) {
+        self.worktree_index = self.worktree_index.saturating_sub(1);
+    }
+
+    pub fn get_selected_worktree(&self) -> Option<&Worktree> {
+        self.worktrees
+            .get(self.worktree_index)
+            .map(|entry| &entry.worktree)
+    }
+
+    /// Removes the selected worktree (see [worktree::remove_worktree]). Unless
+    /// `force` is set, git refuses to remove a worktree with changes.
+    ///
+    /// Returns `()` on success and a `String` with the error message on failure.
+    pub fn remove_selected_worktree(&mut self, force: bool) -> Result<(), String> {
+        let Some(selected_entry) = self.worktrees.get(self.worktree_index) else {
+            return Ok(());
+        };
+        worktree::remove_worktree(
+            &selected_entry.kernel_tree_path,
+            selected_entry.worktree.path(),
+            force,
+        )?;
+
+        self.worktrees.remove(self.worktree_index);
+        if self.worktree_index >= self.worktrees.len() {
+            self.select_above_worktree();
+        }
+        Ok(())
+    }
+}
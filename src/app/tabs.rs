@@ -0,0 +1,198 @@
+use std::{mem, str::FromStr};
+
+use super::{
+    navigation::NavigationHistory,
+    screens::{details_actions::DetailsActions, latest::LatestPatchsets, CurrentScreen},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Screens that belong to tabs. Tabs can only be switched from them, as the
+/// other screens (like the kernel trees management) are shared by every tab.
+pub const TAB_SCREENS: &[CurrentScreen] = &[
+    CurrentScreen::MailingListSelection,
+    CurrentScreen::BookmarkedPatchsets,
+    CurrentScreen::RecentPatchsets,
+    CurrentScreen::LatestPatchsets,
+    CurrentScreen::PatchsetDetails,
+];
+
+/// State of a tab of the workspace. Each tab browses mailing lists and
+/// patchsets independently of the others, with its own latest patchsets (and
+/// Lore session), patchset details and navigation history.
+pub struct Tab {
+    pub current_screen: CurrentScreen,
+    pub latest_patchsets: Option<LatestPatchsets>,
+    pub details_actions: Option<DetailsActions>,
+    pub navigation_history: NavigationHistory,
+}
+
+impl Default for Tab {
+    fn default() -> Self {
+        Tab {
+            current_screen: CurrentScreen::MailingListSelection,
+            latest_patchsets: None,
+            details_actions: None,
+            navigation_history: NavigationHistory::default(),
+        }
+    }
+}
+
+impl Tab {
+    /// Returns the title of the tab, describing what it shows, like the
+    /// mailing list or the title of the patchset
+    pub fn title(&self) -> String {
+        tab_title(
+            &self.current_screen,
+            self.latest_patchsets.as_ref(),
+            self.details_actions.as_ref(),
+        )
+    }
+}
+
+/// Returns the title of a tab in `screen` with `latest_patchsets` and
+/// `details_actions` (see [Tab::title])
+pub fn tab_title(
+    screen: &CurrentScreen,
+    latest_patchsets: Option<&LatestPatchsets>,
+    details_actions: Option<&DetailsActions>,
+) -> String {
+    match (screen, latest_patchsets, details_actions) {
+        (CurrentScreen::PatchsetDetails, _, Some(details_actions)) => {
+            details_actions.representative_patch.title().to_string()
+        }
+        (CurrentScreen::LatestPatchsets, Some(latest_patchsets), _) => {
+            let search_query = latest_patchsets.lore_session().search_query();
+            if search_query.is_empty() {
+                latest_patchsets.target_list().to_string()
+            } else {
+                format!("{} ({search_query})", latest_patchsets.target_list())
+            }
+        }
+        (CurrentScreen::BookmarkedPatchsets, _, _) => "Bookmarked".to_string(),
+        (CurrentScreen::RecentPatchsets, _, _) => "Recent".to_string(),
+        (CurrentScreen::EditConfig, _, _) => "Config".to_string(),
+        (CurrentScreen::Worktrees, _, _) => "Worktrees".to_string(),
+        (CurrentScreen::KernelTrees, _, _) => "Kernel trees".to_string(),
+        (CurrentScreen::ApplyHistory, _, _) => "Apply history".to_string(),
+        _ => "Mailing lists".to_string(),
+    }
+}
+
+/// Tabs of the workspace and which one is active. The state of the active tab
+/// is kept out of here (in [crate::app::App]), so its entry is a placeholder
+/// swapped with the state of another tab when switching to it.
+pub struct Tabs {
+    tabs: Vec<Tab>,
+    active: usize,
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Tabs {
+            tabs: vec![Tab::default()],
+            active: 0,
+        }
+    }
+}
+
+impl Tabs {
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Index of the active tab
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Returns the tabs, with a placeholder in place of the active one
+    pub fn iter(&self) -> impl Iterator<Item = &Tab> {
+        self.tabs.iter()
+    }
+
+    /// Makes tab `index` the active one, swapping its state with `active_tab`,
+    /// the state of the currently active tab. Returns if there is such a tab.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_switch_tabs]
+    pub fn switch(&mut self, index: usize, active_tab: &mut Tab) -> bool {
+        if index >= self.tabs.len() {
+            return false;
+        }
+        mem::swap(&mut self.tabs[self.active], active_tab);
+        mem::swap(&mut self.tabs[index], active_tab);
+        self.active = index;
+        true
+    }
+
+    /// Switches to the tab after the active one, or to the first one
+    pub fn switch_to_next(&mut self, active_tab: &mut Tab) {
+        let index = (self.active + 1) % self.tabs.len();
+        self.switch(index, active_tab);
+    }
+
+    /// Switches to the tab before the active one, or to the last one
+    pub fn switch_to_previous(&mut self, active_tab: &mut Tab) {
+        let index = (self.active + self.tabs.len() - 1) % self.tabs.len();
+        self.switch(index, active_tab);
+    }
+
+    /// Opens a new tab after the active one and switches to it
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_open_and_close_tabs]
+    pub fn open(&mut self, active_tab: &mut Tab) {
+        self.tabs[self.active] = mem::take(active_tab);
+        self.active += 1;
+        self.tabs.insert(self.active, Tab::default());
+    }
+
+    /// Closes the active tab, discarding `active_tab`, and switches to the
+    /// next one (or the previous one, if it was the last). Returns if the tab
+    /// was closed, which it isn't if it is the only one.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_open_and_close_tabs]
+    pub fn close(&mut self, active_tab: &mut Tab) -> bool {
+        if self.tabs.len() == 1 {
+            return false;
+        }
+        self.tabs.remove(self.active);
+        self.active = self.active.min(self.tabs.len() - 1);
+        *active_tab = mem::take(&mut self.tabs[self.active]);
+        true
+    }
+}
+
+/// Operation on the tabs of the workspace run from the command line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabCommand {
+    New,
+    Close,
+    Next,
+    Previous,
+    /// Switch to the tab with the given number, starting from 1
+    Go(usize),
+}
+
+impl FromStr for TabCommand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "new" => Ok(TabCommand::New),
+            "close" => Ok(TabCommand::Close),
+            "next" => Ok(TabCommand::Next),
+            "previous" => Ok(TabCommand::Previous),
+            number => match number.parse() {
+                Ok(number) if number > 0 => Ok(TabCommand::Go(number)),
+                _ => Err(format!("Invalid tab '{s}'")),
+            },
+        }
+    }
+}
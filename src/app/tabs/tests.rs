@@ -0,0 +1,104 @@
+use super::*;
+
+fn tab(current_screen: CurrentScreen) -> Tab {
+    Tab {
+        current_screen,
+        ..Default::default()
+    }
+}
+
+/// Returns the screens of the tabs, with the one of `active_tab` in place of
+/// the placeholder of the active tab
+fn screens(tabs: &Tabs, active_tab: &Tab) -> Vec<CurrentScreen> {
+    tabs.iter()
+        .enumerate()
+        .map(|(i, tab)| {
+            if i == tabs.active() {
+                active_tab.current_screen.clone()
+            } else {
+                tab.current_screen.clone()
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn should_switch_tabs() {
+    let mut tabs = Tabs::default();
+    let mut active_tab = tab(CurrentScreen::LatestPatchsets);
+    tabs.open(&mut active_tab);
+    active_tab.current_screen = CurrentScreen::BookmarkedPatchsets;
+    tabs.open(&mut active_tab);
+    active_tab.current_screen = CurrentScreen::PatchsetDetails;
+
+    assert!(tabs.switch(0, &mut active_tab));
+    assert_eq!(0, tabs.active());
+    assert_eq!(CurrentScreen::LatestPatchsets, active_tab.current_screen);
+    assert_eq!(
+        vec![
+            CurrentScreen::LatestPatchsets,
+            CurrentScreen::BookmarkedPatchsets,
+            CurrentScreen::PatchsetDetails
+        ],
+        screens(&tabs, &active_tab),
+        "The state of the tab switched from should be kept"
+    );
+
+    assert!(!tabs.switch(3, &mut active_tab));
+    assert_eq!(0, tabs.active());
+
+    tabs.switch_to_previous(&mut active_tab);
+    assert_eq!(2, tabs.active());
+    assert_eq!(CurrentScreen::PatchsetDetails, active_tab.current_screen);
+    tabs.switch_to_next(&mut active_tab);
+    assert_eq!(0, tabs.active());
+    tabs.switch_to_next(&mut active_tab);
+    assert_eq!(
+        CurrentScreen::BookmarkedPatchsets,
+        active_tab.current_screen
+    );
+}
+
+#[test]
+fn should_open_and_close_tabs() {
+    let mut tabs = Tabs::default();
+    let mut active_tab = tab(CurrentScreen::LatestPatchsets);
+    assert!(!tabs.close(&mut active_tab), "The only tab shouldn't close");
+
+    tabs.open(&mut active_tab);
+    active_tab.current_screen = CurrentScreen::PatchsetDetails;
+    tabs.switch(0, &mut active_tab);
+    tabs.open(&mut active_tab);
+    assert_eq!(
+        1,
+        tabs.active(),
+        "New tabs should open after the active one"
+    );
+    assert_eq!(
+        vec![
+            CurrentScreen::LatestPatchsets,
+            CurrentScreen::MailingListSelection,
+            CurrentScreen::PatchsetDetails
+        ],
+        screens(&tabs, &active_tab)
+    );
+
+    assert!(tabs.close(&mut active_tab));
+    assert_eq!(1, tabs.active());
+    assert_eq!(CurrentScreen::PatchsetDetails, active_tab.current_screen);
+    assert!(tabs.close(&mut active_tab));
+    assert_eq!(0, tabs.active());
+    assert_eq!(CurrentScreen::LatestPatchsets, active_tab.current_screen);
+    assert_eq!(1, tabs.len());
+}
+
+#[test]
+fn should_parse_tab_commands() {
+    assert_eq!(Ok(TabCommand::New), "new".parse());
+    assert_eq!(Ok(TabCommand::Close), "close".parse());
+    assert_eq!(Ok(TabCommand::Next), "next".parse());
+    assert_eq!(Ok(TabCommand::Previous), "previous".parse());
+    assert_eq!(Ok(TabCommand::Go(2)), "2".parse());
+    assert!("0".parse::<TabCommand>().is_err());
+    assert!("foo".parse::<TabCommand>().is_err());
+}
@@ -0,0 +1,353 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Text,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Built-in color schemes of the UI
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ThemePreset {
+    /// For terminals with dark backgrounds
+    #[default]
+    #[serde(rename = "dark")]
+    Dark,
+    /// For terminals with light backgrounds
+    #[serde(rename = "light")]
+    Light,
+    /// Bright colors and bold text, which don't rely on telling red from
+    /// green
+    #[serde(rename = "high-contrast")]
+    HighContrast,
+    /// Only text attributes (like bold and reversed), used when the `NO_COLOR`
+    /// environment variable is set
+    #[serde(rename = "no-color")]
+    NoColor,
+}
+
+impl Display for ThemePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemePreset::Dark => write!(f, "dark"),
+            ThemePreset::Light => write!(f, "light"),
+            ThemePreset::HighContrast => write!(f, "high-contrast"),
+            ThemePreset::NoColor => write!(f, "no-color"),
+        }
+    }
+}
+
+impl FromStr for ThemePreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dark" => Ok(ThemePreset::Dark),
+            "light" => Ok(ThemePreset::Light),
+            "high-contrast" => Ok(ThemePreset::HighContrast),
+            "no-color" => Ok(ThemePreset::NoColor),
+            _ => Err(format!("invalid theme '{s}'")),
+        }
+    }
+}
+
+impl ThemePreset {
+    /// Returns the syntax theme of `bat` (also used by `delta`) that matches
+    /// the preset when rendering the patch preview
+    pub fn syntax_theme(&self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "Monokai Extended",
+            ThemePreset::Light => "GitHub",
+            ThemePreset::HighContrast | ThemePreset::NoColor => "ansi",
+        }
+    }
+}
+
+/// Styles of each role an element of the UI can have, so every screen and
+/// popup is drawn with the same scheme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Preset the theme is built from, which also picks the colors of the
+    /// patch preview
+    pub preset: ThemePreset,
+    /// Titles of screens, blocks and mode footers
+    pub title: Style,
+    /// Labels of fields, separators and unchecked actions
+    pub label: Style,
+    /// Values of fields and regular text
+    pub text: Style,
+    /// Rows of lists, like the ones of patchsets
+    pub item: Style,
+    /// Less relevant text, like placeholders and unknown statuses
+    pub muted: Style,
+    /// Successful statuses, like passed builds, and checked actions
+    pub success: Style,
+    /// Warnings, like the ones of checkpatch
+    pub warning: Style,
+    /// Errors and failed statuses
+    pub error: Style,
+    /// Information to stand out, like text being typed and active filters
+    pub accent: Style,
+    /// Names of mailing lists and other items of secondary emphasis
+    pub emphasis: Style,
+    /// Hints of the keys available in each screen
+    pub hint: Style,
+    /// Titles and hints of popups
+    pub popup_title: Style,
+    /// Item selected in lists
+    pub selected: Style,
+    /// Values being edited in forms
+    pub editing: Style,
+    /// Cursor of forms
+    pub cursor: Style,
+    /// Matches of the search in the patch preview
+    pub search_match: Style,
+    /// Match of the search in the patch preview jumped to
+    pub search_current: Style,
+}
+
+/// Names of the roles of [Theme], as used in the `theme_colors` option of the
+/// configuration
+const ROLES: [&str; 17] = [
+    "title",
+    "label",
+    "text",
+    "item",
+    "muted",
+    "success",
+    "warning",
+    "error",
+    "accent",
+    "emphasis",
+    "hint",
+    "popup_title",
+    "selected",
+    "editing",
+    "cursor",
+    "search_match",
+    "search_current",
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::preset(ThemePreset::Dark)
+    }
+}
+
+impl Theme {
+    /// Builds the theme of `preset` with the styles of the roles in
+    /// `overrides` (like `{"title": "bold light-green"}`) replaced. If
+    /// `no_color` is set (see [no_color_requested]), the no-color preset is
+    /// used and the overrides are ignored.
+    ///
+    /// Returns the theme and the errors of the overrides that were ignored.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_override_theme_roles]
+    /// [tests::should_ignore_colors_if_no_color_is_requested]
+    pub fn new(
+        preset: ThemePreset,
+        overrides: &HashMap<String, String>,
+        no_color: bool,
+    ) -> (Self, Vec<String>) {
+        if no_color {
+            return (Theme::preset(ThemePreset::NoColor), Vec::new());
+        }
+
+        let mut theme = Theme::preset(preset);
+        let mut errors = Vec::new();
+        let mut overrides: Vec<_> = overrides.iter().collect();
+        overrides.sort();
+        for (role, style) in overrides {
+            match parse_style(style) {
+                Ok(style) => match theme.role_mut(role) {
+                    Some(role_style) => *role_style = style,
+                    None => errors.push(format!(
+                        "invalid role '{role}', expected one of: {}",
+                        ROLES.join(", ")
+                    )),
+                },
+                Err(error) => errors.push(format!("{error} for role '{role}'")),
+            }
+        }
+
+        (theme, errors)
+    }
+
+    /// Returns the built-in theme of `preset`
+    pub fn preset(preset: ThemePreset) -> Self {
+        let fg = |color| Style::default().fg(color);
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let reversed = Style::default().add_modifier(Modifier::REVERSED);
+
+        match preset {
+            ThemePreset::Dark => Theme {
+                preset: ThemePreset::Dark,
+                title: fg(Color::Green),
+                label: fg(Color::Cyan),
+                text: fg(Color::White),
+                item: fg(Color::Yellow),
+                muted: fg(Color::DarkGray),
+                success: fg(Color::Green),
+                warning: fg(Color::Yellow),
+                error: fg(Color::Red),
+                accent: fg(Color::Yellow),
+                emphasis: fg(Color::Magenta),
+                hint: fg(Color::Red),
+                popup_title: bold.fg(Color::Blue),
+                selected: bold.patch(reversed).fg(Color::Cyan),
+                editing: fg(Color::LightYellow),
+                cursor: Style::default().bg(Color::White),
+                search_match: fg(Color::Black).bg(Color::Yellow),
+                search_current: bold.fg(Color::Black).bg(Color::LightRed),
+            },
+            // The 256-color palette has darker shades that stay readable on
+            // white, unlike yellow and the light colors
+            ThemePreset::Light => Theme {
+                preset: ThemePreset::Light,
+                title: fg(Color::Indexed(22)),
+                label: fg(Color::Indexed(25)),
+                text: Style::default(),
+                item: fg(Color::Indexed(94)),
+                muted: fg(Color::Indexed(244)),
+                success: fg(Color::Indexed(28)),
+                warning: fg(Color::Indexed(130)),
+                error: fg(Color::Indexed(124)),
+                accent: fg(Color::Indexed(130)),
+                emphasis: fg(Color::Indexed(90)),
+                hint: fg(Color::Indexed(124)),
+                popup_title: bold.fg(Color::Indexed(25)),
+                selected: bold.patch(reversed).fg(Color::Indexed(25)),
+                editing: bold.fg(Color::Indexed(130)),
+                cursor: Style::default().bg(Color::Black),
+                search_match: fg(Color::Black).bg(Color::Indexed(228)),
+                search_current: bold.fg(Color::White).bg(Color::Indexed(124)),
+            },
+            ThemePreset::HighContrast => Theme {
+                preset: ThemePreset::HighContrast,
+                title: bold.fg(Color::LightCyan),
+                label: fg(Color::LightCyan),
+                text: fg(Color::White),
+                item: fg(Color::White),
+                muted: fg(Color::Gray),
+                success: bold.fg(Color::LightBlue),
+                warning: bold.fg(Color::LightYellow),
+                error: bold.fg(Color::LightMagenta),
+                accent: fg(Color::LightYellow),
+                emphasis: bold.fg(Color::White),
+                hint: fg(Color::LightYellow),
+                popup_title: bold.fg(Color::LightCyan),
+                selected: bold.fg(Color::Black).bg(Color::LightYellow),
+                editing: bold.fg(Color::LightYellow),
+                cursor: Style::default().bg(Color::White),
+                search_match: fg(Color::Black).bg(Color::LightYellow),
+                search_current: bold.fg(Color::Black).bg(Color::LightCyan),
+            },
+            ThemePreset::NoColor => Theme {
+                preset: ThemePreset::NoColor,
+                title: bold,
+                label: Style::default(),
+                text: Style::default(),
+                item: Style::default(),
+                muted: Style::default().add_modifier(Modifier::DIM),
+                success: Style::default(),
+                warning: bold,
+                error: bold,
+                accent: bold,
+                emphasis: bold,
+                hint: Style::default(),
+                popup_title: bold,
+                selected: bold.patch(reversed),
+                editing: bold,
+                cursor: reversed,
+                search_match: reversed,
+                search_current: bold.patch(reversed).add_modifier(Modifier::UNDERLINED),
+            },
+        }
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Style> {
+        let style = match role {
+            "title" => &mut self.title,
+            "label" => &mut self.label,
+            "text" => &mut self.text,
+            "item" => &mut self.item,
+            "muted" => &mut self.muted,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "error" => &mut self.error,
+            "accent" => &mut self.accent,
+            "emphasis" => &mut self.emphasis,
+            "hint" => &mut self.hint,
+            "popup_title" => &mut self.popup_title,
+            "selected" => &mut self.selected,
+            "editing" => &mut self.editing,
+            "cursor" => &mut self.cursor,
+            "search_match" => &mut self.search_match,
+            "search_current" => &mut self.search_current,
+            _ => return None,
+        };
+        Some(style)
+    }
+}
+
+/// Returns if the user requested no colors, by setting the `NO_COLOR`
+/// environment variable to a non-empty value (see <https://no-color.org>)
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// Removes every style of `text`, like the colors of the patch preview
+/// rendered by external programs when no colors are requested.
+///
+/// # Tests
+///
+/// [tests::should_strip_styles]
+pub fn strip_styles(text: &mut Text) {
+    text.style = Style::default();
+    for line in &mut text.lines {
+        line.style = Style::default();
+        for span in &mut line.spans {
+            span.style = Style::default();
+        }
+    }
+}
+
+/// Parses styles like `yellow`, `bold light-red`, `#ff8800 on black` or
+/// `reversed 33`: attributes, then the foreground color and, after `on`, the
+/// background color. Colors are names, `#rrggbb` or indexes of the 256-color
+/// palette.
+///
+/// # Tests
+///
+/// [tests::should_parse_styles]
+pub fn parse_style(s: &str) -> Result<Style, String> {
+    let mut style = Style::default();
+    let mut words = s.split_whitespace();
+    while let Some(word) = words.next() {
+        style = match word.to_ascii_lowercase().as_str() {
+            "bold" => style.add_modifier(Modifier::BOLD),
+            "dim" => style.add_modifier(Modifier::DIM),
+            "italic" => style.add_modifier(Modifier::ITALIC),
+            "underlined" => style.add_modifier(Modifier::UNDERLINED),
+            "reversed" => style.add_modifier(Modifier::REVERSED),
+            "on" => match words.next() {
+                Some(color) => style.bg(parse_color(color)?),
+                None => return Err(format!("missing background color in style '{s}'")),
+            },
+            _ if style.fg.is_some() => {
+                return Err(format!("unexpected '{word}' in style '{s}'"));
+            }
+            color => style.fg(parse_color(color)?),
+        };
+    }
+    Ok(style)
+}
+
+fn parse_color(s: &str) -> Result<Color, String> {
+    Color::from_str(s).map_err(|_| format!("invalid color '{s}'"))
+}
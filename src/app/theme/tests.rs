@@ -0,0 +1,124 @@
+use super::*;
+
+use ratatui::text::{Line, Span};
+
+#[test]
+fn should_parse_styles() {
+    assert_eq!(
+        Ok(Style::default().fg(Color::Yellow)),
+        parse_style("yellow")
+    );
+    assert_eq!(
+        Ok(Style::default()
+            .fg(Color::LightRed)
+            .add_modifier(Modifier::BOLD)),
+        parse_style("bold light-red")
+    );
+    assert_eq!(
+        Ok(Style::default()
+            .fg(Color::Rgb(0xff, 0x88, 0x00))
+            .bg(Color::Black)),
+        parse_style("#ff8800 on black")
+    );
+    assert_eq!(
+        Ok(Style::default()
+            .fg(Color::Indexed(33))
+            .add_modifier(Modifier::REVERSED)),
+        parse_style("Reversed 33")
+    );
+    assert_eq!(
+        Ok(Style::default().bg(Color::White)),
+        parse_style("on white")
+    );
+    assert_eq!(Ok(Style::default()), parse_style(""));
+
+    assert!(parse_style("blurple").is_err());
+    assert!(parse_style("red on").is_err());
+    assert!(parse_style("red blue").is_err());
+}
+
+#[test]
+fn should_parse_and_display_theme_presets() {
+    for preset in [
+        ThemePreset::Dark,
+        ThemePreset::Light,
+        ThemePreset::HighContrast,
+        ThemePreset::NoColor,
+    ] {
+        assert_eq!(Ok(preset), preset.to_string().parse());
+    }
+    assert!("solarized".parse::<ThemePreset>().is_err());
+}
+
+#[test]
+fn should_override_theme_roles() {
+    let overrides = HashMap::from([
+        ("title".to_string(), "bold magenta".to_string()),
+        ("selected".to_string(), "black on light-green".to_string()),
+        ("titel".to_string(), "red".to_string()),
+        ("error".to_string(), "redish".to_string()),
+    ]);
+
+    let (theme, errors) = Theme::new(ThemePreset::Light, &overrides, false);
+
+    let light = Theme::preset(ThemePreset::Light);
+    assert_eq!(
+        Theme {
+            title: Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+            selected: Style::default().fg(Color::Black).bg(Color::LightGreen),
+            ..light
+        },
+        theme
+    );
+    assert_eq!(
+        2,
+        errors.len(),
+        "Invalid roles and styles should be reported"
+    );
+}
+
+#[test]
+fn should_ignore_colors_if_no_color_is_requested() {
+    let overrides = HashMap::from([("title".to_string(), "magenta".to_string())]);
+
+    let (theme, errors) = Theme::new(ThemePreset::Dark, &overrides, true);
+
+    assert_eq!(Theme::preset(ThemePreset::NoColor), theme);
+    assert_eq!(ThemePreset::NoColor, theme.preset);
+    assert!(errors.is_empty());
+
+    let no_color = Theme::preset(ThemePreset::NoColor);
+    for style in [
+        no_color.title,
+        no_color.error,
+        no_color.selected,
+        no_color.search_match,
+    ] {
+        assert_eq!(None, style.fg);
+        assert_eq!(None, style.bg);
+    }
+}
+
+#[test]
+fn should_strip_styles() {
+    let mut text = Text::from(vec![
+        Line::from(vec![
+            Span::styled("+added", Style::default().fg(Color::Green)),
+            Span::raw(" line"),
+        ]),
+        Line::styled("-removed", Style::default().bg(Color::Red)),
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    strip_styles(&mut text);
+
+    assert_eq!(
+        Text::from(vec![
+            Line::from(vec![Span::raw("+added"), Span::raw(" line")]),
+            Line::from("-removed"),
+        ]),
+        text
+    );
+}
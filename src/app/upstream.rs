@@ -0,0 +1,170 @@
+use std::process::Command;
+
+use derive_getters::Getters;
+
+#[cfg(test)]
+mod tests;
+
+/// How a local branch compares to its freshly fetched upstream
+#[derive(Getters, Debug, Clone, Eq, PartialEq)]
+pub struct UpstreamStatus {
+    branch: String,
+    /// Upstream ref of the branch, like `origin/master`
+    upstream: String,
+    /// Number of commits in the local branch that aren't in the upstream
+    ahead: usize,
+    /// Number of commits in the upstream that aren't in the local branch
+    behind: usize,
+}
+
+impl UpstreamStatus {
+    /// Returns a human-readable description of how the local branch compares
+    /// to its upstream. The local branch is never updated, so it is still
+    /// behind after the fetch.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_describe_upstream_status]
+    pub fn describe(&self) -> String {
+        match (self.ahead, self.behind) {
+            (0, 0) => format!("'{}' is up to date with '{}'", self.branch, self.upstream),
+            (0, behind) => format!(
+                "'{}' is {behind} commit(s) behind '{}'",
+                self.branch, self.upstream
+            ),
+            (ahead, 0) => format!(
+                "'{}' is {ahead} commit(s) ahead of '{}'",
+                self.branch, self.upstream
+            ),
+            (ahead, behind) => format!(
+                "'{}' is {behind} commit(s) behind and {ahead} commit(s) ahead of '{}'",
+                self.branch, self.upstream
+            ),
+        }
+    }
+}
+
+/// Parses the output of `git rev-list --left-right --count A...B`, which is
+/// the number of commits only in `A` and the number of commits only in `B`
+/// separated by a tab.
+///
+/// # Tests
+///
+/// [tests::should_parse_left_right_count]
+pub fn parse_left_right_count(output: &str) -> Option<(usize, usize)> {
+    let mut counts = output.split_whitespace().map(str::parse::<usize>);
+    match (counts.next(), counts.next(), counts.next()) {
+        (Some(Ok(left)), Some(Ok(right)), None) => Some((left, right)),
+        _ => None,
+    }
+}
+
+/// Fetches the upstream of `branch` of the repository in `repo_path` from its
+/// remote and compares the local branch to it. Only the upstream branch is
+/// fetched, which updates its remote-tracking ref.
+///
+/// Returns the `UpstreamStatus` or a `String` with the error message on
+/// failure, like when `branch` has no upstream.
+pub fn fetch_upstream(repo_path: &str, branch: &str) -> Result<UpstreamStatus, String> {
+    let upstream = run_git(
+        repo_path,
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            &format!("{branch}@{{upstream}}"),
+        ],
+    )
+    .map_err(|_| format!("branch '{branch}' has no upstream"))?;
+    let remote = run_git(
+        repo_path,
+        &["config", "--get", &format!("branch.{branch}.remote")],
+    )?;
+    let merge = run_git(
+        repo_path,
+        &["config", "--get", &format!("branch.{branch}.merge")],
+    )?;
+
+    // An upstream in the same repository (remote `.`) has nothing to fetch
+    if remote != "." {
+        run_git(repo_path, &["fetch", &remote, &merge])?;
+    }
+
+    let count = run_git(
+        repo_path,
+        &[
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("{branch}...{upstream}"),
+        ],
+    )?;
+    let (ahead, behind) = parse_left_right_count(&count)
+        .ok_or_else(|| format!("unexpected output of `git rev-list`: {count}"))?;
+
+    Ok(UpstreamStatus {
+        branch: branch.to_string(),
+        upstream,
+        ahead,
+        behind,
+    })
+}
+
+/// Chooses the ref to apply on given the outcome of fetching the upstream of
+/// `local_branch` (see [fetch_upstream]), which is `None` if it wasn't
+/// fetched: the fetched upstream if `apply_on_upstream` is set, `local_branch`
+/// otherwise. Failing to fetch (e.g. when offline) doesn't prevent applying on
+/// `local_branch`, it is only reported.
+///
+/// Returns the ref to apply on and the report of the fetch, if any.
+///
+/// # Tests
+///
+/// [tests::should_resolve_apply_base]
+pub fn resolve_apply_base(
+    local_branch: &str,
+    apply_on_upstream: bool,
+    upstream_fetch: Option<&Result<UpstreamStatus, String>>,
+) -> (String, Option<String>) {
+    match upstream_fetch {
+        None => (local_branch.to_string(), None),
+        Some(Ok(status)) if apply_on_upstream => {
+            let report = format!("{}, applied on '{}'", status.describe(), status.upstream);
+            (status.upstream.clone(), Some(report))
+        }
+        Some(Ok(status)) => (
+            local_branch.to_string(),
+            Some(format!(
+                "{}, applied on the local branch",
+                status.describe()
+            )),
+        ),
+        Some(Err(e)) => (
+            local_branch.to_string(),
+            Some(format!("failed to fetch, applied on the local branch: {e}")),
+        ),
+    }
+}
+
+/// Runs `git` with `args` in `repo_path`.
+///
+/// Returns its trimmed stdout on success and a `String` with the error message
+/// on failure.
+fn run_git(repo_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to execute git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`git {}` failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+#[test]
+fn should_parse_left_right_count() {
+    assert_eq!(Some((2, 137)), parse_left_right_count("2\t137\n"));
+    assert_eq!(Some((0, 0)), parse_left_right_count("0\t0"));
+    assert_eq!(None, parse_left_right_count(""));
+    assert_eq!(None, parse_left_right_count("2\t137\t4"));
+    assert_eq!(None, parse_left_right_count("fatal\tbad revision"));
+}
+
+#[test]
+fn should_describe_upstream_status() {
+    let status = |ahead, behind| UpstreamStatus {
+        branch: "master".to_string(),
+        upstream: "origin/master".to_string(),
+        ahead,
+        behind,
+    };
+
+    assert_eq!(
+        "'master' is up to date with 'origin/master'",
+        status(0, 0).describe()
+    );
+    assert_eq!(
+        "'master' is 137 commit(s) behind 'origin/master'",
+        status(0, 137).describe()
+    );
+    assert_eq!(
+        "'master' is 137 commit(s) behind and 2 commit(s) ahead of 'origin/master'",
+        status(2, 137).describe()
+    );
+}
+
+#[test]
+fn should_resolve_apply_base() {
+    let fetched = Ok(UpstreamStatus {
+        branch: "master".to_string(),
+        upstream: "origin/master".to_string(),
+        ahead: 0,
+        behind: 3,
+    });
+
+    assert_eq!(
+        ("master".to_string(), None),
+        resolve_apply_base("master", true, None),
+        "The local branch should be used if the upstream wasn't fetched"
+    );
+    assert_eq!(
+        (
+            "origin/master".to_string(),
+            Some(
+                "'master' is 3 commit(s) behind 'origin/master', applied on 'origin/master'"
+                    .to_string()
+            )
+        ),
+        resolve_apply_base("master", true, Some(&fetched))
+    );
+    assert_eq!(
+        (
+            "master".to_string(),
+            Some(
+                "'master' is 3 commit(s) behind 'origin/master', applied on the local branch"
+                    .to_string()
+            )
+        ),
+        resolve_apply_base("master", false, Some(&fetched))
+    );
+    assert_eq!(
+        (
+            "master".to_string(),
+            Some("failed to fetch, applied on the local branch: offline".to_string())
+        ),
+        resolve_apply_base("master", true, Some(&Err("offline".to_string()))),
+        "Failing to fetch shouldn't prevent applying"
+    );
+}
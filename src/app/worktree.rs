@@ -0,0 +1,140 @@
+use std::{path::Path, process::Command};
+
+use derive_getters::Getters;
+
+#[cfg(test)]
+mod tests;
+
+/// A git worktree of a kernel tree, as listed by `git worktree list`
+#[derive(Getters, Debug, Clone, Eq, PartialEq)]
+pub struct Worktree {
+    path: String,
+    /// Commit checked out in the worktree
+    head: String,
+    /// Branch checked out in the worktree, unless its `HEAD` is detached
+    branch: Option<String>,
+    /// If the directory of the worktree is missing, so it can be pruned
+    prunable: bool,
+}
+
+/// Parses the output of `git worktree list --porcelain`, in which each
+/// worktree is a paragraph of lines like `worktree <path>`, `HEAD <commit>`
+/// and `branch refs/heads/<branch>`.
+///
+/// # Tests
+///
+/// [tests::should_parse_worktree_list]
+pub fn parse_worktree_list(porcelain: &str) -> Vec<Worktree> {
+    let mut worktrees = Vec::new();
+
+    for paragraph in porcelain.split("\n\n") {
+        let mut worktree: Option<Worktree> = None;
+        for line in paragraph.lines() {
+            let (attribute, value) = line.split_once(' ').unwrap_or((line, ""));
+            if attribute == "worktree" {
+                worktree = Some(Worktree {
+                    path: value.to_string(),
+                    head: String::new(),
+                    branch: None,
+                    prunable: false,
+                });
+                continue;
+            }
+            let Some(worktree) = worktree.as_mut() else {
+                continue;
+            };
+            match attribute {
+                "HEAD" => worktree.head = value.to_string(),
+                "branch" => {
+                    let branch = value.strip_prefix("refs/heads/").unwrap_or(value);
+                    worktree.branch = Some(branch.to_string());
+                }
+                "prunable" => worktree.prunable = true,
+                _ => {}
+            }
+        }
+        worktrees.extend(worktree);
+    }
+
+    worktrees
+}
+
+/// Lists the worktrees of the kernel tree in `kernel_tree_path` that are
+/// inside `worktrees_dir`, leaving out the main one and any other created by
+/// the user.
+///
+/// Returns the worktrees or a `String` with the error message on failure.
+pub fn list_worktrees(
+    kernel_tree_path: &str,
+    worktrees_dir: &str,
+) -> Result<Vec<Worktree>, String> {
+    let output = run_git_worktree(kernel_tree_path, &["list", "--porcelain"])?;
+    let worktrees_dir = Path::new(worktrees_dir);
+
+    Ok(parse_worktree_list(&output)
+        .into_iter()
+        .filter(|worktree| Path::new(&worktree.path).starts_with(worktrees_dir))
+        .collect())
+}
+
+/// Creates a worktree in `worktree_path` of the kernel tree in
+/// `kernel_tree_path` with a new branch named `branch` starting at
+/// `base_branch`.
+///
+/// Returns `()` on success and a `String` with the error message on failure.
+pub fn add_worktree(
+    kernel_tree_path: &str,
+    worktree_path: &str,
+    branch: &str,
+    base_branch: &str,
+) -> Result<(), String> {
+    run_git_worktree(
+        kernel_tree_path,
+        &["add", "-b", branch, worktree_path, base_branch],
+    )
+    .map(|_| ())
+}
+
+/// Removes the worktree in `worktree_path` of the kernel tree in
+/// `kernel_tree_path` and prunes the administrative files of worktrees whose
+/// directory is missing. Unless `force` is set, git refuses to remove a
+/// worktree with changes. The branch checked out in the worktree is kept.
+///
+/// Returns `()` on success and a `String` with the error message on failure.
+pub fn remove_worktree(
+    kernel_tree_path: &str,
+    worktree_path: &str,
+    force: bool,
+) -> Result<(), String> {
+    if Path::new(worktree_path).is_dir() {
+        let mut args = vec!["remove", worktree_path];
+        if force {
+            args.insert(1, "--force");
+        }
+        run_git_worktree(kernel_tree_path, &args)?;
+    }
+    run_git_worktree(kernel_tree_path, &["prune"]).map(|_| ())
+}
+
+/// Runs `git worktree` with `args` in `kernel_tree_path`.
+///
+/// Returns its stdout on success and a `String` with the error message on failure.
+fn run_git_worktree(kernel_tree_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(kernel_tree_path)
+        .arg("worktree")
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to execute git-worktree: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`git worktree {}` failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
@@ -0,0 +1,32 @@
+use super::*;
+
+use std::fs;
+
+#[test]
+fn should_parse_worktree_list() {
+    let porcelain = fs::read_to_string("test_samples/app/worktree/worktree_list.txt").unwrap();
+
+    let worktrees = parse_worktree_list(&porcelain);
+
+    assert_eq!(3, worktrees.len());
+    assert_eq!("/home/user/linux", worktrees[0].path());
+    assert_eq!(Some("master"), worktrees[0].branch().as_deref());
+    assert!(!worktrees[0].prunable());
+    assert_eq!(
+        "/home/user/.local/share/patch_hub/worktrees/patchset-2024-10-18-12-00-00",
+        worktrees[1].path()
+    );
+    assert_eq!(
+        "0a1b2c3d4e5f60718293a4b5c6d7e8f901234567",
+        worktrees[1].head()
+    );
+    assert_eq!(
+        Some("patchset-2024-10-18-12-00-00"),
+        worktrees[1].branch().as_deref()
+    );
+    assert!(
+        worktrees[2].branch().is_none(),
+        "Detached worktrees shouldn't have a branch"
+    );
+    assert!(worktrees[2].prunable());
+}
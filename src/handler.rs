@@ -1,8 +1,15 @@
+pub mod apply_history;
 pub mod bookmarked;
+pub mod command_line;
 pub mod details_actions;
 pub mod edit_config;
+pub mod kernel_trees;
 pub mod latest;
 pub mod mail_list;
+pub mod mouse;
+pub mod recent;
+pub mod tabs;
+pub mod worktrees;
 
 use std::{
     ops::ControlFlow,
@@ -10,22 +17,39 @@ use std::{
 };
 
 use crate::{
-    app::{logging::Logger, screens::CurrentScreen, App},
+    app::{
+        keymap::{self, Action, Key, KeyMatch, Keymap},
+        logging::Logger,
+        screens::{details_actions::DetailsActions, CurrentScreen},
+        tabs::TAB_SCREENS,
+        App,
+    },
     loading_screen,
-    ui::draw_ui,
+    ui::{
+        draw_ui,
+        popup::{confirm::Confirmation, PopUpOutcome},
+        FrameAreas,
+    },
 };
 
-use bookmarked::handle_bookmarked_patchsets;
+use apply_history::handle_apply_history;
+use bookmarked::handle_bookmarked_patchsets_action;
 use color_eyre::eyre::bail;
-use details_actions::handle_patchset_details;
+use command_line::{handle_command, run_command};
+use details_actions::{handle_patchset_details_action, handle_search_prompt};
 use edit_config::handle_edit_config;
-use latest::handle_latest_patchsets;
-use mail_list::handle_mailing_list_selection;
+use kernel_trees::handle_kernel_trees;
+use latest::{handle_filter_prompt, handle_latest_patchsets_action};
+use mail_list::{handle_mailing_list_selection_action, handle_target_list_input};
+use mouse::{handle_mouse, ListClick};
 use ratatui::{
     crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
     prelude::Backend,
     Terminal,
 };
+use recent::handle_recent_patchsets_action;
+use tabs::handle_tab_action;
+use worktrees::handle_worktrees;
 
 fn key_handling<B>(
     mut terminal: Terminal<B>,
@@ -36,46 +60,149 @@ where
     B: Backend + Send + 'static,
 {
     if let Some(popup) = app.popup.as_mut() {
-        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
-            app.popup = None;
+        let outcome = if key.code == KeyCode::Esc {
+            PopUpOutcome::Close
         } else {
-            popup.handle(key)?;
+            popup.handle(key)?
+        };
+        match outcome {
+            PopUpOutcome::Continue => {}
+            PopUpOutcome::Close => app.popup = None,
+            PopUpOutcome::Run(command) => {
+                app.popup = None;
+                return run_command(app, command, terminal);
+            }
+            PopUpOutcome::RunCommandLine(input) => {
+                app.popup = None;
+                return handle_command(app, &input, terminal);
+            }
+            PopUpOutcome::Confirm(confirmation) => {
+                app.popup = None;
+                handle_confirmation(app, confirmation)?;
+            }
         }
     } else {
         match app.current_screen {
-            CurrentScreen::MailingListSelection => {
-                return handle_mailing_list_selection(app, key, terminal);
+            CurrentScreen::LatestPatchsets
+                if app
+                    .latest_patchsets
+                    .as_ref()
+                    .unwrap()
+                    .filter_prompt()
+                    .is_some() =>
+            {
+                handle_filter_prompt(app.latest_patchsets.as_mut().unwrap(), key);
             }
-            CurrentScreen::BookmarkedPatchsets => {
-                return handle_bookmarked_patchsets(app, key, terminal);
+            CurrentScreen::PatchsetDetails
+                if app
+                    .details_actions
+                    .as_ref()
+                    .unwrap()
+                    .search_prompt
+                    .is_some() =>
+            {
+                handle_search_prompt(app.details_actions.as_mut().unwrap(), key);
             }
-            CurrentScreen::PatchsetDetails => {
-                handle_patchset_details(app, key, &mut terminal)?;
+            CurrentScreen::MailingListSelection
+            | CurrentScreen::BookmarkedPatchsets
+            | CurrentScreen::RecentPatchsets
+            | CurrentScreen::LatestPatchsets
+            | CurrentScreen::PatchsetDetails => {
+                match read_action(&app.keymap, &app.current_screen, &mut app.replayed_key, key)? {
+                    Some(action) => return handle_action(app, action, terminal),
+                    // Keys not bound to any action type the name of the target list
+                    None if app.current_screen == CurrentScreen::MailingListSelection => {
+                        handle_target_list_input(app, key);
+                    }
+                    None => {}
+                }
             }
             CurrentScreen::EditConfig => {
                 handle_edit_config(app, key)?;
             }
-            CurrentScreen::LatestPatchsets => {
-                return handle_latest_patchsets(app, key, terminal);
+            CurrentScreen::KernelTrees => {
+                handle_kernel_trees(app, key)?;
+            }
+            CurrentScreen::Worktrees => {
+                handle_worktrees(app, key, &mut terminal)?;
+            }
+            CurrentScreen::ApplyHistory => {
+                return handle_apply_history(app, key, terminal);
             }
         }
     }
     Ok(ControlFlow::Continue(terminal))
 }
 
-fn logic_handling<B>(mut terminal: Terminal<B>, app: &mut App) -> color_eyre::Result<Terminal<B>>
+/// Carries out the operation confirmed in a [ConfirmPopUp](crate::ui::popup::confirm::ConfirmPopUp)
+fn handle_confirmation(app: &mut App, confirmation: Confirmation) -> color_eyre::Result<()> {
+    match confirmation {
+        Confirmation::CloseTab => {
+            app.close_tab();
+        }
+        Confirmation::DeleteBranches { force } => {
+            app.delete_apply_history_branches(force);
+        }
+        Confirmation::RemoveWorktree { force } => {
+            app.remove_selected_worktree(force);
+        }
+        Confirmation::RemoveKernelTree(kernel_tree_id) => {
+            app.remove_kernel_tree(&kernel_tree_id)?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs `action` in the current screen of a tab, either bound to the pressed
+/// keys or triggered by the mouse. The tab actions shared by these screens
+/// (see [keymap::is_tab_action]) are run before reaching the screen.
+fn handle_action<B>(
+    app: &mut App,
+    action: Action,
+    mut terminal: Terminal<B>,
+) -> color_eyre::Result<ControlFlow<(), Terminal<B>>>
 where
     B: Backend + Send + 'static,
 {
+    if keymap::is_tab_action(action) && TAB_SCREENS.contains(&app.current_screen) {
+        handle_tab_action(app, action);
+        return Ok(ControlFlow::Continue(terminal));
+    }
+
     match app.current_screen {
         CurrentScreen::MailingListSelection => {
-            if app.mailing_list_selection.mailing_lists.is_empty() {
-                terminal = loading_screen! {
-                    terminal, "Fetching mailing lists" => {
-                        app.mailing_list_selection.refresh_available_mailing_lists()
-                    }
-                };
-            }
+            return handle_mailing_list_selection_action(app, action, terminal);
+        }
+        CurrentScreen::BookmarkedPatchsets => {
+            return handle_bookmarked_patchsets_action(app, action, terminal);
+        }
+        CurrentScreen::RecentPatchsets => {
+            return handle_recent_patchsets_action(app, action, terminal);
+        }
+        CurrentScreen::LatestPatchsets => {
+            return handle_latest_patchsets_action(app, action, terminal);
+        }
+        CurrentScreen::PatchsetDetails => {
+            handle_patchset_details_action(app, action, &mut terminal)?;
+        }
+        _ => {}
+    }
+    Ok(ControlFlow::Continue(terminal))
+}
+
+fn logic_handling<B>(mut terminal: Terminal<B>, app: &mut App) -> color_eyre::Result<Terminal<B>>
+where
+    B: Backend + Send + 'static,
+{
+    match app.current_screen {
+        CurrentScreen::MailingListSelection
+            if app.mailing_list_selection.mailing_lists.is_empty() =>
+        {
+            terminal = loading_screen! {
+                terminal, app.theme, "Fetching mailing lists" => {
+                    app.mailing_list_selection.refresh_available_mailing_lists()
+                }
+            };
         }
         CurrentScreen::LatestPatchsets => {
             let patchsets_state = app.latest_patchsets.as_mut().unwrap();
@@ -83,6 +210,7 @@ where
             if patchsets_state.processed_patchsets_count() == 0 {
                 terminal = loading_screen! {
                     terminal,
+                    app.theme,
                     format!("Fetching patchsets from {}", target_list) => {
                         patchsets_state.fetch_current_page()
                     }
@@ -91,10 +219,22 @@ where
                 app.mailing_list_selection.clear_target_list();
             }
         }
-        CurrentScreen::BookmarkedPatchsets => {
-            if app.bookmarked_patchsets.bookmarked_patchsets.is_empty() {
-                app.set_current_screen(CurrentScreen::MailingListSelection);
-            }
+        CurrentScreen::BookmarkedPatchsets
+            if app.bookmarked_patchsets.bookmarked_patchsets.is_empty() =>
+        {
+            app.exit_screen();
+        }
+        CurrentScreen::PatchsetDetails
+            if app
+                .details_actions
+                .as_ref()
+                .is_some_and(DetailsActions::has_pending_fixes) =>
+        {
+            terminal = loading_screen! {
+                terminal, app.theme, "Resolving Fixes: trailers" => {
+                    app.resolve_previewed_fixes()
+                }
+            };
         }
         _ => {}
     }
@@ -111,30 +251,85 @@ where
         bail!("patch-hub cannot be executed because some dependencies are missing, check logs for more information");
     }
 
+    let mut frame_areas = FrameAreas::default();
+    let mut last_click: Option<ListClick> = None;
     loop {
         terminal = logic_handling(terminal, &mut app)?;
 
-        terminal.draw(|f| draw_ui(f, &app))?;
+        terminal.draw(|f| frame_areas = draw_ui(f, &app))?;
+        if let (Some(details_actions), Some(preview)) =
+            (app.details_actions.as_mut(), frame_areas.preview)
+        {
+            details_actions.preview_viewport = preview.as_size();
+        }
 
         // *IMPORTANT*: Uncommenting the if below makes `patch-hub` not block
         // until an event is captured.  We should only do it when (if ever) we
         // need to refresh the UI independently of any event as doing so gravely
         // hinders the performance to below acceptable.
         // if event::poll(Duration::from_millis(16))? {
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Release {
-                continue;
+        let event = match app.replayed_key.take() {
+            Some(key) => Event::Key(key),
+            None => event::read()?,
+        };
+        let control_flow = match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                key_handling(terminal, &mut app, key)?
+            }
+            // The next draw adapts the layout to the new size
+            Event::Resize(_, _) => {
+                terminal.autoresize()?;
+                ControlFlow::Continue(terminal)
             }
-            match key_handling(terminal, &mut app, key)? {
-                ControlFlow::Continue(t) => terminal = t,
-                ControlFlow::Break(_) => return Ok(()),
+            Event::Mouse(mouse) => {
+                handle_mouse(&mut app, mouse, &frame_areas, &mut last_click, terminal)?
             }
+            _ => ControlFlow::Continue(terminal),
+        };
+        match control_flow {
+            ControlFlow::Continue(t) => terminal = t,
+            ControlFlow::Break(_) => return Ok(()),
         }
         // }
     }
 }
 
-fn wait_key_press(ch: char, wait_time: Duration) -> color_eyre::Result<bool> {
+/// Returns the action of `screen` bound to `key` in `keymap`. If `key` starts a
+/// key sequence (like `g g`), the next keys are awaited for a moment before
+/// giving up on the sequence. If a key doesn't continue the sequence, the keys
+/// before it run the action bound to them, if any, and it's handled on its own
+/// right after, by being stored in `replayed_key` (see [App::replayed_key]).
+pub fn read_action(
+    keymap: &Keymap,
+    screen: &CurrentScreen,
+    replayed_key: &mut Option<KeyEvent>,
+    key: KeyEvent,
+) -> color_eyre::Result<Option<Action>> {
+    let mut keys = vec![Key::from(key)];
+    let mut pending_action = None;
+    let mut last_key = None;
+
+    loop {
+        match keymap.match_keys(screen, &keys) {
+            KeyMatch::Action(action) => return Ok(Some(action)),
+            KeyMatch::None if keys.len() > 1 => {
+                *replayed_key = last_key;
+                return Ok(pending_action);
+            }
+            KeyMatch::None => return Ok(None),
+            KeyMatch::Pending(action) => match wait_key_press(Duration::from_millis(500))? {
+                Some(key) => {
+                    pending_action = action;
+                    last_key = Some(key);
+                    keys.push(Key::from(key));
+                }
+                None => return Ok(action),
+            },
+        }
+    }
+}
+
+fn wait_key_press(wait_time: Duration) -> color_eyre::Result<Option<KeyEvent>> {
     let start = Instant::now();
 
     while Instant::now() - start < wait_time {
@@ -143,12 +338,10 @@ fn wait_key_press(ch: char, wait_time: Duration) -> color_eyre::Result<bool> {
                 if key.kind == KeyEventKind::Release {
                     continue;
                 }
-                if key.code == KeyCode::Char(ch) {
-                    return Ok(true);
-                }
+                return Ok(Some(key));
             }
         }
     }
 
-    Ok(false)
+    Ok(None)
 }
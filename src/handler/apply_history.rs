@@ -0,0 +1,106 @@
+use std::ops::ControlFlow;
+
+use crate::{
+    app::{
+        keymap::{Action, Keymap},
+        screens::CurrentScreen,
+        App,
+    },
+    loading_screen,
+    ui::popup::{
+        confirm::{ConfirmPopUp, Confirmation},
+        help::HelpPopUpBuilder,
+        PopUp,
+    },
+};
+use ratatui::{crossterm::event::KeyEvent, prelude::Backend, Terminal};
+
+use super::{command_line::open_command_line, read_action};
+
+pub fn handle_apply_history<B>(
+    app: &mut App,
+    key: KeyEvent,
+    mut terminal: Terminal<B>,
+) -> color_eyre::Result<ControlFlow<(), Terminal<B>>>
+where
+    B: Backend + Send + 'static,
+{
+    let Some(action) = read_action(&app.keymap, &app.current_screen, &mut app.replayed_key, key)?
+    else {
+        return Ok(ControlFlow::Continue(terminal));
+    };
+    let apply_history = app.apply_history.as_mut().unwrap();
+
+    match action {
+        Action::Help => {
+            let popup = generate_help_popup(&app.keymap);
+            app.popup = Some(popup);
+        }
+        Action::CommandLine => {
+            open_command_line(app);
+        }
+        Action::Exit => {
+            app.reset_apply_history();
+            app.set_current_screen(CurrentScreen::MailingListSelection);
+        }
+        Action::Down => {
+            apply_history.highlight_below_entry();
+        }
+        Action::Up => {
+            apply_history.highlight_above_entry();
+        }
+        Action::Mark => {
+            apply_history.toggle_highlighted_mark();
+        }
+        Action::MarkAll => {
+            apply_history.toggle_all_marks();
+        }
+        Action::DeleteBranches => {
+            let to_delete = apply_history.branches_to_delete();
+            if !to_delete.is_empty() {
+                let mut msg = String::from("Delete the branches (and worktrees) below?\n");
+                for index in to_delete {
+                    let record = &apply_history.entries[index].record;
+                    msg.push_str(&format!(
+                        "\n{} in {}",
+                        record.branch(),
+                        record.kernel_tree_path()
+                    ));
+                    if let Some(worktree) = record.worktree() {
+                        msg.push_str(&format!(" (worktree {worktree})"));
+                    }
+                }
+                app.popup = Some(ConfirmPopUp::generate_confirm_popup(
+                    "Delete Branches",
+                    &msg,
+                    Confirmation::DeleteBranches { force: false },
+                ));
+            }
+        }
+        Action::Open if apply_history.get_highlighted_entry().is_some() => {
+            terminal = loading_screen! {
+                terminal,
+                app.theme,
+                "Loading patchset" => {
+                    let result = app.init_details_actions();
+                    if result.is_ok() {
+                        app.navigate_to(CurrentScreen::PatchsetDetails);
+                    }
+                    result
+                }
+            };
+        }
+        _ => {}
+    }
+    Ok(ControlFlow::Continue(terminal))
+}
+
+pub fn generate_help_popup(keymap: &Keymap) -> Box<dyn PopUp> {
+    let popup = HelpPopUpBuilder::new()
+        .title("Apply History")
+        .description("This screen lists every apply of a patchset, newest first, with the kernel tree, base and branch in which it was applied.\nBranches (and their worktrees) that are no longer needed can be deleted in bulk.")
+        .keymap(keymap, &CurrentScreen::ApplyHistory)
+        .build();
+
+    Box::new(popup)
+}
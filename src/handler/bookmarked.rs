@@ -1,46 +1,54 @@
 use std::ops::ControlFlow;
 
 use crate::{
-    app::{screens::CurrentScreen, App},
+    app::{
+        keymap::{Action, Keymap},
+        screens::CurrentScreen,
+        App,
+    },
     loading_screen,
     ui::popup::{help::HelpPopUpBuilder, PopUp},
 };
-use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent},
-    prelude::Backend,
-    Terminal,
-};
+use ratatui::{prelude::Backend, Terminal};
+
+use super::command_line::open_command_line;
 
-pub fn handle_bookmarked_patchsets<B>(
+/// Runs `action` in the bookmarked patchsets, either bound to a key or
+/// triggered by the mouse
+pub fn handle_bookmarked_patchsets_action<B>(
     app: &mut App,
-    key: KeyEvent,
+    action: Action,
     mut terminal: Terminal<B>,
 ) -> color_eyre::Result<ControlFlow<(), Terminal<B>>>
 where
     B: Backend + Send + 'static,
 {
-    match key.code {
-        KeyCode::Char('?') => {
-            let popup = generate_help_popup();
+    match action {
+        Action::Help => {
+            let popup = generate_help_popup(&app.keymap);
             app.popup = Some(popup);
         }
-        KeyCode::Esc | KeyCode::Char('q') => {
+        Action::CommandLine => {
+            open_command_line(app);
+        }
+        Action::Exit => {
             app.bookmarked_patchsets.patchset_index = 0;
-            app.set_current_screen(CurrentScreen::MailingListSelection);
+            app.exit_screen();
         }
-        KeyCode::Char('j') | KeyCode::Down => {
+        Action::Down => {
             app.bookmarked_patchsets.select_below_patchset();
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             app.bookmarked_patchsets.select_above_patchset();
         }
-        KeyCode::Enter => {
+        Action::Open => {
             terminal = loading_screen! {
                 terminal,
+                app.theme,
                 "Loading patchset" => {
                     let result = app.init_details_actions();
                     if result.is_ok() {
-                        app.set_current_screen(CurrentScreen::PatchsetDetails);
+                        app.navigate_to(CurrentScreen::PatchsetDetails);
                     }
                     result
                 }
@@ -51,15 +59,11 @@ where
     Ok(ControlFlow::Continue(terminal))
 }
 
-pub fn generate_help_popup() -> Box<dyn PopUp> {
+pub fn generate_help_popup(keymap: &Keymap) -> Box<dyn PopUp> {
     let popup = HelpPopUpBuilder::new()
         .title("Bookmarked Patchsets")
         .description("This screen shows all the patchsets you have bookmarked.\nThis is quite useful to keep track of patchsets you are interested in take a look later.")
-        .keybind("ESC", "Exit")
-        .keybind("ENTER", "See details of the selected patchset")
-        .keybind("?", "Show this help screen")
-        .keybind("j/🡇", "Down")
-        .keybind("k/🡅", "Up")
+        .keymap(keymap, &CurrentScreen::BookmarkedPatchsets)
         .build();
 
     Box::new(popup)
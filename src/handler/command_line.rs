@@ -0,0 +1,249 @@
+use std::ops::ControlFlow;
+
+use crate::{
+    app::{
+        command_line::{self, Command, COMMANDS},
+        logging::Logger,
+        screens::CurrentScreen,
+        tabs::TabCommand,
+        App,
+    },
+    loading_screen,
+    ui::popup::{command_line::CommandLinePopUp, help::HelpPopUpBuilder, info_popup::InfoPopUp},
+};
+use ratatui::{prelude::Backend, Terminal};
+
+use super::{
+    details_actions::{handle_git_am_resolution, run_patchset_actions},
+    tabs::close_tab,
+};
+
+/// Opens the command line popup in the current screen
+pub fn open_command_line(app: &mut App) {
+    app.popup = Some(CommandLinePopUp::generate_command_line_popup(
+        &app.current_screen,
+        &app.mailing_list_selection.mailing_lists,
+        &app.command_history,
+    ));
+}
+
+/// Runs the command line `input` entered in the command line popup, adding it
+/// to the command history if it's valid. Invalid commands are reported in a
+/// popup.
+pub fn handle_command<B>(
+    app: &mut App,
+    input: &str,
+    terminal: Terminal<B>,
+) -> color_eyre::Result<ControlFlow<(), Terminal<B>>>
+where
+    B: Backend + Send + 'static,
+{
+    let command = match command_line::parse_command(input, &app.current_screen) {
+        Ok(command) => command,
+        Err(msg) => {
+            app.popup = Some(InfoPopUp::generate_info_popup("Command Fail", &msg));
+            return Ok(ControlFlow::Continue(terminal));
+        }
+    };
+
+    command_line::push_to_history(&mut app.command_history, input);
+    if let Err(e) =
+        command_line::save_command_history(&app.command_history, app.config.command_history_path())
+    {
+        Logger::error(format!("Failed to save command history: {e}"));
+    }
+
+    run_command(app, command, terminal)
+}
+
+/// Runs `command`, either entered in the command line or chosen in a popup
+pub fn run_command<B>(
+    app: &mut App,
+    command: Command,
+    mut terminal: Terminal<B>,
+) -> color_eyre::Result<ControlFlow<(), Terminal<B>>>
+where
+    B: Backend + Send + 'static,
+{
+    match command {
+        Command::Help => {
+            let popup = COMMANDS
+                .iter()
+                .filter(|spec| spec.is_available_in(&app.current_screen))
+                .fold(
+                    HelpPopUpBuilder::new()
+                        .title("Commands")
+                        .description("Commands available in this screen, typed after ':'.\nPress TAB to complete command names and arguments, and 🡅/🡇 to browse the command history."),
+                    |builder, spec| builder.keybind(spec.usage, spec.help),
+                )
+                .build();
+            app.popup = Some(Box::new(popup));
+        }
+        Command::Quit => return Ok(ControlFlow::Break(())),
+        Command::Tab(TabCommand::New) => app.open_tab(),
+        Command::Tab(TabCommand::Close) => close_tab(app),
+        Command::Tab(TabCommand::Next) => app.switch_to_next_tab(),
+        Command::Tab(TabCommand::Previous) => app.switch_to_previous_tab(),
+        Command::Tab(TabCommand::Go(number)) => {
+            if !app.switch_tab(number - 1) {
+                app.popup = Some(InfoPopUp::generate_info_popup(
+                    "Command Fail",
+                    &format!("There is no tab {number}"),
+                ));
+            }
+        }
+        Command::List(list_name) => {
+            if !app
+                .mailing_list_selection
+                .mailing_lists
+                .iter()
+                .any(|list| list.name() == &list_name)
+            {
+                app.popup = Some(InfoPopUp::generate_info_popup(
+                    "Command Fail",
+                    &format!("Unknown mailing list '{list_name}'"),
+                ));
+                return Ok(ControlFlow::Continue(terminal));
+            }
+            terminal = open_latest_patchsets(app, list_name, String::new(), terminal)?;
+        }
+        Command::Search(query) => {
+            let list_name = match (&app.current_screen, &app.latest_patchsets) {
+                (CurrentScreen::LatestPatchsets, Some(latest_patchsets)) => {
+                    latest_patchsets.target_list().to_string()
+                }
+                _ => "all".to_string(),
+            };
+            terminal = open_latest_patchsets(app, list_name, query, terminal)?;
+        }
+        Command::Page(page_number) => {
+            let latest_patchsets = app.latest_patchsets.as_mut().unwrap();
+            let page_result;
+            terminal = loading_screen! {
+                terminal,
+                app.theme,
+                format!("Fetching page {page_number}") => {
+                    page_result = latest_patchsets.go_to_page(page_number);
+                    if page_result.is_ok() && latest_patchsets.my_subsystems_filter() {
+                        app.classify_latest_patchsets_by_my_subsystems();
+                    }
+                    color_eyre::Result::<()>::Ok(())
+                }
+            };
+            if let Err(e) = page_result {
+                app.popup = Some(InfoPopUp::generate_info_popup(
+                    "Command Fail",
+                    &e.to_string(),
+                ));
+            }
+        }
+        Command::Filter(filter_text) => {
+            let latest_patchsets = app.latest_patchsets.as_mut().unwrap();
+            if let Err(e) = latest_patchsets.set_filter(&filter_text) {
+                app.popup = Some(InfoPopUp::generate_info_popup("Command Fail", &e));
+            }
+        }
+        Command::Sort(sort) => {
+            app.latest_patchsets.as_mut().unwrap().set_sort(sort);
+        }
+        Command::Run => {
+            run_patchset_actions(app, &mut terminal)?;
+        }
+        Command::Jump { patch, line } => {
+            let details_actions = app.details_actions.as_mut().unwrap();
+            // Patches are numbered from 1, unless there is a cover letter
+            let first_patch = usize::from(!details_actions.has_cover_letter);
+            match patch
+                .checked_sub(first_patch)
+                .filter(|&patch_index| patch_index < details_actions.patches_preview.len())
+            {
+                Some(patch_index) => details_actions.jump_to_preview_line(patch_index, line),
+                None => {
+                    app.popup = Some(InfoPopUp::generate_info_popup(
+                        "Command Fail",
+                        &format!("There is no patch {patch}"),
+                    ));
+                }
+            }
+        }
+        Command::Base {
+            kernel_tree,
+            branch,
+        } => {
+            let details_actions = app.details_actions.as_mut().unwrap();
+            if let Err(e) =
+                details_actions.choose_apply_base(&app.config, &kernel_tree, branch.as_deref())
+            {
+                app.popup = Some(InfoPopUp::generate_info_popup("Command Fail", &e));
+            }
+        }
+        Command::Am(resolution) => {
+            handle_git_am_resolution(app, resolution, &mut terminal)?;
+        }
+        command => {
+            let details_actions = app.details_actions.as_mut().unwrap();
+            match command {
+                Command::Apply => details_actions.toggle_apply_action(),
+                Command::Bookmark => details_actions.toggle_bookmark_action(),
+                Command::Checkpatch => details_actions.toggle_checkpatch_action(),
+                Command::BuildTest => details_actions.toggle_build_test_action(),
+                Command::Export(None) => details_actions.toggle_export_action(),
+                Command::Export(Some(format)) => details_actions.mark_export_action(format),
+                Command::Reply { trailer, all } => {
+                    details_actions.reply_trailer = trailer;
+                    details_actions.toggle_reply_with_reviewed_by_action(all);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(ControlFlow::Continue(terminal))
+}
+
+/// Fetches the latest patchsets of `list_name` matching `search_query` (all
+/// of them, if empty) and shows them. If none is found, the current screen is
+/// kept and it's reported in a popup.
+fn open_latest_patchsets<B>(
+    app: &mut App,
+    list_name: String,
+    search_query: String,
+    terminal: Terminal<B>,
+) -> color_eyre::Result<Terminal<B>>
+where
+    B: Backend + Send + 'static,
+{
+    let previous_latest_patchsets = app.latest_patchsets.take();
+    app.init_latest_patchsets_of(list_name.clone(), search_query);
+    let fetched_list_name = list_name.clone();
+    let terminal = loading_screen! {
+        terminal,
+        app.theme,
+        format!("Fetching patchsets from {fetched_list_name}") => {
+            app.latest_patchsets.as_mut().unwrap().fetch_current_page()
+        }
+    };
+
+    if app
+        .latest_patchsets
+        .as_ref()
+        .unwrap()
+        .processed_patchsets_count()
+        == 0
+    {
+        app.latest_patchsets = previous_latest_patchsets;
+        app.popup = Some(InfoPopUp::generate_info_popup(
+            "Command Fail",
+            &format!("No patchsets found in {list_name}"),
+        ));
+    } else {
+        // The previous screen, with its patchsets, is recorded to go back to
+        let latest_patchsets = app.latest_patchsets.take();
+        app.latest_patchsets = previous_latest_patchsets;
+        app.navigate_to(CurrentScreen::LatestPatchsets);
+        app.latest_patchsets = latest_patchsets;
+        app.record_list_activity();
+        app.mailing_list_selection.clear_target_list();
+    }
+    Ok(terminal)
+}
@@ -1,154 +1,310 @@
-use std::time::Duration;
+use std::{env, process::Command, sync::Mutex};
 
 use crate::{
-    app::{screens::CurrentScreen, App},
-    ui::popup::{help::HelpPopUpBuilder, review_trailers::ReviewTrailersPopUp, PopUp},
+    app::{
+        git_am::GitAmResolution,
+        keymap::{Action, Keymap},
+        preview_search::SearchDirection,
+        screens::{
+            details_actions::{DetailsActions, PatchsetAction},
+            CurrentScreen,
+        },
+        App,
+    },
+    ui::log_screen,
+    ui::popup::{
+        apply_base::ApplyBasePopUp, checkpatch::CheckpatchPopUp,
+        git_am_conflict::GitAmConflictPopUp, help::HelpPopUpBuilder, info_popup::InfoPopUp,
+        review_trailers::ReviewTrailersPopUp, PopUp,
+    },
     utils,
 };
 use ratatui::{
     backend::Backend,
-    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
     Terminal,
 };
 
-use super::wait_key_press;
+use super::command_line::open_command_line;
 
-pub fn handle_patchset_details<B: Backend>(
+/// Runs `action` in the patchset details, either bound to a key or triggered
+/// by the mouse
+pub fn handle_patchset_details_action<B: Backend + Send>(
     app: &mut App,
-    key: KeyEvent,
+    action: Action,
     terminal: &mut Terminal<B>,
 ) -> color_eyre::Result<()> {
     let patchset_details_and_actions = app.details_actions.as_mut().unwrap();
 
-    if key.modifiers.contains(KeyModifiers::SHIFT) {
-        match key.code {
-            KeyCode::Char('G') => patchset_details_and_actions.go_to_last_line(),
-            KeyCode::Char('R') => {
-                patchset_details_and_actions.toggle_reply_with_reviewed_by_action(true);
-            }
-            _ => {}
-        }
-        return Ok(());
-    }
-
-    if key.modifiers.contains(KeyModifiers::CONTROL) {
-        // TODO: Get preview sub-window height w/out coupling it to UI
-        let terminal_height = terminal.size().unwrap().height as usize;
-        match key.code {
-            KeyCode::Char('b') => {
-                patchset_details_and_actions.preview_scroll_up(terminal_height);
-            }
-            KeyCode::Char('f') => {
-                patchset_details_and_actions.preview_scroll_down(terminal_height);
-            }
-            KeyCode::Char('u') => {
-                patchset_details_and_actions.preview_scroll_up(terminal_height / 2);
-            }
-            KeyCode::Char('d') => {
-                patchset_details_and_actions.preview_scroll_down(terminal_height / 2);
-            }
-            KeyCode::Char('t') => {
-                let popup =
-                    ReviewTrailersPopUp::generate_trailers_popup(patchset_details_and_actions);
-                app.popup = Some(popup);
-            }
-            _ => {}
-        }
-        return Ok(());
-    }
-
-    match key.code {
-        KeyCode::Char('?') => {
-            let popup = generate_help_popup();
+    match action {
+        Action::Help => {
+            let popup = generate_help_popup(&app.keymap);
             app.popup = Some(popup);
         }
-        KeyCode::Esc | KeyCode::Char('q') => {
-            let ps_da_clone = patchset_details_and_actions.last_screen.clone();
-            app.set_current_screen(ps_da_clone);
-            app.reset_details_actions();
+        Action::Exit if patchset_details_and_actions.preview_search.is_some() => {
+            patchset_details_and_actions.clear_preview_search();
         }
-        KeyCode::Char('a') => {
-            patchset_details_and_actions.toggle_apply_action();
+        Action::Exit
+            if app.git_am_conflict.as_ref().is_some_and(|conflict| {
+                conflict.is_of(&patchset_details_and_actions.representative_patch)
+            }) =>
+        {
+            refuse_leaving_details(app);
+        }
+        Action::Exit => {
+            app.exit_screen();
         }
-        KeyCode::Char('j') | KeyCode::Down => {
+        Action::Down => {
             patchset_details_and_actions.preview_scroll_down(1);
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             patchset_details_and_actions.preview_scroll_up(1);
         }
-        KeyCode::Char('h') | KeyCode::Left => {
+        Action::PanLeft => {
             patchset_details_and_actions.preview_pan_left();
         }
-        KeyCode::Char('l') | KeyCode::Right => {
+        Action::PanRight => {
             patchset_details_and_actions.preview_pan_right();
         }
-        KeyCode::Char('0') => {
+        Action::PageUp => {
+            patchset_details_and_actions.preview_page_up(false);
+        }
+        Action::PageDown => {
+            patchset_details_and_actions.preview_page_down(false);
+        }
+        Action::HalfPageUp => {
+            patchset_details_and_actions.preview_page_up(true);
+        }
+        Action::HalfPageDown => {
+            patchset_details_and_actions.preview_page_down(true);
+        }
+        Action::GoToLineStart => {
             patchset_details_and_actions.go_to_beg_of_line();
         }
-        KeyCode::Char('g') => {
-            if let Ok(true) = wait_key_press('g', Duration::from_millis(500)) {
-                patchset_details_and_actions.go_to_first_line();
-            }
+        Action::GoToFirstLine => {
+            patchset_details_and_actions.go_to_first_line();
+        }
+        Action::GoToLastLine => {
+            patchset_details_and_actions.go_to_last_line();
         }
-        KeyCode::Char('f') => {
+        Action::ToggleFullscreen => {
             patchset_details_and_actions.toggle_preview_fullscreen();
         }
-        KeyCode::Char('n') => {
+        Action::NextPatch => {
             patchset_details_and_actions.preview_next_patch();
         }
-        KeyCode::Char('p') => {
+        Action::PreviousPatch => {
             patchset_details_and_actions.preview_previous_patch();
         }
-        KeyCode::Char('b') => {
+        Action::SearchForward => {
+            patchset_details_and_actions.start_preview_search(SearchDirection::Forward);
+        }
+        Action::SearchBackward => {
+            patchset_details_and_actions.start_preview_search(SearchDirection::Backward);
+        }
+        Action::NextMatch => {
+            patchset_details_and_actions.jump_to_next_search_match(false);
+        }
+        Action::PreviousMatch => {
+            patchset_details_and_actions.jump_to_next_search_match(true);
+        }
+        Action::ToggleBookmark => {
             patchset_details_and_actions.toggle_bookmark_action();
         }
-        KeyCode::Char('r') => {
+        Action::ToggleApply => {
+            patchset_details_and_actions.toggle_apply_action();
+        }
+        Action::ToggleApplyPerPatch => {
+            patchset_details_and_actions.toggle_apply_per_patch();
+        }
+        Action::ChooseApplyBase => {
+            app.popup = Some(ApplyBasePopUp::generate_apply_base_popup(
+                &app.config,
+                patchset_details_and_actions.apply_base.as_ref(),
+            ));
+        }
+        Action::ToggleReviewedBy => {
             patchset_details_and_actions.toggle_reply_with_reviewed_by_action(false);
         }
-        KeyCode::Enter => {
-            if patchset_details_and_actions.actions_require_user_io() {
-                utils::setup_user_io(terminal)?;
-                app.consolidate_patchset_actions()?;
-                println!("\nPress ENTER continue...");
-                loop {
-                    if let Event::Key(key) = event::read()? {
-                        if key.kind == KeyEventKind::Press && key.code == KeyCode::Enter {
-                            break;
-                        }
-                    }
-                }
-                utils::teardown_user_io(terminal)?;
-            } else {
-                app.consolidate_patchset_actions()?;
+        Action::ToggleReviewedByAll => {
+            patchset_details_and_actions.toggle_reply_with_reviewed_by_action(true);
+        }
+        Action::ToggleCheckpatch => {
+            patchset_details_and_actions.toggle_checkpatch_action();
+        }
+        Action::ShowCheckpatch => {
+            let popup = CheckpatchPopUp::generate_checkpatch_popup(patchset_details_and_actions);
+            app.popup = Some(popup);
+        }
+        Action::ShowConflict => {
+            if let Some(conflict) = &app.git_am_conflict {
+                app.popup = Some(GitAmConflictPopUp::generate_git_am_conflict_popup(conflict));
             }
-            app.set_current_screen(CurrentScreen::PatchsetDetails);
         }
+        Action::ToggleBuildTest => {
+            patchset_details_and_actions.toggle_build_test_action();
+        }
+        Action::ShowTrailers => {
+            let popup = ReviewTrailersPopUp::generate_trailers_popup(patchset_details_and_actions);
+            app.popup = Some(popup);
+        }
+        Action::ToggleExport => {
+            patchset_details_and_actions.toggle_export_action();
+        }
+        Action::CycleExportFormat => {
+            patchset_details_and_actions.cycle_export_format();
+        }
+        Action::RunActions => {
+            run_patchset_actions(app, terminal)?;
+        }
+        Action::CommandLine => {
+            open_command_line(app);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handles the keys typed in the prompt of a preview search, which are not
+/// subject to the keymap. The search is incremental, so the preview jumps to
+/// the first match as the pattern is typed.
+pub fn handle_search_prompt(details_actions: &mut DetailsActions, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => details_actions.cancel_preview_search(),
+        KeyCode::Enter => details_actions.confirm_preview_search(),
+        KeyCode::Tab => details_actions.toggle_search_across_series(),
+        KeyCode::Backspace => details_actions.pop_search_char(),
+        KeyCode::Char(c) => details_actions.push_search_char(c),
         _ => {}
     }
+}
+
+/// Consolidates the actions marked in the patchset details, suspending the TUI
+/// if they require user IO (like replying) and streaming the output of the
+/// long ones (like build-testing).
+pub fn run_patchset_actions<B: Backend + Send>(
+    app: &mut App,
+    terminal: &mut Terminal<B>,
+) -> color_eyre::Result<()> {
+    let details_actions = app.details_actions.as_mut().unwrap();
+    if details_actions.needs_apply_upstream(&app.config) {
+        let log = Mutex::new(Vec::new());
+        let theme = app.theme;
+        log_screen::stream_log(terminal, &theme, "Fetching upstream", &log, || {
+            details_actions.fetch_apply_upstream(&app.config, &log)
+        });
+    }
+    if app
+        .details_actions
+        .as_ref()
+        .unwrap()
+        .actions_require_user_io()
+    {
+        utils::setup_user_io(terminal)?;
+        app.consolidate_patchset_actions()?;
+        println!("\nPress ENTER continue...");
+        loop {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press && key.code == KeyCode::Enter {
+                    break;
+                }
+            }
+        }
+        utils::teardown_user_io(terminal)?;
+    } else {
+        app.consolidate_patchset_actions()?;
+    }
+    if app.details_actions.as_ref().unwrap().apply_per_patch {
+        let log = Mutex::new(Vec::new());
+        let theme = app.theme;
+        log_screen::stream_log(
+            terminal,
+            &theme,
+            "Applying patchset per patch",
+            &log,
+            || app.apply_patchset_per_patch(&log),
+        );
+    }
+    if let Some(true) = app
+        .details_actions
+        .as_ref()
+        .unwrap()
+        .patchset_actions
+        .get(&PatchsetAction::BuildTest)
+    {
+        let log = Mutex::new(Vec::new());
+        let theme = app.theme;
+        log_screen::stream_log(terminal, &theme, "Build-testing patchset", &log, || {
+            app.build_test_patchset(&log)
+        });
+    }
+    app.set_current_screen(CurrentScreen::PatchsetDetails);
+    Ok(())
+}
+
+/// Refuses to leave the details of a patchset whose `git am` is stopped by
+/// conflicts, showing them instead, so they aren't forgotten.
+///
+/// Returns if leaving was refused.
+pub fn refuse_leaving_details(app: &mut App) -> bool {
+    if app.current_screen != CurrentScreen::PatchsetDetails {
+        return false;
+    }
+    let (Some(conflict), Some(details_actions)) = (&app.git_am_conflict, &app.details_actions)
+    else {
+        return false;
+    };
+    if !conflict.is_of(&details_actions.representative_patch) {
+        return false;
+    }
+
+    app.popup = Some(GitAmConflictPopUp::generate_git_am_conflict_popup(conflict));
+    true
+}
+
+/// Proceeds with the `git am` stopped by conflicts as chosen in
+/// `resolution`. To resolve the conflicts, patch-hub is suspended and a shell
+/// is opened in the repository in the middle of `git am`.
+pub fn handle_git_am_resolution<B: Backend>(
+    app: &mut App,
+    resolution: GitAmResolution,
+    terminal: &mut Terminal<B>,
+) -> color_eyre::Result<()> {
+    let Some(conflict) = &app.git_am_conflict else {
+        return Ok(());
+    };
+
+    if resolution != GitAmResolution::Resolve {
+        app.resolve_git_am_conflict(resolution);
+        return Ok(());
+    }
+
+    let shell = env::var("SHELL").unwrap_or("sh".to_string());
+    utils::setup_user_io(terminal)?;
+    println!(
+        "Resolve the conflicts in '{}' (e.g. with `git mergetool`) and stage them.\nExit the shell to get back to patch-hub.\n",
+        conflict.repo_path()
+    );
+    let status = Command::new(&shell)
+        .current_dir(conflict.repo_path())
+        .status();
+    utils::teardown_user_io(terminal)?;
+
+    app.popup = Some(match status {
+        Ok(_) => GitAmConflictPopUp::generate_git_am_conflict_popup(conflict),
+        Err(e) => InfoPopUp::generate_info_popup(
+            "Patchset Apply Conflict",
+            &format!("failed to run {shell}: {e}"),
+        ),
+    });
     Ok(())
 }
 
-pub fn generate_help_popup() -> Box<dyn PopUp> {
+pub fn generate_help_popup(keymap: &Keymap) -> Box<dyn PopUp> {
     let popup = HelpPopUpBuilder::new()
         .title("Patchset Details and Actions")
-        .description("This screen displays the details of a patchset and allows you to perform actions on it.\nA series of actions are available to you, they are:\n - Bookmark: Save the patchset for later\n - Reply with Reviewed-by: Reply to the patchset with a Reviewed-by tag")
-        .keybind("ESC", "Exit")
-        .keybind("ENTER", "Consolidate marked actions")
-        .keybind("?", "Show this help screen")
-        .keybind("j/🡇", "Scroll down")
-        .keybind("k/🡅", "Scroll up")
-        .keybind("h/🡄", "Pan left")
-        .keybind("l/🡆", "Pan right")
-        .keybind("0", "Go to start of line")
-        .keybind("g", "Go to first line")
-        .keybind("G", "Go to last line")
-        .keybind("f", "Toggle fullscreen")
-        .keybind("n", "Preview next patch")
-        .keybind("p", "Preview previous patch")
-        .keybind("b", "Toggle bookmark action")
-        .keybind("r", "Toggle reply with Reviewed-by action")
-        .keybind("Shift+r", "Toggle reply with Reviewed-by action for all patches")
-        .keybind("Ctrl+t", "Show code-review trailers details")
+        .description("This screen displays the details of a patchset and allows you to perform actions on it.\nA series of actions are available to you, they are:\n - Bookmark: Save the patchset for later\n - Reply with Reviewed-by: Reply to the patchset with a Reviewed-by tag (or Acked-by/Tested-by, chosen with the `:reply` command)\n - Apply: Apply the patchset to the target kernel tree (or the one detected from the mailing list, MAINTAINERS `T:` entries and base-commit), optionally one patch at a time checking each one\n - Checkpatch: Run checkpatch.pl of the target kernel tree against each patch\n - Build-test: Build the target kernel tree with the applied patchset\n - Export: Export the patchset as numbered .patch files, a single mbox with the collected trailers or a quilt series\nThe preview can be searched with regexes (case-sensitive only if they have uppercase characters). While typing a search, TAB toggles looking for matches in all patches of the series.")
+        .keymap(keymap, &CurrentScreen::PatchsetDetails)
         .build();
 
     Box::new(popup)
@@ -1,9 +1,15 @@
 use crate::{
-    app::{screens::CurrentScreen, App},
+    app::{
+        keymap::{Action, Keymap},
+        screens::CurrentScreen,
+        App,
+    },
     ui::popup::{help::HelpPopUpBuilder, PopUp},
 };
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
 
+use super::{command_line::open_command_line, read_action};
+
 pub fn handle_edit_config(app: &mut App, key: KeyEvent) -> color_eyre::Result<()> {
     if let Some(edit_config_state) = app.edit_config.as_mut() {
         match edit_config_state.is_editing() {
@@ -25,44 +31,44 @@ pub fn handle_edit_config(app: &mut App, key: KeyEvent) -> color_eyre::Result<()
                 }
                 _ => {}
             },
-            false => match key.code {
-                KeyCode::Char('?') => {
-                    let popup = generate_help_popup();
-                    app.popup = Some(popup);
-                }
-                KeyCode::Esc | KeyCode::Char('q') => {
-                    app.consolidate_edit_config();
-                    app.config.save_patch_hub_config()?;
-                    app.reset_edit_config();
-                    app.set_current_screen(CurrentScreen::MailingListSelection);
-                }
-                KeyCode::Enter => {
-                    edit_config_state.toggle_editing();
+            false => {
+                match read_action(&app.keymap, &app.current_screen, &mut app.replayed_key, key)? {
+                    Some(Action::Help) => {
+                        let popup = generate_help_popup(&app.keymap);
+                        app.popup = Some(popup);
+                    }
+                    Some(Action::CommandLine) => {
+                        open_command_line(app);
+                    }
+                    Some(Action::Exit) => {
+                        app.consolidate_edit_config();
+                        app.config.save_patch_hub_config()?;
+                        app.reset_edit_config();
+                        app.set_current_screen(CurrentScreen::MailingListSelection);
+                    }
+                    Some(Action::Edit) => {
+                        edit_config_state.toggle_editing();
+                    }
+                    Some(Action::Down) => {
+                        edit_config_state.highlight_next();
+                    }
+                    Some(Action::Up) => {
+                        edit_config_state.highlight_prev();
+                    }
+                    _ => {}
                 }
-                KeyCode::Char('j') | KeyCode::Down => {
-                    edit_config_state.highlight_next();
-                }
-                KeyCode::Char('k') | KeyCode::Up => {
-                    edit_config_state.highlight_prev();
-                }
-                _ => {}
-            },
+            }
         }
     }
     Ok(())
 }
 
 // TODO: Move this to a more appropriate place
-pub fn generate_help_popup() -> Box<dyn PopUp> {
+pub fn generate_help_popup(keymap: &Keymap) -> Box<dyn PopUp> {
     let popup = HelpPopUpBuilder::new()
         .title("Edit Config")
         .description("This screen allows you to edit the configuration options for patch-hub.\nMore configurations may be available in the configuration file.")
-        .keybind("ESC", "Exit")
-        .keybind("ENTER", "Save changes")
-        .keybind("?", "Show this help screen")
-        .keybind("j/🡇", "Down")
-        .keybind("k/🡅", "Up")
-        .keybind("e", "Toggle editing for a configuration option")
+        .keymap(keymap, &CurrentScreen::EditConfig)
         .build();
 
     Box::new(popup)
@@ -0,0 +1,104 @@
+use crate::{
+    app::{
+        keymap::{Action, Keymap},
+        screens::CurrentScreen,
+        App,
+    },
+    ui::popup::{
+        confirm::{ConfirmPopUp, Confirmation},
+        help::HelpPopUpBuilder,
+        PopUp,
+    },
+};
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+use super::{command_line::open_command_line, read_action};
+
+pub fn handle_kernel_trees(app: &mut App, key: KeyEvent) -> color_eyre::Result<()> {
+    let kernel_trees = app.kernel_trees.as_mut().unwrap();
+
+    if let Some(form) = kernel_trees.form.as_mut() {
+        match key.code {
+            KeyCode::Esc => {
+                kernel_trees.close_form();
+            }
+            KeyCode::Tab | KeyCode::Down => {
+                form.focus_next();
+            }
+            KeyCode::BackTab | KeyCode::Up => {
+                form.focus_prev();
+            }
+            KeyCode::Backspace => {
+                form.backspace_edit();
+                form.error = None;
+            }
+            KeyCode::Char(ch) => {
+                form.append_edit(ch);
+                form.error = None;
+            }
+            KeyCode::Enter => {
+                app.consolidate_kernel_tree_form()?;
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    let Some(action) = read_action(&app.keymap, &app.current_screen, &mut app.replayed_key, key)?
+    else {
+        return Ok(());
+    };
+    match action {
+        Action::Help => {
+            let popup = generate_help_popup(&app.keymap);
+            app.popup = Some(popup);
+        }
+        Action::CommandLine => {
+            open_command_line(app);
+        }
+        Action::Exit => {
+            app.reset_kernel_trees();
+            app.set_current_screen(CurrentScreen::MailingListSelection);
+        }
+        Action::Down => {
+            kernel_trees.highlight_next();
+        }
+        Action::Up => {
+            kernel_trees.highlight_prev();
+        }
+        Action::Add => {
+            kernel_trees.open_add_form();
+        }
+        Action::Rename => {
+            kernel_trees.open_rename_form();
+        }
+        Action::Remove => {
+            if let Some(entry) = kernel_trees.get_highlighted() {
+                app.popup = Some(ConfirmPopUp::generate_confirm_popup(
+                    "Remove Kernel Tree",
+                    &format!(
+                        "Remove kernel tree '{}' ({})?\nThe repository itself is kept.",
+                        entry.id,
+                        entry.kernel_tree.path()
+                    ),
+                    Confirmation::RemoveKernelTree(entry.id.clone()),
+                ));
+            }
+        }
+        Action::SetTarget => {
+            app.target_highlighted_kernel_tree()?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+pub fn generate_help_popup(keymap: &Keymap) -> Box<dyn PopUp> {
+    let popup = HelpPopUpBuilder::new()
+        .title("Kernel Trees")
+        .description("This screen allows you to manage the kernel trees in which actions like applying patchsets are run.\nActions are run in the target kernel tree, unless another one is chosen in the patchset details.")
+        .keymap(keymap, &CurrentScreen::KernelTrees)
+        .build();
+
+    Box::new(popup)
+}
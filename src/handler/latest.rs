@@ -1,7 +1,11 @@
 use std::ops::ControlFlow;
 
 use crate::{
-    app::{screens::CurrentScreen, App},
+    app::{
+        keymap::{Action, Keymap},
+        screens::{latest::LatestPatchsets, CurrentScreen},
+        App,
+    },
     loading_screen,
     ui::popup::{help::HelpPopUpBuilder, PopUp},
 };
@@ -11,9 +15,13 @@ use ratatui::{
     Terminal,
 };
 
-pub fn handle_latest_patchsets<B>(
+use super::command_line::open_command_line;
+
+/// Runs `action` in the latest patchsets, either bound to a key or triggered
+/// by the mouse
+pub fn handle_latest_patchsets_action<B>(
     app: &mut App,
-    key: KeyEvent,
+    action: Action,
     mut terminal: Terminal<B>,
 ) -> color_eyre::Result<ControlFlow<(), Terminal<B>>>
 where
@@ -21,41 +29,75 @@ where
 {
     let latest_patchsets = app.latest_patchsets.as_mut().unwrap();
 
-    match key.code {
-        KeyCode::Char('?') => {
-            let popup = generate_help_popup();
+    match action {
+        Action::Help => {
+            let popup = generate_help_popup(&app.keymap);
             app.popup = Some(popup);
         }
-        KeyCode::Esc | KeyCode::Char('q') => {
-            app.reset_latest_patchsets();
-            app.set_current_screen(CurrentScreen::MailingListSelection);
+        Action::CommandLine => {
+            open_command_line(app);
+        }
+        Action::Exit if !latest_patchsets.filter_text().is_empty() => {
+            let _ = latest_patchsets.set_filter("");
         }
-        KeyCode::Char('j') | KeyCode::Down => {
+        Action::Exit => {
+            app.exit_screen();
+        }
+        Action::Down => {
             latest_patchsets.select_below_patchset();
         }
-        KeyCode::Char('k') | KeyCode::Up => {
+        Action::Up => {
             latest_patchsets.select_above_patchset();
         }
-        KeyCode::Char('l') | KeyCode::Right => {
+        Action::NextPage => {
             let list_name = latest_patchsets.target_list().to_string();
             terminal = loading_screen! {
                 terminal,
+                app.theme,
                 format!("Fetching patchsets from {}", list_name) => {
                     latest_patchsets.increment_page();
-                    latest_patchsets.fetch_current_page()
+                    let result = latest_patchsets.fetch_current_page();
+                    latest_patchsets.select_top_patchset();
+                    if result.is_ok() && latest_patchsets.my_subsystems_filter() {
+                        app.classify_latest_patchsets_by_my_subsystems();
+                    }
+                    if result.is_ok() {
+                        app.record_list_activity();
+                    }
+                    result
                 }
             };
         }
-        KeyCode::Char('h') | KeyCode::Left => {
+        Action::PreviousPage => {
             latest_patchsets.decrement_page();
         }
-        KeyCode::Enter => {
+        Action::ToggleMySubsystems => {
+            latest_patchsets.toggle_my_subsystems_filter();
+            if latest_patchsets.my_subsystems_filter() {
+                terminal = loading_screen! {
+                    terminal,
+                    app.theme,
+                    "Checking patchsets against MAINTAINERS" => {
+                        app.classify_latest_patchsets_by_my_subsystems();
+                        color_eyre::Result::<()>::Ok(())
+                    }
+                };
+            }
+        }
+        Action::Filter => {
+            latest_patchsets.start_filter();
+        }
+        Action::CycleSort => {
+            latest_patchsets.cycle_sort();
+        }
+        Action::Open if latest_patchsets.patchset_index().is_some() => {
             terminal = loading_screen! {
                 terminal,
+                app.theme,
                 "Loading patchset" => {
                     let result = app.init_details_actions();
                     if result.is_ok() {
-                        app.set_current_screen(CurrentScreen::PatchsetDetails);
+                        app.navigate_to(CurrentScreen::PatchsetDetails);
                     }
                     result
                 }
@@ -66,17 +108,23 @@ where
     Ok(ControlFlow::Continue(terminal))
 }
 
-pub fn generate_help_popup() -> Box<dyn PopUp> {
+/// Handles the keys typed in the filter bar, which are not subject to the
+/// keymap. The list is filtered as the filter is typed.
+pub fn handle_filter_prompt(latest_patchsets: &mut LatestPatchsets, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc => latest_patchsets.cancel_filter(),
+        KeyCode::Enter => latest_patchsets.confirm_filter(),
+        KeyCode::Backspace => latest_patchsets.pop_filter_char(),
+        KeyCode::Char(c) => latest_patchsets.push_filter_char(c),
+        _ => {}
+    }
+}
+
+pub fn generate_help_popup(keymap: &Keymap) -> Box<dyn PopUp> {
     let popup = HelpPopUpBuilder::new()
         .title("Latest Patchsets")
-        .description("This screen allows you to see a list of the latest patchsets from a mailing list.\nYou might also be able to view the details of a patchset.")
-        .keybind("ESC", "Exit")
-        .keybind("ENTER", "See details of the selected patchset")
-        .keybind("?", "Show this help screen")
-        .keybind("j/🡇", "Down")
-        .keybind("k/🡅", "Up")
-        .keybind("l/🡆", "Next page")
-        .keybind("h/🡄", "Previous page")
+        .description("This screen allows you to see a list of the latest patchsets from a mailing list.\nYou might also be able to view the details of a patchset.\nThe \"my subsystems\" filter only lists patchsets touching MAINTAINERS sections of the target kernel tree in which you are a maintainer or reviewer.\nThe filter bar takes whitespace-separated terms, all of which must match:\n - a:<text>: author name or email contains <text>\n - v:<range> and n:<range>: version and number of patches, like 2, 2-4, 2- or -3\n - type:rfc or type:patch\n - since:<YYYY-MM-DD> and until:<YYYY-MM-DD>: last updated in the range\n - re:<regex>: title matches <regex>\n - any other term: title contains it\nFilters and sorts apply to every patchset fetched so far, which are then split in pages, so a page may be short until the next one is fetched. Sorting by most-reviewed only knows the code-review trailers of patchsets whose details were opened, the others coming last.")
+        .keymap(keymap, &CurrentScreen::LatestPatchsets)
         .build();
     Box::new(popup)
 }
@@ -1,9 +1,13 @@
 use std::ops::ControlFlow;
 
 use crate::{
-    app::{screens::CurrentScreen, App},
+    app::{
+        keymap::{Action, Keymap},
+        screens::CurrentScreen,
+        App,
+    },
     loading_screen,
-    ui::popup::{help::HelpPopUpBuilder, PopUp},
+    ui::popup::{help::HelpPopUpBuilder, info_popup::InfoPopUp, PopUp},
 };
 use ratatui::{
     crossterm::event::{KeyCode, KeyEvent},
@@ -11,76 +15,127 @@ use ratatui::{
     Terminal,
 };
 
-pub fn handle_mailing_list_selection<B>(
+use super::command_line::open_command_line;
+
+/// Types `key`, not bound to any action, in the name of the target list
+pub fn handle_target_list_input(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Backspace => {
+            app.mailing_list_selection.remove_last_target_list_char();
+        }
+        KeyCode::Char(ch) => {
+            app.mailing_list_selection.push_char_to_target_list(ch);
+        }
+        _ => {}
+    }
+}
+
+/// Runs `action` in the mailing list selection, either bound to a key or
+/// triggered by the mouse
+pub fn handle_mailing_list_selection_action<B>(
     app: &mut App,
-    key: KeyEvent,
+    action: Action,
     mut terminal: Terminal<B>,
 ) -> color_eyre::Result<ControlFlow<(), Terminal<B>>>
 where
     B: Backend + Send + 'static,
 {
-    match key.code {
-        KeyCode::Char('?') => {
-            let popup = generate_help_popup();
+    match action {
+        Action::Help => {
+            let popup = generate_help_popup(&app.keymap);
             app.popup = Some(popup);
         }
-        KeyCode::Enter => {
-            if app.mailing_list_selection.has_valid_target_list() {
-                app.init_latest_patchsets();
-                let list_name = app
-                    .latest_patchsets
-                    .as_ref()
-                    .unwrap()
-                    .target_list()
-                    .to_string();
+        Action::CommandLine => {
+            open_command_line(app);
+        }
+        Action::Open if app.mailing_list_selection.has_valid_target_list() => {
+            app.init_latest_patchsets();
+            let list_name = app
+                .latest_patchsets
+                .as_ref()
+                .unwrap()
+                .target_list()
+                .to_string();
 
-                terminal = loading_screen! {
-                    terminal,
-                    format!("Fetching patchsets from {}", list_name) => {
-                        let result =
-                        app.latest_patchsets.as_mut().unwrap()
-                        .fetch_current_page();
-                        if result.is_ok() {
-                            app.mailing_list_selection.clear_target_list();
-                            app.set_current_screen(CurrentScreen::LatestPatchsets);
-                        }
-                        result
+            terminal = loading_screen! {
+                terminal,
+                app.theme,
+                format!("Fetching patchsets from {}", list_name) => {
+                    let result =
+                    app.latest_patchsets.as_mut().unwrap()
+                    .fetch_current_page();
+                    if result.is_ok() {
+                        app.record_list_activity();
+                        app.mailing_list_selection.clear_target_list();
+                        app.navigate_to(CurrentScreen::LatestPatchsets);
                     }
-                };
-            }
+                    result
+                }
+            };
         }
-        KeyCode::F(5) => {
+        Action::RefreshLists => {
             terminal = loading_screen! {
                 terminal,
+                app.theme,
                 "Refreshing lists" => {
                     app.mailing_list_selection
                         .refresh_available_mailing_lists()
                 }
             };
         }
-        KeyCode::F(2) => {
+        Action::EditConfig => {
             app.init_edit_config();
             app.set_current_screen(CurrentScreen::EditConfig);
         }
-        KeyCode::F(1) => {
-            if !app.bookmarked_patchsets.bookmarked_patchsets.is_empty() {
-                app.mailing_list_selection.clear_target_list();
-                app.set_current_screen(CurrentScreen::BookmarkedPatchsets);
-            }
+        Action::ManageWorktrees => {
+            app.init_worktrees();
+            app.mailing_list_selection.clear_target_list();
+            app.set_current_screen(CurrentScreen::Worktrees);
         }
-        KeyCode::Backspace => {
-            app.mailing_list_selection.remove_last_target_list_char();
+        Action::ManageKernelTrees => {
+            app.init_kernel_trees();
+            app.mailing_list_selection.clear_target_list();
+            app.set_current_screen(CurrentScreen::KernelTrees);
+        }
+        Action::ShowApplyHistory => {
+            // Checking if the branch of each record still exists runs git once per record
+            terminal = loading_screen! {
+                terminal,
+                app.theme,
+                "Loading apply history" => {
+                    app.init_apply_history();
+                    color_eyre::Result::<()>::Ok(())
+                }
+            };
+            app.mailing_list_selection.clear_target_list();
+            app.set_current_screen(CurrentScreen::ApplyHistory);
+        }
+        Action::ShowBookmarked if !app.bookmarked_patchsets.bookmarked_patchsets.is_empty() => {
+            app.mailing_list_selection.clear_target_list();
+            app.navigate_to(CurrentScreen::BookmarkedPatchsets);
         }
-        KeyCode::Esc => {
+        Action::ShowRecent if !app.recent_patchsets.recent_patchsets.is_empty() => {
+            app.mailing_list_selection.clear_target_list();
+            app.navigate_to(CurrentScreen::RecentPatchsets);
+        }
+        Action::Exit => {
             return Ok(ControlFlow::Break(()));
         }
-        KeyCode::Char(ch) => {
-            app.mailing_list_selection.push_char_to_target_list(ch);
+        Action::ToggleFavorite => {
+            if let Err(error) = app
+                .mailing_list_selection
+                .toggle_highlighted_list_favorite()
+            {
+                app.popup = Some(InfoPopUp::generate_info_popup(
+                    "Favorite Lists Fail",
+                    &format!("Failed to save the favorite lists: {error}"),
+                ));
+            }
         }
-        KeyCode::Down => {
+        Action::Down => {
             app.mailing_list_selection.highlight_below_list();
         }
-        KeyCode::Up => {
+        Action::Up => {
             app.mailing_list_selection.highlight_above_list();
         }
         _ => {}
@@ -89,18 +144,11 @@ where
 }
 
 // TODO: Move this to a more appropriate place
-pub fn generate_help_popup() -> Box<dyn PopUp> {
+pub fn generate_help_popup(keymap: &Keymap) -> Box<dyn PopUp> {
     let popup = HelpPopUpBuilder::new()
         .title("Mailing List Selection")
-        .description("This is the mailing list selection screen.\nYou can select a mailing list by typing the name of the list.")
-        .keybind("ESC", "Exit")
-        .keybind("ENTER", "Open the selected mailing list")
-        .keybind("?", "Show this help screen")
-        .keybind("🡇", "Down")
-        .keybind("🡅", "Up")
-        .keybind("F1", "Show bookmarked patchsets")
-        .keybind("F2", "Edit config options")
-        .keybind("F5", "Refresh lists")
+        .description("This is the mailing list selection screen.\nYou can select a mailing list by typing (part of) its name or description.\nFavorite lists are pinned to the top and lists browsed before show their activity.")
+        .keymap(keymap, &CurrentScreen::MailingListSelection)
         .build();
 
     Box::new(popup)
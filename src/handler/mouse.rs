@@ -0,0 +1,134 @@
+use std::{
+    ops::ControlFlow,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    app::{keymap::Action, screens::CurrentScreen, App},
+    ui::FrameAreas,
+};
+use ratatui::{
+    crossterm::event::{MouseButton, MouseEvent, MouseEventKind},
+    layout::Position,
+    prelude::Backend,
+    Terminal,
+};
+
+use super::handle_action;
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum time between the clicks of a double-click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+/// Lines of the preview scrolled by each step of the mouse wheel
+const WHEEL_SCROLL_LINES: usize = 3;
+
+/// Click on an item of a list, kept to detect double-clicks
+pub struct ListClick {
+    at: Instant,
+    item: usize,
+}
+
+impl ListClick {
+    /// Returns if clicking `item` at `at` makes a double-click with this click
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_detect_double_clicks]
+    fn is_double_click(&self, item: usize, at: Instant) -> bool {
+        self.item == item && at.saturating_duration_since(self.at) <= DOUBLE_CLICK_INTERVAL
+    }
+}
+
+/// Handles `mouse` over the areas of the last drawn frame. Clicking an item of
+/// a list selects it and double-clicking opens it, clicking a row of the
+/// actions pane toggles its action and the wheel scrolls the preview or moves
+/// the selection of lists. The mouse is ignored while a pop-up or prompt is
+/// open.
+pub fn handle_mouse<B>(
+    app: &mut App,
+    mouse: MouseEvent,
+    frame_areas: &FrameAreas,
+    last_click: &mut Option<ListClick>,
+    terminal: Terminal<B>,
+) -> color_eyre::Result<ControlFlow<(), Terminal<B>>>
+where
+    B: Backend + Send + 'static,
+{
+    if app.popup.is_some() || is_prompt_open(app) {
+        return Ok(ControlFlow::Continue(terminal));
+    }
+
+    let position = Position::new(mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(item) = frame_areas.list_item_at(position) {
+                let now = Instant::now();
+                let is_double_click = last_click
+                    .take()
+                    .is_some_and(|click| click.is_double_click(item, now));
+                select_list_item(app, item);
+                if is_double_click {
+                    return handle_action(app, Action::Open, terminal);
+                }
+                *last_click = Some(ListClick { at: now, item });
+            } else if let Some(action) = frame_areas.action_at(position) {
+                return handle_action(app, action, terminal);
+            }
+        }
+        MouseEventKind::ScrollDown if frame_areas.is_over_preview(position) => {
+            if let Some(details_actions) = app.details_actions.as_mut() {
+                details_actions.preview_scroll_down(WHEEL_SCROLL_LINES);
+            }
+        }
+        MouseEventKind::ScrollUp if frame_areas.is_over_preview(position) => {
+            if let Some(details_actions) = app.details_actions.as_mut() {
+                details_actions.preview_scroll_up(WHEEL_SCROLL_LINES);
+            }
+        }
+        MouseEventKind::ScrollDown if frame_areas.is_over_list(position) => {
+            return handle_action(app, Action::Down, terminal);
+        }
+        MouseEventKind::ScrollUp if frame_areas.is_over_list(position) => {
+            return handle_action(app, Action::Up, terminal);
+        }
+        _ => {}
+    }
+    Ok(ControlFlow::Continue(terminal))
+}
+
+/// Returns if the search prompt of the details or the filter bar of the
+/// latest patchsets is open
+fn is_prompt_open(app: &App) -> bool {
+    match app.current_screen {
+        CurrentScreen::PatchsetDetails => app
+            .details_actions
+            .as_ref()
+            .is_some_and(|details_actions| details_actions.search_prompt.is_some()),
+        CurrentScreen::LatestPatchsets => app
+            .latest_patchsets
+            .as_ref()
+            .is_some_and(|latest_patchsets| latest_patchsets.filter_prompt().is_some()),
+        _ => false,
+    }
+}
+
+/// Selects the item of index `item` of the list of the current screen
+fn select_list_item(app: &mut App, item: usize) {
+    match app.current_screen {
+        CurrentScreen::MailingListSelection => {
+            app.mailing_list_selection.highlighted_list_index = item;
+        }
+        CurrentScreen::BookmarkedPatchsets => {
+            app.bookmarked_patchsets.patchset_index = item;
+        }
+        CurrentScreen::RecentPatchsets => {
+            app.recent_patchsets.patchset_index = item;
+        }
+        CurrentScreen::LatestPatchsets => {
+            app.latest_patchsets.as_mut().unwrap().select_patchset(item);
+        }
+        _ => {}
+    }
+}
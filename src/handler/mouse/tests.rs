@@ -0,0 +1,18 @@
+use super::*;
+
+#[test]
+fn should_detect_double_clicks() {
+    let now = Instant::now();
+    let click = ListClick { at: now, item: 2 };
+
+    assert!(click.is_double_click(2, now + Duration::from_millis(100)));
+    assert!(click.is_double_click(2, now + DOUBLE_CLICK_INTERVAL));
+    assert!(
+        !click.is_double_click(3, now + Duration::from_millis(100)),
+        "Clicks on different items shouldn't make a double-click"
+    );
+    assert!(
+        !click.is_double_click(2, now + DOUBLE_CLICK_INTERVAL + Duration::from_millis(1)),
+        "Clicks further apart than the interval shouldn't make a double-click"
+    );
+}
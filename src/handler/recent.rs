@@ -0,0 +1,70 @@
+use std::ops::ControlFlow;
+
+use crate::{
+    app::{
+        keymap::{Action, Keymap},
+        screens::CurrentScreen,
+        App,
+    },
+    loading_screen,
+    ui::popup::{help::HelpPopUpBuilder, PopUp},
+};
+use ratatui::{prelude::Backend, Terminal};
+
+use super::command_line::open_command_line;
+
+/// Runs `action` in the recent patchsets, either bound to a key or
+/// triggered by the mouse
+pub fn handle_recent_patchsets_action<B>(
+    app: &mut App,
+    action: Action,
+    mut terminal: Terminal<B>,
+) -> color_eyre::Result<ControlFlow<(), Terminal<B>>>
+where
+    B: Backend + Send + 'static,
+{
+    match action {
+        Action::Help => {
+            let popup = generate_help_popup(&app.keymap);
+            app.popup = Some(popup);
+        }
+        Action::CommandLine => {
+            open_command_line(app);
+        }
+        Action::Exit => {
+            app.recent_patchsets.patchset_index = 0;
+            app.exit_screen();
+        }
+        Action::Down => {
+            app.recent_patchsets.select_below_patchset();
+        }
+        Action::Up => {
+            app.recent_patchsets.select_above_patchset();
+        }
+        Action::Open => {
+            terminal = loading_screen! {
+                terminal,
+                app.theme,
+                "Loading patchset" => {
+                    let result = app.init_details_actions();
+                    if result.is_ok() {
+                        app.navigate_to(CurrentScreen::PatchsetDetails);
+                    }
+                    result
+                }
+            };
+        }
+        _ => {}
+    }
+    Ok(ControlFlow::Continue(terminal))
+}
+
+pub fn generate_help_popup(keymap: &Keymap) -> Box<dyn PopUp> {
+    let popup = HelpPopUpBuilder::new()
+        .title("Recent Patchsets")
+        .description("This screen shows the patchsets you have recently viewed, most recent first.\nThis jump list is kept across runs, so you can get back to patchsets you were looking at.")
+        .keymap(keymap, &CurrentScreen::RecentPatchsets)
+        .build();
+
+    Box::new(popup)
+}
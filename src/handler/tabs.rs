@@ -0,0 +1,49 @@
+use crate::{
+    app::{keymap::Action, screens::details_actions::DetailsActions, App},
+    ui::popup::confirm::{ConfirmPopUp, Confirmation},
+};
+
+use super::details_actions::refuse_leaving_details;
+
+/// Runs the tab `action` (like [Action::NewTab] or [Action::Back], which goes
+/// back in the navigation history of the tab), bound to keys in the screens of
+/// tabs (see [crate::app::tabs::TAB_SCREENS])
+pub fn handle_tab_action(app: &mut App, action: Action) {
+    match action {
+        Action::NewTab => app.open_tab(),
+        Action::CloseTab => close_tab(app),
+        Action::NextTab => app.switch_to_next_tab(),
+        Action::PreviousTab => app.switch_to_previous_tab(),
+        Action::Back if !refuse_leaving_details(app) => {
+            app.go_back();
+        }
+        Action::Forward if !refuse_leaving_details(app) => {
+            app.go_forward();
+        }
+        _ => {}
+    }
+}
+
+/// Closes the active tab, unless it's the only one or its patchset details
+/// can't be left (see [refuse_leaving_details]). If the patchset details of
+/// the tab have marked actions, which would be discarded, it's only closed
+/// once confirmed.
+pub fn close_tab(app: &mut App) {
+    if app.tabs.len() > 1 && refuse_leaving_details(app) {
+        return;
+    }
+    if app.tabs.len() > 1
+        && app
+            .details_actions
+            .as_ref()
+            .is_some_and(DetailsActions::has_unfinished_work)
+    {
+        app.popup = Some(ConfirmPopUp::generate_confirm_popup(
+            "Close Tab",
+            "The patchset details of this tab have marked actions.\nClose the tab anyway?",
+            Confirmation::CloseTab,
+        ));
+    } else {
+        app.close_tab();
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    app::{
+        keymap::{Action, Keymap},
+        screens::CurrentScreen,
+        App,
+    },
+    ui::popup::{
+        confirm::{ConfirmPopUp, Confirmation},
+        help::HelpPopUpBuilder,
+        info_popup::InfoPopUp,
+        PopUp,
+    },
+    utils,
+};
+use ratatui::{crossterm::event::KeyEvent, prelude::Backend, Terminal};
+use std::{env, process::Command};
+
+use super::{command_line::open_command_line, read_action};
+
+pub fn handle_worktrees<B: Backend>(
+    app: &mut App,
+    key: KeyEvent,
+    terminal: &mut Terminal<B>,
+) -> color_eyre::Result<()> {
+    let Some(action) = read_action(&app.keymap, &app.current_screen, &mut app.replayed_key, key)?
+    else {
+        return Ok(());
+    };
+    let worktrees = app.worktrees.as_mut().unwrap();
+
+    match action {
+        Action::Help => {
+            let popup = generate_help_popup(&app.keymap);
+            app.popup = Some(popup);
+        }
+        Action::CommandLine => {
+            open_command_line(app);
+        }
+        Action::Exit => {
+            app.reset_worktrees();
+            app.set_current_screen(CurrentScreen::MailingListSelection);
+        }
+        Action::Down => {
+            worktrees.select_below_worktree();
+        }
+        Action::Up => {
+            worktrees.select_above_worktree();
+        }
+        Action::Open => {
+            let Some(selected_worktree) = worktrees.get_selected_worktree() else {
+                return Ok(());
+            };
+            // Opens a shell in the worktree and resumes the TUI once it exits
+            let shell = env::var("SHELL").unwrap_or("sh".to_string());
+            utils::setup_user_io(terminal)?;
+            let status = Command::new(&shell)
+                .current_dir(selected_worktree.path())
+                .status();
+            utils::teardown_user_io(terminal)?;
+
+            if let Err(e) = status {
+                app.popup = Some(InfoPopUp::generate_info_popup(
+                    "Open Worktree Fail",
+                    &format!("failed to run {shell}: {e}"),
+                ));
+            }
+        }
+        Action::Prune => {
+            let Some(selected_worktree) = worktrees.get_selected_worktree() else {
+                return Ok(());
+            };
+            let branch = selected_worktree
+                .branch()
+                .as_deref()
+                .unwrap_or("(detached)");
+            app.popup = Some(ConfirmPopUp::generate_confirm_popup(
+                "Prune Worktree",
+                &format!(
+                    "Remove the worktree below? Its branch is kept.\n\n{} ({})",
+                    selected_worktree.path(),
+                    branch
+                ),
+                Confirmation::RemoveWorktree { force: false },
+            ));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+pub fn generate_help_popup(keymap: &Keymap) -> Box<dyn PopUp> {
+    let popup = HelpPopUpBuilder::new()
+        .title("Worktrees")
+        .description("This screen lists the git worktrees in which patchsets were applied, grouped by kernel tree.\nThe branch of a pruned worktree is kept in the kernel tree.")
+        .keymap(keymap, &CurrentScreen::Worktrees)
+        .build();
+
+    Box::new(popup)
+}
@@ -1,4 +1,9 @@
+pub mod attestation;
+pub mod checkpatch;
+pub mod fixes;
 pub mod lore_api_client;
 pub mod lore_session;
 pub mod mailing_list;
+pub mod maintainers;
 pub mod patch;
+pub mod trailers;
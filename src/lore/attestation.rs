@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::LazyLock;
+
+use derive_getters::Getters;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// Outcome of checking the cryptographic signatures (DKIM and patatt) of a
+/// patch or of a whole patchset.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, Hash, Eq, PartialEq)]
+pub enum AttestationStatus {
+    /// No signature could be checked (none present or no way to check it)
+    #[default]
+    #[serde(rename = "unverified")]
+    Unverified,
+    /// All signatures checked passed
+    #[serde(rename = "verified")]
+    Verified,
+    /// At least one signature checked failed
+    #[serde(rename = "failed")]
+    Failed,
+}
+
+impl Display for AttestationStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttestationStatus::Unverified => write!(f, "unverified"),
+            AttestationStatus::Verified => write!(f, "verified"),
+            AttestationStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl AttestationStatus {
+    /// Consolidates the status of a whole patchset from the status of its
+    /// patches. A single failure fails the patchset and it is only verified if
+    /// every patch is.
+    pub fn from_patches(attestations: &[PatchAttestation]) -> AttestationStatus {
+        if attestations.is_empty() {
+            AttestationStatus::Unverified
+        } else if attestations
+            .iter()
+            .any(|a| a.status == AttestationStatus::Failed)
+        {
+            AttestationStatus::Failed
+        } else if attestations
+            .iter()
+            .all(|a| a.status == AttestationStatus::Verified)
+        {
+            AttestationStatus::Verified
+        } else {
+            AttestationStatus::Unverified
+        }
+    }
+}
+
+/// Attestation of a single patch (or cover letter)
+#[derive(Getters, Debug, Clone, Default, Eq, PartialEq)]
+pub struct PatchAttestation {
+    status: AttestationStatus,
+    /// Signatures checked, like `Signed: DKIM/kernel.org` or
+    /// `BADSIG: openpgp/foo@bar.com`
+    signatures: Vec<String>,
+}
+
+impl PatchAttestation {
+    pub fn new(status: AttestationStatus, signatures: Vec<String>) -> PatchAttestation {
+        PatchAttestation { status, signatures }
+    }
+}
+
+/// Attestation of a patchset as reported by `b4 am`
+///
+/// `b4 am` lists each patch with a checkmark and, after a `---` separator, the
+/// signatures common to the whole series. Patches are indexed by their number
+/// in the series, with `0` being the cover letter.
+#[derive(Getters, Debug, Clone, Default, Eq, PartialEq)]
+pub struct SeriesAttestation {
+    patches: BTreeMap<usize, PatchAttestation>,
+    series: PatchAttestation,
+}
+
+impl SeriesAttestation {
+    /// Returns the attestation of each patch in the order they are stored in
+    /// `DetailsActions::raw_patches`, that is, `total` entries starting with
+    /// the cover letter if `has_cover_letter` is set.
+    ///
+    /// The cover letter isn't listed by `b4 am`, so it gets the series-wide
+    /// attestation. Patches without signatures of their own inherit the
+    /// series-wide ones.
+    pub fn per_patch(&self, total: usize, has_cover_letter: bool) -> Vec<PatchAttestation> {
+        let number_offset = if has_cover_letter { 0 } else { 1 };
+
+        (0..total)
+            .map(|i| match self.patches.get(&(i + number_offset)) {
+                Some(attestation) if attestation.signatures.is_empty() => PatchAttestation {
+                    status: attestation.status,
+                    signatures: self.series.signatures.clone(),
+                },
+                Some(attestation) => attestation.clone(),
+                None if i + number_offset == 0 => self.series.clone(),
+                None => PatchAttestation::default(),
+            })
+            .collect()
+    }
+}
+
+/// Returns the path of the file that stores the `b4 am` attestation report of
+/// the patchset stored in `patchset_path`.
+pub fn attestation_report_path(patchset_path: &str) -> String {
+    patchset_path.replace(".mbx", ".attestation")
+}
+
+/// Parses the attestation report printed by `b4 am`.
+///
+/// Both the `fancy` (✓/✗) and the `simple` (v/x) checkmarks are supported.
+/// Any other checkmark (like the one used for messages without signatures) is
+/// considered [AttestationStatus::Unverified].
+///
+/// # Tests
+///
+/// [tests::should_parse_verified_report]
+/// [tests::should_parse_report_with_failed_patch]
+/// [tests::should_parse_report_without_signatures]
+pub fn parse_b4_attestation_report(report: &str) -> SeriesAttestation {
+    static RE_ANSI_ESCAPE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+    static RE_PATCH_NUMBER: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^\[[^\]]*?(\d+) */ *\d+[^\]]*\]").unwrap());
+
+    let report = RE_ANSI_ESCAPE.replace_all(report, "");
+
+    let mut attestation = SeriesAttestation::default();
+    let mut current_patch: Option<usize> = None;
+    let mut in_series_summary = false;
+    let mut series_statuses: Vec<AttestationStatus> = Vec::new();
+
+    for line in report.lines() {
+        let content = line.trim_start();
+        let indent = line.len() - content.len();
+
+        if indent == 0 {
+            // Top-level separators delimit the attestation section
+            if content == "---" && in_series_summary {
+                break;
+            }
+            current_patch = None;
+            continue;
+        }
+
+        if content == "---" {
+            in_series_summary = true;
+            current_patch = None;
+            continue;
+        }
+
+        let Some((status, entry)) = split_checkmark(content) else {
+            continue;
+        };
+
+        if in_series_summary {
+            series_statuses.push(status);
+            attestation.series.signatures.push(entry.to_string());
+        } else if entry.starts_with('[') {
+            let number = RE_PATCH_NUMBER
+                .captures(entry)
+                .and_then(|capture| capture.get(1))
+                .and_then(|number| number.as_str().parse().ok())
+                .unwrap_or(1);
+            attestation
+                .patches
+                .insert(number, PatchAttestation::new(status, Vec::new()));
+            current_patch = Some(number);
+        } else if let Some(number) = current_patch {
+            if let Some(patch) = attestation.patches.get_mut(&number) {
+                patch.signatures.push(entry.to_string());
+            }
+        }
+    }
+
+    attestation.series.status = if series_statuses.contains(&AttestationStatus::Failed) {
+        AttestationStatus::Failed
+    } else if series_statuses.contains(&AttestationStatus::Verified) {
+        AttestationStatus::Verified
+    } else {
+        AttestationStatus::Unverified
+    };
+
+    attestation
+}
+
+/// Splits a line of the report into its checkmark and the rest of the line.
+/// Lines of trailers added by `b4` (starting with `+`) and notes are ignored.
+fn split_checkmark(content: &str) -> Option<(AttestationStatus, &str)> {
+    let mut chars = content.chars();
+    let checkmark = chars.next()?;
+    let entry = chars.as_str().strip_prefix(' ')?.trim();
+
+    match checkmark {
+        '✓' | 'v' => Some((AttestationStatus::Verified, entry)),
+        '✗' | 'x' => Some((AttestationStatus::Failed, entry)),
+        '+' => None,
+        _ if entry.starts_with('[') => Some((AttestationStatus::Unverified, entry)),
+        _ => None,
+    }
+}
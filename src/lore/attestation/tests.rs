@@ -0,0 +1,93 @@
+use super::*;
+
+use std::fs;
+
+#[test]
+fn should_parse_verified_report() {
+    let report = fs::read_to_string(
+        "test_samples/attestation/parse_b4_attestation_report/verified_report.txt",
+    )
+    .unwrap();
+
+    let attestation = parse_b4_attestation_report(&report);
+
+    assert_eq!(3, attestation.patches().len(), "Wrong number of patches");
+    assert_eq!(
+        &PatchAttestation::new(
+            AttestationStatus::Verified,
+            vec![
+                "Signed: openpgp/john@johnson.com".to_string(),
+                "Signed: DKIM/johnson.com".to_string(),
+            ]
+        ),
+        attestation.series(),
+        "Wrong series-wide attestation"
+    );
+
+    let per_patch = attestation.per_patch(4, true);
+    assert_eq!(4, per_patch.len());
+    for (i, patch_attestation) in per_patch.iter().enumerate() {
+        assert_eq!(
+            attestation.series(),
+            patch_attestation,
+            "Patch {i} should inherit the series-wide signatures"
+        );
+    }
+    assert_eq!(
+        AttestationStatus::Verified,
+        AttestationStatus::from_patches(&per_patch)
+    );
+}
+
+#[test]
+fn should_parse_report_with_failed_patch() {
+    let report = fs::read_to_string(
+        "test_samples/attestation/parse_b4_attestation_report/failed_report.txt",
+    )
+    .unwrap();
+
+    let attestation = parse_b4_attestation_report(&report);
+    let per_patch = attestation.per_patch(2, false);
+
+    assert_eq!(
+        PatchAttestation::new(
+            AttestationStatus::Failed,
+            vec!["BADSIG: DKIM/johnson.com".to_string()]
+        ),
+        per_patch[0],
+        "Wrong attestation for patch 1"
+    );
+    assert_eq!(
+        PatchAttestation::new(
+            AttestationStatus::Verified,
+            vec!["Signed: DKIM/johnson.com".to_string()]
+        ),
+        per_patch[1],
+        "Wrong attestation for patch 2"
+    );
+    assert_eq!(
+        AttestationStatus::Failed,
+        AttestationStatus::from_patches(&per_patch)
+    );
+}
+
+#[test]
+fn should_parse_report_without_signatures() {
+    let report = fs::read_to_string(
+        "test_samples/attestation/parse_b4_attestation_report/unsigned_report.txt",
+    )
+    .unwrap();
+
+    let attestation = parse_b4_attestation_report(&report);
+    let per_patch = attestation.per_patch(1, false);
+
+    assert_eq!(vec![PatchAttestation::default()], per_patch);
+    assert_eq!(
+        AttestationStatus::Unverified,
+        AttestationStatus::from_patches(&per_patch)
+    );
+
+    // A patchset without report (e.g. downloaded by older versions) is unverified
+    let per_patch = parse_b4_attestation_report("").per_patch(3, true);
+    assert_eq!(vec![PatchAttestation::default(); 3], per_patch);
+}
@@ -0,0 +1,143 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::LazyLock;
+
+use derive_getters::Getters;
+use regex::Regex;
+
+#[cfg(test)]
+mod tests;
+
+/// Path of `checkpatch.pl` relative to the root of a kernel tree
+pub const CHECKPATCH_PATH: &str = "scripts/checkpatch.pl";
+
+/// Severity of an issue reported by `checkpatch.pl`
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum CheckpatchLevel {
+    Error,
+    Warning,
+    /// Only reported by `checkpatch.pl --strict`
+    Check,
+}
+
+impl Display for CheckpatchLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpatchLevel::Error => write!(f, "ERROR"),
+            CheckpatchLevel::Warning => write!(f, "WARNING"),
+            CheckpatchLevel::Check => write!(f, "CHECK"),
+        }
+    }
+}
+
+/// An issue reported by `checkpatch.pl`
+#[derive(Getters, Debug, Clone, Eq, PartialEq)]
+pub struct CheckpatchIssue {
+    level: CheckpatchLevel,
+    /// Type of the issue (like `LONG_LINE`), only reported with `--show-types`
+    issue_type: Option<String>,
+    message: String,
+    /// Line of the patch (starting at 1) the issue refers to, if any
+    line: Option<usize>,
+    /// File and line changed by the patch the issue refers to, like
+    /// `drivers/foo.c:42`, if any
+    location: Option<String>,
+}
+
+/// Issues reported by `checkpatch.pl` for a single patch
+#[derive(Getters, Debug, Clone, Default, Eq, PartialEq)]
+pub struct CheckpatchReport {
+    issues: Vec<CheckpatchIssue>,
+}
+
+impl CheckpatchReport {
+    /// Returns the number of issues of severity `level`.
+    pub fn count(&self, level: CheckpatchLevel) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.level == level)
+            .count()
+    }
+}
+
+/// Parses the output of `checkpatch.pl` for a single patch.
+///
+/// # Tests
+///
+/// [tests::should_parse_checkpatch_output]
+/// [tests::should_parse_clean_checkpatch_output]
+pub fn parse_checkpatch_output(output: &str) -> CheckpatchReport {
+    static RE_ISSUE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^(ERROR|WARNING|CHECK):(?:([A-Z0-9_]+):)?\s*(.*)$").unwrap());
+    static RE_LOCATION: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^#(\d+):(?:\s*FILE:\s*(\S+?):?$)?").unwrap());
+
+    let mut report = CheckpatchReport::default();
+
+    for line in output.lines() {
+        if let Some(capture) = RE_ISSUE.captures(line) {
+            let level = match &capture[1] {
+                "ERROR" => CheckpatchLevel::Error,
+                "WARNING" => CheckpatchLevel::Warning,
+                _ => CheckpatchLevel::Check,
+            };
+            report.issues.push(CheckpatchIssue {
+                level,
+                issue_type: capture.get(2).map(|m| m.as_str().to_string()),
+                message: capture[3].to_string(),
+                line: None,
+                location: None,
+            });
+        } else if let Some(capture) = RE_LOCATION.captures(line) {
+            // The location comes right after the issue it refers to
+            if let Some(issue) = report.issues.last_mut() {
+                if issue.line.is_none() {
+                    issue.line = capture[1].parse().ok();
+                    issue.location = capture.get(2).map(|m| m.as_str().to_string());
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Runs the `checkpatch.pl` of the kernel tree in `kernel_tree_path` against
+/// `raw_patch` with the extra `checkpatch_options`.
+///
+/// Fails if the kernel tree has no `checkpatch.pl` or if it can't be run.
+/// Finding issues in the patch isn't considered a failure.
+pub fn run_checkpatch(
+    kernel_tree_path: &str,
+    raw_patch: &str,
+    checkpatch_options: &str,
+) -> io::Result<CheckpatchReport> {
+    let checkpatch_path = Path::new(kernel_tree_path).join(CHECKPATCH_PATH);
+    if !checkpatch_path.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} not found", checkpatch_path.display()),
+        ));
+    }
+
+    let mut checkpatch = Command::new(&checkpatch_path)
+        .current_dir(kernel_tree_path)
+        .arg("--no-summary")
+        .args(checkpatch_options.split_whitespace())
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(mut stdin) = checkpatch.stdin.take() {
+        stdin.write_all(raw_patch.as_bytes())?;
+    }
+    let output = checkpatch.wait_with_output()?;
+
+    Ok(parse_checkpatch_output(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
@@ -0,0 +1,62 @@
+use super::*;
+
+use std::fs;
+
+#[test]
+fn should_parse_checkpatch_output() {
+    let output =
+        fs::read_to_string("test_samples/checkpatch/parse_checkpatch_output/issues_output.txt")
+            .unwrap();
+
+    let report = parse_checkpatch_output(&output);
+
+    assert_eq!(5, report.issues().len(), "Wrong number of issues");
+    assert_eq!(1, report.count(CheckpatchLevel::Error));
+    assert_eq!(3, report.count(CheckpatchLevel::Warning));
+    assert_eq!(1, report.count(CheckpatchLevel::Check));
+
+    assert_eq!(
+        CheckpatchIssue {
+            level: CheckpatchLevel::Warning,
+            issue_type: Some("COMMIT_LOG_LONG_LINE".to_string()),
+            message: "Prefer a maximum 75 chars per line (possible unwrapped commit description?)"
+                .to_string(),
+            line: Some(9),
+            location: None,
+        },
+        report.issues()[0],
+        "Issues in the commit message have no file location"
+    );
+    assert_eq!(
+        CheckpatchIssue {
+            level: CheckpatchLevel::Error,
+            issue_type: Some("CODE_INDENT".to_string()),
+            message: "code indent should use tabs where possible".to_string(),
+            line: Some(26),
+            location: Some("file.c:12".to_string()),
+        },
+        report.issues()[1]
+    );
+    assert_eq!(
+        CheckpatchIssue {
+            level: CheckpatchLevel::Warning,
+            issue_type: None,
+            message: "Missing Signed-off-by: line(s)".to_string(),
+            line: None,
+            location: None,
+        },
+        report.issues()[4],
+        "Issues without type nor location should be parsed"
+    );
+}
+
+#[test]
+fn should_parse_clean_checkpatch_output() {
+    let output =
+        fs::read_to_string("test_samples/checkpatch/parse_checkpatch_output/clean_output.txt")
+            .unwrap();
+
+    let report = parse_checkpatch_output(&output);
+
+    assert_eq!(CheckpatchReport::default(), report);
+}
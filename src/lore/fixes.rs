@@ -0,0 +1,191 @@
+use std::process::Command;
+use std::sync::LazyLock;
+
+use derive_getters::Getters;
+use regex::Regex;
+
+#[cfg(test)]
+mod tests;
+
+/// Minimum length of the abbreviated sha in a `Fixes:` trailer, as required by
+/// the kernel documentation (`Documentation/process/submitting-patches.rst`)
+pub const MIN_FIXES_SHA_LEN: usize = 12;
+
+/// A well-formed `Fixes:` trailer, like `Fixes: 0123456789ab ("foo: Do bar")`
+#[derive(Getters, Debug, Clone, Eq, PartialEq)]
+pub struct FixesTag {
+    /// Abbreviated sha of the fixed commit
+    sha: String,
+    /// Subject of the fixed commit, as quoted in the trailer
+    subject: String,
+}
+
+/// Outcome of resolving a `Fixes:` trailer against a kernel tree
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FixesResolution {
+    /// The trailer doesn't follow the `<sha> ("<subject>")` format. Holds the
+    /// reason it is malformed.
+    Malformed(String),
+    /// The trailer is well-formed, but it wasn't resolved yet (see
+    /// [FixesCheck::resolve])
+    Pending,
+    /// There is no kernel tree to resolve the trailer against
+    Unresolved,
+    /// The sha doesn't name a commit in the kernel tree
+    NotFound,
+    /// The sha names a commit in the kernel tree
+    Found {
+        /// Full sha of the fixed commit
+        commit: String,
+        /// Whether the quoted subject is the subject of `commit`
+        subject_matches: bool,
+        /// Actual subject of `commit`
+        actual_subject: String,
+        /// First tag (release) that contains `commit`, if any
+        first_tag: Option<String>,
+    },
+}
+
+/// A `Fixes:` trailer and the result of resolving it
+#[derive(Getters, Debug, Clone, Eq, PartialEq)]
+pub struct FixesCheck {
+    /// Value of the trailer, that is, everything after `Fixes:`
+    value: String,
+    resolution: FixesResolution,
+}
+
+/// Parses the value of a `Fixes:` trailer.
+///
+/// Returns the reason the trailer is malformed on failure.
+///
+/// # Tests
+///
+/// [tests::should_parse_fixes_value]
+pub fn parse_fixes_value(value: &str) -> Result<FixesTag, String> {
+    static RE_FIXES: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r#"^([0-9a-fA-F]+)\s+\("(.+)"\)$"#).unwrap());
+
+    let value = value.trim();
+    let Some(first_word) = value.split_whitespace().next() else {
+        return Err("empty trailer".to_string());
+    };
+    if !first_word.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("'{first_word}' isn't a commit sha"));
+    }
+    if first_word.len() < MIN_FIXES_SHA_LEN {
+        return Err(format!(
+            "sha '{first_word}' is shorter than {MIN_FIXES_SHA_LEN} characters"
+        ));
+    }
+
+    match RE_FIXES.captures(value) {
+        Some(capture) => Ok(FixesTag {
+            sha: capture[1].to_ascii_lowercase(),
+            subject: capture[2].to_string(),
+        }),
+        None => Err("missing quoted subject".to_string()),
+    }
+}
+
+/// Checks the format of the `Fixes:` trailer with value `value`.
+///
+/// Malformed trailers are reported as such right away, while well-formed ones
+/// are [FixesResolution::Pending] until [FixesCheck::resolve] is called, as
+/// resolving them against a kernel tree runs git commands that may be slow.
+pub fn check_fixes(value: &str) -> FixesCheck {
+    let resolution = match parse_fixes_value(value) {
+        Ok(_) => FixesResolution::Pending,
+        Err(reason) => FixesResolution::Malformed(reason),
+    };
+
+    FixesCheck {
+        value: value.to_string(),
+        resolution,
+    }
+}
+
+impl FixesCheck {
+    /// Returns whether the trailer is waiting to be resolved.
+    pub fn is_pending(&self) -> bool {
+        self.resolution == FixesResolution::Pending
+    }
+
+    /// Resolves a [FixesResolution::Pending] trailer against the git
+    /// repository in `kernel_tree_path`, leaving it
+    /// [FixesResolution::Unresolved] if it is `None`.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_not_resolve_without_kernel_tree]
+    pub fn resolve(&mut self, kernel_tree_path: Option<&str>) {
+        if !self.is_pending() {
+            return;
+        }
+
+        self.resolution = match (parse_fixes_value(&self.value), kernel_tree_path) {
+            (Err(reason), _) => FixesResolution::Malformed(reason),
+            (Ok(_), None) => FixesResolution::Unresolved,
+            (Ok(fixes_tag), Some(kernel_tree_path)) => {
+                resolve_fixes_tag(&fixes_tag, kernel_tree_path)
+            }
+        };
+    }
+}
+
+fn resolve_fixes_tag(fixes_tag: &FixesTag, kernel_tree_path: &str) -> FixesResolution {
+    let Some(commit) = run_git(
+        kernel_tree_path,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", fixes_tag.sha),
+        ],
+    ) else {
+        return FixesResolution::NotFound;
+    };
+
+    let actual_subject =
+        run_git(kernel_tree_path, &["log", "-1", "--format=%s", &commit]).unwrap_or_default();
+    let first_tag = run_git(kernel_tree_path, &["describe", "--contains", &commit])
+        .map(|describe| first_tag_from_describe(&describe));
+
+    FixesResolution::Found {
+        subject_matches: actual_subject == fixes_tag.subject.trim(),
+        commit,
+        actual_subject,
+        first_tag,
+    }
+}
+
+/// Extracts the tag from the output of `git describe --contains`, which is
+/// like `v6.1-rc1~12^2~3`.
+///
+/// # Tests
+///
+/// [tests::should_extract_first_tag_from_describe]
+fn first_tag_from_describe(describe: &str) -> String {
+    describe
+        .split(['~', '^'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Runs `git -C <repo_path> <args>` and returns its trimmed stdout if it
+/// succeeds with a non-empty output.
+fn run_git(repo_path: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !stdout.is_empty() {
+        Some(stdout)
+    } else {
+        None
+    }
+}
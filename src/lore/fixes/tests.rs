@@ -0,0 +1,64 @@
+use super::*;
+
+#[test]
+fn should_parse_fixes_value() {
+    assert_eq!(
+        Ok(FixesTag {
+            sha: "0123456789ab".to_string(),
+            subject: "file: Do baz".to_string(),
+        }),
+        parse_fixes_value("0123456789AB (\"file: Do baz\")")
+    );
+    assert_eq!(
+        Ok(FixesTag {
+            sha: "0123456789abcdef".to_string(),
+            subject: "file: Quote \"things\"".to_string(),
+        }),
+        parse_fixes_value(" 0123456789abcdef  (\"file: Quote \"things\"\") "),
+        "Subjects with quotes should be accepted"
+    );
+
+    assert!(
+        parse_fixes_value("0123456 (\"file: Do baz\")").is_err(),
+        "Short shas should be malformed"
+    );
+    assert!(
+        parse_fixes_value("commit 0123456789ab (\"file: Do baz\")").is_err(),
+        "Values not starting with a sha should be malformed"
+    );
+    assert!(
+        parse_fixes_value("0123456789ab").is_err(),
+        "Values without subject should be malformed"
+    );
+    assert!(
+        parse_fixes_value("0123456789ab file: Do baz").is_err(),
+        "Values with unquoted subject should be malformed"
+    );
+    assert!(parse_fixes_value("").is_err());
+}
+
+#[test]
+fn should_extract_first_tag_from_describe() {
+    assert_eq!("v6.1-rc1", first_tag_from_describe("v6.1-rc1~12^2~3"));
+    assert_eq!("v6.1", first_tag_from_describe("v6.1^0"));
+    assert_eq!("v5.15", first_tag_from_describe("v5.15"));
+}
+
+#[test]
+fn should_not_resolve_without_kernel_tree() {
+    let mut fixes_check = check_fixes("0123456789ab (\"file: Do baz\")");
+    assert!(
+        fixes_check.is_pending(),
+        "Trailers should be resolved lazily"
+    );
+    fixes_check.resolve(None);
+    assert_eq!(FixesResolution::Unresolved, *fixes_check.resolution());
+
+    let mut fixes_check = check_fixes("0123456");
+    assert!(!fixes_check.is_pending());
+    fixes_check.resolve(None);
+    assert!(matches!(
+        fixes_check.resolution(),
+        FixesResolution::Malformed(_)
+    ));
+}
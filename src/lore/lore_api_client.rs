@@ -50,9 +50,13 @@ impl BlockingLoreAPIClient {
 
 #[automock]
 pub trait PatchFeedRequest {
+    /// Requests the feed of patches of `target_list` starting at `min_index`.
+    /// If `search_query` isn't empty, only patches matching it (in the Lore
+    /// search syntax, like `f:foo`) are requested.
     fn request_patch_feed(
         &self,
         target_list: &str,
+        search_query: &str,
         min_index: usize,
     ) -> Result<String, ClientError>;
 }
@@ -61,12 +65,21 @@ impl PatchFeedRequest for BlockingLoreAPIClient {
     fn request_patch_feed(
         &self,
         target_list: &str,
+        search_query: &str,
         min_index: usize,
     ) -> Result<String, ClientError> {
-        let feed_url: String = format!(
-            "{}/{target_list}/{BASE_QUERY_FOR_FEED_REQUEST}&o={min_index}",
-            self.lore_domain
-        );
+        let feed_url: String = if search_query.is_empty() {
+            format!(
+                "{}/{target_list}/{BASE_QUERY_FOR_FEED_REQUEST}&o={min_index}",
+                self.lore_domain
+            )
+        } else {
+            format!(
+                "{}/{target_list}/{BASE_QUERY_FOR_FEED_REQUEST}+AND+({})&o={min_index}",
+                self.lore_domain,
+                encode_query(search_query)
+            )
+        };
 
         let request_builder = self
             .client
@@ -83,6 +96,26 @@ impl PatchFeedRequest for BlockingLoreAPIClient {
     }
 }
 
+/// Encodes `query` to be used as the value of a query parameter, with spaces
+/// as `+`.
+///
+/// # Tests
+///
+/// [tests::should_encode_search_query]
+fn encode_query(query: &str) -> String {
+    let mut encoded = String::new();
+    for byte in query.bytes() {
+        match byte {
+            b' ' => encoded.push('+'),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
 #[automock]
 pub trait AvailableListsRequest {
     fn request_available_lists(&self, min_index: usize) -> Result<String, ClientError>;
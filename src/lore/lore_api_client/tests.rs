@@ -6,7 +6,9 @@ use crate::lore::patch::PatchFeed;
 fn blocking_client_can_request_valid_patch_feed() {
     let lore_api_client = BlockingLoreAPIClient::default();
 
-    let patch_feed = lore_api_client.request_patch_feed("amd-gfx", 0).unwrap();
+    let patch_feed = lore_api_client
+        .request_patch_feed("amd-gfx", "", 0)
+        .unwrap();
     let patch_feed: PatchFeed = serde_xml_rs::from_str(&patch_feed).unwrap();
     let patches = patch_feed.patches();
 
@@ -22,7 +24,7 @@ fn blocking_client_can_request_valid_patch_feed() {
 fn blocking_client_should_detect_failed_patch_feed_request() {
     let lore_api_client = BlockingLoreAPIClient::default();
 
-    if let Err(client_error) = lore_api_client.request_patch_feed("invalid-list", 0) {
+    if let Err(client_error) = lore_api_client.request_patch_feed("invalid-list", "", 0) {
         match client_error {
             ClientError::FromUreq(_) => (),
             _ => {
@@ -33,7 +35,7 @@ fn blocking_client_should_detect_failed_patch_feed_request() {
         panic!("Invalid request shouldn't be successful");
     }
 
-    if let Err(client_error) = lore_api_client.request_patch_feed("amd-gfx", 300000) {
+    if let Err(client_error) = lore_api_client.request_patch_feed("amd-gfx", "", 300000) {
         match client_error {
             ClientError::EndOfFeed => (),
             _ => {
@@ -67,3 +69,14 @@ fn blocking_client_can_request_valid_patch_html() {
         panic!("Valid request should be successful");
     }
 }
+
+#[test]
+fn should_encode_search_query() {
+    assert_eq!("f:foo", encode_query("f:foo"));
+    assert_eq!(
+        "f:foo+AND+s:%22drm%2Famd%22",
+        encode_query("f:foo AND s:\"drm/amd\"")
+    );
+    assert_eq!("d:2.weeks.ago..", encode_query("d:2.weeks.ago.."));
+    assert_eq!("%C3%A9", encode_query("é"));
+}
@@ -1,3 +1,6 @@
+use crate::lore::attestation::{
+    attestation_report_path, parse_b4_attestation_report, AttestationStatus, SeriesAttestation,
+};
 use crate::lore::lore_api_client::{
     AvailableListsRequest, ClientError, PatchFeedRequest, PatchHTMLRequest,
 };
@@ -5,6 +8,7 @@ use crate::lore::mailing_list::MailingList;
 use crate::lore::patch::{Patch, PatchFeed, PatchRegex};
 use derive_getters::Getters;
 use regex::Regex;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_xml_rs::from_str;
 use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader};
@@ -12,6 +16,7 @@ use std::mem::swap;
 use std::path::Path;
 use std::process::{Command, Stdio};
 use std::sync::LazyLock;
+use std::time::Duration;
 use std::{
     fs::{self, File},
     io,
@@ -22,6 +27,9 @@ use thiserror::Error;
 mod tests;
 
 const LORE_PAGE_SIZE: usize = 200;
+/// Time after which a downloaded patchset thread is downloaded again, as new
+/// replies (and trailers) may have been sent since
+const THREAD_CACHE_TTL: Duration = Duration::from_secs(30 * 60);
 
 #[derive(Getters)]
 pub struct LoreSession {
@@ -32,6 +40,8 @@ pub struct LoreSession {
     patch_regex: PatchRegex,
     #[getter(skip)]
     target_list: String,
+    /// Lore search query (like `f:foo`) that patches must match, if not empty
+    search_query: String,
     #[getter(skip)]
     min_index: usize,
 }
@@ -44,8 +54,15 @@ pub enum LoreSessionError {
 
 impl LoreSession {
     pub fn new(target_list: String) -> LoreSession {
+        Self::with_search_query(target_list, String::new())
+    }
+
+    /// Creates a session that only processes patches of `target_list`
+    /// matching `search_query`, in the Lore search syntax.
+    pub fn with_search_query(target_list: String, search_query: String) -> LoreSession {
         LoreSession {
             target_list,
+            search_query,
             representative_patches_ids: Vec::new(),
             processed_patches_map: HashMap::new(),
             patch_regex: PatchRegex::new(),
@@ -63,8 +80,11 @@ impl LoreSession {
         n: usize,
     ) -> Result<(), LoreSessionError> {
         while self.representative_patches_ids.len() < n {
-            let feed_response_body =
-                lore_api_client.request_patch_feed(&self.target_list, self.min_index)?;
+            let feed_response_body = lore_api_client.request_patch_feed(
+                &self.target_list,
+                &self.search_query,
+                self.min_index,
+            )?;
 
             let patch_feed = from_str(&feed_response_body).unwrap();
 
@@ -129,7 +149,8 @@ impl LoreSession {
 
     pub fn get_patch_feed_page(&self, page_size: usize, page_number: usize) -> Option<Vec<&Patch>> {
         let mut patch_feed_page: Vec<&Patch> = Vec::new();
-        let representative_patches_ids_max_index: usize = self.representative_patches_ids.len() - 1;
+        let representative_patches_ids_max_index: usize =
+            self.representative_patches_ids.len().checked_sub(1)?;
         let lower_end: usize = page_size * (page_number - 1);
         let mut upper_end: usize = page_size * page_number;
 
@@ -162,8 +183,7 @@ pub fn download_patchset(output_dir: &str, patch: &Patch) -> io::Result<String>
 
     let filepath: String = format!("{output_dir}/{mbox_name}");
     if !Path::new(&filepath).exists() {
-        Command::new("b4")
-            .arg("--quiet")
+        let b4_am_output = Command::new("b4")
             .arg("am")
             .arg("--use-version")
             .arg(format!("{}", patch.version()))
@@ -173,13 +193,84 @@ pub fn download_patchset(output_dir: &str, patch: &Patch) -> io::Result<String>
             .arg("--mbox-name")
             .arg(&mbox_name)
             .stdout(Stdio::null())
+            .output()?;
+
+        // `b4 am` reports the attestation (DKIM and patatt signatures) of each
+        // message through stderr, so we keep it alongside the patchset
+        fs::write(attestation_report_path(&filepath), b4_am_output.stderr)?;
+    }
+
+    Ok(filepath)
+}
+
+/// Downloads the whole thread of the patchset represented by `patch` (i.e.,
+/// the patches and every reply to them) as an mbox and returns its path.
+///
+/// A previously downloaded thread is reused unless it is older than
+/// [THREAD_CACHE_TTL], in which case it is downloaded again. The old thread is
+/// kept if downloading it again fails.
+pub fn download_patchset_thread(output_dir: &str, patch: &Patch) -> io::Result<String> {
+    let message_id: &str = &patch.message_id().href;
+    let mbox_name: String =
+        extract_mbox_name_from_message_id(message_id).replace(".mbx", ".thread");
+
+    if !Path::new(output_dir).exists() {
+        fs::create_dir_all(output_dir)?;
+    }
+
+    let filepath: String = format!("{output_dir}/{mbox_name}");
+    if is_cache_stale(&filepath, THREAD_CACHE_TTL) {
+        let partial_mbox_name = format!("{mbox_name}.part");
+        let partial_filepath = format!("{output_dir}/{partial_mbox_name}");
+        Command::new("b4")
+            .arg("--quiet")
+            .arg("mbox")
+            .arg(message_id)
+            .arg("--outdir")
+            .arg(output_dir)
+            .arg("--mbox-name")
+            .arg(&partial_mbox_name)
+            .stdout(Stdio::null())
             .stderr(Stdio::null())
             .status()?;
+        if Path::new(&partial_filepath).is_file() {
+            fs::rename(&partial_filepath, &filepath)?;
+        }
+    }
+
+    if !Path::new(&filepath).is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("failed to download thread of {message_id}"),
+        ));
     }
 
     Ok(filepath)
 }
 
+/// Returns whether the file at `path` doesn't exist or was last modified more
+/// than `ttl` ago.
+///
+/// # Tests
+///
+/// [tests::should_consider_old_or_missing_cache_stale]
+fn is_cache_stale(path: &str, ttl: Duration) -> bool {
+    match fs::metadata(path).and_then(|metadata| metadata.modified()) {
+        Ok(modified) => modified.elapsed().is_ok_and(|elapsed| elapsed > ttl),
+        Err(_) => true,
+    }
+}
+
+/// Loads the attestation of the patchset stored in `patchset_path` from the
+/// report saved by [download_patchset]. Patchsets without a report are
+/// considered unverified.
+pub fn load_patchset_attestation(patchset_path: &str) -> SeriesAttestation {
+    match fs::read_to_string(attestation_report_path(patchset_path)) {
+        Ok(report) => parse_b4_attestation_report(&report),
+        Err(_) => SeriesAttestation::default(),
+    }
+}
+
 fn extract_mbox_name_from_message_id(message_id: &str) -> String {
     let mut mbox_name: String = message_id
         .replace(r#"http://lore.kernel.org/"#, "")
@@ -273,10 +364,14 @@ fn extract_patches(mbox_path: &Path, patches: &mut Vec<String>) {
     }
 }
 
-pub fn save_bookmarked_patchsets(
-    bookmarked_patchsets: &Vec<Patch>,
-    filepath: &str,
-) -> io::Result<()> {
+/// Saves `value` as JSON in the file in `filepath`, creating its parent
+/// directories. The file is written to a temporary file first and then renamed,
+/// so it is never left half-written.
+///
+/// # Tests
+///
+/// [tests::should_save_and_load_json]
+pub fn save_json<T: Serialize + ?Sized>(value: &T, filepath: &str) -> io::Result<()> {
     if let Some(parent) = Path::new(filepath).parent() {
         fs::create_dir_all(parent)?;
     }
@@ -284,16 +379,32 @@ pub fn save_bookmarked_patchsets(
     let tmp_filename = format!("{}.tmp", filepath);
     {
         let tmp_file = File::create(&tmp_filename)?;
-        serde_json::to_writer(tmp_file, &bookmarked_patchsets)?;
+        serde_json::to_writer(tmp_file, value)?;
     }
     fs::rename(tmp_filename, filepath)?;
     Ok(())
 }
 
+/// Loads the value saved as JSON in the file in `filepath`.
+///
+/// # Tests
+///
+/// [tests::should_save_and_load_json]
+pub fn load_json<T: DeserializeOwned>(filepath: &str) -> io::Result<T> {
+    let file = File::open(filepath)?;
+    let value = serde_json::from_reader(file)?;
+    Ok(value)
+}
+
+pub fn save_bookmarked_patchsets(
+    bookmarked_patchsets: &Vec<Patch>,
+    filepath: &str,
+) -> io::Result<()> {
+    save_json(bookmarked_patchsets, filepath)
+}
+
 pub fn load_bookmarked_patchsets(filepath: &str) -> io::Result<Vec<Patch>> {
-    let bookmarked_patchsets_file = File::open(filepath)?;
-    let bookmarked_patchesets = serde_json::from_reader(bookmarked_patchsets_file)?;
-    Ok(bookmarked_patchesets)
+    load_json(filepath)
 }
 
 pub fn fetch_available_lists<T>(lore_api_client: &T) -> Result<Vec<MailingList>, LoreSessionError>
@@ -362,31 +473,23 @@ fn process_available_lists(available_lists_str: String) -> Vec<MailingList> {
 }
 
 pub fn save_available_lists(available_lists: &Vec<MailingList>, filepath: &str) -> io::Result<()> {
-    if let Some(parent) = Path::new(filepath).parent() {
-        fs::create_dir_all(parent)?;
-    }
-
-    let tmp_filename = format!("{}.tmp", filepath);
-    {
-        let tmp_file = File::create(&tmp_filename)?;
-        serde_json::to_writer(tmp_file, &available_lists)?;
-    }
-    fs::rename(tmp_filename, filepath)?;
-    Ok(())
+    save_json(available_lists, filepath)
 }
 
 pub fn load_available_lists(filepath: &str) -> io::Result<Vec<MailingList>> {
-    let available_lists_file = File::open(filepath)?;
-    let available_lists = serde_json::from_reader(available_lists_file)?;
-    Ok(available_lists)
+    load_json(filepath)
 }
 
-pub fn prepare_reply_patchset_with_reviewed_by<T>(
+/// Prepares the `git send-email` commands to reply each patch of `patches`
+/// marked in `patches_to_reply` with the trailer `trailer` (like
+/// `Reviewed-by`) signed with `git_signature`.
+pub fn prepare_reply_patchset_with_trailer<T>(
     lore_api_client: &T,
     tmp_dir: &Path,
     target_list: &str,
     patches: &[String],
     patches_to_reply: &[bool],
+    trailer: &str,
     git_signature: &str,
     git_send_email_options: &str,
 ) -> Result<Vec<Command>, LoreSessionError>
@@ -412,7 +515,7 @@ where
 
         let reply_path = tmp_dir.join(format!("{message_id}-reply.mbx"));
         let mut reply = generate_patch_reply_template(patch);
-        reply.push_str(&format!("\nReviewed-by: {git_signature}\n"));
+        reply.push_str(&format!("\n{trailer}: {git_signature}\n"));
         fs::write(&reply_path, &reply).unwrap();
 
         let patch_body = lore_api_client.request_patch_html(target_list, message_id)?;
@@ -521,21 +624,20 @@ pub fn save_reviewed_patchsets(
     reviewed_patchsets: &HashMap<String, HashSet<usize>>,
     filepath: &str,
 ) -> io::Result<()> {
-    if let Some(parent) = Path::new(filepath).parent() {
-        fs::create_dir_all(parent)?;
-    }
-
-    let tmp_filename = format!("{}.tmp", filepath);
-    {
-        let tmp_file = File::create(&tmp_filename)?;
-        serde_json::to_writer(tmp_file, &reviewed_patchsets)?;
-    }
-    fs::rename(tmp_filename, filepath)?;
-    Ok(())
+    save_json(reviewed_patchsets, filepath)
 }
 
 pub fn load_reviewed_patchsets(filepath: &str) -> io::Result<HashMap<String, HashSet<usize>>> {
-    let reviewed_patchsets_file = File::open(filepath)?;
-    let reviewed_patchsets = serde_json::from_reader(reviewed_patchsets_file)?;
-    Ok(reviewed_patchsets)
+    load_json(filepath)
+}
+
+pub fn save_attestation_statuses(
+    attestation_statuses: &HashMap<String, AttestationStatus>,
+    filepath: &str,
+) -> io::Result<()> {
+    save_json(attestation_statuses, filepath)
+}
+
+pub fn load_attestation_statuses(filepath: &str) -> io::Result<HashMap<String, AttestationStatus>> {
+    load_json(filepath)
 }
@@ -12,6 +12,7 @@ mock! {
         fn request_patch_feed(
                     &self,
                     target_list: &str,
+                    search_query: &str,
                     min_index: usize,
                 ) -> Result<String, ClientError>;
     }
@@ -49,11 +50,11 @@ fn should_process_one_representative_patch() {
 
     lore_api_client
         .expect_request_patch_feed()
-        .withf(move |target_list_arg, min_index_arg| {
-            target_list_arg == target_list && *min_index_arg == 0
+        .withf(move |target_list_arg, search_query_arg, min_index_arg| {
+            target_list_arg == target_list && search_query_arg.is_empty() && *min_index_arg == 0
         })
         .times(1)
-        .returning(move |_, _| Ok(fs::read_to_string(src_path).unwrap()));
+        .returning(move |_, _, _| Ok(fs::read_to_string(src_path).unwrap()));
 
     let mut lore_session: LoreSession = LoreSession::new(target_list.to_string());
 
@@ -113,11 +114,11 @@ fn should_process_multiple_representative_patches() {
 
     lore_api_client
         .expect_request_patch_feed()
-        .withf(move |target_list_arg, min_index_arg| {
-            target_list_arg == target_list && *min_index_arg == 0
+        .withf(move |target_list_arg, search_query_arg, min_index_arg| {
+            target_list_arg == target_list && search_query_arg.is_empty() && *min_index_arg == 0
         })
         .times(1)
-        .returning(move |_, _| Ok(fs::read_to_string(src_path).unwrap()));
+        .returning(move |_, _, _| Ok(fs::read_to_string(src_path).unwrap()));
 
     let mut lore_session: LoreSession = LoreSession::new(target_list.to_string());
 
@@ -504,7 +505,7 @@ fn should_prepare_reply_patchset_with_reviewed_by() {
             tmp_dir.display()
         ));
 
-    let expected_git_reply_commands = vec![
+    let expected_git_reply_commands = [
         expected_git_reply_command_0,
         expected_git_reply_command_1,
         expected_git_reply_command_2,
@@ -538,12 +539,13 @@ fn should_prepare_reply_patchset_with_reviewed_by() {
 
     let patches_to_reply = vec![true; patches.len()];
 
-    let git_reply_commands = prepare_reply_patchset_with_reviewed_by(
+    let git_reply_commands = prepare_reply_patchset_with_trailer(
         &lore_api_client,
         tmp_dir,
         target_list,
         &patches,
         &patches_to_reply,
+        "Reviewed-by",
         "Bar Foo <bar@foo.bar.foo>",
         "--dry-run --suppress-cc=all",
     )
@@ -632,3 +634,62 @@ fn should_get_local_git_signature() {
 
     fs::remove_dir_all(mocked_git_repo).unwrap();
 }
+
+#[test]
+fn should_request_patch_feed_with_search_query() {
+    let src_path = "test_samples/lore_session/process_representative_patch/patch_feed_sample_1.xml";
+
+    let mut lore_api_client = MockBlockingLoreAPIClient::new();
+
+    lore_api_client
+        .expect_request_patch_feed()
+        .withf(|target_list_arg, search_query_arg, min_index_arg| {
+            target_list_arg == "all" && search_query_arg == "f:foo" && *min_index_arg == 0
+        })
+        .times(1)
+        .returning(move |_, _, _| Ok(fs::read_to_string(src_path).unwrap()));
+
+    let mut lore_session = LoreSession::with_search_query("all".to_string(), "f:foo".to_string());
+    lore_session
+        .process_n_representative_patches(&lore_api_client, 1)
+        .unwrap();
+
+    assert_eq!("f:foo", lore_session.search_query());
+    assert_eq!(1, lore_session.representative_patches_ids().len());
+}
+
+#[test]
+fn should_save_and_load_json() {
+    let dir = std::env::temp_dir().join(format!("patch-hub-save-json-{}", std::process::id()));
+    let path = dir.join("nested").join("value.json");
+    let path = path.to_str().unwrap();
+    let value: HashMap<String, Vec<usize>> = HashMap::from([("foo".to_string(), vec![1, 2])]);
+
+    save_json(&value, path).unwrap();
+    assert_eq!(
+        value,
+        load_json::<HashMap<String, Vec<usize>>>(path).unwrap()
+    );
+    assert!(
+        !Path::new(&format!("{path}.tmp")).exists(),
+        "The temporary file should be renamed"
+    );
+    assert!(load_json::<Vec<String>>(path).is_err());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn should_consider_old_or_missing_cache_stale() {
+    let path = std::env::temp_dir().join(format!("patch-hub-cache-{}", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    assert!(is_cache_stale(path, Duration::from_secs(60)));
+
+    fs::write(path, "").unwrap();
+    assert!(!is_cache_stale(path, Duration::from_secs(60)));
+    std::thread::sleep(Duration::from_millis(10));
+    assert!(is_cache_stale(path, Duration::ZERO));
+
+    fs::remove_file(path).unwrap();
+}
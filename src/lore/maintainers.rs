@@ -0,0 +1,301 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use derive_getters::Getters;
+use regex::Regex;
+
+#[cfg(test)]
+mod tests;
+
+/// Path of the `MAINTAINERS` file relative to the root of a kernel tree
+pub const MAINTAINERS_PATH: &str = "MAINTAINERS";
+
+/// Role of a person in a `MAINTAINERS` section
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub enum MaintainerRole {
+    /// Listed as `M:`
+    Maintainer,
+    /// Listed as `R:`
+    Reviewer,
+}
+
+/// A section of the `MAINTAINERS` file, like
+///
+/// ```text
+/// PATCH-HUB DRIVER
+/// M:      Foo Bar <foo@bar.foo>
+/// R:      Lima Luma <lima@luma.rs>
+/// L:      foo@vger.kernel.org
+/// T:      git git://git.kernel.org/pub/scm/linux/kernel/git/foo/foo.git
+/// F:      drivers/foo/
+/// ```
+#[derive(Getters, Debug, Clone, Default, Eq, PartialEq)]
+pub struct MaintainersSection {
+    name: String,
+    /// Entries of `M:`, like `Foo Bar <foo@bar.foo>`
+    maintainers: Vec<String>,
+    /// Entries of `R:`
+    reviewers: Vec<String>,
+    /// Entries of `L:`, like `foo@vger.kernel.org`
+    lists: Vec<String>,
+    /// Entries of `T:`, like `git git://git.kernel.org/...`
+    trees: Vec<String>,
+    /// Patterns of `F:`, like `drivers/foo/` or `include/linux/foo*.h`
+    files: Vec<FilePattern>,
+    /// Patterns of `X:`, which exclude files matched by `F:`
+    excluded_files: Vec<FilePattern>,
+    /// Regexes of `N:`, which match files by name
+    file_regexes: Vec<FilePattern>,
+}
+
+/// A pattern of a `F:`, `X:` or `N:` entry, compiled to a regex once when
+/// `MAINTAINERS` is parsed. Patterns are equal if their text is.
+#[derive(Debug, Clone)]
+pub struct FilePattern {
+    pattern: String,
+    /// `None` if the pattern is invalid, so it matches no file
+    regex: Option<Regex>,
+}
+
+impl FilePattern {
+    /// Compiles the `F:`/`X:` `pattern`, following the semantics described in
+    /// the header of `MAINTAINERS`: a trailing slash matches all files in and
+    /// below the directory, `*` and `?` don't match slashes, and a pattern
+    /// without wildcards also matches all files below it if it names a
+    /// directory.
+    pub fn glob(pattern: &str) -> FilePattern {
+        let (glob, suffix) = match pattern.strip_suffix('/') {
+            Some(directory) => (directory, "/.*"),
+            None if !pattern.contains(['*', '?']) => (pattern, "(/.*)?"),
+            None => (pattern, ""),
+        };
+
+        let mut regex = String::from("^");
+        for c in glob.chars() {
+            match c {
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        regex.push_str(suffix);
+        regex.push('$');
+
+        FilePattern {
+            pattern: pattern.to_string(),
+            regex: Regex::new(&regex).ok(),
+        }
+    }
+
+    /// Compiles the `N:` `regex`, which matches anywhere in the path
+    pub fn regex(regex: &str) -> FilePattern {
+        FilePattern {
+            pattern: regex.to_string(),
+            regex: Regex::new(regex).ok(),
+        }
+    }
+
+    /// Returns the pattern as written in `MAINTAINERS`
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Returns if the file in `path` (relative to the root of the kernel
+    /// tree) is matched by the pattern
+    pub fn matches(&self, path: &str) -> bool {
+        self.regex
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(path))
+    }
+}
+
+impl PartialEq for FilePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for FilePattern {}
+
+impl MaintainersSection {
+    /// Returns if the file in `path` (relative to the root of the kernel tree)
+    /// belongs to the section.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_match_files_of_section]
+    pub fn matches(&self, path: &str) -> bool {
+        let matches_pattern = |pattern: &FilePattern| pattern.matches(path);
+
+        if self.excluded_files.iter().any(matches_pattern) {
+            return false;
+        }
+
+        self.files.iter().any(matches_pattern) || self.file_regexes.iter().any(matches_pattern)
+    }
+
+    /// Returns the role of the person with `email` in the section, if any. The
+    /// address of each `M:`/`R:` entry (between `<` and `>`, if any) must be
+    /// `email`, ignoring case. If the person is both a maintainer and a
+    /// reviewer, they are considered a maintainer.
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_find_role_by_exact_address]
+    pub fn role_of(&self, email: &str) -> Option<MaintainerRole> {
+        let email = email.trim();
+        if email.is_empty() {
+            return None;
+        }
+        let is_listed = |people: &Vec<String>| {
+            people
+                .iter()
+                .any(|person| entry_address(person).eq_ignore_ascii_case(email))
+        };
+
+        if is_listed(&self.maintainers) {
+            Some(MaintainerRole::Maintainer)
+        } else if is_listed(&self.reviewers) {
+            Some(MaintainerRole::Reviewer)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the address of a `M:`/`R:` entry, like `foo@bar.foo` in
+/// `Foo Bar <foo@bar.foo>`. Entries without `<...>` are taken as the address.
+fn entry_address(entry: &str) -> &str {
+    entry
+        .rsplit_once('<')
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map_or(entry, |(address, _)| address)
+        .trim()
+}
+
+/// A `MAINTAINERS` section touched by a patchset and the role of the user in
+/// it
+#[derive(Getters, Debug, Clone, Eq, PartialEq)]
+pub struct TouchedSection {
+    name: String,
+    role: Option<MaintainerRole>,
+    /// Entries of `T:` of the section
+    trees: Vec<String>,
+}
+
+/// Parses the contents of a `MAINTAINERS` file.
+///
+/// Sections are paragraphs whose first line is the name of the section and
+/// the other ones are entries like `F: drivers/foo/`. Paragraphs without
+/// entries (like the introduction of the file) are ignored.
+///
+/// # Tests
+///
+/// [tests::should_parse_maintainers]
+pub fn parse_maintainers(contents: &str) -> Vec<MaintainersSection> {
+    static RE_ENTRY: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^([A-Z]):\s*(.*?)\s*$").unwrap());
+
+    let mut sections = Vec::new();
+    let mut current_section: Option<MaintainersSection> = None;
+    let mut has_entries = false;
+
+    for line in contents.lines().chain(std::iter::once("")) {
+        if line.trim().is_empty() {
+            if let Some(section) = current_section.take() {
+                if has_entries {
+                    sections.push(section);
+                }
+            }
+            has_entries = false;
+            continue;
+        }
+
+        let Some(section) = current_section.as_mut() else {
+            current_section = Some(MaintainersSection {
+                name: line.trim().to_string(),
+                ..Default::default()
+            });
+            continue;
+        };
+
+        let Some(capture) = RE_ENTRY.captures(line) else {
+            continue;
+        };
+        has_entries = true;
+
+        let value = capture[2].to_string();
+        match &capture[1] {
+            "M" => section.maintainers.push(value),
+            "R" => section.reviewers.push(value),
+            "L" => section.lists.push(value),
+            "T" => section.trees.push(value),
+            "F" => section.files.push(FilePattern::glob(&value)),
+            "X" => section.excluded_files.push(FilePattern::glob(&value)),
+            "N" => section.file_regexes.push(FilePattern::regex(&value)),
+            _ => {}
+        }
+    }
+
+    sections
+}
+
+/// Loads and parses the `MAINTAINERS` file of the kernel tree in
+/// `kernel_tree_path`.
+pub fn load_maintainers(kernel_tree_path: &str) -> io::Result<Vec<MaintainersSection>> {
+    let contents = fs::read_to_string(Path::new(kernel_tree_path).join(MAINTAINERS_PATH))?;
+    Ok(parse_maintainers(&contents))
+}
+
+/// Returns the files (relative to the root of the kernel tree) changed by
+/// `raw_patch`. Both the old and new paths of renamed files are considered.
+///
+/// # Tests
+///
+/// [tests::should_extract_touched_files]
+pub fn touched_files(raw_patch: &str) -> Vec<String> {
+    static RE_DIFF_HEADER: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(?m)^diff --git a/(\S+) b/(\S+)$").unwrap());
+
+    let mut files: Vec<String> = Vec::new();
+    for capture in RE_DIFF_HEADER.captures_iter(raw_patch) {
+        for path in [&capture[1], &capture[2]] {
+            if !files.iter().any(|file| file == path) {
+                files.push(path.to_string());
+            }
+        }
+    }
+
+    files
+}
+
+/// Returns the sections in `sections` touched by any of `raw_patches`, in the
+/// order they appear in `MAINTAINERS`, along with the role in each of them of
+/// the user with `user_email`.
+///
+/// # Tests
+///
+/// [tests::should_find_touched_sections]
+pub fn touched_sections(
+    sections: &[MaintainersSection],
+    raw_patches: &[String],
+    user_email: &str,
+) -> Vec<TouchedSection> {
+    let files: HashSet<String> = raw_patches
+        .iter()
+        .flat_map(|raw_patch| touched_files(raw_patch))
+        .collect();
+
+    sections
+        .iter()
+        .filter(|section| files.iter().any(|file| section.matches(file)))
+        .map(|section| TouchedSection {
+            name: section.name.clone(),
+            role: section.role_of(user_email),
+            trees: section.trees.clone(),
+        })
+        .collect()
+}
@@ -0,0 +1,142 @@
+use super::*;
+
+use std::fs;
+
+const SAMPLES_DIR: &str = "test_samples/maintainers";
+
+fn load_sample_maintainers() -> Vec<MaintainersSection> {
+    parse_maintainers(&fs::read_to_string(format!("{SAMPLES_DIR}/MAINTAINERS")).unwrap())
+}
+
+#[test]
+fn should_parse_maintainers() {
+    let sections = load_sample_maintainers();
+
+    assert_eq!(
+        vec!["BAR LIBRARY", "FOO DRIVER", "THE REST"],
+        sections
+            .iter()
+            .map(|s| s.name().as_str())
+            .collect::<Vec<_>>(),
+        "Introduction paragraphs shouldn't be parsed as sections"
+    );
+    assert_eq!(
+        MaintainersSection {
+            name: "FOO DRIVER".to_string(),
+            maintainers: vec!["Foo Bar <foo@bar.foo.bar>".to_string()],
+            reviewers: vec!["Roberto Silva <roberto@silva.br>".to_string()],
+            lists: vec!["foo@vger.kernel.org".to_string()],
+            trees: vec![
+                "git git://git.kernel.org/pub/scm/linux/kernel/git/foo/foo.git".to_string()
+            ],
+            files: vec![
+                FilePattern::glob("drivers/foo/"),
+                FilePattern::glob("Documentation/foo")
+            ],
+            excluded_files: vec![FilePattern::glob("drivers/foo/legacy/")],
+            file_regexes: vec![FilePattern::regex("foo_")],
+        },
+        sections[1]
+    );
+}
+
+#[test]
+fn should_match_files_of_section() {
+    let sections = load_sample_maintainers();
+    let (bar, foo, rest) = (&sections[0], &sections[1], &sections[2]);
+
+    assert!(foo.matches("drivers/foo/foo.c"));
+    assert!(foo.matches("drivers/foo/core/core.c"));
+    assert!(!foo.matches("drivers/foo/legacy/old.c"), "Excluded file");
+    assert!(foo.matches("Documentation/foo/index.rst"));
+    assert!(foo.matches("include/linux/foo_bar.h"), "Matched by N:");
+    assert!(!foo.matches("drivers/foobar/foobar.c"));
+
+    assert!(bar.matches("lib/bar_baz.c"));
+    assert!(
+        !bar.matches("lib/sub/bar.c"),
+        "Wildcards don't match slashes"
+    );
+    assert!(bar.matches("include/linux/bar.h"));
+    assert!(!bar.matches("include/linux/bar.c"));
+
+    assert!(rest.matches("Makefile"));
+    assert!(rest.matches("drivers/foo/foo.c"));
+}
+
+#[test]
+fn should_find_role_by_exact_address() {
+    let section = MaintainersSection {
+        maintainers: vec![
+            "Foo Bar <foo@bar.foo>".to_string(),
+            "baz@bar.foo".to_string(),
+        ],
+        reviewers: vec!["Lima Luma <lima@luma.rs>".to_string()],
+        ..Default::default()
+    };
+
+    assert_eq!(
+        Some(MaintainerRole::Maintainer),
+        section.role_of(" Foo@Bar.foo ")
+    );
+    assert_eq!(
+        Some(MaintainerRole::Maintainer),
+        section.role_of("baz@bar.foo")
+    );
+    assert_eq!(
+        Some(MaintainerRole::Reviewer),
+        section.role_of("lima@luma.rs")
+    );
+    assert_eq!(
+        None,
+        section.role_of("o@bar.foo"),
+        "Addresses containing the email shouldn't match"
+    );
+    assert_eq!(None, section.role_of("Foo Bar"));
+    assert_eq!(None, section.role_of(""));
+}
+
+#[test]
+fn should_extract_touched_files() {
+    let raw_patch = fs::read_to_string(format!("{SAMPLES_DIR}/patch.mbx")).unwrap();
+
+    assert_eq!(
+        vec!["drivers/foo/foo.c", "lib/baz.c", "lib/bar_baz.c"],
+        touched_files(&raw_patch)
+    );
+}
+
+#[test]
+fn should_find_touched_sections() {
+    let sections = load_sample_maintainers();
+    let raw_patches = vec![fs::read_to_string(format!("{SAMPLES_DIR}/patch.mbx")).unwrap()];
+
+    assert_eq!(
+        vec![
+            TouchedSection {
+                name: "BAR LIBRARY".to_string(),
+                role: None,
+                trees: vec![],
+            },
+            TouchedSection {
+                name: "FOO DRIVER".to_string(),
+                role: Some(MaintainerRole::Reviewer),
+                trees: vec![
+                    "git git://git.kernel.org/pub/scm/linux/kernel/git/foo/foo.git".to_string()
+                ],
+            },
+            TouchedSection {
+                name: "THE REST".to_string(),
+                role: None,
+                trees: vec![],
+            },
+        ],
+        touched_sections(&sections, &raw_patches, "Roberto@Silva.br")
+    );
+    assert_eq!(
+        Some(MaintainerRole::Maintainer),
+        touched_sections(&sections, &raw_patches, "lima@luma.rs")[0]
+            .role()
+            .to_owned()
+    );
+}
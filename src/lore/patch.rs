@@ -25,6 +25,9 @@ pub struct Patch {
     #[serde(default = "default_total_in_series")]
     #[getter(skip)]
     total_in_series: usize,
+    /// If the patch tag marks the patch as an RFC, like `[RFC PATCH]`
+    #[serde(default)]
+    is_rfc: bool,
     author: Author,
     #[serde(rename = "link")]
     message_id: MessageID,
@@ -75,6 +78,7 @@ impl Patch {
             version: 1,
             number_in_series: 1,
             total_in_series: 1,
+            is_rfc: false,
             message_id,
             in_reply_to,
             updated,
@@ -100,6 +104,7 @@ impl Patch {
         };
 
         self.remove_patch_tag_from_title(&patch_tag);
+        self.is_rfc = patch_tag.to_ascii_uppercase().contains("RFC");
         self.set_version(&patch_tag, &patch_regex.re_patch_version);
         self.set_number_in_series(&patch_tag, &patch_regex.re_patch_series);
         self.set_total_in_series(&patch_tag, &patch_regex.re_patch_series);
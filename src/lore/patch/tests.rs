@@ -108,4 +108,23 @@ fn test_update_patch_metadata() {
     assert_eq!(7, patch.version(), "Wrong version!");
     assert_eq!(3, patch.number_in_series(), "Wrong number in series!");
     assert_eq!(42, patch.total_in_series(), "Wrong total in series!");
+    assert!(!patch.is_rfc(), "Patch shouldn't be an RFC!");
+
+    let mut patch: Patch = Patch::new(
+        "[rfc PATCH net-next] net: foo".to_string(),
+        Author {
+            name: "Foo Bar".to_string(),
+            email: "foo@bar.foo.bar".to_string(),
+        },
+        MessageID {
+            href: "http://lore.kernel.org/some-list/5678-1-foo@bar.foo.bar".to_string(),
+        },
+        None,
+        "2024-07-06T19:16:53Z".to_string(),
+    );
+
+    patch.update_patch_metadata(&patch_regex);
+
+    assert_eq!("net: foo", patch.title());
+    assert!(patch.is_rfc(), "Patch should be an RFC!");
 }
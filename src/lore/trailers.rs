@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::lore::lore_session::split_cover;
+use crate::lore::patch::Author;
+
+#[cfg(test)]
+mod tests;
+
+/// Code-review trailers, whose counts are always relevant (even if zero)
+pub const CODE_REVIEW_TRAILERS: [&str; 3] = ["Reviewed-by", "Tested-by", "Acked-by"];
+
+/// Name of the pseudo-trailer that represents `Cc:` trailers to the stable
+/// mailing list, which flags patches that should be backported.
+pub const STABLE_TRAILER: &str = "Cc: stable";
+
+/// Trailers collected by default
+pub const DEFAULT_TRAILER_NAMES: [&str; 10] = [
+    "Reviewed-by",
+    "Tested-by",
+    "Acked-by",
+    "Reported-by",
+    "Suggested-by",
+    "Co-developed-by",
+    "Fixes",
+    "Closes",
+    "Link",
+    STABLE_TRAILER,
+];
+
+/// A trailer given to a patch and the message it was given in
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct Trailer {
+    /// Value of the trailer, like `Foo Bar <foo@bar.foo>` for `Reviewed-by`
+    /// or `https://...` for `Link`
+    pub value: String,
+    /// Message-ID of the message that contains the trailer, which is empty if
+    /// the message has none
+    pub source: String,
+}
+
+impl Trailer {
+    /// Returns the author of the trailer if its value is in the
+    /// `Name <email>` format.
+    pub fn author(&self) -> Option<Author> {
+        let (name, email) = self.value.split_once('<')?;
+        let email = email.strip_suffix('>')?;
+
+        Some(Author {
+            name: name.trim().to_string(),
+            email: email.trim().to_string(),
+        })
+    }
+
+    /// Trailers with the same key are considered duplicates. The key is the
+    /// author email for trailers with an author and the value otherwise.
+    fn key(&self) -> String {
+        match self.author() {
+            Some(author) => author.email.to_ascii_lowercase(),
+            None => self.value.to_ascii_lowercase(),
+        }
+    }
+}
+
+impl Display for Trailer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)?;
+        if !self.source.is_empty() {
+            write!(f, " (from: <{}>)", self.source)?;
+        }
+        Ok(())
+    }
+}
+
+/// Trailers of a single patch mapped by trailer name (e.g. `Reviewed-by`).
+///
+/// Names are kept in the order they were registered and there are no
+/// duplicated trailers (see [Trailer::key]) for a given name.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Trailers {
+    trailers: Vec<(String, Vec<Trailer>)>,
+}
+
+impl Trailers {
+    /// Creates an empty `Trailers` that accepts only trailers named `names`.
+    pub fn new<S: AsRef<str>>(names: &[S]) -> Trailers {
+        let mut trailers: Vec<(String, Vec<Trailer>)> = Vec::new();
+
+        for name in names {
+            let name = name.as_ref().trim();
+            if !name.is_empty() && !trailers.iter().any(|(n, _)| n.eq_ignore_ascii_case(name)) {
+                trailers.push((name.to_string(), Vec::new()));
+            }
+        }
+
+        Trailers { trailers }
+    }
+
+    /// Returns the trailers named `name`, which is case-insensitive.
+    pub fn get(&self, name: &str) -> &[Trailer] {
+        self.trailers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map_or(&[], |(_, trailers)| trailers.as_slice())
+    }
+
+    /// Iterates over the names with at least one trailer, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[Trailer])> {
+        self.trailers
+            .iter()
+            .filter(|(_, trailers)| !trailers.is_empty())
+            .map(|(name, trailers)| (name.as_str(), trailers.as_slice()))
+    }
+
+    /// Returns whether trailers named `name` are accepted, which includes `Cc`
+    /// if [STABLE_TRAILER] is accepted.
+    fn accepts(&self, name: &str) -> bool {
+        let accepts_name = |accepted: &str| {
+            self.trailers
+                .iter()
+                .any(|(n, _)| n.eq_ignore_ascii_case(accepted))
+        };
+
+        accepts_name(name) || (name.eq_ignore_ascii_case("Cc") && accepts_name(STABLE_TRAILER))
+    }
+
+    /// Adds `trailer` as a `@name` trailer unless `@name` isn't accepted or
+    /// it is a duplicate.
+    fn insert(&mut self, name: &str, trailer: Trailer) {
+        let Some((_, trailers)) = self
+            .trailers
+            .iter_mut()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        else {
+            return;
+        };
+
+        let key = trailer.key();
+        if !trailers.iter().any(|t| t.key() == key) {
+            trailers.push(trailer);
+        }
+    }
+
+    /// Adds all trailers found in `text` given in the message whose Message-ID
+    /// is `source`.
+    fn insert_from_text(&mut self, text: &str, source: &str) {
+        let trailers = extract_trailers(text, |name| self.accepts(name));
+        self.insert_all(trailers, source);
+    }
+
+    /// Adds all trailers found in the unquoted lines of a reply in `text`
+    /// given in the message whose Message-ID is `source`.
+    fn insert_from_reply(&mut self, text: &str, source: &str) {
+        let trailers = extract_reply_trailers(text, |name| self.accepts(name));
+        self.insert_all(trailers, source);
+    }
+
+    /// Adds `trailers`, as pairs of trailer name and value, given in the
+    /// message whose Message-ID is `source`.
+    fn insert_all(&mut self, trailers: Vec<(String, String)>, source: &str) {
+        for (name, value) in trailers {
+            // `Cc:` trailers to stable are also registered under their own name
+            if name.eq_ignore_ascii_case("Cc") && value.contains("stable@") {
+                self.insert(
+                    STABLE_TRAILER,
+                    Trailer {
+                        value: value.clone(),
+                        source: source.to_string(),
+                    },
+                );
+            }
+            self.insert(
+                &name,
+                Trailer {
+                    value,
+                    source: source.to_string(),
+                },
+            );
+        }
+    }
+}
+
+/// A message of a thread with only the information needed to collect trailers
+struct ThreadMessage<'a> {
+    message_id: Option<String>,
+    /// Message-IDs of the ancestors of the message, from the farthest to the
+    /// closest one
+    ancestors: Vec<String>,
+    body: &'a str,
+}
+
+/// Collects the trailers named `trailer_names` (see [DEFAULT_TRAILER_NAMES])
+/// of each patch in `raw_patches` from the whole patchset thread.
+///
+/// Besides the trailers in each patch itself, trailers in replies (and in
+/// replies to replies) to a patch are given to that patch, while trailers in
+/// replies to the cover letter are given to every patch of the series. Only
+/// the trailer block of each patch is considered (see [extract_trailers]),
+/// while every unquoted line of replies is (see [extract_reply_trailers]), as
+/// reviewers often sign off their replies after the trailers. The returned vector has one entry
+/// for each patch in `raw_patches`.
+///
+/// # Tests
+///
+/// [tests::should_collect_trailers_from_patches_only]
+/// [tests::should_collect_trailers_from_thread]
+/// [tests::should_collect_only_named_trailers]
+pub fn collect_thread_trailers<S: AsRef<str>>(
+    raw_patches: &[String],
+    has_cover_letter: bool,
+    raw_thread: &str,
+    trailer_names: &[S],
+) -> Vec<Trailers> {
+    let mut patches_trailers = vec![Trailers::new(trailer_names); raw_patches.len()];
+    let mut patches_indexes: HashMap<String, usize> = HashMap::new();
+
+    for (i, raw_patch) in raw_patches.iter().enumerate() {
+        let (raw_cover, _) = split_cover(raw_patch);
+        let patch = parse_message(raw_cover);
+        let source = patch.message_id.unwrap_or_default();
+
+        patches_trailers[i].insert_from_text(patch.body, &source);
+        if !source.is_empty() {
+            patches_indexes.insert(source, i);
+        }
+    }
+
+    for raw_message in split_mbox(raw_thread) {
+        let message = parse_message(raw_message);
+
+        if matches!(&message.message_id, Some(id) if patches_indexes.contains_key(id)) {
+            continue;
+        }
+
+        let Some(&patch_index) = message
+            .ancestors
+            .iter()
+            .rev()
+            .find_map(|ancestor| patches_indexes.get(ancestor))
+        else {
+            continue;
+        };
+
+        let replied_patches = if has_cover_letter && patch_index == 0 {
+            0..raw_patches.len()
+        } else {
+            patch_index..patch_index + 1
+        };
+
+        let unquoted_body = message
+            .body
+            .lines()
+            .filter(|line| !line.starts_with('>'))
+            .collect::<Vec<&str>>()
+            .join("\n");
+        for i in replied_patches {
+            patches_trailers[i].insert_from_reply(
+                &unquoted_body,
+                message.message_id.as_deref().unwrap_or_default(),
+            );
+        }
+    }
+
+    patches_trailers
+}
+
+/// Matches trailer-like lines (`Name: value`)
+static RE_TRAILER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z][A-Za-z0-9-]*):[ \t]*(\S.*?)[ \t]*$").unwrap());
+
+/// Extracts the trailers of `text` whose names are `is_known` as pairs of
+/// trailer name and value.
+///
+/// Like `git interpret-trailers`, only the trailer block is considered, which
+/// is the last paragraph of `text` (ignoring a `-- ` signature) as long as it
+/// is made only of trailer-like lines (`Name: value`) or at least a quarter of
+/// its lines are trailer-like with one of them being known. Indented lines
+/// continue the value of the previous trailer.
+///
+/// # Tests
+///
+/// [tests::should_extract_only_known_trailers_of_trailer_block]
+fn extract_trailers(text: &str, is_known: impl Fn(&str) -> bool) -> Vec<(String, String)> {
+    let lines: Vec<&str> = text
+        .lines()
+        .take_while(|line| line.trim_end() != "--")
+        .collect();
+    let Some(block_end) = lines.iter().rposition(|line| !line.trim().is_empty()) else {
+        return Vec::new();
+    };
+    let block_start = lines[..block_end]
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .map_or(0, |blank_line| blank_line + 1);
+
+    let mut trailers: Vec<(String, String)> = Vec::new();
+    let mut other_lines = 0;
+    for line in &lines[block_start..=block_end] {
+        if let Some(capture) = RE_TRAILER.captures(line) {
+            trailers.push((capture[1].to_string(), capture[2].to_string()));
+        } else if line.starts_with([' ', '\t']) && !trailers.is_empty() {
+            let (_, value) = trailers.last_mut().unwrap();
+            value.push(' ');
+            value.push_str(line.trim());
+        } else {
+            other_lines += 1;
+        }
+    }
+
+    let has_known_trailer = trailers.iter().any(|(name, _)| is_known(name));
+    if other_lines > 0 && (!has_known_trailer || trailers.len() * 3 < other_lines) {
+        return Vec::new();
+    }
+
+    trailers.retain(|(name, _)| is_known(name));
+    trailers
+}
+
+/// Extracts the trailers of the reply in `text`, already without quoted lines,
+/// whose names are `is_known` as pairs of trailer name and value.
+///
+/// Unlike [extract_trailers], every line before a `-- ` signature is
+/// considered, since replies often end with a greeting after the trailers.
+/// Indented lines right after a known trailer continue its value.
+///
+/// # Tests
+///
+/// [tests::should_extract_known_trailers_of_any_reply_line]
+fn extract_reply_trailers(text: &str, is_known: impl Fn(&str) -> bool) -> Vec<(String, String)> {
+    let mut trailers: Vec<(String, String)> = Vec::new();
+    let mut continues_trailer = false;
+
+    for line in text.lines().take_while(|line| line.trim_end() != "--") {
+        if let Some(capture) = RE_TRAILER.captures(line) {
+            continues_trailer = is_known(&capture[1]);
+            if continues_trailer {
+                trailers.push((capture[1].to_string(), capture[2].to_string()));
+            }
+        } else if continues_trailer && line.starts_with([' ', '\t']) && !line.trim().is_empty() {
+            let (_, value) = trailers.last_mut().unwrap();
+            value.push(' ');
+            value.push_str(line.trim());
+        } else {
+            continues_trailer = false;
+        }
+    }
+
+    trailers
+}
+
+/// Splits an mbox into its messages.
+fn split_mbox(raw_mbox: &str) -> Vec<&str> {
+    let mut messages = Vec::new();
+    let mut message_start: Option<usize> = None;
+    let mut offset = 0;
+
+    for line in raw_mbox.split_inclusive('\n') {
+        if line.starts_with("From ") {
+            if let Some(start) = message_start {
+                messages.push(&raw_mbox[start..offset]);
+            }
+            message_start = Some(offset + line.len());
+        }
+        offset += line.len();
+    }
+
+    if let Some(start) = message_start {
+        messages.push(&raw_mbox[start..]);
+    }
+
+    messages
+}
+
+/// Parses the headers relevant to trailer collection of a raw message and
+/// separates its body.
+fn parse_message(raw_message: &str) -> ThreadMessage<'_> {
+    static RE_MESSAGE_ID: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<([^>]+)>").unwrap());
+
+    let (raw_headers, body) = match raw_message.find("\n\n") {
+        Some(headers_end) => (&raw_message[..headers_end], &raw_message[headers_end + 2..]),
+        None => (raw_message, ""),
+    };
+
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in raw_headers.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    let mut message = ThreadMessage {
+        message_id: None,
+        ancestors: Vec::new(),
+        body,
+    };
+    let mut in_reply_to = Vec::new();
+    for (name, value) in headers {
+        match name.as_str() {
+            "message-id" => {
+                message.message_id = RE_MESSAGE_ID
+                    .captures(&value)
+                    .map(|capture| capture[1].to_string());
+            }
+            "references" => {
+                message.ancestors = RE_MESSAGE_ID
+                    .captures_iter(&value)
+                    .map(|capture| capture[1].to_string())
+                    .collect();
+            }
+            "in-reply-to" => {
+                in_reply_to = RE_MESSAGE_ID
+                    .captures_iter(&value)
+                    .map(|capture| capture[1].to_string())
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+    message.ancestors.append(&mut in_reply_to);
+
+    message
+}
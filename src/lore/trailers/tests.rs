@@ -0,0 +1,212 @@
+use super::*;
+
+use std::fs;
+
+const SAMPLES_DIR: &str = "test_samples/trailers/collect_thread_trailers";
+
+fn trailer(value: &str, source: &str) -> Trailer {
+    Trailer {
+        value: value.to_string(),
+        source: source.to_string(),
+    }
+}
+
+fn read_raw_patches() -> Vec<String> {
+    ["cover_letter.cover", "patch_1.mbx", "patch_2.mbx"]
+        .iter()
+        .map(|file| fs::read_to_string(format!("{SAMPLES_DIR}/{file}")).unwrap())
+        .collect()
+}
+
+#[test]
+fn should_collect_trailers_from_patches_only() {
+    let raw_patches = read_raw_patches();
+
+    let patches_trailers = collect_thread_trailers(&raw_patches, true, "", &DEFAULT_TRAILER_NAMES);
+
+    assert_eq!(3, patches_trailers.len(), "Wrong number of patches");
+    assert_eq!(0, patches_trailers[0].iter().count());
+    assert_eq!(
+        vec![(
+            "Link",
+            &[trailer(
+                "https://lore.kernel.org/r/1234-report@johnson.com",
+                "1234.567-1-foo@bar.foo.bar"
+            )][..]
+        )],
+        patches_trailers[1].iter().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            (
+                "Reviewed-by",
+                &[trailer(
+                    "Lima Luma <lima@luma.rs>",
+                    "1234.567-2-foo@bar.foo.bar"
+                )][..]
+            ),
+            (
+                "Fixes",
+                &[trailer(
+                    "0123456789ab (\"file: Do baz\")",
+                    "1234.567-2-foo@bar.foo.bar"
+                )][..]
+            ),
+            (
+                STABLE_TRAILER,
+                &[trailer(
+                    "stable@vger.kernel.org",
+                    "1234.567-2-foo@bar.foo.bar"
+                )][..]
+            ),
+        ],
+        patches_trailers[2].iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn should_collect_trailers_from_thread() {
+    let raw_patches = read_raw_patches();
+    let raw_thread = fs::read_to_string(format!("{SAMPLES_DIR}/thread.mbox")).unwrap();
+
+    let patches_trailers =
+        collect_thread_trailers(&raw_patches, true, &raw_thread, &DEFAULT_TRAILER_NAMES);
+
+    let series_acked_by = [trailer(
+        "John Johnson <john@johnson.com>",
+        "reply-0@johnson.com",
+    )];
+
+    assert_eq!(
+        vec![("Acked-by", &series_acked_by[..])],
+        patches_trailers[0].iter().collect::<Vec<_>>(),
+        "Cover letter should only have series-wide trailers"
+    );
+
+    assert_eq!(
+        &[trailer(
+            "Roberto Silva <roberto@silva.br>",
+            "reply-1@silva.br"
+        )],
+        patches_trailers[1].get("Reviewed-by"),
+        "Duplicated and quoted trailers shouldn't be collected"
+    );
+    assert_eq!(
+        &[trailer(
+            "Roberto Silva <roberto@silva.br>",
+            "reply-2@silva.br"
+        )],
+        patches_trailers[1].get("tested-by"),
+        "Trailers given in replies to replies should be collected"
+    );
+    assert_eq!(&series_acked_by, patches_trailers[1].get("Acked-by"));
+
+    assert_eq!(
+        &[trailer(
+            "Lima Luma <lima@luma.rs>",
+            "1234.567-2-foo@bar.foo.bar"
+        )],
+        patches_trailers[2].get("Reviewed-by")
+    );
+    assert_eq!(
+        &[trailer("Lima Luma <lima@luma.rs>", "reply-3@luma.rs")],
+        patches_trailers[2].get("Tested-by")
+    );
+    assert_eq!(&series_acked_by, patches_trailers[2].get("Acked-by"));
+}
+
+#[test]
+fn should_collect_only_named_trailers() {
+    let raw_patches = read_raw_patches();
+
+    let patches_trailers =
+        collect_thread_trailers(&raw_patches, true, "", &["Signed-off-by", "Cc"]);
+
+    assert_eq!(
+        vec![(
+            "Signed-off-by",
+            &[trailer(
+                "Foo Bar <foo@bar.foo.bar>",
+                "1234.567-2-foo@bar.foo.bar"
+            )][..]
+        )],
+        patches_trailers[2]
+            .iter()
+            .filter(|(name, _)| *name == "Signed-off-by")
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        &[trailer(
+            "stable@vger.kernel.org",
+            "1234.567-2-foo@bar.foo.bar"
+        )],
+        patches_trailers[2].get("Cc")
+    );
+    assert!(patches_trailers[2].get("Reviewed-by").is_empty());
+    assert!(patches_trailers[2].get(STABLE_TRAILER).is_empty());
+}
+
+#[test]
+fn should_extract_only_known_trailers_of_trailer_block() {
+    let is_known = |name: &str| ["Reviewed-by", "Link"].contains(&name);
+
+    let text = "Fix: something\nReviewed-by: Not Trailer <not@trailer.com>\n\n\
+                Description\n\nReviewed-by: Foo Bar <foo@bar.com>\nLink: https://\n  lore\n\
+                Signed-off-by: Foo Bar <foo@bar.com>\n\n-- \nReviewed-by: Signature";
+    assert_eq!(
+        vec![
+            (
+                "Reviewed-by".to_string(),
+                "Foo Bar <foo@bar.com>".to_string()
+            ),
+            ("Link".to_string(), "https:// lore".to_string()),
+        ],
+        extract_trailers(text, is_known)
+    );
+
+    let text = "Looks good, but:\nReviewed-by: Foo Bar <foo@bar.com>";
+    assert_eq!(1, extract_trailers(text, is_known).len());
+
+    let text = "Reviewed-by: Foo Bar <foo@bar.com>\n\nThanks,\nFoo";
+    assert!(
+        extract_trailers(text, is_known).is_empty(),
+        "Trailers must be in the last paragraph"
+    );
+
+    let text = "Some\nlong\ntext\nmentioning\nLink: https://lore";
+    assert!(
+        extract_trailers(text, is_known).is_empty(),
+        "Trailer blocks need a quarter of trailer lines"
+    );
+
+    let text = "Text\nSigned-off-by: Foo Bar <foo@bar.com>";
+    assert!(
+        extract_trailers(text, is_known).is_empty(),
+        "Trailer blocks with other lines need a known trailer"
+    );
+}
+
+#[test]
+fn should_extract_known_trailers_of_any_reply_line() {
+    let is_known = |name: &str| ["Reviewed-by", "Link"].contains(&name);
+
+    let text = "Reviewed-by: X <x@y>\n\nThanks,\nName";
+    assert_eq!(
+        vec![("Reviewed-by".to_string(), "X <x@y>".to_string())],
+        extract_reply_trailers(text, is_known),
+        "Trailers followed by a greeting should be collected"
+    );
+
+    let text = "Looks good.\nNote: not a trailer\n  indented\nLink: https://\n  lore\n\
+                Reviewed-by: Foo Bar <foo@bar.com>\n\nCheers\n\n-- \nReviewed-by: Signature";
+    assert_eq!(
+        vec![
+            ("Link".to_string(), "https:// lore".to_string()),
+            (
+                "Reviewed-by".to_string(),
+                "Foo Bar <foo@bar.com>".to_string()
+            ),
+        ],
+        extract_reply_trailers(text, is_known)
+    );
+}
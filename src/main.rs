@@ -20,13 +20,17 @@ fn main() -> color_eyre::Result<()> {
 
     let config = Config::build();
     config.create_dirs();
+    if config.mouse_support() {
+        utils::enable_mouse_capture()?;
+    }
 
     match args.resolve(terminal, &config) {
         ControlFlow::Break(b) => return b,
         ControlFlow::Continue(t) => terminal = t,
     }
 
-    let app = App::new(config)?;
+    let mut app = App::new(config)?;
+    app.check_git_am_conflict();
 
     run_app(terminal, app)?;
     utils::restore()?;
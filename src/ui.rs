@@ -1,22 +1,107 @@
-use crate::app::{screens::CurrentScreen, App};
+use crate::app::{
+    build_test::BuildStatus, keymap::Action, screens::CurrentScreen, theme::Theme, App,
+};
+use patch_hub::lore::attestation::AttestationStatus;
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style, Stylize},
-    text::Text,
-    widgets::{Block, Borders, Clear, Paragraph},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Position, Rect},
+    style::{Style, Stylize},
+    text::{Span, Text},
+    widgets::{Block, Borders, Clear, ListState, Paragraph},
     Frame,
 };
 
+mod apply_history;
 mod bookmarked;
 mod details_actions;
 mod edit_config;
+mod kernel_trees;
 mod latest;
 pub mod loading_screen;
+pub mod log_screen;
 mod mail_list;
 mod navigation_bar;
 pub mod popup;
+mod recent;
+mod worktrees;
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum width of the author column of lists of patchsets
+const MAX_AUTHOR_WIDTH: usize = 30;
+
+/// Areas of the last drawn frame, to handle the mouse and size scrolls to
+/// what is actually displayed
+#[derive(Debug, Clone, Default)]
+pub struct FrameAreas {
+    /// Area of the rows of the list of the screen, if any, and the index of
+    /// the item displayed in each row, from the top
+    pub list: Option<(Rect, Vec<usize>)>,
+    /// Area in which the text of the patch preview is displayed, if it is
+    pub preview: Option<Rect>,
+    /// Area of the rows of the actions pane, if displayed, and the action
+    /// toggled by clicking each row, from the top
+    pub actions: Option<(Rect, Vec<Action>)>,
+}
 
-pub fn draw_ui(f: &mut Frame, app: &App) {
+impl FrameAreas {
+    /// Returns the index of the list item displayed at `position`, if any
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_find_list_item_at_position]
+    pub fn list_item_at(&self, position: Position) -> Option<usize> {
+        let (area, items) = self.list.as_ref()?;
+        if !area.contains(position) {
+            return None;
+        }
+        items.get((position.y - area.y) as usize).copied()
+    }
+
+    /// Returns the action toggled by the row of the actions pane at
+    /// `position`, if any
+    ///
+    /// # Tests
+    ///
+    /// [tests::should_find_action_at_position]
+    pub fn action_at(&self, position: Position) -> Option<Action> {
+        let (area, actions) = self.actions.as_ref()?;
+        if !area.contains(position) {
+            return None;
+        }
+        actions.get((position.y - area.y) as usize).copied()
+    }
+
+    pub fn is_over_list(&self, position: Position) -> bool {
+        self.list
+            .as_ref()
+            .is_some_and(|(area, _)| area.contains(position))
+    }
+
+    pub fn is_over_preview(&self, position: Position) -> bool {
+        self.preview.is_some_and(|area| area.contains(position))
+    }
+}
+
+/// Returns the area of the rows of a list with borders rendered in `chunk`
+/// and the index of the item in each row, given the index of each item of the
+/// list in `items` and the `list_state` with which it was rendered.
+///
+/// # Tests
+///
+/// [tests::should_find_list_item_at_position]
+fn list_mouse_area(chunk: Rect, items: Vec<usize>, list_state: &ListState) -> (Rect, Vec<usize>) {
+    let area = chunk.inner(Margin::new(1, 1));
+    let items = items
+        .into_iter()
+        .skip(list_state.offset())
+        .take(area.height as usize)
+        .collect();
+    (area, items)
+}
+
+/// Draws the UI of `app` and returns the areas of the drawn frame
+pub fn draw_ui(f: &mut Frame, app: &App) -> FrameAreas {
     // Clear the whole screen for sanitizing reasons
     f.render_widget(Clear, f.area());
 
@@ -25,20 +110,56 @@ pub fn draw_ui(f: &mut Frame, app: &App) {
         .constraints([
             Constraint::Length(3),
             Constraint::Min(1),
-            Constraint::Length(3),
+            Constraint::Length(navigation_bar::height(app)),
         ])
         .split(f.area());
 
-    render_title(f, chunks[0]);
+    render_title(f, &app.theme, chunks[0]);
 
+    let mut frame_areas = FrameAreas::default();
     match app.current_screen {
-        CurrentScreen::MailingListSelection => mail_list::render_main(f, app, chunks[1]),
+        CurrentScreen::MailingListSelection => {
+            frame_areas.list = Some(mail_list::render_main(f, app, chunks[1]));
+        }
         CurrentScreen::BookmarkedPatchsets => {
-            bookmarked::render_main(f, &app.bookmarked_patchsets, chunks[1])
+            frame_areas.list = Some(bookmarked::render_main(
+                f,
+                &app.bookmarked_patchsets.bookmarked_patchsets,
+                app.bookmarked_patchsets.patchset_index,
+                &app.attestation_statuses,
+                &app.build_statuses,
+                &app.theme,
+                chunks[1],
+            ));
+        }
+        CurrentScreen::RecentPatchsets => {
+            frame_areas.list = Some(bookmarked::render_main(
+                f,
+                &app.recent_patchsets.recent_patchsets,
+                app.recent_patchsets.patchset_index,
+                &app.attestation_statuses,
+                &app.build_statuses,
+                &app.theme,
+                chunks[1],
+            ));
+        }
+        CurrentScreen::LatestPatchsets => {
+            frame_areas.list = Some(latest::render_main(f, app, chunks[1]));
+        }
+        CurrentScreen::PatchsetDetails => {
+            frame_areas = details_actions::render_main(f, app, chunks[1]);
         }
-        CurrentScreen::LatestPatchsets => latest::render_main(f, app, chunks[1]),
-        CurrentScreen::PatchsetDetails => details_actions::render_main(f, app, chunks[1]),
         CurrentScreen::EditConfig => edit_config::render_main(f, app, chunks[1]),
+        CurrentScreen::KernelTrees => kernel_trees::render_main(f, app, chunks[1]),
+        CurrentScreen::Worktrees => {
+            worktrees::render_main(f, app.worktrees.as_ref().unwrap(), &app.theme, chunks[1])
+        }
+        CurrentScreen::ApplyHistory => apply_history::render_main(
+            f,
+            app.apply_history.as_ref().unwrap(),
+            &app.theme,
+            chunks[1],
+        ),
     }
 
     navigation_bar::render(f, app, chunks[2]);
@@ -46,11 +167,13 @@ pub fn draw_ui(f: &mut Frame, app: &App) {
     app.popup.as_ref().inspect(|p| {
         let (x, y) = p.dimensions();
         let rect = centered_rect(x, y, f.area());
-        p.render(f, rect);
+        p.render(f, rect, &app.theme);
     });
+
+    frame_areas
 }
 
-fn render_title(f: &mut Frame, chunk: Rect) {
+fn render_title(f: &mut Frame, theme: &Theme, chunk: Rect) {
     let title_block = Block::default()
         .borders(Borders::ALL)
         .style(Style::default())
@@ -58,16 +181,64 @@ fn render_title(f: &mut Frame, chunk: Rect) {
 
     let title_content: String = "patch-hub".to_string();
 
-    let title = Paragraph::new(Text::styled(
-        title_content,
-        Style::default().fg(Color::Green).bold(),
-    ))
-    .centered()
-    .block(title_block);
+    let title = Paragraph::new(Text::styled(title_content, theme.title.bold()))
+        .centered()
+        .block(title_block);
 
     f.render_widget(title, chunk);
 }
 
+/// Returns a colored badge that represents the attestation status of a patch
+/// or patchset, like _**✓ verified**_.
+fn attestation_badge(status: AttestationStatus, theme: &Theme) -> Span<'static> {
+    match status {
+        AttestationStatus::Verified => Span::styled("✓ verified", theme.success),
+        AttestationStatus::Unverified => Span::styled("? unverified", theme.muted),
+        AttestationStatus::Failed => Span::styled("✗ failed", theme.error),
+    }
+}
+
+/// Returns a colored badge that represents the build-test status of a
+/// patchset, like _**✓ build**_, or an empty one if it wasn't build-tested.
+fn build_badge(status: Option<BuildStatus>, theme: &Theme) -> Span<'static> {
+    match status {
+        Some(BuildStatus::Passed) => Span::styled("✓ build", theme.success),
+        Some(BuildStatus::Failed) => Span::styled("✗ build", theme.error),
+        None => Span::raw(""),
+    }
+}
+
+/// Returns `text` padded or truncated, with an ellipsis, to `width`
+/// characters.
+///
+/// # Tests
+///
+/// [tests::should_fit_text_to_width]
+fn fit_to_width(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return format!("{text:width$}");
+    }
+    match width {
+        0 => String::new(),
+        _ => format!("{}…", text.chars().take(width - 1).collect::<String>()),
+    }
+}
+
+/// Returns the widths of the title and author columns of a list of
+/// patchsets `list_width` columns wide whose other columns take
+/// `other_columns_width`. The title takes 70% of the free width and the author
+/// the rest, up to [MAX_AUTHOR_WIDTH] columns.
+///
+/// # Tests
+///
+/// [tests::should_split_title_and_author_widths]
+fn title_and_author_widths(list_width: u16, other_columns_width: usize) -> (usize, usize) {
+    // Borders and highlight symbol
+    let free_width = (list_width as usize).saturating_sub(other_columns_width + 3);
+    let author_width = (free_width * 3 / 10).min(MAX_AUTHOR_WIDTH);
+    (free_width - author_width, author_width)
+}
+
 /// helper function to create a centered rect using up certain percentage of the available rect `r`
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     // Cut the given rectangle into three vertical pieces
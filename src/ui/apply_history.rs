@@ -0,0 +1,103 @@
+use crate::app::{
+    apply_history::ApplyResult,
+    keymap::{Action, Keymap},
+    screens::{apply_history::ApplyHistory, CurrentScreen},
+    theme::Theme,
+};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+pub fn render_main(f: &mut Frame, apply_history: &ApplyHistory, theme: &Theme, chunk: Rect) {
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Double)
+        .style(Style::default());
+
+    if apply_history.entries.is_empty() {
+        let message = Paragraph::new(Line::styled("No patchsets applied yet", theme.muted))
+            .block(list_block)
+            .centered();
+        f.render_widget(message, chunk);
+        return;
+    }
+
+    let mut list_items = Vec::<ListItem>::new();
+
+    for (index, entry) in apply_history.entries.iter().enumerate() {
+        let record = &entry.record;
+        let mark = if apply_history.marked.contains(&index) {
+            Span::styled("[x] ", theme.success)
+        } else {
+            Span::styled("[ ] ", theme.label)
+        };
+        let result_style = match record.result() {
+            ApplyResult::Applied => theme.success,
+            ApplyResult::Failed => theme.error,
+            ApplyResult::Conflict => theme.emphasis,
+        };
+        let branch = format!("{:width$}", record.branch(), width = 30);
+        let branch = format!("{:.width$}", branch, width = 30);
+
+        let mut line = vec![
+            mark,
+            Span::styled(format!("{:03}. {} ", index, record.timestamp()), theme.item),
+            Span::styled(format!("{:8}", record.result().to_string()), result_style),
+            Span::styled(
+                format!(" | {} | {}", branch, record.patchset().title()),
+                theme.item,
+            ),
+        ];
+        if !entry.branch_exists {
+            line.push(Span::styled(" (deleted)", theme.muted));
+        }
+        list_items.push(ListItem::new(Line::from(line)));
+    }
+
+    let list = List::new(list_items)
+        .block(list_block)
+        .highlight_style(theme.selected)
+        .highlight_symbol(">")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(apply_history.highlighted));
+
+    f.render_stateful_widget(list, chunk, &mut list_state);
+}
+
+pub fn mode_footer_text(apply_history: &ApplyHistory, theme: &Theme) -> Vec<Span<'static>> {
+    let mut text = vec![Span::styled("Apply History", theme.title)];
+    if let Some(entry) = apply_history.get_highlighted_entry() {
+        text.push(Span::styled(
+            format!(
+                " | {} ({})",
+                entry.record.kernel_tree_path(),
+                entry.record.base()
+            ),
+            theme.text,
+        ));
+    }
+    text
+}
+
+pub fn keys_hint(keymap: &Keymap, theme: &Theme) -> Span<'static> {
+    Span::styled(
+        keymap.keys_hint(
+            &CurrentScreen::ApplyHistory,
+            &[
+                (Action::Exit, "to return"),
+                (Action::Open, "to open"),
+                (Action::Mark, "mark"),
+                (Action::MarkAll, "mark all"),
+                (Action::DeleteBranches, "delete branches"),
+                (Action::Help, "help"),
+            ],
+        ),
+        theme.hint,
+    )
+}
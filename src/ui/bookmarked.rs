@@ -1,34 +1,65 @@
-use crate::app;
-use app::screens::bookmarked::BookmarkedPatchsets;
+use std::collections::HashMap;
+
+use super::{
+    attestation_badge, build_badge, fit_to_width, list_mouse_area, title_and_author_widths,
+};
+use crate::app::{
+    build_test::BuildStatus,
+    keymap::{Action, Keymap},
+    screens::CurrentScreen,
+    theme::Theme,
+};
+use patch_hub::lore::{attestation::AttestationStatus, patch::Patch};
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, HighlightSpacing, List, ListItem, ListState},
     Frame,
 };
 
-pub fn render_main(f: &mut Frame, bookmarked_patchsets: &BookmarkedPatchsets, chunk: Rect) {
-    let patchset_index = bookmarked_patchsets.patchset_index;
+/// Renders the list of `patchsets` with the one of `patchset_index` selected.
+/// Also used for the recent patchsets.
+pub fn render_main(
+    f: &mut Frame,
+    patchsets: &[Patch],
+    patchset_index: usize,
+    attestation_statuses: &HashMap<String, AttestationStatus>,
+    build_statuses: &HashMap<String, BuildStatus>,
+    theme: &Theme,
+    chunk: Rect,
+) -> (Rect, Vec<usize>) {
     let mut list_items = Vec::<ListItem>::new();
+    // Index, version, number of patches, separators and badges
+    let (title_width, author_width) = title_and_author_widths(chunk.width, 43);
 
-    for (index, patch) in bookmarked_patchsets.bookmarked_patchsets.iter().enumerate() {
-        let patch_title = format!("{:width$}", patch.title(), width = 70);
-        let patch_title = format!("{:.width$}", patch_title, width = 70);
-        let patch_author = format!("{:width$}", patch.author().name, width = 30);
-        let patch_author = format!("{:.width$}", patch_author, width = 30);
+    for (index, patch) in patchsets.iter().enumerate() {
+        let patch_title = fit_to_width(patch.title(), title_width);
+        let patch_author = fit_to_width(&patch.author().name, author_width);
+        let attestation_status = attestation_statuses
+            .get(&patch.message_id().href)
+            .copied()
+            .unwrap_or_default();
+        let mut badge = attestation_badge(attestation_status, theme);
+        badge.content = format!("{:12}", badge.content).into();
+        let mut build = build_badge(build_statuses.get(&patch.message_id().href).copied(), theme);
+        build.content = format!(" {:7}", build.content).into();
         list_items.push(ListItem::new(
-            Line::from(Span::styled(
-                format!(
-                    "{:03}. V{:02} | #{:02} | {} | {}",
-                    index,
-                    patch.version(),
-                    patch.total_in_series(),
-                    patch_title,
-                    patch_author
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{:03}. V{:02} | #{:02} | {} | {} | ",
+                        index,
+                        patch.version(),
+                        patch.total_in_series(),
+                        patch_title,
+                        patch_author
+                    ),
+                    theme.item,
                 ),
-                Style::default().fg(Color::Yellow),
-            ))
+                badge,
+                build,
+            ])
             .centered(),
         ));
     }
@@ -40,12 +71,7 @@ pub fn render_main(f: &mut Frame, bookmarked_patchsets: &BookmarkedPatchsets, ch
 
     let list = List::new(list_items)
         .block(list_block)
-        .highlight_style(
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .add_modifier(Modifier::REVERSED)
-                .fg(Color::Cyan),
-        )
+        .highlight_style(theme.selected)
         .highlight_symbol(">")
         .highlight_spacing(HighlightSpacing::Always);
 
@@ -53,18 +79,25 @@ pub fn render_main(f: &mut Frame, bookmarked_patchsets: &BookmarkedPatchsets, ch
     list_state.select(Some(patchset_index));
 
     f.render_stateful_widget(list, chunk, &mut list_state);
+
+    let items = (0..patchsets.len()).collect();
+    list_mouse_area(chunk, items, &list_state)
 }
 
-pub fn mode_footer_text() -> Vec<Span<'static>> {
-    vec![Span::styled(
-        "Bookmarked Patchsets",
-        Style::default().fg(Color::Green),
-    )]
+pub fn mode_footer_text(theme: &Theme) -> Vec<Span<'static>> {
+    vec![Span::styled("Bookmarked Patchsets", theme.title)]
 }
 
-pub fn keys_hint() -> Span<'static> {
+pub fn keys_hint(keymap: &Keymap, theme: &Theme) -> Span<'static> {
     Span::styled(
-        "(ESC / q) to return | (ENTER) to select | (?) help",
-        Style::default().fg(Color::Red),
+        keymap.keys_hint(
+            &CurrentScreen::BookmarkedPatchsets,
+            &[
+                (Action::Exit, "to return"),
+                (Action::Open, "to select"),
+                (Action::Help, "help"),
+            ],
+        ),
+        theme.hint,
     )
 }
@@ -1,54 +1,290 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Padding, Paragraph, Wrap},
     Frame,
 };
 
+use super::{attestation_badge, FrameAreas};
 use crate::app::{
-    screens::details_actions::{DetailsActions, PatchsetAction},
+    keymap::{Action, Keymap},
+    preview_search::{self, SearchDirection},
+    screens::{
+        details_actions::{DetailsActions, PatchsetAction, ReplyTrailer},
+        CurrentScreen,
+    },
+    theme::Theme,
     App,
 };
+use patch_hub::lore::{
+    checkpatch::CheckpatchLevel, fixes::FixesResolution, maintainers::MaintainerRole,
+    trailers::CODE_REVIEW_TRAILERS,
+};
+
+/// Terminals narrower than this get the stacked layout
+const STACKED_LAYOUT_MAX_WIDTH: u16 = 100;
+/// Height of the details and actions panes in the stacked layout, enough for
+/// every action
+const STACKED_PANES_HEIGHT: u16 = 12;
 
 /// Returns a `Line` type that represents a line containing stats about reply
-/// trailers. It currently considers the _Reviewed-by_, _Tested-by_, and
-/// _Acked-by_ trailers and colors them depending if they are 0 or not.  Example
-/// of line returned:
+/// trailers. The code-review trailers (_Reviewed-by_, _Tested-by_, and
+/// _Acked-by_) are always present, followed by any other trailer collected for
+/// the patch, and are colored depending if they are 0 or not.  Example of line
+/// returned:
 ///
-/// _**Reviewed-by: 1 | Tested-by: 0 | Acked-by: 2**_
-fn review_trailers_details(details_actions: &DetailsActions) -> Line<'static> {
-    let i = details_actions.preview_index;
+/// _**Reviewed-by: 1 | Tested-by: 0 | Acked-by: 2 | Fixes: 1**_
+fn review_trailers_details(details_actions: &DetailsActions, theme: &Theme) -> Line<'static> {
+    let trailers = &details_actions.trailers[details_actions.preview_index];
 
-    let resolve_color = |n_trailers: usize| -> Style {
+    let resolve_style = |n_trailers: usize| -> Style {
         if n_trailers == 0 {
-            Style::default().fg(Color::White)
+            theme.text
         } else {
-            Style::default().fg(Color::Green)
+            theme.success
         }
     };
 
-    Line::from(vec![
-        Span::styled("Reviewed-by: ", Style::default().fg(Color::Cyan)),
-        Span::styled(
-            details_actions.reviewed_by[i].len().to_string(),
-            resolve_color(details_actions.reviewed_by[i].len()),
-        ),
-        Span::styled(" | Tested-by: ", Style::default().fg(Color::Cyan)),
-        Span::styled(
-            details_actions.tested_by[i].len().to_string(),
-            resolve_color(details_actions.tested_by[i].len()),
-        ),
-        Span::styled(" | Acked-by: ", Style::default().fg(Color::Cyan)),
-        Span::styled(
-            details_actions.acked_by[i].len().to_string(),
-            resolve_color(details_actions.acked_by[i].len()),
+    let other_trailers = trailers
+        .iter()
+        .filter(|(name, _)| !CODE_REVIEW_TRAILERS.contains(name))
+        .map(|(name, trailers)| (name, trailers.len()));
+    let trailers_count = CODE_REVIEW_TRAILERS
+        .iter()
+        .map(|name| (*name, trailers.get(name).len()))
+        .chain(other_trailers);
+
+    let mut line = Vec::new();
+    for (name, n_trailers) in trailers_count {
+        if !line.is_empty() {
+            line.push(Span::styled(" | ", theme.label));
+        }
+        line.push(Span::styled(format!("{name}: "), theme.label));
+        line.push(Span::styled(
+            n_trailers.to_string(),
+            resolve_style(n_trailers),
+        ));
+    }
+
+    Line::from(line)
+}
+
+/// Returns a `Line` for each `Fixes:` trailer of the currently previewed
+/// patch, describing the result of resolving it against the target kernel
+/// tree. Examples of lines returned:
+///
+/// _**Fixes: 0123456789ab ✓ in v6.1-rc1**_
+///
+/// _**Fixes: 0123456789ab ✓ in v6.1-rc1 (subject mismatch: "foo: Do bar")**_
+///
+/// _**Fixes: 0123456 ✗ malformed (sha '0123456' is shorter than 12 characters)**_
+fn fixes_details(details_actions: &DetailsActions, theme: &Theme) -> Vec<Line<'static>> {
+    let fixes = &details_actions.fixes[details_actions.preview_index];
+
+    fixes
+        .iter()
+        .map(|fixes_check| {
+            let sha = fixes_check
+                .value()
+                .split_whitespace()
+                .next()
+                .unwrap_or_default();
+            let mut line = vec![
+                Span::styled("Fixes: ", theme.label),
+                Span::styled(format!("{sha} "), theme.text),
+            ];
+
+            match fixes_check.resolution() {
+                FixesResolution::Malformed(reason) => {
+                    line.push(Span::styled(format!("✗ malformed ({reason})"), theme.error))
+                }
+                FixesResolution::Pending | FixesResolution::Unresolved => {
+                    line.push(Span::styled("? unresolved", theme.muted))
+                }
+                FixesResolution::NotFound => line.push(Span::styled("✗ not in tree", theme.error)),
+                FixesResolution::Found {
+                    subject_matches,
+                    actual_subject,
+                    first_tag,
+                    ..
+                } => {
+                    let first_tag = match first_tag {
+                        Some(tag) => format!("in {tag}"),
+                        None => "not in any release".to_string(),
+                    };
+                    line.push(Span::styled(format!("✓ {first_tag}"), theme.success));
+                    if !subject_matches {
+                        line.push(Span::styled(
+                            format!(" (subject mismatch: \"{actual_subject}\")"),
+                            theme.warning,
+                        ));
+                    }
+                }
+            }
+
+            Line::from(line)
+        })
+        .collect()
+}
+
+/// Returns a `Line` type that represents the counts of issues reported by
+/// `checkpatch.pl` for the currently previewed patch, colored depending if they
+/// are 0 or not. Example of line returned:
+///
+/// _**Checkpatch: 1 errors | 2 warnings | 0 checks**_
+fn checkpatch_details(details_actions: &DetailsActions, theme: &Theme) -> Line<'static> {
+    let mut line = vec![Span::styled("Checkpatch: ", theme.label)];
+
+    let Some(report) = details_actions
+        .checkpatch_reports
+        .as_ref()
+        .and_then(|reports| reports.get(details_actions.preview_index))
+    else {
+        line.push(Span::styled("not run", theme.muted));
+        return Line::from(line);
+    };
+
+    let counts = [
+        (report.count(CheckpatchLevel::Error), "errors", theme.error),
+        (
+            report.count(CheckpatchLevel::Warning),
+            "warnings",
+            theme.warning,
         ),
-    ])
+        (report.count(CheckpatchLevel::Check), "checks", theme.label),
+    ];
+    for (i, (count, name, style)) in counts.into_iter().enumerate() {
+        if i > 0 {
+            line.push(Span::styled(" | ", theme.label));
+        }
+        let style = if count == 0 { theme.success } else { style };
+        line.push(Span::styled(format!("{count} {name}"), style));
+    }
+
+    Line::from(line)
+}
+
+/// Returns a `Line` type that represents the `MAINTAINERS` sections touched by
+/// the patchset, highlighting the ones in which the user is a maintainer or
+/// reviewer. Example of line returned:
+///
+/// _**Maintainers: FOO DRIVER (reviewer), THE REST**_
+fn maintainers_details(details_actions: &DetailsActions, theme: &Theme) -> Line<'static> {
+    let mut line = vec![Span::styled("Maintainers: ", theme.label)];
+
+    let Some(sections) = &details_actions.maintainers else {
+        line.push(Span::styled(
+            "unknown (invalid target kernel tree)",
+            theme.muted,
+        ));
+        return Line::from(line);
+    };
+    if sections.is_empty() {
+        line.push(Span::styled("none", theme.muted));
+    }
+
+    for (i, section) in sections.iter().enumerate() {
+        if i > 0 {
+            line.push(Span::styled(", ", theme.label));
+        }
+        match section.role() {
+            Some(MaintainerRole::Maintainer) => line.push(Span::styled(
+                format!("{} (maintainer)", section.name()),
+                theme.success,
+            )),
+            Some(MaintainerRole::Reviewer) => line.push(Span::styled(
+                format!("{} (reviewer)", section.name()),
+                theme.success,
+            )),
+            None => line.push(Span::styled(section.name().to_string(), theme.text)),
+        }
+    }
+
+    Line::from(line)
+}
+
+/// Returns a `Line` type that represents the attestation (DKIM and patatt
+/// signatures check) of the currently previewed patch followed by the checked
+/// signatures. Example of line returned:
+///
+/// _**Attestation: ✓ verified (Signed: DKIM/kernel.org)**_
+fn attestation_details(details_actions: &DetailsActions, theme: &Theme) -> Line<'static> {
+    let attestation = &details_actions.attestations[details_actions.preview_index];
+
+    let mut line = vec![
+        Span::styled("Attestation: ", theme.label),
+        attestation_badge(*attestation.status(), theme),
+    ];
+    if !attestation.signatures().is_empty() {
+        line.push(Span::styled(
+            format!(" ({})", attestation.signatures().join(", ")),
+            theme.text,
+        ));
+    }
+
+    Line::from(line)
+}
+
+/// Returns the spans of `label` of `action` in the actions pane. If `action`
+/// is bound to the key `mnemonic` in the patchset details, its first occurrence
+/// in `label` is underlined. Otherwise, `label` is followed by the keys bound
+/// to `action`, if any.
+///
+/// # Tests
+///
+/// [super::tests::should_underline_bound_action_mnemonic]
+pub(super) fn action_label(
+    keymap: &Keymap,
+    action: Action,
+    label: &str,
+    mnemonic: char,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    let is_bound_to_mnemonic = keymap
+        .bindings(&CurrentScreen::PatchsetDetails)
+        .iter()
+        .filter(|binding| binding.action == action)
+        .flat_map(|binding| &binding.keys)
+        .any(|keys| keys.to_string() == mnemonic.to_string());
+
+    match label.find(mnemonic).filter(|_| is_bound_to_mnemonic) {
+        Some(index) => {
+            let (before, rest) = label.split_at(index);
+            let after = &rest[mnemonic.len_utf8()..];
+            vec![
+                Span::styled(before.to_string(), theme.label),
+                Span::styled(
+                    mnemonic.to_string(),
+                    theme
+                        .label
+                        .add_modifier(Modifier::UNDERLINED)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(after.to_string(), theme.label),
+            ]
+        }
+        None => {
+            let keys = keymap.keys_of(&CurrentScreen::PatchsetDetails, action, "/");
+            let mut spans = vec![Span::styled(label.to_string(), theme.label)];
+            if !keys.is_empty() {
+                spans.push(Span::styled(format!(" ({keys})"), theme.hint));
+            }
+            spans
+        }
+    }
 }
 
-fn render_details_and_actions(f: &mut Frame, app: &App, details_chunk: Rect, actions_chunk: Rect) {
+/// Renders the details and the actions panes and returns the area of the rows
+/// of the actions (see [FrameAreas::actions])
+fn render_details_and_actions(
+    f: &mut Frame,
+    app: &App,
+    details_chunk: Rect,
+    actions_chunk: Rect,
+) -> (Rect, Vec<Action>) {
     let patchset_details_and_actions = app.details_actions.as_ref().unwrap();
+    let theme = &app.theme;
 
     let mut staged_to_reply = String::new();
     if let Some(true) = patchset_details_and_actions
@@ -77,46 +313,76 @@ fn render_details_and_actions(f: &mut Frame, app: &App, details_chunk: Rect, act
     let patchset_details = &patchset_details_and_actions.representative_patch;
     let mut patchset_details = vec![
         Line::from(vec![
-            Span::styled(r#"  Title: "#, Style::default().fg(Color::Cyan)),
-            Span::styled(
-                patchset_details.title().to_string(),
-                Style::default().fg(Color::White),
-            ),
+            Span::styled(r#"  Title: "#, theme.label),
+            Span::styled(patchset_details.title().to_string(), theme.text),
         ]),
         Line::from(vec![
-            Span::styled("Author: ", Style::default().fg(Color::Cyan)),
-            Span::styled(
-                patchset_details.author().name.to_string(),
-                Style::default().fg(Color::White),
-            ),
+            Span::styled("Author: ", theme.label),
+            Span::styled(patchset_details.author().name.to_string(), theme.text),
         ]),
         Line::from(vec![
-            Span::styled("Version: ", Style::default().fg(Color::Cyan)),
-            Span::styled(
-                format!("{}", patchset_details.version()),
-                Style::default().fg(Color::White),
-            ),
+            Span::styled("Version: ", theme.label),
+            Span::styled(format!("{}", patchset_details.version()), theme.text),
         ]),
         Line::from(vec![
-            Span::styled("Patch count: ", Style::default().fg(Color::Cyan)),
+            Span::styled("Patch count: ", theme.label),
             Span::styled(
                 format!("{}", patchset_details.total_in_series()),
-                Style::default().fg(Color::White),
+                theme.text,
             ),
         ]),
         Line::from(vec![
-            Span::styled("Last updated: ", Style::default().fg(Color::Cyan)),
-            Span::styled(
-                patchset_details.updated().to_string(),
-                Style::default().fg(Color::White),
-            ),
+            Span::styled("Last updated: ", theme.label),
+            Span::styled(patchset_details.updated().to_string(), theme.text),
         ]),
-        review_trailers_details(patchset_details_and_actions),
+        review_trailers_details(patchset_details_and_actions, theme),
     ];
+    patchset_details.extend(fixes_details(patchset_details_and_actions, theme));
+    patchset_details.push(checkpatch_details(patchset_details_and_actions, theme));
+    patchset_details.push(maintainers_details(patchset_details_and_actions, theme));
+    patchset_details.push(attestation_details(patchset_details_and_actions, theme));
+    if let Some(apply_base) = &patchset_details_and_actions.apply_base {
+        patchset_details.push(Line::from(vec![
+            Span::styled("Apply base: ", theme.label),
+            Span::styled(
+                format!("{} ({})", apply_base.kernel_tree_id(), apply_base.branch()),
+                theme.text,
+            ),
+            Span::styled(
+                format!(
+                    " — {} ({})",
+                    apply_base.reason(),
+                    app.keymap.keys_of(
+                        &CurrentScreen::PatchsetDetails,
+                        Action::ChooseApplyBase,
+                        " / "
+                    )
+                ),
+                theme.muted,
+            ),
+        ]));
+    }
+    if let Some(conflict) = &app.git_am_conflict {
+        patchset_details.push(Line::from(vec![
+            Span::styled("Apply: ", theme.label),
+            Span::styled(
+                format!(
+                    "conflicts in '{}' ({})",
+                    conflict.repo_path(),
+                    app.keymap.keys_of(
+                        &CurrentScreen::PatchsetDetails,
+                        Action::ShowConflict,
+                        " / "
+                    )
+                ),
+                theme.error,
+            ),
+        ]));
+    }
     if !staged_to_reply.is_empty() {
         patchset_details.push(Line::from(vec![
-            Span::styled("Staged to reply: ", Style::default().fg(Color::Cyan)),
-            Span::styled(staged_to_reply, Style::default().fg(Color::White)),
+            Span::styled("Staged to reply: ", theme.label),
+            Span::styled(staged_to_reply, theme.text),
         ]));
     }
 
@@ -125,7 +391,7 @@ fn render_details_and_actions(f: &mut Frame, app: &App, details_chunk: Rect, act
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(ratatui::widgets::BorderType::Double)
-                .title(Line::styled(" Details ", Style::default().fg(Color::Green)).left_aligned())
+                .title(Line::styled(" Details ", theme.title).left_aligned())
                 .padding(Padding::vertical(1)),
         )
         .left_aligned()
@@ -134,73 +400,106 @@ fn render_details_and_actions(f: &mut Frame, app: &App, details_chunk: Rect, act
     f.render_widget(patchset_details, details_chunk);
 
     let patchset_actions = &patchset_details_and_actions.patchset_actions;
-    // TODO: Create a function to produce new action lines
+    let checkbox = |checked: bool| {
+        if checked {
+            Span::styled("[x] ", theme.success)
+        } else {
+            Span::styled("[ ] ", theme.label)
+        }
+    };
+    let action_line = |checked: bool, action: Action, label: &str, mnemonic: char| {
+        let mut line = vec![checkbox(checked)];
+        line.extend(action_label(&app.keymap, action, label, mnemonic, theme));
+        line
+    };
+
+    let mut apply_line = action_line(
+        *patchset_actions.get(&PatchsetAction::Apply).unwrap(),
+        Action::ToggleApply,
+        "apply",
+        'a',
+    );
+    if patchset_details_and_actions.apply_per_patch {
+        apply_line.push(Span::styled(" (per patch)", theme.label));
+    }
+    if let Some(apply_base) = &patchset_details_and_actions.apply_base {
+        apply_line.push(Span::styled(
+            format!(" → {}", apply_base.kernel_tree_id()),
+            theme.label,
+        ));
+    }
+    let reply_label = match patchset_details_and_actions.reply_trailer {
+        ReplyTrailer::Reviewed => "reviewed-by".to_string(),
+        reply_trailer => format!("reply ({reply_trailer})"),
+    };
+
     let patchset_actions = vec![
-        Line::from(vec![
-            if *patchset_actions.get(&PatchsetAction::Bookmark).unwrap() {
-                Span::styled("[x] ", Style::default().fg(Color::Green))
-            } else {
-                Span::styled("[ ] ", Style::default().fg(Color::Cyan))
-            },
-            Span::styled(
-                "b",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::UNDERLINED)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("ookmark", Style::default().fg(Color::Cyan)),
-        ]),
-        Line::from(vec![
-            if *patchset_actions.get(&PatchsetAction::Apply).unwrap() {
-                Span::styled("[x] ", Style::default().fg(Color::Green))
-            } else {
-                Span::styled("[ ] ", Style::default().fg(Color::Cyan))
-            },
-            Span::styled(
-                "a",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::UNDERLINED)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("pply", Style::default().fg(Color::Cyan)),
-        ]),
-        Line::from(vec![
-            if *patchset_details_and_actions
+        Line::from(action_line(
+            *patchset_actions.get(&PatchsetAction::Bookmark).unwrap(),
+            Action::ToggleBookmark,
+            "bookmark",
+            'b',
+        )),
+        Line::from(apply_line),
+        Line::from(action_line(
+            *patchset_actions.get(&PatchsetAction::Checkpatch).unwrap(),
+            Action::ToggleCheckpatch,
+            "checkpatch",
+            'c',
+        )),
+        Line::from(action_line(
+            *patchset_actions.get(&PatchsetAction::BuildTest).unwrap(),
+            Action::ToggleBuildTest,
+            "build-test",
+            't',
+        )),
+        Line::from(action_line(
+            *patchset_actions.get(&PatchsetAction::Export).unwrap(),
+            Action::ToggleExport,
+            &format!("export ({})", patchset_details_and_actions.export_format),
+            'e',
+        )),
+        Line::from(action_line(
+            *patchset_details_and_actions
                 .patches_to_reply
                 .get(patchset_details_and_actions.preview_index)
-                .unwrap()
-            {
-                Span::styled("[x] ", Style::default().fg(Color::Green))
-            } else {
-                Span::styled("[ ] ", Style::default().fg(Color::Cyan))
-            },
-            Span::styled(
-                "r",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::UNDERLINED)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("eviewed-by", Style::default().fg(Color::Cyan)),
-        ]),
+                .unwrap(),
+            Action::ToggleReviewedBy,
+            &reply_label,
+            'r',
+        )),
     ];
     let patchset_actions = Paragraph::new(patchset_actions)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(ratatui::widgets::BorderType::Double)
-                .title(Line::styled(" Actions ", Style::default().fg(Color::Green)).left_aligned())
+                .title(Line::styled(" Actions ", theme.title).left_aligned())
                 .padding(Padding::vertical(1)),
         )
         .centered();
 
     f.render_widget(patchset_actions, actions_chunk);
+
+    // Rows of the actions, inside the borders and padding of the pane
+    (
+        actions_chunk.inner(Margin::new(1, 2)),
+        vec![
+            Action::ToggleBookmark,
+            Action::ToggleApply,
+            Action::ToggleCheckpatch,
+            Action::ToggleBuildTest,
+            Action::ToggleExport,
+            Action::ToggleReviewedBy,
+        ],
+    )
 }
 
-fn render_preview(f: &mut Frame, app: &App, chunk: Rect) {
+/// Renders the preview of the current patch and returns the area in which its
+/// text is displayed
+fn render_preview(f: &mut Frame, app: &App, chunk: Rect) -> Rect {
     let patchset_details_and_actions = app.details_actions.as_ref().unwrap();
+    let theme = &app.theme;
 
     let preview_index = patchset_details_and_actions.preview_index;
 
@@ -208,76 +507,183 @@ fn render_preview(f: &mut Frame, app: &App, chunk: Rect) {
         .representative_patch
         .message_id()
         .href;
+    let reply_trailer = patchset_details_and_actions
+        .reply_trailer
+        .to_string()
+        .to_uppercase();
     let mut preview_title = String::from(" Preview ");
     if matches!(
         app.reviewed_patchsets.get(representative_patch_message_id),
         Some(successful_indexes) if successful_indexes.contains(&preview_index)
     ) {
-        preview_title = " Preview [REVIEWED-BY] ".to_string();
+        preview_title = format!(" Preview [{reply_trailer}] ");
     } else if *patchset_details_and_actions
         .patches_to_reply
         .get(preview_index)
         .unwrap()
     {
-        preview_title = " Preview [REVIEWED-BY]* ".to_string();
+        preview_title = format!(" Preview [{reply_trailer}]* ");
     };
 
     let preview_offset = patchset_details_and_actions.preview_scroll_offset;
     let preview_pan = patchset_details_and_actions.preview_pan;
-    let patch_preview = patchset_details_and_actions.patches_preview[preview_index].clone();
+    let patch_preview = &patchset_details_and_actions.patches_preview[preview_index];
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Double)
+        .title(Line::styled(preview_title, theme.title).left_aligned())
+        .padding(Padding::vertical(1));
+    if let Some(search_line) = search_line(patchset_details_and_actions, theme) {
+        block = block.title_bottom(search_line.left_aligned());
+    }
 
+    let viewport = block.inner(chunk);
+    let visible_lines = preview_offset..preview_offset + viewport.height as usize;
+    let patch_preview = match &patchset_details_and_actions.preview_search {
+        Some(search) => preview_search::highlight_matches(
+            patch_preview,
+            preview_index,
+            visible_lines,
+            &search.matches,
+            search.current.map(|i| &search.matches[i]),
+            theme,
+        ),
+        None => preview_search::highlight_matches(
+            patch_preview,
+            preview_index,
+            visible_lines,
+            &[],
+            None,
+            theme,
+        ),
+    };
+    // Only the visible lines are rendered, so the preview is only panned
     let patch_preview = Paragraph::new(patch_preview)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(ratatui::widgets::BorderType::Double)
-                .title(
-                    Line::styled(preview_title, Style::default().fg(Color::Green)).left_aligned(),
-                )
-                .padding(Padding::vertical(1)),
-        )
+        .block(block)
         .left_aligned()
-        .scroll((preview_offset as u16, preview_pan as u16));
+        .scroll((0, preview_pan as u16));
 
     f.render_widget(patch_preview, chunk);
+    viewport
+}
+
+/// Returns the line with the prompt of the search being typed or the status
+/// of the current search, if any
+fn search_line(details_actions: &DetailsActions, theme: &Theme) -> Option<Line<'static>> {
+    let direction = |direction| match direction {
+        SearchDirection::Forward => "/",
+        SearchDirection::Backward => "?",
+    };
+    let scope = |across_series| if across_series { " [series]" } else { "" };
+
+    if let Some(prompt) = &details_actions.search_prompt {
+        let mut spans = vec![
+            Span::styled(
+                format!(" {}{}", direction(prompt.direction), prompt.input),
+                theme.accent,
+            ),
+            Span::styled("\u{2588}", theme.accent),
+            Span::styled(
+                format!("{} (TAB) scope ", scope(prompt.across_series)),
+                theme.muted,
+            ),
+        ];
+        if let Some(error) = &prompt.error {
+            let error = error.lines().last().unwrap_or_default().to_string();
+            spans.push(Span::styled(format!("{error} "), theme.error));
+        }
+        return Some(Line::from(spans));
+    }
+
+    let search = details_actions.preview_search.as_ref()?;
+    let (position, count) = search.current_position(details_actions.preview_index);
+    let status = if count == 0 {
+        Span::styled("Pattern not found ", theme.error)
+    } else {
+        Span::styled(format!("[{position}/{count}] "), theme.muted)
+    };
+    Some(Line::from(vec![
+        Span::styled(
+            format!(
+                " {}{}{} ",
+                direction(search.direction),
+                search.pattern,
+                scope(search.across_series)
+            ),
+            theme.accent,
+        ),
+        status,
+    ]))
 }
 
-pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
+/// Renders the details, actions and preview panes and returns their areas
+pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) -> FrameAreas {
     let patchset_details_and_actions = app.details_actions.as_ref().unwrap();
 
     if patchset_details_and_actions.preview_fullscreen {
-        render_preview(f, app, chunk);
+        let preview = render_preview(f, app, chunk);
+        return FrameAreas {
+            preview: Some(preview),
+            ..Default::default()
+        };
+    }
+
+    let (details_chunk, actions_chunk, preview_chunk) = if chunk.width < STACKED_LAYOUT_MAX_WIDTH {
+        // Narrow terminals get the details and actions side by side on top of
+        // the preview, so the preview keeps the full width
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(STACKED_PANES_HEIGHT), Constraint::Min(0)])
+            .split(chunk);
+        let details_and_actions_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chunks[0]);
+        (
+            details_and_actions_chunks[0],
+            details_and_actions_chunks[1],
+            chunks[1],
+        )
     } else {
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
             .split(chunk);
-
         let details_and_actions_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
             .split(chunks[0]);
-
-        render_details_and_actions(
-            f,
-            app,
+        (
             details_and_actions_chunks[0],
             details_and_actions_chunks[1],
-        );
-        render_preview(f, app, chunks[1]);
+            chunks[1],
+        )
+    };
+
+    let actions = render_details_and_actions(f, app, details_chunk, actions_chunk);
+    let preview = render_preview(f, app, preview_chunk);
+    FrameAreas {
+        preview: Some(preview),
+        actions: Some(actions),
+        ..Default::default()
     }
 }
 
-pub fn mode_footer_text() -> Vec<Span<'static>> {
-    vec![Span::styled(
-        "Patchset Details and Actions",
-        Style::default().fg(Color::Green),
-    )]
+pub fn mode_footer_text(theme: &Theme) -> Vec<Span<'static>> {
+    vec![Span::styled("Patchset Details and Actions", theme.title)]
 }
 
-pub fn keys_hint() -> Span<'static> {
+pub fn keys_hint(keymap: &Keymap, theme: &Theme) -> Span<'static> {
     Span::styled(
-        "(ESC / q) to return | (ENTER) run actions | (?) help",
-        Style::default().fg(Color::Red),
+        keymap.keys_hint(
+            &CurrentScreen::PatchsetDetails,
+            &[
+                (Action::Exit, "to return"),
+                (Action::RunActions, "run actions"),
+                (Action::Help, "help"),
+            ],
+        ),
+        theme.hint,
     )
 }
@@ -1,13 +1,13 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
 use crate::app::logging::Logger;
-use crate::app::App;
+use crate::app::{keymap::Action, screens::CurrentScreen, App};
 
 pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
     let edit_config = app.edit_config.as_ref().unwrap();
@@ -39,7 +39,7 @@ pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
         let value = Line::from(if edit_config.is_editing() && i == highlighted_entry {
             vec![
                 Span::styled(edit_config.curr_edit().to_string(), Style::default()),
-                Span::styled(" ", Style::default().bg(Color::White)),
+                Span::styled(" ", app.theme.cursor),
             ]
         } else {
             vec![Span::from(value)]
@@ -49,13 +49,9 @@ pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
             .centered()
             .block(Block::default().borders(Borders::ALL).title(config))
             .style(if i == highlighted_entry && edit_config.is_editing() {
-                Style::default()
-                    .fg(Color::LightYellow)
-                    .add_modifier(Modifier::BOLD)
+                app.theme.editing.add_modifier(Modifier::BOLD)
             } else if i == highlighted_entry {
-                Style::default()
-                    .fg(Color::DarkGray)
-                    .add_modifier(Modifier::BOLD)
+                app.theme.muted.add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             });
@@ -64,25 +60,30 @@ pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
     }
 }
 
-pub fn mode_footer_text(app: &App) -> Vec<Span> {
+pub fn mode_footer_text(app: &App) -> Vec<Span<'_>> {
     let edit_config_state = app.edit_config.as_ref().unwrap();
     vec![if edit_config_state.is_editing() {
-        Span::styled("Editing...", Style::default().fg(Color::LightYellow))
+        Span::styled("Editing...", app.theme.editing)
     } else {
-        Span::styled("Edit Configurations", Style::default().fg(Color::Green))
+        Span::styled("Edit Configurations", app.theme.title)
     }]
 }
 
-pub fn keys_hint(app: &App) -> Span {
+pub fn keys_hint(app: &App) -> Span<'_> {
     let edit_config_state = app.edit_config.as_ref().unwrap();
     match edit_config_state.is_editing() {
-        true => Span::styled(
-            "(ESC) cancel | (ENTER) confirm",
-            Style::default().fg(Color::Red),
-        ),
+        true => Span::styled("(ESC) cancel | (ENTER) confirm", app.theme.hint),
         false => Span::styled(
-            "(ESC / q) exit | (ENTER) edit | (jk| 🡇 🡅 ) down up",
-            Style::default().fg(Color::Red),
+            app.keymap.keys_hint(
+                &CurrentScreen::EditConfig,
+                &[
+                    (Action::Exit, "exit"),
+                    (Action::Edit, "edit"),
+                    (Action::Down, "down"),
+                    (Action::Up, "up"),
+                ],
+            ),
+            app.theme.hint,
         ),
     }
 }
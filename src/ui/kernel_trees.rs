@@ -0,0 +1,149 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::app::{
+    keymap::Action,
+    screens::{
+        kernel_trees::{KernelTreeForm, KernelTreeFormKind},
+        CurrentScreen,
+    },
+    theme::Theme,
+    App,
+};
+
+pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
+    let kernel_trees = app.kernel_trees.as_ref().unwrap();
+
+    let list_chunk = match &kernel_trees.form {
+        Some(form) => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(1),
+                    Constraint::Length(3 * form.fields.len() as u16),
+                ])
+                .split(chunk);
+            render_form(f, form, &app.theme, chunks[1]);
+            chunks[0]
+        }
+        None => chunk,
+    };
+
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Double)
+        .style(Style::default());
+
+    if kernel_trees.kernel_trees.is_empty() {
+        let message = Paragraph::new(Line::styled(
+            "No kernel trees registered, press (a) to add one",
+            app.theme.muted,
+        ))
+        .block(list_block)
+        .centered();
+        f.render_widget(message, list_chunk);
+        return;
+    }
+
+    let list_items: Vec<ListItem> = kernel_trees
+        .kernel_trees
+        .iter()
+        .map(|entry| {
+            let id = format!("{:width$.width$}", entry.id, width = 20);
+            let branch = format!("{:width$.width$}", entry.kernel_tree.branch(), width = 25);
+            let mut line = vec![
+                Span::styled(if entry.is_target { "* " } else { "  " }, app.theme.success),
+                Span::styled(
+                    format!("{} | {} | {} ", id, branch, entry.kernel_tree.path()),
+                    app.theme.item,
+                ),
+            ];
+            line.push(match &entry.validation {
+                Ok(_) => Span::styled("✓", app.theme.success),
+                Err(msg) => Span::styled(format!("✗ {msg}"), app.theme.error),
+            });
+            ListItem::new(Line::from(line))
+        })
+        .collect();
+
+    let list = List::new(list_items)
+        .block(list_block)
+        .highlight_style(app.theme.selected)
+        .highlight_symbol(">")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(kernel_trees.highlighted));
+
+    f.render_stateful_widget(list, list_chunk, &mut list_state);
+}
+
+fn render_form(f: &mut Frame, form: &KernelTreeForm, theme: &Theme, chunk: Rect) {
+    let field_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(3); form.fields.len()])
+        .split(chunk);
+
+    for (i, (name, value)) in form.fields.iter().enumerate() {
+        let mut line = vec![Span::from(value.clone())];
+        if i == form.focused {
+            line.push(Span::styled(" ", theme.cursor));
+        }
+        let field = Paragraph::new(Line::from(line))
+            .block(Block::default().borders(Borders::ALL).title(*name))
+            .style(if i == form.focused {
+                theme.editing.add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            });
+        f.render_widget(field, field_chunks[i]);
+    }
+}
+
+pub fn mode_footer_text(app: &App) -> Vec<Span<'_>> {
+    let kernel_trees = app.kernel_trees.as_ref().unwrap();
+    let mut text = vec![match kernel_trees.form.as_ref().map(|form| &form.kind) {
+        Some(KernelTreeFormKind::Add) => Span::styled("Adding kernel tree...", app.theme.editing),
+        Some(KernelTreeFormKind::Rename(id)) => {
+            Span::styled(format!("Renaming '{id}'..."), app.theme.editing)
+        }
+        None => Span::styled("Kernel Trees", app.theme.title),
+    }];
+    if let Some(error) = kernel_trees
+        .form
+        .as_ref()
+        .and_then(|form| form.error.as_ref())
+    {
+        text.push(Span::styled(format!(" ✗ {error}"), app.theme.error));
+    }
+    text
+}
+
+pub fn keys_hint(app: &App) -> Span<'_> {
+    let kernel_trees = app.kernel_trees.as_ref().unwrap();
+    match kernel_trees.form {
+        Some(_) => Span::styled(
+            "(ESC) cancel | (TAB) next field | (ENTER) confirm",
+            app.theme.hint,
+        ),
+        None => Span::styled(
+            app.keymap.keys_hint(
+                &CurrentScreen::KernelTrees,
+                &[
+                    (Action::Exit, "to return"),
+                    (Action::SetTarget, "set target"),
+                    (Action::Add, "add"),
+                    (Action::Rename, "rename"),
+                    (Action::Remove, "remove"),
+                    (Action::Help, "help"),
+                ],
+            ),
+            app.theme.hint,
+        ),
+    }
+}
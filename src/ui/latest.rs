@@ -1,86 +1,159 @@
-use crate::app::App;
-use patch_hub::lore::patch::Patch;
+use super::{build_badge, fit_to_width, list_mouse_area, title_and_author_widths};
+use crate::app::{
+    keymap::{Action, Keymap},
+    patchset_filter::PatchsetSort,
+    screens::CurrentScreen,
+    theme::Theme,
+    App,
+};
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, HighlightSpacing, List, ListItem, ListState},
     Frame,
 };
 
-pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
-    let page_number = app.latest_patchsets.as_ref().unwrap().page_number();
-    let patchset_index = app.latest_patchsets.as_ref().unwrap().patchset_index();
+/// Renders the listed patchsets of the current page and returns the area of
+/// their rows (see [super::FrameAreas::list])
+pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) -> (Rect, Vec<usize>) {
+    let latest_patchsets = app.latest_patchsets.as_ref().unwrap();
+    let patchset_index = *latest_patchsets.patchset_index();
     let mut list_items = Vec::<ListItem>::new();
+    let mut selected_item = None;
+    let mut items = Vec::new();
+    // Index, version, number of patches, separators and build badge
+    let (title_width, author_width) = title_and_author_widths(chunk.width, 30);
 
-    let patch_feed_page: Vec<&Patch> = app
-        .latest_patchsets
-        .as_ref()
-        .unwrap()
-        .get_current_patch_feed_page()
-        .unwrap();
-
-    let mut index: usize = (page_number - 1) * app.config.page_size();
-    for patch in patch_feed_page {
-        let patch_title = format!("{:width$}", patch.title(), width = 70);
-        let patch_title = format!("{:.width$}", patch_title, width = 70);
-        let patch_author = format!("{:width$}", patch.author().name, width = 30);
-        let patch_author = format!("{:.width$}", patch_author, width = 30);
+    for index in latest_patchsets.listed_patchsets_in_current_page() {
+        let message_id = &latest_patchsets.lore_session().representative_patches_ids()[index];
+        let Some(patch) = latest_patchsets
+            .lore_session()
+            .get_processed_patch(message_id)
+        else {
+            continue;
+        };
+        if Some(index) == patchset_index {
+            selected_item = Some(list_items.len());
+        }
+        items.push(index);
+        let patch_title = if patch.is_rfc() {
+            format!("[RFC] {}", patch.title())
+        } else {
+            patch.title().to_string()
+        };
+        let patch_title = fit_to_width(&patch_title, title_width);
+        let patch_author = fit_to_width(&patch.author().name, author_width);
+        let mut build = build_badge(
+            app.build_statuses.get(&patch.message_id().href).copied(),
+            &app.theme,
+        );
+        build.content = format!(" | {:7}", build.content).into();
         list_items.push(ListItem::new(
-            Line::from(Span::styled(
-                format!(
-                    "{:03}. V{:02} | #{:02} | {} | {}",
-                    index,
-                    patch.version(),
-                    patch.total_in_series(),
-                    patch_title,
-                    patch_author
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{:03}. V{:02} | #{:02} | {} | {}",
+                        index,
+                        patch.version(),
+                        patch.total_in_series(),
+                        patch_title,
+                        patch_author
+                    ),
+                    app.theme.item,
                 ),
-                Style::default().fg(Color::Yellow),
-            ))
+                build,
+            ])
             .centered(),
         ));
-        index += 1;
     }
 
-    let list_block = Block::default()
+    let mut list_block = Block::default()
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Double)
         .style(Style::default());
+    if let Some(prompt) = latest_patchsets.filter_prompt() {
+        let mut spans = vec![
+            Span::styled(format!(" Filter: {}", prompt.input), app.theme.accent),
+            Span::styled("\u{2588} ", app.theme.accent),
+        ];
+        if let Some(error) = &prompt.error {
+            let error = error.lines().last().unwrap_or_default().to_string();
+            spans.push(Span::styled(format!("{error} "), app.theme.error));
+        }
+        list_block = list_block.title_bottom(Line::from(spans).left_aligned());
+    }
 
     let list = List::new(list_items)
         .block(list_block)
-        .highlight_style(
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .add_modifier(Modifier::REVERSED)
-                .fg(Color::Cyan),
-        )
+        .highlight_style(app.theme.selected)
         .highlight_symbol(">")
         .highlight_spacing(HighlightSpacing::Always);
 
     let mut list_state = ListState::default();
-    list_state.select(Some(
-        patchset_index - (page_number - 1) * app.config.page_size(),
-    ));
+    list_state.select(selected_item);
 
     f.render_stateful_widget(list, chunk, &mut list_state);
+
+    list_mouse_area(chunk, items, &list_state)
 }
 
-pub fn mode_footer_text(app: &App) -> Vec<Span> {
-    vec![Span::styled(
+pub fn mode_footer_text(app: &App) -> Vec<Span<'_>> {
+    let latest_patchsets = app.latest_patchsets.as_ref().unwrap();
+    let mut footer = vec![Span::styled(
         format!(
             "Latest Patchsets from {} (page {})",
-            &app.latest_patchsets.as_ref().unwrap().target_list(),
-            &app.latest_patchsets.as_ref().unwrap().page_number()
+            latest_patchsets.target_list(),
+            latest_patchsets.page_number()
         ),
-        Style::default().fg(Color::Green),
-    )]
+        app.theme.title,
+    )];
+    let search_query = latest_patchsets.lore_session().search_query();
+    if !search_query.is_empty() {
+        footer.push(Span::styled(
+            format!(" [search: {search_query}]"),
+            app.theme.accent,
+        ));
+    }
+    if !latest_patchsets.filter_text().is_empty() {
+        footer.push(Span::styled(
+            format!(" [filter: {}]", latest_patchsets.filter_text()),
+            app.theme.accent,
+        ));
+    }
+    match latest_patchsets.sort() {
+        PatchsetSort::Newest => {}
+        // Only the review counts of the patchsets opened in details are known
+        PatchsetSort::MostReviewed => footer.push(Span::styled(
+            format!(
+                " [sort: most-reviewed, {} opened]",
+                latest_patchsets.review_counts().len()
+            ),
+            app.theme.accent,
+        )),
+        sort => footer.push(Span::styled(format!(" [sort: {sort}]"), app.theme.accent)),
+    }
+    if latest_patchsets.my_subsystems_filter() {
+        footer.push(Span::styled(" [my subsystems]", app.theme.accent));
+    }
+    footer
 }
 
-pub fn keys_hint() -> Span<'static> {
+pub fn keys_hint(keymap: &Keymap, theme: &Theme) -> Span<'static> {
     Span::styled(
-        "(ESC / q) to return | (ENTER) to select | ( h / 🡄 ) previous page | ( l / 🡆 ) next page | (?) help",
-        Style::default().fg(Color::Red),
+        keymap.keys_hint(
+            &CurrentScreen::LatestPatchsets,
+            &[
+                (Action::Exit, "to return"),
+                (Action::Open, "to select"),
+                (Action::PreviousPage, "previous page"),
+                (Action::NextPage, "next page"),
+                (Action::ToggleMySubsystems, "my subsystems"),
+                (Action::Filter, "filter"),
+                (Action::CycleSort, "sort"),
+                (Action::Help, "help"),
+            ],
+        ),
+        theme.hint,
     )
 }
@@ -2,13 +2,13 @@ use std::fmt::Display;
 
 use ratatui::{
     prelude::Backend,
-    style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame, Terminal,
 };
 
 use super::centered_rect;
+use crate::app::theme::Theme;
 
 const SPINNER: [char; 8] = [
     '\u{1F311}',
@@ -25,10 +25,14 @@ static mut SPINNER_TICK: usize = 1;
 const LOADING_AREA_EXTRA_FACTOR_WIDTH: f32 = 1.3;
 const LOADING_AREA_EXTRA_LINES: u16 = 2;
 
-/// This function renders a loading screen taking a `terminal` instance and a
-/// `title`.
-pub fn render<B: Backend>(mut terminal: Terminal<B>, title: impl Display) -> Terminal<B> {
-    let _ = terminal.draw(|f| draw_loading_screen(f, title));
+/// This function renders a loading screen taking a `terminal` instance, the
+/// `theme` to draw it with and a `title`.
+pub fn render<B: Backend>(
+    mut terminal: Terminal<B>,
+    theme: &Theme,
+    title: impl Display,
+) -> Terminal<B> {
+    let _ = terminal.draw(|f| draw_loading_screen(f, theme, title));
     terminal
 }
 
@@ -43,7 +47,7 @@ fn spinner() -> char {
 
 /// The actual implementation of the loading screen rendering. Currently the
 /// loading notification is static.
-fn draw_loading_screen(f: &mut Frame, title: impl Display) {
+fn draw_loading_screen(f: &mut Frame, theme: &Theme, title: impl Display) {
     let frame_area = f.area();
     let loading_text = format!("{} {}", title, spinner());
 
@@ -52,13 +56,10 @@ fn draw_loading_screen(f: &mut Frame, title: impl Display) {
 
     let loading_area = centered_rect(width_pct, height_pct, frame_area);
 
-    let loading_par = Paragraph::new(Line::from(Span::styled(
-        loading_text,
-        Style::default().fg(Color::Green),
-    )))
-    .block(Block::default().borders(Borders::ALL))
-    .centered()
-    .wrap(Wrap { trim: true });
+    let loading_par = Paragraph::new(Line::from(Span::styled(loading_text, theme.title)))
+        .block(Block::default().borders(Borders::ALL))
+        .centered()
+        .wrap(Wrap { trim: true });
 
     f.render_widget(loading_par, loading_area);
 }
@@ -0,0 +1,74 @@
+use std::{
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use ratatui::{
+    prelude::Backend,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame, Terminal,
+};
+
+use crate::app::theme::Theme;
+
+/// Runs `f` while another thread renders the lines appended to `log` (like the
+/// output of a command run by `f`) in a full screen titled `title`, so the
+/// user can follow long operations as they happen.
+///
+/// Returns the value returned by `f`.
+pub fn stream_log<B, T>(
+    terminal: &mut Terminal<B>,
+    theme: &Theme,
+    title: impl Display + Sync,
+    log: &Mutex<Vec<String>>,
+    f: impl FnOnce() -> T,
+) -> T
+where
+    B: Backend + Send,
+{
+    let running = AtomicBool::new(true);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            while running.load(Ordering::Relaxed) {
+                let _ = terminal.draw(|f| draw_log_screen(f, theme, &title, log));
+                thread::sleep(Duration::from_millis(200));
+            }
+        });
+
+        let result = f();
+        running.store(false, Ordering::Relaxed);
+        result
+    })
+}
+
+/// Renders the last lines of `log` that fit the screen.
+fn draw_log_screen(f: &mut Frame, theme: &Theme, title: impl Display, log: &Mutex<Vec<String>>) {
+    let area = f.area();
+    let visible_lines = area.height.saturating_sub(2) as usize;
+
+    let lines: Vec<Line> = match log.lock() {
+        Ok(log) => log
+            .iter()
+            .skip(log.len().saturating_sub(visible_lines))
+            .map(|line| Line::styled(line.clone(), theme.text))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let log_par = Paragraph::new(lines).block(
+        Block::default()
+            .title(Line::styled(format!(" {title} "), theme.title))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double),
+    );
+
+    f.render_widget(Clear, area);
+    f.render_widget(log_par, area);
+}
@@ -1,31 +1,49 @@
+use chrono::Utc;
 use ratatui::{
     layout::Rect,
-    style::{Color, Modifier, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, HighlightSpacing, List, ListItem, ListState},
     Frame,
 };
 
-use crate::app::App;
+use super::list_mouse_area;
+use crate::app::{
+    keymap::{Action, Keymap},
+    screens::CurrentScreen,
+    theme::Theme,
+    App,
+};
 
-pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
-    let highlighted_list_index = app.mailing_list_selection.highlighted_list_index;
+/// Renders the mailing lists matching the typed name, with favorites marked
+/// and the activity of the lists fetched before, and returns the area of their
+/// rows (see [super::FrameAreas::list])
+pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) -> (Rect, Vec<usize>) {
+    let mailing_list_selection = &app.mailing_list_selection;
+    let highlighted_list_index = mailing_list_selection.highlighted_list_index;
     let mut list_items = Vec::<ListItem>::new();
+    let now = Utc::now();
 
-    for mailing_list in &app.mailing_list_selection.possible_mailing_lists {
-        list_items.push(ListItem::new(
-            Line::from(vec![
-                Span::styled(
-                    mailing_list.name().to_string(),
-                    Style::default().fg(Color::Magenta),
-                ),
-                Span::styled(
-                    format!(" - {}", mailing_list.description()),
-                    Style::default().fg(Color::White),
-                ),
-            ])
-            .centered(),
-        ))
+    for mailing_list in &mailing_list_selection.possible_mailing_lists {
+        let mut spans = vec![
+            if mailing_list_selection.is_favorite(mailing_list.name()) {
+                Span::styled("★ ", app.theme.accent)
+            } else {
+                Span::raw("  ")
+            },
+            Span::styled(mailing_list.name().to_string(), app.theme.emphasis),
+            Span::styled(format!(" - {}", mailing_list.description()), app.theme.text),
+        ];
+        if let Some(activity) = mailing_list_selection
+            .list_activity
+            .get(mailing_list.name())
+        {
+            spans.push(Span::styled(
+                format!(" [{}]", activity.summary(now)),
+                app.theme.muted,
+            ));
+        }
+        list_items.push(ListItem::new(Line::from(spans).centered()))
     }
 
     let list_block = Block::default()
@@ -35,12 +53,7 @@ pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
 
     let list = List::new(list_items)
         .block(list_block)
-        .highlight_style(
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .add_modifier(Modifier::REVERSED)
-                .fg(Color::Cyan),
-        )
+        .highlight_style(app.theme.selected)
         .highlight_symbol(">")
         .highlight_spacing(HighlightSpacing::Always);
 
@@ -48,51 +61,39 @@ pub fn render_main(f: &mut Frame, app: &App, chunk: Rect) {
     list_state.select(Some(highlighted_list_index));
 
     f.render_stateful_widget(list, chunk, &mut list_state);
-}
 
-pub fn mode_footer_text(app: &App) -> Vec<Span> {
-    let mut text_area = Span::default();
+    let items = (0..mailing_list_selection.possible_mailing_lists.len()).collect();
+    list_mouse_area(chunk, items, &list_state)
+}
 
-    if app.mailing_list_selection.target_list.is_empty() {
-        text_area = Span::styled("type the target list", Style::default().fg(Color::DarkGray))
+pub fn mode_footer_text(app: &App) -> Vec<Span<'_>> {
+    let text_area = if app.mailing_list_selection.target_list.is_empty() {
+        Span::styled("type the target list", app.theme.muted)
     } else {
-        for mailing_list in &app.mailing_list_selection.mailing_lists {
-            if mailing_list
-                .name()
-                .eq(&app.mailing_list_selection.target_list)
-            {
-                text_area = Span::styled(
-                    &app.mailing_list_selection.target_list,
-                    Style::default().fg(Color::Green),
-                );
-                break;
-            } else if mailing_list
-                .name()
-                .starts_with(&app.mailing_list_selection.target_list)
-            {
-                text_area = Span::styled(
-                    &app.mailing_list_selection.target_list,
-                    Style::default().fg(Color::LightCyan),
-                );
-            }
-        }
-        if text_area.content.is_empty() {
-            text_area = Span::styled(
-                &app.mailing_list_selection.target_list,
-                Style::default().fg(Color::Red),
-            );
-        }
-    }
+        let target_list = &app.mailing_list_selection.target_list;
+        let possible_mailing_lists = &app.mailing_list_selection.possible_mailing_lists;
+        // An exact match is always listed first
+        let style = match possible_mailing_lists.first() {
+            Some(mailing_list) if mailing_list.name() == target_list => app.theme.success,
+            Some(_) => app.theme.label,
+            None => app.theme.error,
+        };
+        Span::styled(target_list, style)
+    };
 
-    vec![
-        Span::styled("Target List: ", Style::default().fg(Color::Green)),
-        text_area,
-    ]
+    vec![Span::styled("Target List: ", app.theme.title), text_area]
 }
 
-pub fn keys_hint() -> Span<'static> {
+pub fn keys_hint(keymap: &Keymap, theme: &Theme) -> Span<'static> {
     Span::styled(
-        "(ESC) to quit | (ENTER) to confirm | (?) help",
-        Style::default().fg(Color::Red),
+        keymap.keys_hint(
+            &CurrentScreen::MailingListSelection,
+            &[
+                (Action::Exit, "to quit"),
+                (Action::Open, "to confirm"),
+                (Action::Help, "help"),
+            ],
+        ),
+        theme.hint,
     )
 }
@@ -1,20 +1,53 @@
-use super::{bookmarked, details_actions, edit_config, latest, mail_list};
+use super::{
+    apply_history, bookmarked, details_actions, edit_config, fit_to_width, kernel_trees, latest,
+    mail_list, recent, worktrees,
+};
 use crate::app::{self, App};
 use app::screens::CurrentScreen;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     text::Line,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Tabs},
     Frame,
 };
 
+/// Maximum width of the titles of tabs, which are truncated beyond it
+const MAX_TAB_TITLE_WIDTH: usize = 24;
+
+/// Returns the height of the navigation bar, which has a line of tabs above
+/// the footers if there is more than one tab
+pub fn height(app: &App) -> u16 {
+    if app.tabs.len() > 1 {
+        4
+    } else {
+        3
+    }
+}
+
 pub fn render(f: &mut Frame, app: &App, chunk: Rect) {
+    let chunk = if app.tabs.len() > 1 {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(3)])
+            .split(chunk);
+        render_tabs(f, app, chunks[0]);
+        chunks[1]
+    } else {
+        chunk
+    };
+
     let mode_footer_text = match app.current_screen {
         CurrentScreen::MailingListSelection => mail_list::mode_footer_text(app),
-        CurrentScreen::BookmarkedPatchsets => bookmarked::mode_footer_text(),
+        CurrentScreen::BookmarkedPatchsets => bookmarked::mode_footer_text(&app.theme),
+        CurrentScreen::RecentPatchsets => recent::mode_footer_text(&app.theme),
         CurrentScreen::LatestPatchsets => latest::mode_footer_text(app),
-        CurrentScreen::PatchsetDetails => details_actions::mode_footer_text(),
+        CurrentScreen::PatchsetDetails => details_actions::mode_footer_text(&app.theme),
         CurrentScreen::EditConfig => edit_config::mode_footer_text(app),
+        CurrentScreen::Worktrees => worktrees::mode_footer_text(&app.theme),
+        CurrentScreen::KernelTrees => kernel_trees::mode_footer_text(app),
+        CurrentScreen::ApplyHistory => {
+            apply_history::mode_footer_text(app.apply_history.as_ref().unwrap(), &app.theme)
+        }
     };
     let mode_footer = Paragraph::new(Line::from(mode_footer_text))
         .block(Block::default().borders(Borders::ALL))
@@ -22,11 +55,15 @@ pub fn render(f: &mut Frame, app: &App, chunk: Rect) {
 
     let current_keys_hint = {
         match app.current_screen {
-            CurrentScreen::MailingListSelection => mail_list::keys_hint(),
-            CurrentScreen::BookmarkedPatchsets => bookmarked::keys_hint(),
-            CurrentScreen::LatestPatchsets => latest::keys_hint(),
-            CurrentScreen::PatchsetDetails => details_actions::keys_hint(),
+            CurrentScreen::MailingListSelection => mail_list::keys_hint(&app.keymap, &app.theme),
+            CurrentScreen::BookmarkedPatchsets => bookmarked::keys_hint(&app.keymap, &app.theme),
+            CurrentScreen::RecentPatchsets => recent::keys_hint(&app.keymap, &app.theme),
+            CurrentScreen::LatestPatchsets => latest::keys_hint(&app.keymap, &app.theme),
+            CurrentScreen::PatchsetDetails => details_actions::keys_hint(&app.keymap, &app.theme),
             CurrentScreen::EditConfig => edit_config::keys_hint(app),
+            CurrentScreen::Worktrees => worktrees::keys_hint(&app.keymap, &app.theme),
+            CurrentScreen::KernelTrees => kernel_trees::keys_hint(app),
+            CurrentScreen::ApplyHistory => apply_history::keys_hint(&app.keymap, &app.theme),
         }
     };
 
@@ -42,3 +79,23 @@ pub fn render(f: &mut Frame, app: &App, chunk: Rect) {
     f.render_widget(mode_footer, footer_chunks[0]);
     f.render_widget(keys_hint_footer, footer_chunks[1]);
 }
+
+/// Renders the numbered titles of the tabs, highlighting the active one
+fn render_tabs(f: &mut Frame, app: &App, chunk: Rect) {
+    let titles = app.tab_titles().into_iter().enumerate().map(|(i, title)| {
+        let title = if title.chars().count() > MAX_TAB_TITLE_WIDTH {
+            fit_to_width(&title, MAX_TAB_TITLE_WIDTH)
+        } else {
+            title
+        };
+        Line::from(format!("{}: {title}", i + 1))
+    });
+
+    let tabs = Tabs::new(titles)
+        .select(app.tabs.active())
+        .style(app.theme.label)
+        .highlight_style(app.theme.selected)
+        .divider("|");
+
+    f.render_widget(tabs, chunk);
+}
@@ -2,8 +2,18 @@ use std::fmt::Debug;
 
 use ratatui::{crossterm::event::KeyEvent, layout::Rect, Frame};
 
+use crate::app::{command_line::Command, theme::Theme};
+
+use confirm::Confirmation;
+
+pub mod apply_base;
+pub mod checkpatch;
+pub mod command_line;
+pub mod confirm;
+pub mod git_am_conflict;
 pub mod help;
 pub mod info_popup;
+pub mod log_viewer;
 pub mod review_trailers;
 
 /// A trait that represents a popup that can be rendered on top of a screen
@@ -14,13 +24,32 @@ pub trait PopUp: Debug {
     /// Those dimensions are used to create the `chunk` used in the render function
     fn dimensions(&self) -> (u16, u16);
 
-    /// Renders the popup on the given frame using the given chunk
+    /// Renders the popup on the given frame using the given chunk and theme
     /// This chunk is a centered rectangle with the dimensions returned by `dimensions`
-    fn render(&self, f: &mut Frame, chunk: Rect);
+    fn render(&self, f: &mut Frame, chunk: Rect, theme: &Theme);
 
-    /// Handles the key event for the popup
+    /// Handles the key event for the popup, returning what should happen
+    /// next (see [PopUpOutcome])
     ///
-    /// Is important to notice that except for the 'ESC' key, all other keys are hijacked by the popup
-    /// So the screens handlers won't be called
-    fn handle(&mut self, key: KeyEvent) -> color_eyre::Result<()>;
+    /// Is important to notice that except for the 'ESC' key, which always closes the popup, all
+    /// other keys are hijacked by the popup, so the screens handlers won't be called
+    fn handle(&mut self, key: KeyEvent) -> color_eyre::Result<PopUpOutcome>;
+}
+
+/// What should happen after a popup handles a key
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum PopUpOutcome {
+    /// The popup stays open
+    #[default]
+    Continue,
+    /// The popup is closed
+    Close,
+    /// The popup is closed and the command is run, like when the user chooses
+    /// an entry of the popup
+    Run(Command),
+    /// The popup is closed and the command line typed in it is run and
+    /// recorded in the command history
+    RunCommandLine(String),
+    /// The popup is closed and the operation it asked about is carried out
+    Confirm(Confirmation),
 }
@@ -0,0 +1,143 @@
+use ratatui::{
+    crossterm::event::KeyCode,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use crate::app::{
+    base_tree::{self, BaseTreeChoice},
+    command_line::Command,
+    config::Config,
+    theme::Theme,
+};
+
+use super::{PopUp, PopUpOutcome};
+
+#[derive(Debug)]
+pub struct ApplyBasePopUp {
+    /// Pairs of kernel tree id and branch that can be chosen
+    choices: Vec<(String, String)>,
+    selected: usize,
+    dimensions: (u16, u16),
+}
+
+impl ApplyBasePopUp {
+    /// Generate a pop-up that lists the local branches of every kernel tree
+    /// in `@config`, starting at the `current` choice, if any.
+    ///
+    /// Selecting a branch chooses it (and its kernel tree) to apply the
+    /// patchset, overriding the detected one.
+    pub fn generate_apply_base_popup(
+        config: &Config,
+        current: Option<&BaseTreeChoice>,
+    ) -> Box<dyn PopUp> {
+        let mut kernel_tree_ids: Vec<&String> = config.kernel_trees().into_iter().collect();
+        kernel_tree_ids.sort();
+
+        let choices: Vec<(String, String)> = kernel_tree_ids
+            .into_iter()
+            .filter_map(|id| Some((id, config.get_kernel_tree(id)?)))
+            .flat_map(|(id, kernel_tree)| {
+                base_tree::local_branches(kernel_tree.path())
+                    .into_iter()
+                    .map(move |branch| (id.clone(), branch))
+            })
+            .collect();
+        let selected = current
+            .and_then(|current| {
+                choices.iter().position(|(id, branch)| {
+                    id == current.kernel_tree_id() && branch == current.branch()
+                })
+            })
+            .unwrap_or_default();
+
+        // TODO: Calculate percentage based on the lines and screen size
+        let dimensions = (50, 50);
+
+        Box::new(ApplyBasePopUp {
+            choices,
+            selected,
+            dimensions,
+        })
+    }
+}
+
+impl PopUp for ApplyBasePopUp {
+    fn dimensions(&self) -> (u16, u16) {
+        self.dimensions
+    }
+
+    /// Renders a centered overlaying pop-up with an entry for each branch of
+    /// each kernel tree, highlighting the selected one.
+    fn render(&self, f: &mut ratatui::Frame, chunk: Rect, theme: &Theme) {
+        let mut contents = vec![];
+
+        if self.choices.is_empty() {
+            contents.push(Line::styled("No kernel tree with branches", theme.muted));
+        }
+
+        for (i, (kernel_tree_id, branch)) in self.choices.iter().enumerate() {
+            let mut line = Line::from(vec![
+                Span::styled(format!("{kernel_tree_id} "), theme.label),
+                Span::styled(branch.clone(), theme.text),
+            ]);
+            if i == self.selected {
+                line = line.add_modifier(Modifier::REVERSED);
+            }
+            contents.push(line);
+        }
+
+        let block = Block::default()
+            .title("Apply Base")
+            .title_alignment(Alignment::Center)
+            .title_style(theme.popup_title)
+            .title_bottom(Line::styled(
+                "(ESC / q) Close | (ENTER) Apply on branch",
+                theme.popup_title,
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .style(Style::default());
+
+        // Keep the selected branch visible
+        let height = chunk.height.saturating_sub(2) as usize;
+        let offset = (self.selected + 1).saturating_sub(height) as u16;
+
+        let pop_up = Paragraph::new(contents)
+            .block(block)
+            .alignment(Alignment::Left)
+            .scroll((offset, 0));
+
+        f.render_widget(Clear, chunk);
+        f.render_widget(pop_up, chunk);
+    }
+
+    /// Handles navigation between branches and choosing the selected one.
+    fn handle(
+        &mut self,
+        key: ratatui::crossterm::event::KeyEvent,
+    ) -> color_eyre::Result<PopUpOutcome> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(PopUpOutcome::Close),
+            KeyCode::Up | KeyCode::Char('k') if self.selected > 0 => {
+                self.selected -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.choices.len() => {
+                self.selected += 1;
+            }
+            KeyCode::Enter => {
+                if let Some((kernel_tree_id, branch)) = self.choices.get(self.selected) {
+                    return Ok(PopUpOutcome::Run(Command::Base {
+                        kernel_tree: kernel_tree_id.clone(),
+                        branch: Some(branch.clone()),
+                    }));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(PopUpOutcome::Continue)
+    }
+}
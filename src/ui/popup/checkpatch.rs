@@ -0,0 +1,150 @@
+use patch_hub::lore::checkpatch::{CheckpatchIssue, CheckpatchLevel};
+use ratatui::{
+    crossterm::event::KeyCode,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use crate::app::{command_line::Command, screens::details_actions::DetailsActions, theme::Theme};
+
+use super::{PopUp, PopUpOutcome};
+
+#[derive(Debug)]
+pub struct CheckpatchPopUp {
+    /// Issues of all patches paired with the index of the patch
+    issues: Vec<(usize, CheckpatchIssue)>,
+    /// Number of the first patch in the series (`0` if there is a cover letter)
+    number_offset: usize,
+    selected: usize,
+    dimensions: (u16, u16),
+}
+
+impl CheckpatchPopUp {
+    /// Generate a pop-up that lists the issues reported by `checkpatch.pl` for
+    /// every patch of the patchset in `@details_actions`.
+    ///
+    /// Selecting an issue jumps the preview to the line of the patch the issue
+    /// refers to. This function succeeds regardless if `checkpatch.pl` wasn't
+    /// run or reported no issues.
+    pub fn generate_checkpatch_popup(details_actions: &DetailsActions) -> Box<dyn PopUp> {
+        let issues = details_actions
+            .checkpatch_reports
+            .iter()
+            .flatten()
+            .enumerate()
+            .flat_map(|(i, report)| report.issues().iter().map(move |issue| (i, issue.clone())))
+            .collect();
+        let number_offset = if details_actions.has_cover_letter {
+            0
+        } else {
+            1
+        };
+
+        // TODO: Calculate percentage based on the lines and screen size
+        let dimensions = (70, 50);
+
+        Box::new(CheckpatchPopUp {
+            issues,
+            number_offset,
+            selected: 0,
+            dimensions,
+        })
+    }
+}
+
+impl PopUp for CheckpatchPopUp {
+    fn dimensions(&self) -> (u16, u16) {
+        self.dimensions
+    }
+
+    /// Renders a centered overlaying pop-up with an entry for each issue,
+    /// highlighting the selected one.
+    fn render(&self, f: &mut ratatui::Frame, chunk: Rect, theme: &Theme) {
+        let mut contents = vec![];
+
+        if self.issues.is_empty() {
+            contents.push(Line::styled("No issues found", theme.success));
+        }
+
+        for (i, (patch_index, issue)) in self.issues.iter().enumerate() {
+            let level_style = match issue.level() {
+                CheckpatchLevel::Error => theme.error,
+                CheckpatchLevel::Warning => theme.warning,
+                CheckpatchLevel::Check => theme.label,
+            };
+            let mut line = vec![
+                Span::styled(
+                    format!("Patch {}: ", patch_index + self.number_offset),
+                    theme.text,
+                ),
+                Span::styled(
+                    format!("{}: ", issue.level()),
+                    level_style.add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(issue.message().clone(), theme.text),
+            ];
+            if let Some(location) = issue.location() {
+                line.push(Span::styled(format!(" ({location})"), theme.muted));
+            }
+
+            let mut line = Line::from(line);
+            if i == self.selected {
+                line = line.add_modifier(Modifier::REVERSED);
+            }
+            contents.push(line);
+        }
+
+        let block = Block::default()
+            .title("Checkpatch Issues")
+            .title_alignment(Alignment::Center)
+            .title_style(theme.popup_title)
+            .title_bottom(Line::styled(
+                "(ESC / q) Close | (ENTER) Jump to issue",
+                theme.popup_title,
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .style(Style::default());
+
+        // Keep the selected issue visible
+        let height = chunk.height.saturating_sub(2) as usize;
+        let offset = (self.selected + 1).saturating_sub(height) as u16;
+
+        let pop_up = Paragraph::new(contents)
+            .block(block)
+            .alignment(Alignment::Left)
+            .scroll((offset, 0));
+
+        f.render_widget(Clear, chunk);
+        f.render_widget(pop_up, chunk);
+    }
+
+    /// Handles navigation between issues and jumping to the selected one.
+    fn handle(
+        &mut self,
+        key: ratatui::crossterm::event::KeyEvent,
+    ) -> color_eyre::Result<PopUpOutcome> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(PopUpOutcome::Close),
+            KeyCode::Up | KeyCode::Char('k') if self.selected > 0 => {
+                self.selected -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.issues.len() => {
+                self.selected += 1;
+            }
+            KeyCode::Enter => {
+                if let Some((patch_index, issue)) = self.issues.get(self.selected) {
+                    return Ok(PopUpOutcome::Run(Command::Jump {
+                        patch: patch_index + self.number_offset,
+                        line: issue.line().unwrap_or(1),
+                    }));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(PopUpOutcome::Continue)
+    }
+}
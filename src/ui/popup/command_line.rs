@@ -0,0 +1,209 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use patch_hub::lore::mailing_list::MailingList;
+
+use crate::app::{
+    command_line::{self, COMMANDS},
+    screens::CurrentScreen,
+    theme::Theme,
+};
+
+use super::{PopUp, PopUpOutcome};
+
+/// A popup with an ex-style command line (like `:list netdev`) to run the
+/// commands of [COMMANDS], with completion and history
+#[derive(Debug)]
+pub struct CommandLinePopUp {
+    input: String,
+    /// Screen in which the command line was opened, which restricts the
+    /// available commands
+    screen: CurrentScreen,
+    /// Mailing lists to complete the arguments of `list`
+    mailing_lists: Vec<MailingList>,
+    /// Completions of the input when TAB was first pressed and the index of
+    /// the one in the input, if cycling through them
+    completions: Vec<String>,
+    completion_index: Option<usize>,
+    /// Commands run before, oldest first, and the index of the one in the
+    /// input, if browsing them
+    history: Vec<String>,
+    history_index: Option<usize>,
+}
+
+impl CommandLinePopUp {
+    pub fn generate_command_line_popup(
+        screen: &CurrentScreen,
+        mailing_lists: &[MailingList],
+        history: &[String],
+    ) -> Box<dyn PopUp> {
+        Box::new(CommandLinePopUp {
+            input: String::new(),
+            screen: screen.clone(),
+            mailing_lists: mailing_lists.to_vec(),
+            completions: Vec::new(),
+            completion_index: None,
+            history: history.to_vec(),
+            history_index: None,
+        })
+    }
+
+    /// Replaces the input with the next (or previous, if `backwards`)
+    /// completion. The first time, the completions of the current input are
+    /// computed, and if there is only one, it is used.
+    fn complete(&mut self, backwards: bool) {
+        if self.completion_index.is_none() {
+            self.completions =
+                command_line::complete(&self.input, &self.screen, &self.mailing_lists);
+        }
+        if self.completions.is_empty() {
+            return;
+        }
+
+        let len = self.completions.len();
+        let index = match (self.completion_index, backwards) {
+            (None, false) => 0,
+            (None, true) => len - 1,
+            (Some(index), false) => (index + 1) % len,
+            (Some(index), true) => (index + len - 1) % len,
+        };
+        self.input = self.completions[index].clone();
+        if len == 1 {
+            self.reset_completions();
+            self.input.push(' ');
+        } else {
+            self.completion_index = Some(index);
+        }
+    }
+
+    fn reset_completions(&mut self) {
+        self.completions.clear();
+        self.completion_index = None;
+    }
+
+    fn history_previous(&mut self) {
+        let index = match self.history_index {
+            Some(0) => return,
+            Some(index) => index - 1,
+            None if self.history.is_empty() => return,
+            None => self.history.len() - 1,
+        };
+        self.history_index = Some(index);
+        self.input = self.history[index].clone();
+    }
+
+    fn history_next(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.history_index = Some(index + 1);
+            self.input = self.history[index + 1].clone();
+        } else {
+            self.history_index = None;
+            self.input.clear();
+        }
+    }
+
+    /// Returns the help of the command being typed, if it's a valid one
+    fn usage_hint(&self, theme: &Theme) -> Option<Line<'static>> {
+        let name = self.input.split_whitespace().next()?;
+        let name = if name == "q" { "quit" } else { name };
+        let spec = COMMANDS
+            .iter()
+            .find(|spec| spec.name == name && spec.is_available_in(&self.screen))?;
+        Some(Line::from(vec![
+            Span::styled(spec.usage, theme.label),
+            Span::styled(format!(" — {}", spec.help), theme.muted),
+        ]))
+    }
+}
+
+impl PopUp for CommandLinePopUp {
+    fn dimensions(&self) -> (u16, u16) {
+        (60, 40)
+    }
+
+    fn render(&self, f: &mut ratatui::Frame, chunk: Rect, theme: &Theme) {
+        let title_style = theme.popup_title;
+        let block = Block::default()
+            .title("Command")
+            .title_alignment(Alignment::Center)
+            .title_style(title_style)
+            .title_bottom(Line::styled(
+                "(TAB) complete | (🡅 / 🡇) history | (ENTER) run | (ESC) cancel",
+                title_style,
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .style(Style::default());
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled(":", theme.accent),
+            Span::raw(self.input.clone()),
+            Span::styled("█", theme.accent),
+        ])];
+        if let Some(usage_hint) = self.usage_hint(theme) {
+            lines.push(usage_hint);
+        }
+        if !self.completions.is_empty() {
+            lines.push(Line::raw(""));
+        }
+        for (i, completion) in self.completions.iter().enumerate() {
+            let style = if Some(i) == self.completion_index {
+                theme.selected
+            } else {
+                theme.text
+            };
+            lines.push(Line::styled(completion.clone(), style));
+        }
+
+        // Keeps the highlighted completion visible
+        let height = chunk.height.saturating_sub(2) as usize;
+        let offset = self
+            .completion_index
+            .map_or(0, |index| (index + 3).saturating_sub(height));
+
+        let pop_up = Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Left)
+            .scroll((offset as u16, 0));
+
+        f.render_widget(Clear, chunk);
+        f.render_widget(pop_up, chunk);
+    }
+
+    /// Handles editing the command line, which takes every character typed
+    /// (so 'q' doesn't close the popup), and running it.
+    fn handle(&mut self, key: KeyEvent) -> color_eyre::Result<PopUpOutcome> {
+        match key.code {
+            KeyCode::Tab => {
+                self.complete(false);
+                return Ok(PopUpOutcome::Continue);
+            }
+            KeyCode::BackTab => {
+                self.complete(true);
+                return Ok(PopUpOutcome::Continue);
+            }
+            KeyCode::Up => self.history_previous(),
+            KeyCode::Down => self.history_next(),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(ch) => {
+                self.input.push(ch);
+            }
+            KeyCode::Enter => {
+                return Ok(PopUpOutcome::RunCommandLine(self.input.clone()));
+            }
+            _ => {}
+        }
+        self.reset_completions();
+        Ok(PopUpOutcome::Continue)
+    }
+}
@@ -0,0 +1,107 @@
+use ratatui::{
+    crossterm::event::KeyCode,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::theme::Theme;
+
+use super::{PopUp, PopUpOutcome};
+
+/// An operation that is only carried out once confirmed in a [ConfirmPopUp]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Confirmation {
+    /// Closing the active tab, discarding its patchset details
+    CloseTab,
+    /// Deleting the branches (and worktrees) chosen in the apply history,
+    /// forcing it or not
+    DeleteBranches { force: bool },
+    /// Removing the worktree selected in the worktrees screen, forcing it or
+    /// not
+    RemoveWorktree { force: bool },
+    /// Removing the kernel tree with the given name from the config
+    RemoveKernelTree(String),
+}
+
+#[derive(Debug)]
+pub struct ConfirmPopUp {
+    title: String,
+    message: String,
+    confirmation: Confirmation,
+    offset: u16,
+    max_offset: u16,
+}
+
+impl ConfirmPopUp {
+    /// Generate a pop-up asking to confirm `confirmation`, described by
+    /// `message`.
+    pub fn generate_confirm_popup(
+        title: &str,
+        message: &str,
+        confirmation: Confirmation,
+    ) -> Box<dyn PopUp> {
+        Box::new(ConfirmPopUp {
+            title: title.to_string(),
+            message: message.to_string(),
+            confirmation,
+            offset: 0,
+            max_offset: message.lines().count() as u16,
+        })
+    }
+}
+
+impl PopUp for ConfirmPopUp {
+    fn dimensions(&self) -> (u16, u16) {
+        (50, 40)
+    }
+
+    /// Renders a centered overlaying pop-up with the message and the keys to
+    /// confirm or not.
+    fn render(&self, f: &mut ratatui::Frame, chunk: Rect, theme: &Theme) {
+        let title_style = theme.popup_title;
+        let block = Block::default()
+            .title(self.title.clone())
+            .title_alignment(Alignment::Center)
+            .title_style(title_style)
+            .title_bottom(Line::styled("(y) yes | (ESC / n) no", title_style))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .style(Style::default());
+
+        let lines: Vec<Line> = self
+            .message
+            .lines()
+            .map(|line| Line::styled(line.to_string(), theme.text))
+            .collect();
+        let pop_up = Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: false })
+            .scroll((self.offset, 0));
+
+        f.render_widget(Clear, chunk);
+        f.render_widget(pop_up, chunk);
+    }
+
+    /// Handles scrolling the message and confirming or not.
+    fn handle(
+        &mut self,
+        key: ratatui::crossterm::event::KeyEvent,
+    ) -> color_eyre::Result<PopUpOutcome> {
+        match key.code {
+            KeyCode::Char('y') => return Ok(PopUpOutcome::Confirm(self.confirmation.clone())),
+            KeyCode::Char('n') | KeyCode::Char('q') => return Ok(PopUpOutcome::Close),
+            KeyCode::Up | KeyCode::Char('k') if self.offset > 0 => {
+                self.offset -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.offset < self.max_offset => {
+                self.offset += 1;
+            }
+            _ => {}
+        }
+
+        Ok(PopUpOutcome::Continue)
+    }
+}
@@ -0,0 +1,113 @@
+use ratatui::{
+    crossterm::event::KeyCode,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{
+    command_line::Command,
+    git_am::{GitAmConflict, GitAmResolution},
+    theme::Theme,
+};
+
+use super::{PopUp, PopUpOutcome};
+
+#[derive(Debug)]
+pub struct GitAmConflictPopUp {
+    repo_path: String,
+    output: String,
+    offset: u16,
+    max_offset: u16,
+}
+
+impl GitAmConflictPopUp {
+    /// Generate a pop-up with the output of a `git am` stopped by conflicts
+    /// that lets the user choose how to proceed.
+    pub fn generate_git_am_conflict_popup(conflict: &GitAmConflict) -> Box<dyn PopUp> {
+        Box::new(GitAmConflictPopUp {
+            repo_path: conflict.repo_path().to_string(),
+            output: conflict.output().to_string(),
+            offset: 0,
+            max_offset: conflict.output().lines().count() as u16,
+        })
+    }
+}
+
+impl PopUp for GitAmConflictPopUp {
+    fn dimensions(&self) -> (u16, u16) {
+        (70, 60)
+    }
+
+    /// Renders a centered overlaying pop-up with the output of `git am` and
+    /// the ways to proceed.
+    fn render(&self, f: &mut ratatui::Frame, chunk: Rect, theme: &Theme) {
+        let title_style = theme.popup_title;
+        let block = Block::default()
+            .title("Patchset Apply Conflict")
+            .title_alignment(Alignment::Center)
+            .title_style(title_style)
+            .title_bottom(Line::styled(
+                "(r) resolve in a shell | (c) continue | (s) skip | (a) abort | (ESC / q) decide later",
+                title_style,
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .style(Style::default());
+
+        let mut lines = vec![
+            Line::styled(
+                format!(
+                    "`git am --3way` stopped by conflicts in '{}'",
+                    self.repo_path
+                ),
+                theme.warning,
+            ),
+            Line::default(),
+        ];
+        lines.extend(
+            self.output
+                .lines()
+                .map(|line| Line::styled(line.to_string(), theme.text)),
+        );
+
+        let pop_up = Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Left)
+            .wrap(Wrap { trim: false })
+            .scroll((self.offset, 0));
+
+        f.render_widget(Clear, chunk);
+        f.render_widget(pop_up, chunk);
+    }
+
+    /// Handles scrolling the output and choosing how to proceed.
+    fn handle(
+        &mut self,
+        key: ratatui::crossterm::event::KeyEvent,
+    ) -> color_eyre::Result<PopUpOutcome> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(PopUpOutcome::Close),
+            KeyCode::Up | KeyCode::Char('k') if self.offset > 0 => {
+                self.offset -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.offset < self.max_offset => {
+                self.offset += 1;
+            }
+            KeyCode::Char('r') => {
+                return Ok(PopUpOutcome::Run(Command::Am(GitAmResolution::Resolve)))
+            }
+            KeyCode::Char('c') => {
+                return Ok(PopUpOutcome::Run(Command::Am(GitAmResolution::Continue)))
+            }
+            KeyCode::Char('s') => return Ok(PopUpOutcome::Run(Command::Am(GitAmResolution::Skip))),
+            KeyCode::Char('a') => {
+                return Ok(PopUpOutcome::Run(Command::Am(GitAmResolution::Abort)))
+            }
+            _ => {}
+        }
+
+        Ok(PopUpOutcome::Continue)
+    }
+}
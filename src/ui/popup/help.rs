@@ -1,13 +1,14 @@
 use ratatui::{
     crossterm::event::KeyCode,
     layout::Alignment,
-    style::{Style, Stylize},
     text::Line,
     widgets::{Clear, Paragraph},
 };
 use std::fmt::Display;
 
-use super::PopUp;
+use crate::app::{keymap::Keymap, screens::CurrentScreen, theme::Theme};
+
+use super::{PopUp, PopUpOutcome};
 
 /// A popup that displays a help message
 ///
@@ -77,6 +78,18 @@ impl HelpPopUpBuilder {
         self
     }
 
+    /// Adds a help entry for each action of `screen` bound to keys in
+    /// `keymap`, so the listed keys never drift from the active ones
+    pub fn keymap(self, keymap: &Keymap, screen: &CurrentScreen) -> Self {
+        keymap
+            .bindings(screen)
+            .iter()
+            .filter(|binding| !binding.keys.is_empty())
+            .fold(self, |builder, binding| {
+                builder.keybind(keymap.keys_of(screen, binding.action, "/"), binding.help)
+            })
+    }
+
     /// Builds the `HelpPopUp` with the given parameters
     pub fn build(self) -> HelpPopUp {
         let key_len = self
@@ -119,15 +132,12 @@ impl PopUp for HelpPopUp {
         self.dimensions
     }
 
-    fn render(&self, f: &mut ratatui::Frame, chunk: ratatui::prelude::Rect) {
+    fn render(&self, f: &mut ratatui::Frame, chunk: ratatui::prelude::Rect, theme: &Theme) {
         let block = ratatui::widgets::Block::default()
             .title(self.to_string())
             .title_alignment(Alignment::Center)
-            .title_style(ratatui::style::Style::default().bold().blue())
-            .title_bottom(Line::styled(
-                "(ESC / q) Close",
-                Style::default().bold().blue(),
-            ))
+            .title_style(theme.popup_title)
+            .title_bottom(Line::styled("(ESC / q) Close", theme.popup_title))
             .borders(ratatui::widgets::Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Double)
             .style(ratatui::style::Style::default());
@@ -156,32 +166,28 @@ impl PopUp for HelpPopUp {
         f.render_widget(text, chunk);
     }
 
-    fn handle(&mut self, key: ratatui::crossterm::event::KeyEvent) -> color_eyre::Result<()> {
+    fn handle(
+        &mut self,
+        key: ratatui::crossterm::event::KeyEvent,
+    ) -> color_eyre::Result<PopUpOutcome> {
         match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.offset.0 > 0 {
-                    self.offset.0 -= 1;
-                }
+            KeyCode::Char('q') => return Ok(PopUpOutcome::Close),
+            KeyCode::Up | KeyCode::Char('k') if self.offset.0 > 0 => {
+                self.offset.0 -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.offset.0 < self.max_offset.0 {
-                    self.offset.0 += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j') if self.offset.0 < self.max_offset.0 => {
+                self.offset.0 += 1;
             }
-            KeyCode::Left | KeyCode::Char('h') => {
-                if self.offset.1 > 0 {
-                    self.offset.1 -= 1;
-                }
+            KeyCode::Left | KeyCode::Char('h') if self.offset.1 > 0 => {
+                self.offset.1 -= 1;
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                if self.offset.1 < self.max_offset.1 {
-                    self.offset.1 += 1;
-                }
+            KeyCode::Right | KeyCode::Char('l') if self.offset.1 < self.max_offset.1 => {
+                self.offset.1 += 1;
             }
             _ => {}
         }
 
-        Ok(())
+        Ok(PopUpOutcome::Continue)
     }
 }
 
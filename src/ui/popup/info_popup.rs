@@ -1,12 +1,14 @@
 use ratatui::{
     crossterm::event::KeyCode,
     layout::{Alignment, Rect},
-    style::{Color, Modifier, Style},
+    style::Style,
     text::Line,
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
 };
 
-use super::PopUp;
+use crate::app::theme::Theme;
+
+use super::{PopUp, PopUpOutcome};
 
 #[derive(Debug)]
 pub struct InfoPopUp {
@@ -49,15 +51,13 @@ impl PopUp for InfoPopUp {
     }
 
     /// Renders a centered overlaying pop-up with a title and an arbitrary info.
-    fn render(&self, f: &mut ratatui::Frame, chunk: Rect) {
-        let bold_blue = Style::default()
-            .add_modifier(Modifier::BOLD)
-            .fg(Color::Blue);
+    fn render(&self, f: &mut ratatui::Frame, chunk: Rect, theme: &Theme) {
+        let title_style = theme.popup_title;
         let block = Block::default()
             .title(self.title.clone())
             .title_alignment(Alignment::Center)
-            .title_style(bold_blue)
-            .title_bottom(Line::styled("(ESC / q) Close", bold_blue))
+            .title_style(title_style)
+            .title_bottom(Line::styled("(ESC / q) Close", title_style))
             .borders(Borders::ALL)
             .border_type(BorderType::Double)
             .style(Style::default());
@@ -73,31 +73,27 @@ impl PopUp for InfoPopUp {
     }
 
     /// Handles simple one-char width navigation.
-    fn handle(&mut self, key: ratatui::crossterm::event::KeyEvent) -> color_eyre::Result<()> {
+    fn handle(
+        &mut self,
+        key: ratatui::crossterm::event::KeyEvent,
+    ) -> color_eyre::Result<PopUpOutcome> {
         match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.offset.0 > 0 {
-                    self.offset.0 -= 1;
-                }
+            KeyCode::Char('q') => return Ok(PopUpOutcome::Close),
+            KeyCode::Up | KeyCode::Char('k') if self.offset.0 > 0 => {
+                self.offset.0 -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.offset.0 < self.max_offset.0 {
-                    self.offset.0 += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j') if self.offset.0 < self.max_offset.0 => {
+                self.offset.0 += 1;
             }
-            KeyCode::Left | KeyCode::Char('h') => {
-                if self.offset.1 > 0 {
-                    self.offset.1 -= 1;
-                }
+            KeyCode::Left | KeyCode::Char('h') if self.offset.1 > 0 => {
+                self.offset.1 -= 1;
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                if self.offset.1 < self.max_offset.1 {
-                    self.offset.1 += 1;
-                }
+            KeyCode::Right | KeyCode::Char('l') if self.offset.1 < self.max_offset.1 => {
+                self.offset.1 += 1;
             }
             _ => {}
         }
 
-        Ok(())
+        Ok(PopUpOutcome::Continue)
     }
 }
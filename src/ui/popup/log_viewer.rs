@@ -0,0 +1,114 @@
+use ratatui::{
+    crossterm::event::KeyCode,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use crate::app::theme::Theme;
+
+use super::{PopUp, PopUpOutcome};
+
+#[derive(Debug)]
+pub struct LogViewerPopUp {
+    title: String,
+    lines: Vec<String>,
+    offset: (u16, u16),
+    max_offset: (u16, u16),
+    dimensions: (u16, u16),
+}
+
+impl LogViewerPopUp {
+    /// Generate a pop-up with a title and the lines of a log (like the output
+    /// of a command), initially scrolled to the end of the log.
+    pub fn generate_log_viewer_popup(title: &str, lines: Vec<String>) -> Box<dyn PopUp> {
+        let columns = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let max_offset = (
+            u16::try_from(lines.len().saturating_sub(1)).unwrap_or(u16::MAX),
+            u16::try_from(columns).unwrap_or(u16::MAX),
+        );
+
+        // TODO: Calculate percentage based on the lines and screen size
+        let dimensions = (80, 80);
+
+        Box::new(LogViewerPopUp {
+            title: title.to_string(),
+            offset: (
+                u16::try_from(lines.len().saturating_sub(10)).unwrap_or(u16::MAX),
+                0,
+            ),
+            lines,
+            max_offset,
+            dimensions,
+        })
+    }
+}
+
+impl PopUp for LogViewerPopUp {
+    fn dimensions(&self) -> (u16, u16) {
+        self.dimensions
+    }
+
+    /// Renders a centered overlaying pop-up with the lines of the log.
+    fn render(&self, f: &mut ratatui::Frame, chunk: Rect, theme: &Theme) {
+        let title_style = theme.popup_title;
+        let block = Block::default()
+            .title(self.title.clone())
+            .title_alignment(Alignment::Center)
+            .title_style(title_style)
+            .title_bottom(Line::styled(
+                "(ESC / q) Close | (g / G) Go to top / bottom",
+                title_style,
+            ))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .style(Style::default());
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .map(|line| Line::styled(line.clone(), theme.text))
+            .collect();
+
+        let pop_up = Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Left)
+            .scroll(self.offset);
+
+        f.render_widget(Clear, chunk);
+        f.render_widget(pop_up, chunk);
+    }
+
+    /// Handles simple one-char width navigation and jumping to the start and
+    /// end of the log.
+    fn handle(
+        &mut self,
+        key: ratatui::crossterm::event::KeyEvent,
+    ) -> color_eyre::Result<PopUpOutcome> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(PopUpOutcome::Close),
+            KeyCode::Up | KeyCode::Char('k') if self.offset.0 > 0 => {
+                self.offset.0 -= 1;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.offset.0 < self.max_offset.0 => {
+                self.offset.0 += 1;
+            }
+            KeyCode::Left | KeyCode::Char('h') if self.offset.1 > 0 => {
+                self.offset.1 -= 1;
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.offset.1 < self.max_offset.1 => {
+                self.offset.1 += 1;
+            }
+            KeyCode::Char('g') => {
+                self.offset.0 = 0;
+            }
+            KeyCode::Char('G') => {
+                self.offset.0 = self.max_offset.0;
+            }
+            _ => {}
+        }
+
+        Ok(PopUpOutcome::Continue)
+    }
+}
@@ -1,76 +1,68 @@
-use std::collections::HashSet;
-
-use patch_hub::lore::patch::Author;
+use patch_hub::lore::trailers::CODE_REVIEW_TRAILERS;
 use ratatui::{
     crossterm::event::KeyCode,
     layout::Alignment,
-    style::{Color, Modifier, Style, Stylize},
+    style::Modifier,
     text::Line,
     widgets::{Clear, Paragraph},
 };
 
-use crate::app::screens::details_actions::DetailsActions;
+use crate::app::{screens::details_actions::DetailsActions, theme::Theme};
 
-use super::PopUp;
+use super::{PopUp, PopUpOutcome};
 
 #[derive(Debug)]
 pub struct ReviewTrailersPopUp {
-    reviewed_by_text: String,
-    tested_by_text: String,
-    acked_by_text: String,
+    /// Pairs of trailer name and the text listing its entries
+    entries: Vec<(String, String)>,
     offset: (u16, u16),
     max_offset: (u16, u16),
     dimensions: (u16, u16),
 }
 
 impl ReviewTrailersPopUp {
-    /// Generate a pop-up that contains details about the code-review trailers
-    /// of a specific patch.
+    /// Generate a pop-up that contains details about the trailers of a
+    /// specific patch.
     ///
     /// The specific patch is defined by the currently previewing patch of
     /// `@details_actions` and the information about the trailers is stored in
-    /// the fields `reviewed_by`, `tested_by`, and `acked_by`, which are the
-    /// tags considered for the generated pop-up. This function succeeds regardless if
-    /// there are no code-review trailers for the specific patch.
+    /// its `trailers`. The code-review trailers (see [CODE_REVIEW_TRAILERS])
+    /// are always listed, followed by any other trailer collected for the
+    /// patch. Each entry shows the message in which the trailer was given.
+    /// This function succeeds regardless if there are no trailers for the
+    /// specific patch.
     pub fn generate_trailers_popup(details_actions: &DetailsActions) -> Box<dyn PopUp> {
-        let i = details_actions.preview_index;
-        let mut reviewed_by_text = String::new();
-        let mut tested_by_text = String::new();
-        let mut acked_by_text = String::new();
+        let trailers = &details_actions.trailers[details_actions.preview_index];
+        let mut entries = Vec::new();
+        let mut lines = 0;
         let mut columns = 0;
 
-        // Auxiliary routines to avoid code duplications
-        let mut update_columns = |line_len: usize| {
-            if line_len > columns {
-                columns = line_len;
-            }
-        };
-        let mut fill_text = |text: &mut String, authors: &HashSet<Author>| {
-            for author in authors {
-                let author = format!(" - {}\n", author);
-                text.push_str(&author);
-                update_columns(author.len());
+        let other_trailers = trailers
+            .iter()
+            .filter(|(name, _)| !CODE_REVIEW_TRAILERS.contains(name));
+        let named_trailers = CODE_REVIEW_TRAILERS
+            .iter()
+            .map(|name| (*name, trailers.get(name)))
+            .chain(other_trailers);
+
+        for (name, trailers) in named_trailers {
+            let mut text = String::new();
+            for trailer in trailers {
+                let trailer = format!(" - {}\n", trailer);
+                columns = columns.max(trailer.len());
+                text.push_str(&trailer);
             }
-        };
-
-        fill_text(&mut reviewed_by_text, &details_actions.reviewed_by[i]);
-        fill_text(&mut tested_by_text, &details_actions.tested_by[i]);
-        fill_text(&mut acked_by_text, &details_actions.acked_by[i]);
-
-        let lines = (3
-            + details_actions.reviewed_by[i].len()
-            + details_actions.tested_by[i].len()
-            + details_actions.acked_by[i].len()) as u16;
+            lines += 2 + trailers.len();
+            entries.push((name.to_string(), text));
+        }
 
         // TODO: Calculate percentage based on the lines and screen size
         let dimensions = (50, 40);
 
         Box::new(ReviewTrailersPopUp {
-            reviewed_by_text,
-            tested_by_text,
-            acked_by_text,
+            entries,
             offset: (0, 0),
-            max_offset: (lines, columns as u16),
+            max_offset: (lines as u16, columns as u16),
             dimensions,
         })
     }
@@ -81,41 +73,30 @@ impl PopUp for ReviewTrailersPopUp {
         self.dimensions
     }
 
-    /// Renders a centered overlaying pop-up with entries for code-review
-    /// trailers of "Reviewed-by", "Tested-by", and "Acked-by".
-    fn render(&self, f: &mut ratatui::Frame, chunk: ratatui::prelude::Rect) {
+    /// Renders a centered overlaying pop-up with an entry for each trailer
+    /// name, starting with "Reviewed-by", "Tested-by", and "Acked-by".
+    fn render(&self, f: &mut ratatui::Frame, chunk: ratatui::prelude::Rect, theme: &Theme) {
         let mut contents = vec![];
 
-        // Auxilliary closure to avoid code duplication
-        let mut add_entry_to_contents = |name: &str, text: &str| {
+        for (name, text) in &self.entries {
             contents.push(Line::styled(
                 name.to_string(),
-                Style::default()
-                    .fg(Color::Cyan)
+                theme
+                    .label
                     .add_modifier(Modifier::BOLD)
                     .add_modifier(Modifier::UNDERLINED),
             ));
             for line in text.lines() {
-                contents.push(Line::styled(
-                    line.to_string(),
-                    Style::default().fg(Color::White),
-                ));
+                contents.push(Line::styled(line.to_string(), theme.text));
             }
             contents.push(Line::from("")); // equivalent to newline
-        };
-
-        add_entry_to_contents("Reviewed-by", &self.reviewed_by_text);
-        add_entry_to_contents("Tested-by", &self.tested_by_text);
-        add_entry_to_contents("Acked-by", &self.acked_by_text);
+        }
 
         let block = ratatui::widgets::Block::default()
-            .title("Code-Review Trailers")
+            .title("Trailers")
             .title_alignment(Alignment::Center)
-            .title_style(ratatui::style::Style::default().bold().blue())
-            .title_bottom(Line::styled(
-                "(ESC / q) Close",
-                Style::default().bold().blue(),
-            ))
+            .title_style(theme.popup_title)
+            .title_bottom(Line::styled("(ESC / q) Close", theme.popup_title))
             .borders(ratatui::widgets::Borders::ALL)
             .border_type(ratatui::widgets::BorderType::Double)
             .style(ratatui::style::Style::default());
@@ -131,31 +112,27 @@ impl PopUp for ReviewTrailersPopUp {
     }
 
     /// Handles simple one-char width navigation.
-    fn handle(&mut self, key: ratatui::crossterm::event::KeyEvent) -> color_eyre::Result<()> {
+    fn handle(
+        &mut self,
+        key: ratatui::crossterm::event::KeyEvent,
+    ) -> color_eyre::Result<PopUpOutcome> {
         match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.offset.0 > 0 {
-                    self.offset.0 -= 1;
-                }
+            KeyCode::Char('q') => return Ok(PopUpOutcome::Close),
+            KeyCode::Up | KeyCode::Char('k') if self.offset.0 > 0 => {
+                self.offset.0 -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.offset.0 < self.max_offset.0 {
-                    self.offset.0 += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j') if self.offset.0 < self.max_offset.0 => {
+                self.offset.0 += 1;
             }
-            KeyCode::Left | KeyCode::Char('h') => {
-                if self.offset.1 > 0 {
-                    self.offset.1 -= 1;
-                }
+            KeyCode::Left | KeyCode::Char('h') if self.offset.1 > 0 => {
+                self.offset.1 -= 1;
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                if self.offset.1 < self.max_offset.1 {
-                    self.offset.1 += 1;
-                }
+            KeyCode::Right | KeyCode::Char('l') if self.offset.1 < self.max_offset.1 => {
+                self.offset.1 += 1;
             }
             _ => {}
         }
 
-        Ok(())
+        Ok(PopUpOutcome::Continue)
     }
 }
@@ -0,0 +1,24 @@
+use crate::app::{
+    keymap::{Action, Keymap},
+    screens::CurrentScreen,
+    theme::Theme,
+};
+use ratatui::text::Span;
+
+pub fn mode_footer_text(theme: &Theme) -> Vec<Span<'static>> {
+    vec![Span::styled("Recent Patchsets", theme.title)]
+}
+
+pub fn keys_hint(keymap: &Keymap, theme: &Theme) -> Span<'static> {
+    Span::styled(
+        keymap.keys_hint(
+            &CurrentScreen::RecentPatchsets,
+            &[
+                (Action::Exit, "to return"),
+                (Action::Open, "to select"),
+                (Action::Help, "help"),
+            ],
+        ),
+        theme.hint,
+    )
+}
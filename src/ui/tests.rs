@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use ratatui::style::Modifier;
+
+use super::*;
+use crate::app::keymap::Keymap;
+
+#[test]
+fn should_fit_text_to_width() {
+    assert_eq!("foo  ", fit_to_width("foo", 5));
+    assert_eq!("foo", fit_to_width("foo", 3));
+    assert_eq!("fo…", fit_to_width("foobar", 3));
+    assert_eq!(
+        "çã…",
+        fit_to_width("çãõ bar", 3),
+        "Widths should count characters"
+    );
+    assert_eq!("", fit_to_width("foo", 0));
+}
+
+#[test]
+fn should_split_title_and_author_widths() {
+    assert_eq!((70, 30), title_and_author_widths(133, 30));
+    assert_eq!(
+        (117, 30),
+        title_and_author_widths(180, 30),
+        "The author column shouldn't grow beyond its maximum width"
+    );
+    assert_eq!((35, 15), title_and_author_widths(83, 30));
+    assert_eq!((0, 0), title_and_author_widths(20, 30));
+}
+
+#[test]
+fn should_find_list_item_at_position() {
+    // A bordered list of 10 items in a 4-row chunk, scrolled past 3 items
+    let list_state = ListState::default().with_offset(3);
+    let (area, items) = list_mouse_area(Rect::new(0, 5, 20, 4), (0..10).collect(), &list_state);
+    let frame_areas = FrameAreas {
+        list: Some((area, items)),
+        ..Default::default()
+    };
+
+    assert_eq!(Some(3), frame_areas.list_item_at(Position::new(1, 6)));
+    assert_eq!(
+        Some(4),
+        frame_areas.list_item_at(Position::new(18, 7)),
+        "Items should be offset by the items scrolled past"
+    );
+    assert_eq!(None, frame_areas.list_item_at(Position::new(1, 5)));
+    assert_eq!(None, frame_areas.list_item_at(Position::new(0, 6)));
+    assert_eq!(None, frame_areas.list_item_at(Position::new(19, 6)));
+    assert_eq!(
+        None,
+        frame_areas.list_item_at(Position::new(1, 8)),
+        "Clicks on the borders shouldn't hit any item"
+    );
+
+    // A bordered list of 2 items in a 6-row chunk
+    let (area, items) = list_mouse_area(Rect::new(0, 0, 20, 6), vec![0, 1], &ListState::default());
+    let frame_areas = FrameAreas {
+        list: Some((area, items)),
+        ..Default::default()
+    };
+
+    assert_eq!(Some(1), frame_areas.list_item_at(Position::new(1, 2)));
+    assert!(frame_areas.is_over_list(Position::new(1, 3)));
+    assert_eq!(
+        None,
+        frame_areas.list_item_at(Position::new(1, 3)),
+        "Clicks below the last item shouldn't hit any item"
+    );
+    assert_eq!(
+        None,
+        FrameAreas::default().list_item_at(Position::new(1, 1)),
+        "Screens without a list shouldn't hit any item"
+    );
+}
+
+#[test]
+fn should_find_action_at_position() {
+    let frame_areas = FrameAreas {
+        actions: Some((
+            Rect::new(41, 11, 30, 3),
+            vec![Action::ToggleBookmark, Action::ToggleApply],
+        )),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        Some(Action::ToggleBookmark),
+        frame_areas.action_at(Position::new(41, 11))
+    );
+    assert_eq!(
+        Some(Action::ToggleApply),
+        frame_areas.action_at(Position::new(70, 12))
+    );
+    assert_eq!(
+        None,
+        frame_areas.action_at(Position::new(41, 13)),
+        "Rows below the last action shouldn't hit any action"
+    );
+    assert_eq!(None, frame_areas.action_at(Position::new(40, 11)));
+    assert_eq!(None, frame_areas.action_at(Position::new(41, 10)));
+    assert_eq!(None, frame_areas.action_at(Position::new(71, 11)));
+    assert_eq!(None, FrameAreas::default().action_at(Position::new(41, 11)));
+}
+
+#[test]
+fn should_underline_bound_action_mnemonic() {
+    let theme = Theme::default();
+    let text = |spans: &[Span]| {
+        spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect::<String>()
+    };
+
+    let (keymap, _) = Keymap::new(&HashMap::new());
+    let spans =
+        details_actions::action_label(&keymap, Action::ToggleBuildTest, "build-test", 't', &theme);
+    assert_eq!("build-test", text(&spans));
+    assert_eq!("t", spans[1].content);
+    assert!(spans[1].style.add_modifier.contains(Modifier::UNDERLINED));
+
+    let overrides = HashMap::from([(
+        "details".to_string(),
+        HashMap::from([("toggle_bookmark".to_string(), vec!["Ctrl+b".to_string()])]),
+    )]);
+    let (keymap, errors) = Keymap::new(&overrides);
+    assert!(errors.is_empty());
+    let spans =
+        details_actions::action_label(&keymap, Action::ToggleBookmark, "bookmark", 'b', &theme);
+    assert_eq!(
+        "bookmark (Ctrl+b)",
+        text(&spans),
+        "The mnemonic shouldn't be underlined if the action isn't bound to it"
+    );
+    assert!(spans
+        .iter()
+        .all(|span| !span.style.add_modifier.contains(Modifier::UNDERLINED)));
+}
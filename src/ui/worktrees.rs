@@ -0,0 +1,87 @@
+use crate::app::{
+    keymap::{Action, Keymap},
+    screens::{worktrees::Worktrees, CurrentScreen},
+    theme::Theme,
+};
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, HighlightSpacing, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+pub fn render_main(f: &mut Frame, worktrees: &Worktrees, theme: &Theme, chunk: Rect) {
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Double)
+        .style(Style::default());
+
+    if worktrees.worktrees.is_empty() {
+        let message = Paragraph::new(Line::styled(
+            "No worktrees of applied patchsets",
+            theme.muted,
+        ))
+        .block(list_block)
+        .centered();
+        f.render_widget(message, chunk);
+        return;
+    }
+
+    let mut list_items = Vec::<ListItem>::new();
+
+    for (index, entry) in worktrees.worktrees.iter().enumerate() {
+        let worktree = &entry.worktree;
+        let kernel_tree_id = format!("{:width$}", entry.kernel_tree_id, width = 15);
+        let kernel_tree_id = format!("{:.width$}", kernel_tree_id, width = 15);
+        let branch = worktree.branch().as_deref().unwrap_or("(detached)");
+        let branch = format!("{:width$}", branch, width = 40);
+        let branch = format!("{:.width$}", branch, width = 40);
+        let head = worktree.head().get(..12).unwrap_or(worktree.head());
+        let mut line = vec![Span::styled(
+            format!(
+                "{:03}. {} | {} | {} | {}",
+                index,
+                kernel_tree_id,
+                branch,
+                head,
+                worktree.path()
+            ),
+            theme.item,
+        )];
+        if worktree.prunable() {
+            line.push(Span::styled(" (missing)", theme.error));
+        }
+        list_items.push(ListItem::new(Line::from(line)));
+    }
+
+    let list = List::new(list_items)
+        .block(list_block)
+        .highlight_style(theme.selected)
+        .highlight_symbol(">")
+        .highlight_spacing(HighlightSpacing::Always);
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(worktrees.worktree_index));
+
+    f.render_stateful_widget(list, chunk, &mut list_state);
+}
+
+pub fn mode_footer_text(theme: &Theme) -> Vec<Span<'static>> {
+    vec![Span::styled("Worktrees", theme.title)]
+}
+
+pub fn keys_hint(keymap: &Keymap, theme: &Theme) -> Span<'static> {
+    Span::styled(
+        keymap.keys_hint(
+            &CurrentScreen::Worktrees,
+            &[
+                (Action::Exit, "to return"),
+                (Action::Open, "to open"),
+                (Action::Prune, "prune"),
+                (Action::Help, "help"),
+            ],
+        ),
+        theme.hint,
+    )
+}
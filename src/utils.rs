@@ -2,10 +2,12 @@ use color_eyre::{config::HookBuilder, eyre};
 use ratatui::layout::Position;
 use std::io::{self, stdout, Stdout};
 use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use ratatui::{
     backend::CrosstermBackend,
     crossterm::{
+        event::{DisableMouseCapture, EnableMouseCapture},
         execute,
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     },
@@ -25,8 +27,20 @@ pub fn init() -> io::Result<Tui> {
     Terminal::new(CrosstermBackend::new(stdout()))
 }
 
+/// If true, mouse events are captured while the TUI is displayed
+static MOUSE_CAPTURE: AtomicBool = AtomicBool::new(false);
+
+/// Captures mouse events, also after [teardown_user_io]
+pub fn enable_mouse_capture() -> io::Result<()> {
+    MOUSE_CAPTURE.store(true, Ordering::Relaxed);
+    execute!(stdout(), EnableMouseCapture)
+}
+
 /// Restore the terminal to its original state
 pub fn restore() -> io::Result<()> {
+    if MOUSE_CAPTURE.load(Ordering::Relaxed) {
+        execute!(stdout(), DisableMouseCapture)?;
+    }
     execute!(stdout(), LeaveAlternateScreen)?;
     disable_raw_mode()?;
     Ok(())
@@ -66,12 +80,18 @@ pub fn setup_user_io<B: Backend>(terminal: &mut Terminal<B>) -> color_eyre::Resu
     terminal.clear()?;
     terminal.set_cursor_position(Position::new(0, 0))?;
     terminal.show_cursor()?;
+    if MOUSE_CAPTURE.load(Ordering::Relaxed) {
+        execute!(stdout(), DisableMouseCapture)?;
+    }
     disable_raw_mode()?;
     Ok(())
 }
 
 pub fn teardown_user_io<B: Backend>(terminal: &mut Terminal<B>) -> color_eyre::Result<()> {
     enable_raw_mode()?;
+    if MOUSE_CAPTURE.load(Ordering::Relaxed) {
+        execute!(stdout(), EnableMouseCapture)?;
+    }
     terminal.clear()?;
     Ok(())
 }
@@ -89,7 +109,8 @@ pub fn binary_exists(binary: &str) -> bool {
 #[macro_export]
 /// Macro that encapsulates a piece of code that takes long to run and displays a loading screen while it runs.
 ///
-/// This macro takes two arguments: the terminal and the title of the loading screen (anything that implements `Display`).
+/// This macro takes three arguments: the terminal, the theme to draw the loading screen with and its title (anything
+/// that implements `Display`).
 /// After a `=>` token, you can pass the code that takes long to run.
 ///
 /// When the execution finishes, the macro will return the terminal.
@@ -101,20 +122,21 @@ pub fn binary_exists(binary: &str) -> bool {
 ///
 /// # Example
 /// ```rust norun
-/// terminal = loading_screen! { terminal, "Loading stuff" => {
+/// terminal = loading_screen! { terminal, app.theme, "Loading stuff" => {
 ///    // code that takes long to run
 /// }};
 /// ```
 macro_rules! loading_screen {
-    { $terminal:expr, $title:expr => $inst:expr} => {
+    { $terminal:expr, $theme:expr, $title:expr => $inst:expr} => {
         {
             let loading = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
             let loading_clone = std::sync::Arc::clone(&loading);
             let mut terminal = $terminal;
+            let theme = $theme;
 
             let handle = std::thread::spawn(move || {
                 while loading_clone.load(std::sync::atomic::Ordering::Relaxed) {
-                    terminal = $crate::ui::loading_screen::render(terminal, $title);
+                    terminal = $crate::ui::loading_screen::render(terminal, &theme, $title);
                     std::thread::sleep(std::time::Duration::from_millis(200));
                 }
 